@@ -0,0 +1,287 @@
+//! Server calls made on demand, e.g. from a button or a form submit.
+//!
+//! The generated hooks fetch when a component mounts and when their parameters change.
+//! An [`Action`] runs its function only when it's dispatched, and keeps the state of the
+//! calls for the component: whether one is pending, the result of the latest one and how
+//! many have settled:
+//!
+//! ```ignore
+//! #[function_component]
+//! fn NewTodo() -> Html {
+//!     let add = use_action(|title: String| add_todo(title));
+//!     let onclick = add.dispatcher().reform(|_| "Buy milk".to_string());
+//!
+//!     html! {
+//!         <>
+//!             <button {onclick} disabled={add.pending()}>{ "Add" }</button>
+//!             if let Some(Err(error)) = add.value() {
+//!                 <p class="error">{ error }</p>
+//!             }
+//!         </>
+//!     }
+//! }
+//! ```
+//!
+//! Outside components, [`Action::new`] makes one and [`Action::run`] awaits a call.
+//!
+//! POST, PUT, PATCH and DELETE endpoints also get a `use_*_mutation` hook built on one,
+//! returning a [`Mutation`] whose `trigger` takes the endpoint's parameters struct.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use yew::prelude::*;
+
+type ActionFuture<T> = Pin<Box<dyn Future<Output = Result<T, String>>>>;
+type ActionFn<I, T> = Rc<dyn Fn(I) -> ActionFuture<T>>;
+
+struct State<I, T> {
+    /// Input of the latest dispatch, while calls are pending
+    input: Option<I>,
+    value: Option<Result<T, String>>,
+    pending: usize,
+    version: usize,
+    /// Number of the latest dispatch, and of the latest one whose result is kept
+    dispatched: u64,
+    settled: u64,
+}
+
+struct Inner<I, T> {
+    run: RefCell<ActionFn<I, T>>,
+    state: RefCell<State<I, T>>,
+    on_change: RefCell<Option<Rc<dyn Fn()>>>,
+}
+
+/// A server call run on demand, and the state of its calls
+pub struct Action<I, T> {
+    inner: Rc<Inner<I, T>>,
+}
+
+impl<I, T> Action<I, T>
+where
+    I: Clone + 'static,
+    T: Clone + 'static,
+{
+    pub fn new<F, Fut>(f: F) -> Self
+    where
+        F: Fn(I) -> Fut + 'static,
+        Fut: Future<Output = Result<T, String>> + 'static,
+    {
+        Self {
+            inner: Rc::new(Inner {
+                run: RefCell::new(Self::boxed(f)),
+                state: RefCell::new(State {
+                    input: None,
+                    value: None,
+                    pending: 0,
+                    version: 0,
+                    dispatched: 0,
+                    settled: 0,
+                }),
+                on_change: RefCell::new(None),
+            }),
+        }
+    }
+
+    fn boxed<F, Fut>(f: F) -> ActionFn<I, T>
+    where
+        F: Fn(I) -> Fut + 'static,
+        Fut: Future<Output = Result<T, String>> + 'static,
+    {
+        Rc::new(move |input| Box::pin(f(input)))
+    }
+
+    /// Starts a call with `input` without waiting for it
+    pub fn dispatch(&self, input: I) {
+        let action = self.clone();
+        yew::platform::spawn_local(async move {
+            let _ = action.run(input).await;
+        });
+    }
+
+    /// A callback dispatching its argument, for event handlers
+    pub fn dispatcher(&self) -> Callback<I> {
+        let action = self.clone();
+        Callback::from(move |input| action.dispatch(input))
+    }
+
+    /// Runs a call with `input` and returns its result, updating the state like
+    /// [`dispatch`](Self::dispatch)
+    pub async fn run(&self, input: I) -> Result<T, String> {
+        let run = self.inner.run.borrow().clone();
+        let number = {
+            let mut state = self.inner.state.borrow_mut();
+            state.dispatched += 1;
+            state.pending += 1;
+            state.input = Some(input.clone());
+            state.dispatched
+        };
+        self.notify();
+
+        let result = run(input).await;
+
+        {
+            let mut state = self.inner.state.borrow_mut();
+            state.pending -= 1;
+            state.version += 1;
+            // A call settling after a later one doesn't replace its result
+            if number > state.settled {
+                state.settled = number;
+                state.value = Some(result.clone());
+            }
+            if state.pending == 0 {
+                state.input = None;
+            }
+        }
+        self.notify();
+        result
+    }
+
+    /// Whether a call is running
+    pub fn pending(&self) -> bool {
+        self.inner.state.borrow().pending > 0
+    }
+
+    /// The result of the latest call that settled, `None` before the first one
+    pub fn value(&self) -> Option<Result<T, String>> {
+        self.inner.state.borrow().value.clone()
+    }
+
+    /// The input of the latest call while calls are pending, e.g. to show it optimistically
+    pub fn input(&self) -> Option<I> {
+        self.inner.state.borrow().input.clone()
+    }
+
+    /// The number of calls that settled, successful or not
+    pub fn version(&self) -> usize {
+        self.inner.state.borrow().version
+    }
+
+    fn notify(&self) {
+        let on_change = self.inner.on_change.borrow().clone();
+        if let Some(on_change) = on_change {
+            on_change();
+        }
+    }
+}
+
+impl<I, T> Clone for Action<I, T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// Actions are equal when they are the same action, so they can be passed as props
+impl<I, T> PartialEq for Action<I, T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<I, T> fmt::Debug for Action<I, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.state.borrow();
+        f.debug_struct("Action")
+            .field("pending", &state.pending)
+            .field("version", &state.version)
+            .finish()
+    }
+}
+
+/// What the `use_*_mutation` hook generated for a POST, PUT, PATCH or DELETE endpoint
+/// returns. Nothing is sent until `trigger` is called:
+///
+/// ```ignore
+/// #[yewserverhook(path = "/api/todos", method = "POST")]
+/// pub async fn add_todo(title: String) -> Result<Todo, AppError> { ... }
+///
+/// let add = use_add_todo_mutation();
+/// let onclick = add.trigger.reform(|_| AddTodoParams { title: "Buy milk".to_string() });
+///
+/// html! { <button {onclick} disabled={add.is_pending}>{ "Add" }</button> }
+/// ```
+pub struct Mutation<I, T> {
+    /// Sends a request with the given parameters, e.g. from a button's `onclick`
+    pub trigger: Callback<I>,
+    /// Whether a request is running
+    pub is_pending: bool,
+    /// The response of the latest request, if it succeeded
+    pub data: Option<T>,
+    /// The error of the latest request, if it failed
+    pub error: Option<String>,
+    /// The action sending the requests, e.g. to await one with [`Action::run`]
+    pub action: Action<I, T>,
+}
+
+impl<I, T> Mutation<I, T>
+where
+    I: Clone + 'static,
+    T: Clone + 'static,
+{
+    /// The state of `action` as it is now
+    pub fn new(action: Action<I, T>) -> Self {
+        let (data, error) = match action.value() {
+            Some(Ok(data)) => (Some(data), None),
+            Some(Err(error)) => (None, Some(error)),
+            None => (None, None),
+        };
+        Self {
+            trigger: action.dispatcher(),
+            is_pending: action.pending(),
+            data,
+            error,
+            action,
+        }
+    }
+}
+
+impl<I, T: Clone> Clone for Mutation<I, T> {
+    fn clone(&self) -> Self {
+        Self {
+            trigger: self.trigger.clone(),
+            is_pending: self.is_pending,
+            data: self.data.clone(),
+            error: self.error.clone(),
+            action: self.action.clone(),
+        }
+    }
+}
+
+impl<I, T> fmt::Debug for Mutation<I, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mutation")
+            .field("is_pending", &self.is_pending)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+/// An [`Action`] calling `f`, re-rendering the component when its state changes.
+///
+/// The action stays the same for the life of the component, and its calls run the `f`
+/// passed with the latest render.
+#[hook]
+pub fn use_action<I, T, F, Fut>(f: F) -> Action<I, T>
+where
+    I: Clone + 'static,
+    T: Clone + 'static,
+    F: Fn(I) -> Fut + 'static,
+    Fut: Future<Output = Result<T, String>> + 'static,
+{
+    let rerender = use_force_update();
+    let slot = use_mut_ref(|| None::<Action<I, T>>);
+    let action = match &*slot.borrow() {
+        Some(action) => {
+            *action.inner.run.borrow_mut() = Action::boxed(f);
+            action.clone()
+        }
+        None => Action::new(f),
+    };
+    *slot.borrow_mut() = Some(action.clone());
+    *action.inner.on_change.borrow_mut() = Some(Rc::new(move || rerender.force_update()));
+    action
+}
@@ -0,0 +1,99 @@
+//! Mounts the routes registered by `yew_server_hook` onto an actix-web `App`.
+//!
+//! Every request is converted to the axum request the generated wrapper expects, so
+//! extraction, validation and error mapping work as behind axum's router:
+//!
+//! ```ignore
+//! use actix_web::{App, HttpServer};
+//!
+//! HttpServer::new(|| App::new().configure(yew_extra::actix::configure))
+//!     .bind(("127.0.0.1", 8080))?
+//!     .run()
+//!     .await
+//! ```
+//!
+//! Responses are buffered before they are handed to actix, so `stream_json` endpoints
+//! send their list in one piece.
+
+use crate::route_registry::{registered_routes, Handler};
+use crate::server::{build_request, call, ServerRequest, ServerResponse};
+use actix_web::http::{Method as ActixMethod, StatusCode as ActixStatusCode};
+use actix_web::{web, HttpRequest, HttpResponse};
+use axum::body::{Body, Bytes};
+use axum::http::Request;
+
+/// Registers every route of [`crate::route_registry`], for `App::configure`
+pub fn configure(config: &mut web::ServiceConfig) {
+    for route in registered_routes() {
+        let method =
+            ActixMethod::from_bytes(route.method.as_str().as_bytes()).unwrap_or_else(|e| {
+                panic!(
+                    "Unsupported method for {} {}: {}",
+                    route.method, route.path, e
+                )
+            });
+        let handler = route.handler;
+        config.route(
+            &actix_path(route.path),
+            web::method(method)
+                .to(move |req: HttpRequest, body: web::Bytes| call_route(handler, req, body)),
+        );
+    }
+}
+
+/// Translates an axum path to actix's syntax: `{id}` stays, `{*rest}` becomes `{rest:.*}`.
+/// The Axum 0.7 forms `:id` and `*rest` are translated the same way.
+pub fn actix_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            if let Some(rest) = segment.strip_prefix("{*") {
+                format!("{{{}:.*}}", rest.trim_end_matches('}'))
+            } else if let Some(rest) = segment.strip_prefix('*') {
+                format!("{{{}:.*}}", rest)
+            } else if let Some(name) = segment.strip_prefix(':') {
+                format!("{{{}}}", name)
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// An actix request with its body, as the generated wrappers take it
+struct ActixRequest(HttpRequest, web::Bytes);
+
+impl ServerRequest for ActixRequest {
+    fn into_request(self) -> Result<Request<Body>, String> {
+        let ActixRequest(req, body) = self;
+        build_request(
+            req.method().as_str(),
+            &req.uri().to_string(),
+            req.headers()
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_bytes())),
+            body,
+        )
+    }
+}
+
+/// Wrapper so the response conversion can be implemented for actix's response type
+struct ActixResponse(HttpResponse);
+
+impl ServerResponse for ActixResponse {
+    fn from_parts(status: u16, headers: Vec<(String, Vec<u8>)>, body: Bytes) -> Self {
+        let status =
+            ActixStatusCode::from_u16(status).unwrap_or(ActixStatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = HttpResponse::build(status);
+        for (name, value) in headers {
+            response.append_header((name, value));
+        }
+        ActixResponse(response.body(body.to_vec()))
+    }
+}
+
+/// Runs a generated wrapper for an actix request and converts its response back
+async fn call_route(handler: Handler, req: HttpRequest, body: web::Bytes) -> HttpResponse {
+    let ActixResponse(response) = call(handler, ActixRequest(req, body)).await;
+    response
+}
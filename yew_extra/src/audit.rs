@@ -0,0 +1,169 @@
+//! Audit trail of the mutations handled by the generated endpoints.
+//!
+//! Once a sink is registered with [`set_audit_sink`], every call of a non-GET endpoint
+//! reports an [`AuditRecord`] after its response is ready, without code in the endpoints:
+//!
+//! ```ignore
+//! use yew_extra::audit::{set_audit_sink, set_principal_resolver};
+//!
+//! set_principal_resolver(|request| {
+//!     request.extensions().get::<CurrentUser>().map(|user| user.id.to_string())
+//! });
+//!
+//! let audit_pool = pool.clone();
+//! set_audit_sink(move |record| {
+//!     let pool = audit_pool.clone();
+//!     async move {
+//!         let _ = insert_audit_record(&pool, &record).await;
+//!     }
+//! });
+//! ```
+//!
+//! The response waits for the sink, so a record is written for every mutation the client
+//! saw succeed. The parameters are only kept as a digest, the SHA-256 of their JSON, so the
+//! trail doesn't hold personal data.
+
+use axum::body::Body;
+use axum::http::{Request, Response};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Header the request id is read from, as set by proxies or `tower_http::request_id`
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+type SinkFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type Sink = Arc<dyn Fn(AuditRecord) -> SinkFuture + Send + Sync>;
+type PrincipalResolver = Arc<dyn Fn(&Request<Body>) -> Option<String> + Send + Sync>;
+
+static SINK: Lazy<RwLock<Option<Sink>>> = Lazy::new(Default::default);
+static PRINCIPAL_RESOLVER: Lazy<RwLock<Option<PrincipalResolver>>> = Lazy::new(Default::default);
+
+/// One call of a mutating endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Who made the request, as told by the resolver of [`set_principal_resolver`]
+    pub principal: Option<String>,
+    /// HTTP method of the endpoint
+    pub method: String,
+    /// Path of the endpoint, as declared in `#[yewserverhook]`
+    pub path: String,
+    /// Hex SHA-256 of the parameters as JSON, `None` if they couldn't be parsed or the
+    /// endpoint has none
+    pub params_digest: Option<String>,
+    /// Status of the response
+    pub status: u16,
+    /// The request's `x-request-id` header
+    pub request_id: Option<String>,
+}
+
+impl AuditRecord {
+    /// Whether the endpoint answered with a 2xx status
+    pub fn succeeded(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Sends the record of every call of a mutating endpoint to `sink`, replacing the previous
+/// sink
+pub fn set_audit_sink<F, Fut>(sink: F)
+where
+    F: Fn(AuditRecord) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let sink: Sink = Arc::new(move |record| Box::pin(sink(record)));
+    *SINK.write().unwrap_or_else(|e| e.into_inner()) = Some(sink);
+}
+
+/// Removes the sink registered with [`set_audit_sink`], which stops the auditing
+pub fn clear_audit_sink() {
+    SINK.write().unwrap_or_else(|e| e.into_inner()).take();
+}
+
+/// Tells who made a request, e.g. from a user an auth middleware left in the extensions
+pub fn set_principal_resolver(
+    resolver: impl Fn(&Request<Body>) -> Option<String> + Send + Sync + 'static,
+) {
+    *PRINCIPAL_RESOLVER
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(resolver));
+}
+
+/// Hex SHA-256 of `params` serialized as JSON
+pub fn params_digest<T: Serialize>(params: &T) -> Option<String> {
+    let json = serde_json::to_vec(params).ok()?;
+    let digest = Sha256::digest(&json);
+    Some(digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Digest of the parameters, filled in once the wrapper parsed them
+#[derive(Clone, Default)]
+struct ParamsDigest(Arc<Mutex<Option<String>>>);
+
+/// A call of a mutating endpoint being audited, see [`begin`] and [`finish`]
+pub struct AuditScope {
+    sink: Sink,
+    record: AuditRecord,
+    params_digest: ParamsDigest,
+}
+
+/// Starts auditing a call of the endpoint at `path`, `None` without a sink. Called by the
+/// generated wrappers before handling `request`.
+pub fn begin(path: &str, request: &mut Request<Body>) -> Option<AuditScope> {
+    let sink = SINK.read().unwrap_or_else(|e| e.into_inner()).clone()?;
+    let resolver = PRINCIPAL_RESOLVER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let record = AuditRecord {
+        principal: resolver.and_then(|resolve| resolve(request)),
+        method: request.method().to_string(),
+        path: path.to_string(),
+        params_digest: None,
+        status: 0,
+        request_id: request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+    };
+    let params_digest = ParamsDigest::default();
+    request.extensions_mut().insert(params_digest.clone());
+    Some(AuditScope {
+        sink,
+        record,
+        params_digest,
+    })
+}
+
+/// Keeps the digest of `params` for the record of the request being handled, if it's
+/// audited. Called by the generated wrappers once the parameters are parsed.
+pub async fn record_params<T: Serialize>(params: &T) {
+    let slot =
+        crate::extract::with_request_parts(|parts| parts.extensions.get::<ParamsDigest>().cloned())
+            .await
+            .flatten();
+    if let Some(ParamsDigest(slot)) = slot {
+        *slot.lock().unwrap_or_else(|e| e.into_inner()) = params_digest(params);
+    }
+}
+
+/// Sends the record of an audited call, answered with `response`, to the sink. Called by
+/// the generated wrappers with the scope of [`begin`].
+pub async fn finish(scope: Option<AuditScope>, response: Response<Body>) -> Response<Body> {
+    let Some(AuditScope {
+        sink,
+        mut record,
+        params_digest: ParamsDigest(digest),
+    }) = scope
+    else {
+        return response;
+    };
+    record.status = response.status().as_u16();
+    record.params_digest = digest.lock().unwrap_or_else(|e| e.into_inner()).take();
+    sink(record).await;
+    response
+}
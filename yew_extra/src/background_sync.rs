@@ -0,0 +1,182 @@
+//! Idempotent mutations that the service worker can queue while offline.
+//!
+//! Client functions of endpoints declared with `background_sync = true` send an
+//! `Idempotency-Key` header. When the request can't reach the server, the bundled
+//! service worker stores it in IndexedDB, answers with `202 Accepted` and the
+//! [`QUEUED_HEADER`] header, and replays it through the Background Sync API (even after
+//! the tab was closed). The server remembers the response sent for each key, so a
+//! request that is replayed after all is answered without running the function twice.
+
+/// Header carrying the idempotency key of a mutation
+pub const IDEMPOTENCY_HEADER: &str = "idempotency-key";
+
+/// Header set by the service worker on the `202` response of a queued request
+pub const QUEUED_HEADER: &str = "x-yew-queued";
+
+/// Error returned by client functions when their request was queued for later
+pub const QUEUED_ERROR: &str =
+    "You are offline. The request was queued and will be sent when the connection returns";
+
+/// Returns `true` if a client function error means the request was queued
+pub fn is_queued(error: &str) -> bool {
+    error == QUEUED_ERROR
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::{idempotency_key, run_idempotent};
+
+#[cfg(feature = "client")]
+pub use client::new_idempotency_key;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod server {
+    use super::IDEMPOTENCY_HEADER;
+    use axum::body::{to_bytes, Body, Bytes};
+    use axum::http::{HeaderValue, Request, Response, StatusCode};
+    use dashmap::mapref::entry::Entry;
+    use dashmap::DashMap;
+    use once_cell::sync::Lazy;
+    use std::future::Future;
+    use std::time::{Duration, Instant};
+
+    /// Responses are remembered for this long after they were sent
+    const KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// Header set on responses that were answered from the stored response
+    const REPLAYED_HEADER: &str = "idempotent-replayed";
+
+    enum StoredResponse {
+        /// The request is still being handled
+        Pending(Instant),
+        Done {
+            status: StatusCode,
+            content_type: Option<HeaderValue>,
+            body: Bytes,
+            created: Instant,
+        },
+    }
+
+    impl StoredResponse {
+        fn created(&self) -> Instant {
+            match self {
+                StoredResponse::Pending(created) => *created,
+                StoredResponse::Done { created, .. } => *created,
+            }
+        }
+    }
+
+    /// Global storage for sent responses, keyed by method, path and idempotency key
+    static RESPONSES: Lazy<DashMap<String, StoredResponse>> = Lazy::new(DashMap::new);
+
+    /// Reads the idempotency key of a request, scoped to its method and path
+    pub fn idempotency_key<B>(req: &Request<B>) -> Option<String> {
+        let key = req.headers().get(IDEMPOTENCY_HEADER)?.to_str().ok()?;
+        if key.is_empty() {
+            return None;
+        }
+        Some(format!("{} {} {}", req.method(), req.uri().path(), key))
+    }
+
+    /// Runs `handler` once per idempotency key.
+    ///
+    /// A repeated request gets the stored response of the first one, or `409 Conflict`
+    /// while the first one is still running. Server errors (5xx) aren't stored, so the
+    /// request can be retried. Requests without a key always run the handler.
+    pub async fn run_idempotent<F>(key: Option<String>, handler: F) -> Response<Body>
+    where
+        F: Future<Output = Response<Body>>,
+    {
+        let Some(key) = key else {
+            return handler.await;
+        };
+
+        RESPONSES.retain(|_, stored| stored.created().elapsed() < KEY_TTL);
+
+        match RESPONSES.entry(key.clone()) {
+            Entry::Occupied(entry) => {
+                return match entry.get() {
+                    StoredResponse::Pending(_) => {
+                        let mut response = Response::new(Body::from(
+                            "A request with this idempotency key is already being processed",
+                        ));
+                        *response.status_mut() = StatusCode::CONFLICT;
+                        response
+                    }
+                    StoredResponse::Done {
+                        status,
+                        content_type,
+                        body,
+                        ..
+                    } => replayed_response(*status, content_type.clone(), body.clone()),
+                };
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(StoredResponse::Pending(Instant::now()));
+            }
+        }
+
+        let response = handler.await;
+        if response.status().is_server_error() {
+            RESPONSES.remove(&key);
+            return response;
+        }
+
+        let (parts, body) = response.into_parts();
+        let body = match to_bytes(body, usize::MAX).await {
+            Ok(body) => body,
+            Err(e) => {
+                RESPONSES.remove(&key);
+                let mut response =
+                    Response::new(Body::from(format!("Failed to read response: {}", e)));
+                *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                return response;
+            }
+        };
+
+        let content_type = parts.headers.get(axum::http::header::CONTENT_TYPE).cloned();
+        RESPONSES.insert(
+            key,
+            StoredResponse::Done {
+                status: parts.status,
+                content_type,
+                body: body.clone(),
+                created: Instant::now(),
+            },
+        );
+
+        Response::from_parts(parts, Body::from(body))
+    }
+
+    fn replayed_response(
+        status: StatusCode,
+        content_type: Option<HeaderValue>,
+        body: Bytes,
+    ) -> Response<Body> {
+        let mut response = Response::new(Body::from(body));
+        *response.status_mut() = status;
+        if let Some(content_type) = content_type {
+            response
+                .headers_mut()
+                .insert(axum::http::header::CONTENT_TYPE, content_type);
+        }
+        response
+            .headers_mut()
+            .insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+        response
+    }
+}
+
+#[cfg(feature = "client")]
+mod client {
+    /// Generates a new idempotency key for a mutation
+    pub fn new_idempotency_key() -> String {
+        let random = || (js_sys::Math::random() * u32::MAX as f64) as u32;
+        format!(
+            "{:x}-{:08x}{:08x}{:08x}",
+            js_sys::Date::now() as u64,
+            random(),
+            random(),
+            random()
+        )
+    }
+}
@@ -0,0 +1,189 @@
+//! Deployment of the whole app under a sub-path, e.g. `https://example.com/myapp/` behind a
+//! reverse proxy.
+//!
+//! The generated hooks and client functions send their requests to [`base_path()`]
+//! followed by the endpoint path. It's configured at runtime, so one build can be deployed
+//! under any prefix:
+//!
+//! - on the server, with [`set_base_path`] or the `YEW_BASE_PATH` environment variable
+//! - in the browser, with [`set_base_path`] or the `<meta name="yew-base-path">` tag the SSR
+//!   shell renders with [`meta_tag`] (`client` feature)
+//!
+//! Clients built with the `YEW_API_BASE` environment variable, or given an API base at
+//! runtime with [`set_api_base`], send their requests there instead, see [`api_base()`].
+//! Clients running in a dedicated worker have no page to read the meta tag from, and
+//! resolve relative URLs against the worker script, so they're usually given an absolute
+//! API base.
+//!
+//! ```ignore
+//! // Server
+//! let app = yew_extra::base_path::mount(Router::new().merge(routes()).fallback(render_app));
+//!
+//! // SSR shell
+//! format!(
+//!     r#"<head>{}<script type="module" src="{}"></script></head>"#,
+//!     base_path::meta_tag(),
+//!     base_path::url("/pkg/app.js"),
+//! )
+//! ```
+
+use std::sync::RwLock;
+
+/// Name of the meta tag the browser reads the base path from
+pub const META_NAME: &str = "yew-base-path";
+
+/// Environment variable the server reads the base path from
+pub const ENV_VAR: &str = "YEW_BASE_PATH";
+
+/// Environment variable the generated clients read at compile time, e.g.
+/// `YEW_API_BASE=http://localhost:4000 trunk serve`
+pub const API_BASE_ENV_VAR: &str = "YEW_API_BASE";
+
+/// The configured base path, once known
+static BASE_PATH: RwLock<Option<String>> = RwLock::new(None);
+
+/// The API base set with [`set_api_base`]
+static API_BASE: RwLock<Option<String>> = RwLock::new(None);
+
+/// The `YEW_API_BASE` the generated clients were built with, once one asked for it
+static COMPILED_API_BASE: RwLock<Option<String>> = RwLock::new(None);
+
+/// `/myapp/` and `myapp` become `/myapp`, `/` becomes the empty root path
+fn normalize(path: &str) -> String {
+    let path = path.trim().trim_matches('/');
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", path)
+    }
+}
+
+/// Sets the prefix of every generated client URL and of [`url`]
+pub fn set_base_path(path: &str) {
+    *BASE_PATH.write().unwrap_or_else(|e| e.into_inner()) = Some(normalize(path));
+}
+
+/// The prefix the app is deployed under, e.g. `/myapp`, or an empty string at the root
+pub fn base_path() -> String {
+    if let Some(path) = BASE_PATH.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return path.clone();
+    }
+    let detected = normalize(&detect().unwrap_or_default());
+    *BASE_PATH.write().unwrap_or_else(|e| e.into_inner()) = Some(detected.clone());
+    detected
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn detect() -> Option<String> {
+    std::env::var(ENV_VAR).ok()
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "client"))]
+fn detect() -> Option<String> {
+    web_sys::window()?
+        .document()?
+        .query_selector(&format!("meta[name=\"{}\"]", META_NAME))
+        .ok()??
+        .get_attribute("content")
+}
+
+#[cfg(all(target_arch = "wasm32", not(feature = "client")))]
+fn detect() -> Option<String> {
+    None
+}
+
+/// Sends the requests of the generated clients to `base`, e.g. `https://api.example.com`,
+/// over the `YEW_API_BASE` they were built with and the base path
+pub fn set_api_base(base: &str) {
+    let base = base.trim().trim_end_matches('/').to_string();
+    *API_BASE.write().unwrap_or_else(|e| e.into_inner()) = Some(base);
+}
+
+/// Removes the API base set with [`set_api_base`]
+pub fn clear_api_base() {
+    API_BASE.write().unwrap_or_else(|e| e.into_inner()).take();
+}
+
+/// Where the generated clients send their requests: the base of [`set_api_base`],
+/// `compiled`, the `YEW_API_BASE` the client was built with, or [`base_path()`] without
+/// either.
+///
+/// The variable is read with `option_env!` in the crate using the macro, so changing it
+/// rebuilds the client:
+///
+/// ```
+/// use yew_extra::base_path::api_base;
+///
+/// assert_eq!(api_base(Some("http://localhost:4000/")), "http://localhost:4000");
+/// ```
+pub fn api_base(compiled: Option<&str>) -> String {
+    if let Some(base) = API_BASE.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return base.clone();
+    }
+    match compiled.map(str::trim).filter(|base| !base.is_empty()) {
+        Some(base) => {
+            let base = base.trim_end_matches('/').to_string();
+            let recorded = COMPILED_API_BASE
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone();
+            if recorded.as_ref() != Some(&base) {
+                *COMPILED_API_BASE.write().unwrap_or_else(|e| e.into_inner()) = Some(base.clone());
+            }
+            base
+        }
+        None => base_path(),
+    }
+}
+
+/// The path of [`api_base()`], e.g. `/v1` for `https://api.example.com/v1` or the base
+/// path, which the generated clients put in front of every endpoint path
+pub(crate) fn api_base_path() -> String {
+    let compiled = COMPILED_API_BASE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let base = api_base(compiled.as_deref());
+    let path = match base.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |start| &rest[start..]),
+        None => &base,
+    };
+    path.trim_end_matches('/').to_string()
+}
+
+/// `path` under the base path, e.g. `/pkg/app.js` becomes `/myapp/pkg/app.js`
+pub fn url(path: &str) -> String {
+    format!("{}/{}", base_path(), path.trim_start_matches('/'))
+}
+
+/// The meta tag telling the browser the base path, for the `<head>` of the SSR shell
+pub fn meta_tag() -> String {
+    let content: String = base_path()
+        .chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '"' => "&quot;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            c => c.to_string(),
+        })
+        .collect();
+    format!(r#"<meta name="{}" content="{}">"#, META_NAME, content)
+}
+
+/// Serves `router` under the base path, or as is at the root.
+///
+/// Use it when the reverse proxy forwards the prefix; proxies stripping it need the app at
+/// the root while the clients still use the prefix.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn mount<S>(router: axum::Router<S>) -> axum::Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let path = base_path();
+    if path.is_empty() {
+        router
+    } else {
+        axum::Router::new().nest(&path, router)
+    }
+}
@@ -0,0 +1,31 @@
+//! Small browser helpers shared by the client-side modules.
+//!
+//! They go through the global object rather than `window`, so they work in dedicated
+//! workers too.
+
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Property `name` of the global `navigator`, a `Navigator` in pages and a
+/// `WorkerNavigator` in workers
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+pub(crate) fn navigator_property(name: &str) -> Option<JsValue> {
+    let navigator =
+        js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("navigator")).ok()?;
+    js_sys::Reflect::get(&navigator, &JsValue::from_str(name))
+        .ok()
+        .filter(|value| !value.is_undefined())
+}
+
+/// Waits for the given number of milliseconds using `setTimeout`
+pub(crate) async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let global = js_sys::global();
+        let scheduled = js_sys::Reflect::get(&global, &JsValue::from_str("setTimeout"))
+            .and_then(|set_timeout| set_timeout.dyn_into::<js_sys::Function>())
+            .and_then(|set_timeout| set_timeout.call2(&global, &resolve, &JsValue::from(ms)));
+        if scheduled.is_err() {
+            let _ = resolve.call0(&JsValue::NULL);
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
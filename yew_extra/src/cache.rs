@@ -0,0 +1,810 @@
+//! Client-side cache of the data fetched by the hooks generated by `yew_server_hook`.
+//!
+//! The hooks of GET endpoints keep their responses here, keyed by URL, so a component
+//! mounting later shows the last response right away while it is refetched. Entries older
+//! than the stale time are refetched, entries nobody read or wrote for the cache time are
+//! dropped, and the least recently used entries are evicted beyond `max_entries`:
+//!
+//! ```
+//! use std::time::Duration;
+//! use yew_extra::cache::{self, CacheConfig};
+//!
+//! cache::configure(
+//!     CacheConfig::new()
+//!         .stale_time(Duration::from_secs(30))
+//!         .cache_time(Duration::from_secs(600))
+//!         .max_entries(200),
+//! );
+//! ```
+//!
+//! Hooks mounting while a request for the same key is running wait for its response
+//! instead of sending their own, see [`CacheScope::dedupe`]. After a mutation,
+//! [`invalidate`] marks the responses of a path stale and refetches the mounted hooks
+//! showing them.
+
+use crate::RequestOptions;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::task::{Poll, Waker};
+use std::time::Duration;
+use web_time::Instant;
+
+/// Limits of the shared cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// How long a response is used without refetching it
+    pub stale_time: Duration,
+    /// How long an entry is kept after it was last read or written
+    pub cache_time: Duration,
+    /// Number of entries kept before the least recently used ones are evicted
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            stale_time: Duration::ZERO,
+            cache_time: Duration::from_secs(5 * 60),
+            max_entries: 100,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long a response is used without refetching it
+    pub fn stale_time(mut self, stale_time: Duration) -> Self {
+        self.stale_time = stale_time;
+        self
+    }
+
+    /// Sets how long an unused entry is kept
+    pub fn cache_time(mut self, cache_time: Duration) -> Self {
+        self.cache_time = cache_time;
+        self
+    }
+
+    /// Sets the number of entries kept
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+}
+
+/// A value read from the cache
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cached<T> {
+    pub value: T,
+    /// Whether the value is older than the stale time and should be refetched
+    pub is_stale: bool,
+}
+
+#[derive(Clone)]
+struct CacheEntry {
+    value: Rc<dyn Any>,
+    fetched_at: Instant,
+    last_used: Instant,
+    /// Marked stale by an invalidation, whatever its age
+    invalidated: bool,
+}
+
+/// Cache of fetched values of any type, keyed by string
+pub struct QueryCache {
+    config: CacheConfig,
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        Self::new(CacheConfig::default())
+    }
+}
+
+impl QueryCache {
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    /// Replaces the limits and drops the entries that no longer fit them
+    pub fn set_config(&mut self, config: CacheConfig) {
+        self.config = config;
+        self.collect_garbage();
+    }
+
+    /// Returns the value stored under `key`, unless it expired or has another type
+    pub fn get<T: Clone + 'static>(&mut self, key: &str) -> Option<Cached<T>> {
+        let now = Instant::now();
+        let entry = self.entries.get_mut(key)?;
+        if now.duration_since(entry.last_used) > self.config.cache_time {
+            self.entries.remove(key);
+            return None;
+        }
+        let value = entry.value.downcast_ref::<T>()?.clone();
+        entry.last_used = now;
+        Some(Cached {
+            value,
+            is_stale: entry.invalidated
+                || now.duration_since(entry.fetched_at) >= self.config.stale_time,
+        })
+    }
+
+    /// Whether a value younger than the stale time is stored under `key`
+    pub fn is_fresh(&self, key: &str) -> bool {
+        let now = Instant::now();
+        self.entries.get(key).is_some_and(|entry| {
+            now.duration_since(entry.last_used) <= self.config.cache_time
+                && now.duration_since(entry.fetched_at) < self.config.stale_time
+                && !entry.invalidated
+        })
+    }
+
+    /// Stores a freshly fetched value under `key`
+    pub fn insert<T: 'static>(&mut self, key: impl Into<String>, value: T) {
+        let now = Instant::now();
+        self.entries.insert(
+            key.into(),
+            CacheEntry {
+                value: Rc::new(value),
+                fetched_at: now,
+                last_used: now,
+                invalidated: false,
+            },
+        );
+        self.collect_garbage();
+    }
+
+    /// Changes the value stored under `key` in place, keeping its age.
+    ///
+    /// Returns `false` if there is no value of type `T` under `key`.
+    pub fn update<T: Clone + 'static>(&mut self, key: &str, f: impl FnOnce(&mut T)) -> bool {
+        let Some(entry) = self.entries.get_mut(key) else {
+            return false;
+        };
+        let Some(value) = entry.value.downcast_ref::<T>() else {
+            return false;
+        };
+        let mut value = value.clone();
+        f(&mut value);
+        entry.value = Rc::new(value);
+        entry.last_used = Instant::now();
+        true
+    }
+
+    /// Removes the entry stored under `key`
+    pub fn remove(&mut self, key: &str) {
+        self.entries.remove(key);
+    }
+
+    /// Marks the value stored under `key` stale, so it's refetched before being used as
+    /// fresh. Returns `false` if there is none.
+    pub fn invalidate(&mut self, key: &str) -> bool {
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                entry.invalidated = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The keys stored, in no particular order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drops expired entries, then the least recently used ones beyond `max_entries`
+    pub fn collect_garbage(&mut self) {
+        let now = Instant::now();
+        let cache_time = self.config.cache_time;
+        self.entries
+            .retain(|_, entry| now.duration_since(entry.last_used) <= cache_time);
+
+        if self.entries.len() > self.config.max_entries {
+            let mut by_use: Vec<(Instant, String)> = self
+                .entries
+                .iter()
+                .map(|(key, entry)| (entry.last_used, key.clone()))
+                .collect();
+            by_use.sort();
+            let excess = self.entries.len() - self.config.max_entries;
+            for (_, key) in by_use.into_iter().take(excess) {
+                self.entries.remove(&key);
+            }
+        }
+    }
+}
+
+type Listener = Rc<dyn Fn()>;
+
+/// Outcome of a request other callers wait for
+enum Shared {
+    Running(Vec<Waker>),
+    Done(Result<Rc<dyn Any>, String>),
+    /// The request was dropped before it finished
+    Abandoned,
+}
+
+/// Marks the request of `key` as done, or abandoned if it's dropped before finishing
+struct InFlightGuard {
+    scope: Rc<ScopeState>,
+    key: String,
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl InFlightGuard {
+    fn finish(&self, outcome: Shared) {
+        self.scope.in_flight.borrow_mut().remove(&self.key);
+        let previous = std::mem::replace(&mut *self.shared.borrow_mut(), outcome);
+        if let Shared::Running(wakers) = previous {
+            wakers.into_iter().for_each(Waker::wake);
+        }
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if matches!(*self.shared.borrow(), Shared::Running(_)) {
+            self.finish(Shared::Abandoned);
+        }
+    }
+}
+
+#[derive(Default)]
+struct ScopeState {
+    cache: RefCell<QueryCache>,
+    listeners: RefCell<HashMap<String, Vec<(u64, Listener)>>>,
+    /// Called when their key is invalidated, e.g. by the mounted hooks to refetch
+    invalidation_listeners: RefCell<HashMap<String, Vec<(u64, Listener)>>>,
+    next_listener: Cell<u64>,
+    /// Requests running, by key
+    in_flight: RefCell<HashMap<String, Rc<RefCell<Shared>>>>,
+}
+
+impl ScopeState {
+    fn listeners(&self, kind: ListenerKind) -> &RefCell<HashMap<String, Vec<(u64, Listener)>>> {
+        match kind {
+            ListenerKind::Change => &self.listeners,
+            ListenerKind::Invalidation => &self.invalidation_listeners,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ListenerKind {
+    Change,
+    Invalidation,
+}
+
+/// A cache together with the subscribers of its entries and the requests running for
+/// them, the query client of the generated hooks.
+///
+/// The generated hooks use the scope provided by the closest `<QueryScope>` (`client`
+/// feature) or `yew::ContextProvider<CacheScope>` above them, and the global scope
+/// otherwise. Mounting a scope gives its subtree an isolated cache, e.g. in tests, preview
+/// panes or dashboards showing several tenants at once.
+#[derive(Clone, Default)]
+pub struct CacheScope(Rc<ScopeState>);
+
+/// [`CacheScope`] under the name query libraries give it
+pub type QueryClient = CacheScope;
+
+impl PartialEq for CacheScope {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl std::fmt::Debug for CacheScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheScope")
+            .field("config", &self.with(|cache| cache.config().clone()))
+            .field("len", &self.with(|cache| cache.len()))
+            .finish()
+    }
+}
+
+thread_local! {
+    static GLOBAL: CacheScope = CacheScope::default();
+}
+
+impl CacheScope {
+    /// An empty scope, isolated from every other one
+    pub fn new(config: CacheConfig) -> Self {
+        let scope = Self::default();
+        scope.configure(config);
+        scope
+    }
+
+    /// The scope used outside of any `<QueryScope>`
+    pub fn global() -> Self {
+        GLOBAL.with(Clone::clone)
+    }
+
+    /// Runs `f` with the cache of this scope
+    pub fn with<R>(&self, f: impl FnOnce(&mut QueryCache) -> R) -> R {
+        f(&mut self.0.cache.borrow_mut())
+    }
+
+    /// Sets the limits of the cache
+    pub fn configure(&self, config: CacheConfig) {
+        self.with(|cache| cache.set_config(config));
+    }
+
+    /// Reads a value from the cache
+    pub fn get<T: Clone + 'static>(&self, key: &str) -> Option<Cached<T>> {
+        self.with(|cache| cache.get(key))
+    }
+
+    /// Stores a value in the cache
+    pub fn insert<T: 'static>(&self, key: impl Into<String>, value: T) {
+        self.with(|cache| cache.insert(key, value));
+    }
+
+    /// Changes a value of the cache in place and notifies its subscribers
+    pub fn update<T: Clone + 'static>(&self, key: &str, f: impl FnOnce(&mut T)) -> bool {
+        let updated = self.with(|cache| cache.update(key, f));
+        if updated {
+            self.notify(key);
+        }
+        updated
+    }
+
+    /// Removes a value from the cache
+    pub fn remove(&self, key: &str) {
+        self.with(|cache| cache.remove(key));
+    }
+
+    /// Empties the cache
+    pub fn clear(&self) {
+        self.with(|cache| cache.clear());
+    }
+
+    /// Calls `f` whenever the value under `key` is changed by [`CacheScope::update`] or an
+    /// [`OptimisticUpdate`], until the returned subscription is dropped
+    pub fn subscribe(&self, key: impl Into<String>, f: impl Fn() + 'static) -> CacheSubscription {
+        self.add_listener(ListenerKind::Change, key.into(), Rc::new(f))
+    }
+
+    /// Calls `f` whenever `key` is invalidated, whether a value is cached under it or not,
+    /// until the returned subscription is dropped. The generated hooks of GET endpoints
+    /// refetch then.
+    pub fn subscribe_invalidation(
+        &self,
+        key: impl Into<String>,
+        f: impl Fn() + 'static,
+    ) -> CacheSubscription {
+        self.add_listener(ListenerKind::Invalidation, key.into(), Rc::new(f))
+    }
+
+    fn add_listener(&self, kind: ListenerKind, key: String, f: Listener) -> CacheSubscription {
+        let id = self.0.next_listener.get();
+        self.0.next_listener.set(id + 1);
+        self.0
+            .listeners(kind)
+            .borrow_mut()
+            .entry(key.clone())
+            .or_default()
+            .push((id, f));
+        CacheSubscription {
+            scope: Rc::downgrade(&self.0),
+            kind,
+            key,
+            id,
+        }
+    }
+
+    /// Marks the responses of `path` stale, whatever their query string, and refetches
+    /// the mounted hooks showing them, e.g. after a mutation changed them.
+    ///
+    /// `path` is the path of the endpoint, e.g. `/api/todos`, without the base path or the
+    /// API base the hooks send their requests to. Endpoints with a `base` of their own are
+    /// matched with the path of that base, e.g. `/v2/api/todos`.
+    pub fn invalidate(&self, path: &str) {
+        self.invalidate_where(|key_path| key_path == path);
+    }
+
+    /// Like [`invalidate`](Self::invalidate) for every path starting with `prefix`, e.g.
+    /// `/api/todos` for `/api/todos/7` too
+    pub fn invalidate_matching(&self, prefix: &str) {
+        self.invalidate_where(|key_path| key_path.starts_with(prefix));
+    }
+
+    fn invalidate_where(&self, matches: impl Fn(&str) -> bool) {
+        let prefix = crate::base_path::api_base_path();
+        let matches = |key: &str| matches(key_path(key, &prefix));
+        let mut keys: Vec<String> = self.with(|cache| {
+            cache
+                .keys()
+                .filter(|key| matches(key))
+                .map(str::to_string)
+                .collect()
+        });
+        keys.extend(
+            self.0
+                .invalidation_listeners
+                .borrow()
+                .keys()
+                .filter(|key| matches(key) && !keys.contains(key))
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+        for key in keys {
+            self.with(|cache| cache.invalidate(&key));
+            self.call_listeners(ListenerKind::Invalidation, &key);
+        }
+    }
+
+    /// Runs `fetch` unless a request for `key` is already running in this scope, in which
+    /// case its result is shared. Components mounting together, or refetching at once,
+    /// then send one request.
+    ///
+    /// If the running request is dropped before it finishes, or returns another type,
+    /// `fetch` runs instead.
+    pub async fn dedupe<T, F>(&self, key: &str, fetch: F) -> Result<T, String>
+    where
+        T: Clone + 'static,
+        F: Future<Output = Result<T, String>>,
+    {
+        let running = self.0.in_flight.borrow().get(key).cloned();
+        if let Some(shared) = running {
+            let outcome = std::future::poll_fn(|cx| match &mut *shared.borrow_mut() {
+                Shared::Running(wakers) => {
+                    wakers.push(cx.waker().clone());
+                    Poll::Pending
+                }
+                Shared::Done(result) => Poll::Ready(Some(result.clone())),
+                Shared::Abandoned => Poll::Ready(None),
+            })
+            .await;
+            match outcome {
+                Some(Ok(value)) => {
+                    if let Some(value) = value.downcast_ref::<T>() {
+                        return Ok(value.clone());
+                    }
+                }
+                Some(Err(error)) => return Err(error),
+                None => {}
+            }
+            return fetch.await;
+        }
+
+        let guard = InFlightGuard {
+            scope: self.0.clone(),
+            key: key.to_string(),
+            shared: Rc::new(RefCell::new(Shared::Running(Vec::new()))),
+        };
+        self.0
+            .in_flight
+            .borrow_mut()
+            .insert(key.to_string(), guard.shared.clone());
+        let result = fetch.await;
+        guard.finish(Shared::Done(
+            result.clone().map(|value| Rc::new(value) as Rc<dyn Any>),
+        ));
+        result
+    }
+
+    /// Whether a request for `key` is running in this scope
+    pub fn is_fetching(&self, key: &str) -> bool {
+        self.0.in_flight.borrow().contains_key(key)
+    }
+
+    /// Calls the subscribers of `key`
+    pub fn notify(&self, key: &str) {
+        self.call_listeners(ListenerKind::Change, key);
+    }
+
+    fn call_listeners(&self, kind: ListenerKind, key: &str) {
+        // Cloned out so subscribers can read the cache and subscribe themselves
+        let subscribers: Vec<Listener> = self
+            .0
+            .listeners(kind)
+            .borrow()
+            .get(key)
+            .map(|subscribers| subscribers.iter().map(|(_, f)| f.clone()).collect())
+            .unwrap_or_default();
+        for subscriber in subscribers {
+            subscriber();
+        }
+    }
+}
+
+/// Runs `f` with the global cache
+pub fn with_cache<R>(f: impl FnOnce(&mut QueryCache) -> R) -> R {
+    CacheScope::global().with(f)
+}
+
+/// Sets the limits of the global cache
+pub fn configure(config: CacheConfig) {
+    CacheScope::global().configure(config);
+}
+
+/// Reads a value from the global cache
+pub fn get<T: Clone + 'static>(key: &str) -> Option<Cached<T>> {
+    CacheScope::global().get(key)
+}
+
+/// Stores a value in the global cache
+pub fn insert<T: 'static>(key: impl Into<String>, value: T) {
+    CacheScope::global().insert(key, value);
+}
+
+/// Changes a value of the global cache in place and notifies its subscribers
+pub fn update<T: Clone + 'static>(key: &str, f: impl FnOnce(&mut T)) -> bool {
+    CacheScope::global().update(key, f)
+}
+
+/// Removes a value from the global cache
+pub fn remove(key: &str) {
+    CacheScope::global().remove(key);
+}
+
+/// Empties the global cache, e.g. when the user logs out
+pub fn clear() {
+    CacheScope::global().clear();
+}
+
+/// Marks the responses of `path` in the global cache stale and refetches the mounted hooks
+/// showing them, see [`CacheScope::invalidate`]
+pub fn invalidate(path: &str) {
+    CacheScope::global().invalidate(path);
+}
+
+/// Invalidates the responses of every path starting with `prefix` in the global cache
+pub fn invalidate_matching(prefix: &str) {
+    CacheScope::global().invalidate_matching(prefix);
+}
+
+/// The path of the URL a [`request_key`] was built from, without its origin, query and
+/// the `prefix` of the API base
+fn key_path<'a>(key: &'a str, prefix: &str) -> &'a str {
+    let url = key.strip_prefix("GET ").unwrap_or(key);
+    let url = url.split([' ', '?', '#']).next().unwrap_or_default();
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |start| &rest[start..]),
+        None => url,
+    };
+    match path.strip_prefix(prefix) {
+        Some(rest) if !prefix.is_empty() && rest.starts_with('/') => rest,
+        _ => path,
+    }
+}
+
+/// Key the generated hooks store the response of a GET request under
+pub fn request_key(url: &str, options: &RequestOptions) -> String {
+    let mut key = format!("GET {}", url);
+    for (name, value) in &options.query {
+        key.push_str(&format!(" {}={}", name, value));
+    }
+    key
+}
+
+/// A GET request that fills the global cache, built by the `prefetch_<function>` functions
+/// `yew_server_hook` generates for GET endpoints
+pub struct Prefetch {
+    key: String,
+    fetch: Pin<Box<dyn Future<Output = Result<(), String>>>>,
+}
+
+impl Prefetch {
+    /// `fetch` stores the response under `key`
+    pub fn new(
+        key: impl Into<String>,
+        fetch: impl Future<Output = Result<(), String>> + 'static,
+    ) -> Self {
+        Self {
+            key: key.into(),
+            fetch: Box::pin(fetch),
+        }
+    }
+
+    /// Key the response is stored under, see [`request_key`]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+/// Runs `prefetches` in parallel, e.g. right after hydration, so the first navigations
+/// find their data in the cache. Keys whose cached value is still fresh aren't fetched.
+///
+/// Returns the outcome of each prefetch, by key:
+///
+/// ```ignore
+/// let results = cache::warm_cache([
+///     prefetch_get_user(user_id),
+///     prefetch_get_products(),
+/// ])
+/// .await;
+/// ```
+pub async fn warm_cache(
+    prefetches: impl IntoIterator<Item = Prefetch>,
+) -> Vec<(String, Result<(), String>)> {
+    let fetches = prefetches.into_iter().map(|prefetch| async move {
+        let fresh = CacheScope::global().with(|cache| cache.is_fresh(&prefetch.key));
+        let result = if fresh { Ok(()) } else { prefetch.fetch.await };
+        (prefetch.key, result)
+    });
+    futures_util::future::join_all(fetches).await
+}
+
+/// Keeps a [`subscribe`] callback registered until it is dropped
+pub struct CacheSubscription {
+    scope: Weak<ScopeState>,
+    kind: ListenerKind,
+    key: String,
+    id: u64,
+}
+
+impl Drop for CacheSubscription {
+    fn drop(&mut self) {
+        let Some(scope) = self.scope.upgrade() else {
+            return;
+        };
+        let mut listeners = scope.listeners(self.kind).borrow_mut();
+        if let Some(subscribers) = listeners.get_mut(&self.key) {
+            subscribers.retain(|(id, _)| *id != self.id);
+            if subscribers.is_empty() {
+                listeners.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// Calls `f` whenever the value under `key` in the global cache is changed by [`update`]
+/// or an [`OptimisticUpdate`], until the returned subscription is dropped.
+///
+/// The generated hooks of GET endpoints subscribe to their response, so they show
+/// patched values right away.
+pub fn subscribe(key: impl Into<String>, f: impl Fn() + 'static) -> CacheSubscription {
+    CacheScope::global().subscribe(key, f)
+}
+
+/// Calls the subscribers of `key` in the global cache
+pub fn notify(key: &str) {
+    CacheScope::global().notify(key);
+}
+
+/// Cache changes made before a mutation's response arrives, undone if it fails.
+///
+/// The client functions of endpoints declared with `optimistic = "..."` pass one to that
+/// function, then commit it when the request succeeds and roll it back otherwise:
+///
+/// ```
+/// use yew_extra::cache::{self, OptimisticUpdate};
+///
+/// cache::insert("GET /api/todos", vec!["Write docs".to_string()]);
+///
+/// let mut optimistic = OptimisticUpdate::new();
+/// optimistic.update::<Vec<String>>("GET /api/todos", |todos| todos.push("Ship".to_string()));
+/// assert_eq!(cache::get::<Vec<String>>("GET /api/todos").unwrap().value.len(), 2);
+///
+/// // The request failed
+/// optimistic.rollback();
+/// assert_eq!(cache::get::<Vec<String>>("GET /api/todos").unwrap().value.len(), 1);
+/// ```
+///
+/// Client functions don't know the component calling them, so these changes apply to
+/// the global cache; use [`OptimisticUpdate::in_scope`] to patch a scoped one.
+pub struct OptimisticUpdate {
+    scope: CacheScope,
+    /// Entries as they were before their first change
+    snapshots: Vec<(String, Option<CacheEntry>)>,
+}
+
+impl Default for OptimisticUpdate {
+    fn default() -> Self {
+        Self::in_scope(CacheScope::global())
+    }
+}
+
+impl OptimisticUpdate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Changes made to the cache of `scope` instead of the global one
+    pub fn in_scope(scope: CacheScope) -> Self {
+        Self {
+            scope,
+            snapshots: Vec::new(),
+        }
+    }
+
+    /// Changes the value under `key` like [`update`], remembering the previous value
+    pub fn update<T: Clone + 'static>(&mut self, key: &str, f: impl FnOnce(&mut T)) -> bool {
+        if !self.snapshots.iter().any(|(snapshot, _)| snapshot == key) {
+            let entry = self.scope.with(|cache| cache.entries.get(key).cloned());
+            self.snapshots.push((key.to_string(), entry));
+        }
+        self.scope.update(key, f)
+    }
+
+    /// Keeps the changes, once the server accepted the mutation
+    pub fn commit(self) {}
+
+    /// Restores the values from before the changes
+    pub fn rollback(self) {
+        for (key, entry) in self.snapshots {
+            self.scope.with(|cache| match entry {
+                Some(entry) => {
+                    cache.entries.insert(key.clone(), entry);
+                }
+                None => cache.remove(&key),
+            });
+            self.scope.notify(&key);
+        }
+    }
+
+    /// Commits the changes if `success`, rolls them back otherwise
+    pub fn settle(self, success: bool) {
+        if success {
+            self.commit();
+        } else {
+            self.rollback();
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+pub use scope::{QueryScope, QueryScopeProps};
+
+#[cfg(feature = "client")]
+mod scope {
+    use super::{CacheConfig, CacheScope};
+    use yew::prelude::*;
+
+    #[derive(Properties, PartialEq)]
+    pub struct QueryScopeProps {
+        /// Limits of the scoped cache, read when the scope mounts
+        #[prop_or_default]
+        pub config: CacheConfig,
+        #[prop_or_default]
+        pub children: Html,
+    }
+
+    /// Gives its children an isolated cache, dropped when the scope unmounts:
+    ///
+    /// ```ignore
+    /// html! {
+    ///     <QueryScope>
+    ///         <TenantDashboard tenant={tenant} />
+    ///     </QueryScope>
+    /// }
+    /// ```
+    #[function_component(QueryScope)]
+    pub fn query_scope(props: &QueryScopeProps) -> Html {
+        let scope = use_state(|| CacheScope::new(props.config.clone()));
+
+        html! {
+            <ContextProvider<CacheScope> context={(*scope).clone()}>
+                { props.children.clone() }
+            </ContextProvider<CacheScope>>
+        }
+    }
+}
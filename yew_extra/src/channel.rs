@@ -0,0 +1,368 @@
+//! Experimental bidirectional channels over WebTransport, falling back to WebSocket.
+//!
+//! Where [`subscription`](crate::subscription) streams updates from the server, a channel
+//! carries messages both ways with low latency. It has two kinds of messages: reliable,
+//! ordered messages on a bidirectional stream, and datagrams, which may be lost or arrive
+//! out of order but aren't held up by earlier ones, for cursors, game state and other
+//! updates that are soon outdated. Browsers without WebTransport, or networks blocking
+//! HTTP/3, get a WebSocket instead, where datagrams are sent as regular messages:
+//!
+//! ```ignore
+//! use yew_extra::channel::{Channel, ChannelEvent, ChannelOptions};
+//!
+//! let options = ChannelOptions::new(
+//!     "https://example.com:4433/cursors",
+//!     "wss://example.com/cursors",
+//! );
+//! let channel = Channel::connect(options, move |event| match event {
+//!     ChannelEvent::Datagram(bytes) | ChannelEvent::Message(bytes) => move_cursor(&bytes),
+//!     ChannelEvent::Closed => show_offline(),
+//! })
+//! .await?;
+//!
+//! channel.send_datagram(&position).await?;
+//! ```
+//!
+//! A WebTransport stream is a stream of bytes, so its messages are framed with
+//! [`encode_frame`] and split again with a [`FrameDecoder`], in the browser and on the
+//! server. A WebSocket message is one message.
+//!
+//! The API may change while browsers settle their WebTransport support.
+
+use std::fmt;
+
+#[cfg(feature = "client")]
+pub use client::{webtransport_supported, Channel, ChannelEvent, ChannelOptions, ChannelTransport};
+
+/// An error opening or using a `Channel`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelError(pub String);
+
+impl fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ChannelError {}
+
+/// Prefixes `message` with its length as a 4-byte big-endian integer, the framing of the
+/// messages on a WebTransport stream
+///
+/// # Panics
+///
+/// Panics if the message is 4 GiB or larger.
+pub fn encode_frame(message: &[u8]) -> Vec<u8> {
+    let length = u32::try_from(message.len()).expect("A channel message must be below 4 GiB");
+    let mut frame = Vec::with_capacity(4 + message.len());
+    frame.extend_from_slice(&length.to_be_bytes());
+    frame.extend_from_slice(message);
+    frame
+}
+
+/// Splits the chunks read from a stream into the messages of [`encode_frame`]
+#[derive(Debug, Default)]
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+}
+
+impl FrameDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a chunk, returning the messages it completes
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<Vec<u8>> {
+        self.buffer.extend_from_slice(chunk);
+        let mut messages = Vec::new();
+        let mut start = 0;
+        while let Some(header) = self.buffer.get(start..start + 4) {
+            let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+            let Some(message) = self.buffer.get(start + 4..start + 4 + length) else {
+                break;
+            };
+            messages.push(message.to_vec());
+            start += 4 + length;
+        }
+        self.buffer.drain(..start);
+        messages
+    }
+
+    /// Whether a message was started but not completed
+    pub fn has_partial(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use super::{encode_frame, ChannelError, FrameDecoder};
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+    thread_local! {
+        static WEBTRANSPORT_FAILED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// How a [`Channel`] is connected
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ChannelTransport {
+        WebTransport,
+        WebSocket,
+    }
+
+    /// What a [`Channel`] received
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ChannelEvent {
+        /// A reliable message, in the order it was sent
+        Message(Vec<u8>),
+        /// A datagram, only received over WebTransport
+        Datagram(Vec<u8>),
+        /// The connection was closed by the server or the network
+        Closed,
+    }
+
+    /// Where a [`Channel`] connects to
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ChannelOptions {
+        /// `https://` URL of the WebTransport endpoint
+        pub webtransport_url: String,
+        /// `wss://` URL of the WebSocket endpoint used as the fallback
+        pub websocket_url: String,
+    }
+
+    impl ChannelOptions {
+        pub fn new(webtransport_url: impl Into<String>, websocket_url: impl Into<String>) -> Self {
+            Self {
+                webtransport_url: webtransport_url.into(),
+                websocket_url: websocket_url.into(),
+            }
+        }
+    }
+
+    /// Whether the browser has WebTransport
+    pub fn webtransport_supported() -> bool {
+        js_sys::Reflect::has(&js_sys::global(), &"WebTransport".into()).unwrap_or(false)
+    }
+
+    /// A connection sending and receiving messages, closed when dropped
+    pub struct Channel {
+        inner: Inner,
+    }
+
+    enum Inner {
+        WebTransport {
+            transport: JsValue,
+            datagram_writer: JsValue,
+            stream_writer: JsValue,
+        },
+        WebSocket {
+            socket: web_sys::WebSocket,
+            _on_message: Closure<dyn FnMut(web_sys::MessageEvent)>,
+            _on_close: Closure<dyn FnMut()>,
+        },
+    }
+
+    impl Channel {
+        /// Connects over WebTransport if the browser supports it, and over WebSocket
+        /// otherwise or if that fails. `on_event` is called with everything received.
+        ///
+        /// After WebTransport failed once, the rest of the session uses WebSocket, as the
+        /// network likely blocks it.
+        pub async fn connect(
+            options: ChannelOptions,
+            on_event: impl Fn(ChannelEvent) + 'static,
+        ) -> Result<Channel, ChannelError> {
+            let on_event: Rc<dyn Fn(ChannelEvent)> = Rc::new(on_event);
+            if webtransport_supported() && !WEBTRANSPORT_FAILED.with(Cell::get) {
+                match connect_webtransport(&options.webtransport_url, on_event.clone()).await {
+                    Ok(channel) => return Ok(channel),
+                    Err(_) => WEBTRANSPORT_FAILED.with(|failed| failed.set(true)),
+                }
+            }
+            connect_websocket(&options.websocket_url, on_event).await
+        }
+
+        /// How the channel is connected
+        pub fn transport(&self) -> ChannelTransport {
+            match self.inner {
+                Inner::WebTransport { .. } => ChannelTransport::WebTransport,
+                Inner::WebSocket { .. } => ChannelTransport::WebSocket,
+            }
+        }
+
+        /// Sends a reliable message, received in order
+        pub async fn send(&self, message: &[u8]) -> Result<(), ChannelError> {
+            match &self.inner {
+                Inner::WebTransport { stream_writer, .. } => {
+                    write(stream_writer, &encode_frame(message)).await
+                }
+                Inner::WebSocket { socket, .. } => socket
+                    .send_with_u8_array(message)
+                    .map_err(|e| js_error("Failed to send the message", e)),
+            }
+        }
+
+        /// Sends a datagram, which may be lost or reordered. Over WebSocket, it is sent as
+        /// a regular message.
+        pub async fn send_datagram(&self, datagram: &[u8]) -> Result<(), ChannelError> {
+            match &self.inner {
+                Inner::WebTransport {
+                    datagram_writer, ..
+                } => write(datagram_writer, datagram).await,
+                Inner::WebSocket { .. } => self.send(datagram).await,
+            }
+        }
+
+        /// Closes the connection, without a [`ChannelEvent::Closed`] over WebSocket
+        pub fn close(&self) {
+            match &self.inner {
+                Inner::WebTransport { transport, .. } => {
+                    let _ = call(transport, "close", &[]);
+                }
+                Inner::WebSocket { socket, .. } => {
+                    // The handlers are dropped with the channel
+                    socket.set_onmessage(None);
+                    socket.set_onclose(None);
+                    let _ = socket.close();
+                }
+            }
+        }
+    }
+
+    impl Drop for Channel {
+        fn drop(&mut self) {
+            self.close();
+        }
+    }
+
+    fn js_error(context: &str, e: JsValue) -> ChannelError {
+        ChannelError(format!("{}: {:?}", context, e))
+    }
+
+    fn get(target: &JsValue, name: &str) -> Result<JsValue, JsValue> {
+        js_sys::Reflect::get(target, &JsValue::from_str(name))
+    }
+
+    /// Calls the method `name` of `target`
+    fn call(target: &JsValue, name: &str, args: &[JsValue]) -> Result<JsValue, JsValue> {
+        let method: js_sys::Function = get(target, name)?.dyn_into()?;
+        method.apply(target, &args.iter().collect::<js_sys::Array>())
+    }
+
+    /// Waits for `value` if it's a promise
+    async fn settle(value: JsValue) -> Result<JsValue, JsValue> {
+        JsFuture::from(js_sys::Promise::resolve(&value)).await
+    }
+
+    async fn write(writer: &JsValue, bytes: &[u8]) -> Result<(), ChannelError> {
+        let chunk = js_sys::Uint8Array::from(bytes);
+        let written =
+            call(writer, "write", &[chunk.into()]).map_err(|e| js_error("Failed to send", e))?;
+        settle(written)
+            .await
+            .map(drop)
+            .map_err(|e| js_error("Failed to send", e))
+    }
+
+    /// Calls `on_chunk` with every chunk of the stream `reader` reads, until it ends
+    async fn read_chunks(reader: JsValue, mut on_chunk: impl FnMut(Vec<u8>)) {
+        async fn read(reader: &JsValue) -> Result<JsValue, JsValue> {
+            settle(call(reader, "read", &[])?).await
+        }
+
+        while let Ok(result) = read(&reader).await {
+            if get(&result, "done").map_or(true, |done| done.is_truthy()) {
+                break;
+            }
+            if let Ok(value) = get(&result, "value") {
+                on_chunk(js_sys::Uint8Array::new(&value).to_vec());
+            }
+        }
+    }
+
+    async fn connect_webtransport(
+        url: &str,
+        on_event: Rc<dyn Fn(ChannelEvent)>,
+    ) -> Result<Channel, JsValue> {
+        let constructor: js_sys::Function = get(&js_sys::global(), "WebTransport")?.dyn_into()?;
+        let transport = js_sys::Reflect::construct(&constructor, &js_sys::Array::of1(&url.into()))?;
+        settle(get(&transport, "ready")?).await?;
+
+        let datagrams = get(&transport, "datagrams")?;
+        let datagram_writer = call(&get(&datagrams, "writable")?, "getWriter", &[])?;
+        let datagram_reader = call(&get(&datagrams, "readable")?, "getReader", &[])?;
+        let stream = settle(call(&transport, "createBidirectionalStream", &[])?).await?;
+        let stream_writer = call(&get(&stream, "writable")?, "getWriter", &[])?;
+        let stream_reader = call(&get(&stream, "readable")?, "getReader", &[])?;
+
+        let on_datagram = on_event.clone();
+        spawn_local(async move {
+            read_chunks(datagram_reader, |datagram| {
+                on_datagram(ChannelEvent::Datagram(datagram))
+            })
+            .await;
+        });
+        spawn_local(async move {
+            let mut decoder = FrameDecoder::new();
+            read_chunks(stream_reader, |chunk| {
+                for message in decoder.push(&chunk) {
+                    on_event(ChannelEvent::Message(message));
+                }
+            })
+            .await;
+            on_event(ChannelEvent::Closed);
+        });
+
+        Ok(Channel {
+            inner: Inner::WebTransport {
+                transport,
+                datagram_writer,
+                stream_writer,
+            },
+        })
+    }
+
+    async fn connect_websocket(
+        url: &str,
+        on_event: Rc<dyn Fn(ChannelEvent)>,
+    ) -> Result<Channel, ChannelError> {
+        let socket = web_sys::WebSocket::new(url)
+            .map_err(|e| js_error("Failed to open the WebSocket", e))?;
+        socket.set_binary_type(web_sys::BinaryType::Arraybuffer);
+        let opened = js_sys::Promise::new(&mut |resolve, reject| {
+            socket.set_onopen(Some(&resolve));
+            socket.set_onerror(Some(&reject));
+        });
+        let opened = JsFuture::from(opened).await;
+        socket.set_onopen(None);
+        socket.set_onerror(None);
+        opened.map_err(|e| js_error("Failed to connect the WebSocket", e))?;
+
+        let on_message = Closure::<dyn FnMut(web_sys::MessageEvent)>::new({
+            let on_event = on_event.clone();
+            move |event: web_sys::MessageEvent| {
+                let data = event.data();
+                let message = match data.as_string() {
+                    Some(text) => text.into_bytes(),
+                    None => js_sys::Uint8Array::new(&data).to_vec(),
+                };
+                on_event(ChannelEvent::Message(message));
+            }
+        });
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        let on_close = Closure::<dyn FnMut()>::new(move || on_event(ChannelEvent::Closed));
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+        Ok(Channel {
+            inner: Inner::WebSocket {
+                socket,
+                _on_message: on_message,
+                _on_close: on_close,
+            },
+        })
+    }
+}
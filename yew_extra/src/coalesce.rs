@@ -0,0 +1,160 @@
+//! Coalescing of identical concurrent GET requests.
+//!
+//! The generated wrappers of endpoints declared with `coalesce = true` run their handler
+//! through [`run_coalesced`]. While a request is being handled, identical requests (same
+//! path and query string) wait for it and get a copy of its response instead of running
+//! the function again, which protects expensive reads from bursts of the same request.
+//!
+//! Requests are matched on the URL only, so only coalesce endpoints whose response
+//! doesn't depend on the caller (cookies, authorization headers, ...). Responses that are
+//! for their caller only, i.e. set cookies or are marked `Cache-Control: private`, and
+//! bodies over [`MAX_SHARED_BODY`] are never shared: the waiting requests run their own
+//! handler instead.
+
+use axum::body::{Body, Bytes};
+use axum::http::{header, HeaderMap, HeaderValue, Request, Response, StatusCode};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use futures_util::{stream, StreamExt};
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Header set on responses that were shared from another request
+pub const COALESCED_HEADER: &str = "x-yew-coalesced";
+
+/// Size limit of the response bodies buffered for sharing. Bigger responses go to the
+/// leading request only.
+pub const MAX_SHARED_BODY: usize = 2 * 1024 * 1024;
+
+/// A response buffered so every waiting request can get a copy
+struct SharedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+type Waiter = watch::Receiver<Option<Arc<SharedResponse>>>;
+
+/// Requests currently being handled, keyed by method, path and query string
+static IN_FLIGHT: Lazy<DashMap<String, Waiter>> = Lazy::new(DashMap::new);
+
+/// Removes the in-flight entry once the leading request is done or dropped
+struct InFlightGuard(String);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        IN_FLIGHT.remove(&self.0);
+    }
+}
+
+/// Returns the key identical requests share: method, path and query string
+pub fn coalesce_key<B>(req: &Request<B>) -> String {
+    let uri = req
+        .uri()
+        .path_and_query()
+        .map(|path_and_query| path_and_query.as_str())
+        .unwrap_or_else(|| req.uri().path());
+    format!("{} {}", req.method(), uri)
+}
+
+/// Runs `handler` unless an identical request is already running, in which case its
+/// response is shared.
+///
+/// If the running request is dropped before it responds (e.g. because its client
+/// disconnected), the waiting requests run their own handler.
+pub async fn run_coalesced<F>(key: String, handler: F) -> Response<Body>
+where
+    F: Future<Output = Response<Body>>,
+{
+    let sender = match IN_FLIGHT.entry(key.clone()) {
+        Entry::Occupied(entry) => {
+            let waiter = entry.get().clone();
+            drop(entry);
+            return match wait(waiter).await {
+                Some(shared) => shared_response(&shared),
+                None => handler.await,
+            };
+        }
+        Entry::Vacant(entry) => {
+            let (sender, receiver) = watch::channel(None);
+            entry.insert(receiver);
+            sender
+        }
+    };
+    let guard = InFlightGuard(key);
+
+    let response = handler.await;
+    if is_per_caller(response.headers()) {
+        // Dropping the sender sends the waiting requests to their own handler
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+
+    let mut chunks = body.into_data_stream();
+    let mut buffered = Vec::new();
+    let mut size = 0;
+    while let Some(chunk) = chunks.next().await {
+        match chunk {
+            Ok(chunk) => {
+                size += chunk.len();
+                buffered.push(chunk);
+                if size > MAX_SHARED_BODY {
+                    // Too big to keep around, stream the rest to the leading request only
+                    let body = stream::iter(buffered.into_iter().map(Ok)).chain(chunks);
+                    return Response::from_parts(parts, Body::from_stream(body));
+                }
+            }
+            Err(e) => {
+                let mut response =
+                    Response::new(Body::from(format!("Failed to read response: {}", e)));
+                *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                return response;
+            }
+        }
+    }
+    let body = Bytes::from(buffered.concat());
+
+    // Requests arriving from now on run the handler again
+    drop(guard);
+    let _ = sender.send(Some(Arc::new(SharedResponse {
+        status: parts.status,
+        headers: parts.headers.clone(),
+        body: body.clone(),
+    })));
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Whether the response was made for its caller only: it sets cookies, e.g. a session, or
+/// is marked `Cache-Control: private`
+fn is_per_caller(headers: &HeaderMap) -> bool {
+    headers.contains_key(header::SET_COOKIE)
+        || headers
+            .get_all(header::CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|directive| directive.trim().eq_ignore_ascii_case("private"))
+}
+
+/// Waits for the leading request, returning `None` if it was dropped without responding
+async fn wait(mut waiter: Waiter) -> Option<Arc<SharedResponse>> {
+    loop {
+        if let Some(shared) = waiter.borrow_and_update().as_ref() {
+            return Some(shared.clone());
+        }
+        waiter.changed().await.ok()?;
+    }
+}
+
+fn shared_response(shared: &SharedResponse) -> Response<Body> {
+    let mut response = Response::new(Body::from(shared.body.clone()));
+    *response.status_mut() = shared.status;
+    *response.headers_mut() = shared.headers.clone();
+    response
+        .headers_mut()
+        .insert(COALESCED_HEADER, HeaderValue::from_static("true"));
+    response
+}
@@ -0,0 +1,35 @@
+//! Custom media types for the responses of an endpoint.
+//!
+//! Endpoints declared with `content_type = "application/vnd.myapp.v2+json"` answer with
+//! that media type instead of `application/json`, and their generated clients send it as
+//! the `Accept` header, for APIs versioned by media type:
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/orders", method = "GET", content_type = "application/vnd.shop.v2+json")]
+//! pub async fn list_orders() -> Result<Vec<Order>, AppError> { ... }
+//! ```
+//!
+//! Only successful JSON responses are stamped, errors keep `application/json` so every
+//! client can read them.
+
+use axum::body::Body;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{HeaderValue, Response};
+
+/// Replaces the `application/json` content type of a successful `response` with
+/// `content_type`. Other responses are returned as they are.
+pub fn with_content_type(
+    mut response: Response<Body>,
+    content_type: &'static str,
+) -> Response<Body> {
+    let json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if response.status().is_success() && json {
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    }
+    response
+}
@@ -0,0 +1,150 @@
+//! Request context passed explicitly to server functions.
+//!
+//! [`extract()`](crate::extract) finds the request through tokio's task-local storage,
+//! which needs a tokio runtime. A server function can take a [`RequestContext`] parameter
+//! instead; `yew_server_hook` hands it the request parts
+//! directly and leaves it out of the parameters sent by the client. This is the only way
+//! to extract request data with the `workers` feature, e.g. on Cloudflare Workers:
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/me", method = "GET")]
+//! pub async fn get_me(mut cx: RequestContext) -> Result<User, AppError> {
+//!     let jar: CookieJar = cx.extract().await?;
+//!     // ...
+//! }
+//! ```
+
+use crate::ValidationErrors;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use std::convert::Infallible;
+use std::fmt::Debug;
+
+/// Error type for extraction failures
+#[derive(Debug)]
+pub enum ExtractError {
+    /// No request parts were found in context
+    MissingParts(String),
+    /// The request body wasn't kept for the server function
+    MissingBody(String),
+    /// Extraction failed
+    ExtractionFailed(String),
+    /// Parameters of the query or body couldn't be deserialized, by field
+    InvalidFields(ValidationErrors),
+}
+
+impl std::fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtractError::MissingParts(msg) => write!(f, "Missing request parts: {}", msg),
+            ExtractError::MissingBody(msg) => write!(f, "Missing request body: {}", msg),
+            ExtractError::ExtractionFailed(msg) => write!(f, "Extraction failed: {}", msg),
+            ExtractError::InvalidFields(errors) => write!(f, "Invalid parameters: {}", errors),
+        }
+    }
+}
+
+impl std::error::Error for ExtractError {}
+
+/// Body of the error responses without field errors
+#[derive(serde::Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+/// Failed extractions are the client's fault (`400 Bad Request`) unless the request wasn't
+/// provided at all, which is a setup mistake on the server (`500`)
+impl IntoResponse for ExtractError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            ExtractError::InvalidFields(errors) => {
+                return (StatusCode::BAD_REQUEST, axum::Json(errors)).into_response();
+            }
+            ExtractError::ExtractionFailed(_) => StatusCode::BAD_REQUEST,
+            ExtractError::MissingParts(_) | ExtractError::MissingBody(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        let body = ErrorBody {
+            error: self.to_string(),
+        };
+        (status, axum::Json(body)).into_response()
+    }
+}
+
+/// The request a server function is answering
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    parts: Parts,
+}
+
+impl RequestContext {
+    pub fn new(parts: Parts) -> Self {
+        Self { parts }
+    }
+
+    pub fn parts(&self) -> &Parts {
+        &self.parts
+    }
+
+    pub fn parts_mut(&mut self) -> &mut Parts {
+        &mut self.parts
+    }
+
+    /// Where the request comes from, like [`geo()`](crate::geo())
+    pub fn geo(&self) -> crate::geo::Geo {
+        crate::geo::Geo::from_headers(&self.parts.headers)
+    }
+
+    /// The pool provided to the router with `provide_db()`, like `db::use_db()`
+    pub fn db<P>(&self) -> Result<P, ExtractError>
+    where
+        P: Clone + Send + Sync + 'static,
+    {
+        self.parts.extensions.get::<P>().cloned().ok_or_else(|| {
+            ExtractError::MissingParts(format!(
+                "No {} provided, add it to the router with provide_db()",
+                std::any::type_name::<P>()
+            ))
+        })
+    }
+
+    /// Extracts data from the request like [`extract()`](crate::extract)
+    pub async fn extract<T>(&mut self) -> Result<T, ExtractError>
+    where
+        T: FromRequestParts<()>,
+        T::Rejection: Debug,
+    {
+        self.extract_with_state(&()).await
+    }
+
+    /// Extracts data from the request like [`extract_with_state()`](crate::extract_with_state)
+    pub async fn extract_with_state<T, S>(&mut self, state: &S) -> Result<T, ExtractError>
+    where
+        T: FromRequestParts<S>,
+        T::Rejection: Debug,
+    {
+        T::from_request_parts(&mut self.parts, state)
+            .await
+            .map_err(|e| ExtractError::ExtractionFailed(format!("{:?}", e)))
+    }
+}
+
+// Axum 0.7 declares its extractor traits with `async_trait`
+#[cfg_attr(
+    all(
+        feature = "axum-07",
+        not(feature = "axum-08"),
+        not(target_arch = "wasm32")
+    ),
+    axum::async_trait
+)]
+impl<S: Send + Sync> FromRequestParts<S> for RequestContext {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::new(parts.clone()))
+    }
+}
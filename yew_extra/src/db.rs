@@ -0,0 +1,239 @@
+//! Database pools handed to server functions by the request, instead of through globals.
+//!
+//! The router provides the pool once with [`provide_db`], and server functions read it
+//! with [`use_db`]:
+//!
+//! ```ignore
+//! let pool = PgPool::connect(&database_url).await?;
+//! let app = yew_extra::db::provide_db(Router::new().merge(your_generated_routes()), pool);
+//!
+//! #[yewserverhook(path = "/api/products")]
+//! pub async fn list_products() -> Result<Vec<Product>, AppError> {
+//!     let pool: PgPool = yew_extra::db::use_db().await?;
+//!     Ok(sqlx::query_as("SELECT * FROM products").fetch_all(&pool).await?)
+//! }
+//! ```
+//!
+//! The pool goes into the request extensions, so axum's `Extension<PgPool>` and
+//! [`RequestContext::db`](crate::RequestContext::db) read it too. Work that should share
+//! one connection for the whole request takes it with [`db_connection`].
+
+use crate::extract::with_request_parts;
+use crate::ExtractError;
+use axum::{Extension, Router};
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+/// Adds `pool` to every request of `router`, for [`use_db`]. Call it after the routes are
+/// merged, it only reaches the routes already there.
+pub fn provide_db<S, P>(router: Router<S>, pool: P) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    P: Clone + Send + Sync + 'static,
+{
+    router.layer(Extension(pool))
+}
+
+/// The pool given to [`provide_db`], for the request being handled
+pub async fn use_db<P>() -> Result<P, ExtractError>
+where
+    P: Clone + Send + Sync + 'static,
+{
+    with_request_parts(|parts| parts.extensions.get::<P>().cloned())
+        .await
+        .ok_or_else(|| ExtractError::MissingParts("No request parts provided".to_string()))?
+        .ok_or_else(|| {
+            ExtractError::MissingParts(format!(
+                "No {} provided, add it to the router with provide_db()",
+                std::any::type_name::<P>()
+            ))
+        })
+}
+
+/// The connection of the request, kept in the extensions of the shared parts
+struct ConnectionSlot<C>(Arc<Mutex<Option<C>>>);
+
+impl<C> Clone for ConnectionSlot<C> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// The connection of the request being handled, acquired with `acquire` on the first call
+/// and handed to the later ones, e.g. to run every query of a request in one transaction:
+///
+/// ```ignore
+/// let pool: PgPool = use_db().await?;
+/// let mut conn = db_connection(|| pool.acquire()).await?;
+/// sqlx::query("SELECT 1").execute(&mut **conn).await?;
+/// ```
+///
+/// Callers wait while another holds it, so drop it before calling a function that takes it
+/// too. The connection goes back when the request ends. Outside a request, e.g. in tests,
+/// every call acquires a connection of its own.
+pub async fn db_connection<C, E, F, Fut>(acquire: F) -> Result<DbConnection<C>, E>
+where
+    C: Send + 'static,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<C, E>>,
+{
+    let slot = with_request_parts(|parts| {
+        if let Some(slot) = parts.extensions.get::<ConnectionSlot<C>>() {
+            return slot.clone();
+        }
+        let slot = ConnectionSlot(Arc::new(Mutex::new(None)));
+        parts.extensions.insert(slot.clone());
+        slot
+    })
+    .await
+    .unwrap_or_else(|| ConnectionSlot(Arc::new(Mutex::new(None))));
+
+    let mut guard = slot.0.lock_owned().await;
+    if guard.is_none() {
+        *guard = Some(acquire().await?);
+    }
+    Ok(DbConnection(guard))
+}
+
+/// The connection of [`db_connection`], held until dropped
+pub struct DbConnection<C>(OwnedMutexGuard<Option<C>>);
+
+impl<C> Deref for DbConnection<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.0.as_ref().expect("acquired before handing it out")
+    }
+}
+
+impl<C> DerefMut for DbConnection<C> {
+    fn deref_mut(&mut self) -> &mut C {
+        self.0.as_mut().expect("acquired before handing it out")
+    }
+}
+
+#[cfg(feature = "sqlx")]
+pub use transaction::{provide_transactions, tx, Tx};
+
+#[cfg(feature = "sqlx")]
+mod transaction {
+    use crate::extract::with_request_parts;
+    use axum::extract::{Request, State};
+    use axum::http::StatusCode;
+    use axum::middleware::{from_fn_with_state, Next};
+    use axum::response::{IntoResponse, Response};
+    use axum::Router;
+    use sqlx::{Database, Pool, Transaction};
+    use std::ops::{Deref, DerefMut};
+    use std::sync::Arc;
+    use tokio::sync::{Mutex, OwnedMutexGuard};
+
+    /// The transaction of the request, begun by the first [`tx`]
+    struct TxSlot<DB: Database> {
+        pool: Pool<DB>,
+        transaction: Arc<Mutex<Option<Transaction<'static, DB>>>>,
+    }
+
+    impl<DB: Database> Clone for TxSlot<DB> {
+        fn clone(&self) -> Self {
+            Self {
+                pool: self.pool.clone(),
+                transaction: self.transaction.clone(),
+            }
+        }
+    }
+
+    /// Runs every request of `router` in a transaction of `pool`, for [`tx`]. The
+    /// transaction is committed when the response is a success and rolled back otherwise,
+    /// or when the handler panics. Call it after the routes are merged, like
+    /// [`provide_db`](super::provide_db).
+    pub fn provide_transactions<S, DB>(router: Router<S>, pool: Pool<DB>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+        DB: Database,
+    {
+        router.layer(from_fn_with_state(pool, run_in_transaction::<DB>))
+    }
+
+    async fn run_in_transaction<DB: Database>(
+        State(pool): State<Pool<DB>>,
+        mut request: Request,
+        next: Next,
+    ) -> Response {
+        let slot = TxSlot {
+            pool,
+            transaction: Arc::new(Mutex::new(None)),
+        };
+        request.extensions_mut().insert(slot.clone());
+        let response = next.run(request).await;
+
+        // Server functions holding the transaction past their response drop it here
+        let Some(transaction) = slot.transaction.lock().await.take() else {
+            return response;
+        };
+        let status = response.status();
+        if status.is_client_error() || status.is_server_error() {
+            let _ = transaction.rollback().await;
+            return response;
+        }
+        match transaction.commit().await {
+            Ok(()) => response,
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to commit the transaction: {}", e),
+            )
+                .into_response(),
+        }
+    }
+
+    /// The transaction of the request being handled, begun on the first call in routers
+    /// given to [`provide_transactions`]:
+    ///
+    /// ```ignore
+    /// #[yewserverhook(path = "/api/orders", method = "POST")]
+    /// pub async fn place_order(order: NewOrder) -> Result<Order, AppError> {
+    ///     let mut tx = yew_extra::tx::<Postgres>().await?;
+    ///     let order = insert_order(&mut **tx, &order).await?;
+    ///     reserve_stock(&mut **tx, &order).await?;
+    ///     Ok(order)
+    /// }
+    /// ```
+    ///
+    /// An `Err` of the server function rolls back everything done through it. Callers wait
+    /// while another holds it, so drop it before calling a function that takes it too.
+    pub async fn tx<DB: Database>() -> Result<Tx<DB>, sqlx::Error> {
+        let slot = with_request_parts(|parts| parts.extensions.get::<TxSlot<DB>>().cloned())
+            .await
+            .flatten()
+            .ok_or_else(|| {
+                sqlx::Error::Configuration(
+                    "No transaction for this request, add provide_transactions() to the router"
+                        .into(),
+                )
+            })?;
+        let mut transaction = slot.transaction.lock_owned().await;
+        if transaction.is_none() {
+            *transaction = Some(slot.pool.begin().await?);
+        }
+        Ok(Tx(transaction))
+    }
+
+    /// The transaction of [`tx`], held until dropped
+    pub struct Tx<DB: Database>(OwnedMutexGuard<Option<Transaction<'static, DB>>>);
+
+    impl<DB: Database> Deref for Tx<DB> {
+        type Target = Transaction<'static, DB>;
+
+        fn deref(&self) -> &Self::Target {
+            self.0.as_ref().expect("begun before handing it out")
+        }
+    }
+
+    impl<DB: Database> DerefMut for Tx<DB> {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            self.0.as_mut().expect("begun before handing it out")
+        }
+    }
+}
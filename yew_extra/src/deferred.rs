@@ -0,0 +1,123 @@
+//! Work that runs once the response has been sent.
+//!
+//! Server functions queue emails, webhooks or cache warming with [`after_response`] instead
+//! of `tokio::spawn`, so the user doesn't wait for them and they only start once the request
+//! is answered:
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/signup")]
+//! pub async fn signup(email: String) -> Result<User, AppError> {
+//!     let user = create_user(&email).await?;
+//!     yew_extra::after_response(async move {
+//!         let path = yew_extra::uri().await.map(|uri| uri.path().to_string());
+//!         if let Err(e) = send_welcome_email(&email).await {
+//!             log::warn!("{:?}: welcome email to {} failed: {}", path, email, e);
+//!         }
+//!     })
+//!     .await?;
+//!     Ok(user)
+//! }
+//! ```
+//!
+//! The tasks run one after the other on a task of their own, with the parts of the request
+//! provided, so `extract()` and the accessors like `uri()` work for logging. They start when
+//! the body has been sent, or dropped because the client went away.
+
+use crate::extract::{provide_request_parts, request_scope, with_request_parts};
+use crate::ExtractError;
+use axum::body::{Body, Bytes};
+use axum::http::request::Parts;
+use axum::http::Response;
+use futures_util::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+type Task = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Tasks queued with [`after_response`], kept in the extensions of the shared parts
+#[derive(Clone, Default)]
+struct AfterResponse(Arc<Mutex<Vec<Task>>>);
+
+/// Runs `task` after the response of the request being handled has been sent.
+///
+/// The handlers generated by `yew_server_hook` start the tasks with their response.
+/// Handlers written by hand pass their response through [`run_after_response`].
+pub async fn after_response<F>(task: F) -> Result<(), ExtractError>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let tasks = with_request_parts(|parts| {
+        parts
+            .extensions
+            .get_or_insert_default::<AfterResponse>()
+            .clone()
+    })
+    .await
+    .ok_or_else(|| ExtractError::MissingParts("No request parts provided".to_string()))?;
+    tasks
+        .0
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(Box::pin(task));
+    Ok(())
+}
+
+/// Starts the tasks of [`after_response`] once the body of `response` has been sent. Called
+/// before [`clear_request_parts()`], which drops them.
+pub async fn run_after_response(response: Response<Body>) -> Response<Body> {
+    let queued = with_request_parts(|parts| {
+        let tasks = parts.extensions.remove::<AfterResponse>()?;
+        Some((tasks, parts.clone()))
+    })
+    .await
+    .flatten();
+    let Some((AfterResponse(tasks), parts)) = queued else {
+        return response;
+    };
+    let tasks = std::mem::take(&mut *tasks.lock().unwrap_or_else(|e| e.into_inner()));
+    if tasks.is_empty() {
+        return response;
+    }
+
+    let (head, body) = response.into_parts();
+    let body = SpawnOnDrop {
+        body: body.into_data_stream(),
+        pending: Some((tasks, parts)),
+    };
+    Response::from_parts(head, Body::from_stream(body))
+}
+
+/// A response body starting the queued tasks when it's done with
+struct SpawnOnDrop<S> {
+    body: S,
+    pending: Option<(Vec<Task>, Parts)>,
+}
+
+impl<S: Stream<Item = Result<Bytes, axum::Error>> + Unpin> Stream for SpawnOnDrop<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.body).poll_next(cx)
+    }
+}
+
+impl<S> Drop for SpawnOnDrop<S> {
+    fn drop(&mut self) {
+        let Some((tasks, parts)) = self.pending.take() else {
+            return;
+        };
+        // Without a runtime there is nothing to run them on
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        // The spawned task has a request scope of its own
+        runtime.spawn(request_scope(async move {
+            provide_request_parts(parts).await;
+            for task in tasks {
+                task.await;
+            }
+        }));
+    }
+}
@@ -0,0 +1,216 @@
+//! Retiring endpoints with the standard deprecation headers.
+//!
+//! Endpoints declared with `deprecated(...)` answer with the `Deprecation`, `Sunset` and
+//! `Link` headers, and their generated clients warn once in the console when they are
+//! first called, so callers learn about the retirement before the endpoint goes away:
+//!
+//! ```ignore
+//! #[yewserverhook(
+//!     path = "/api/users",
+//!     method = "GET",
+//!     deprecated(since = "2025-06-01", sunset = "2025-12-01", replacement = "/api/v2/users")
+//! )]
+//! pub async fn list_users() -> Result<Vec<User>, AppError> { ... }
+//! ```
+//!
+//! Dates are written `YYYY-MM-DD` and mean midnight UTC.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+/// When an endpoint was deprecated, when it goes away and what replaces it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Date the endpoint was deprecated
+    pub since: Option<&'static str>,
+    /// Date the endpoint stops answering
+    pub sunset: Option<&'static str>,
+    /// Path or URL of the endpoint to use instead
+    pub replacement: Option<&'static str>,
+}
+
+impl Deprecation {
+    pub const fn new() -> Self {
+        Self {
+            since: None,
+            sunset: None,
+            replacement: None,
+        }
+    }
+
+    pub const fn since(mut self, date: &'static str) -> Self {
+        self.since = Some(date);
+        self
+    }
+
+    pub const fn sunset(mut self, date: &'static str) -> Self {
+        self.sunset = Some(date);
+        self
+    }
+
+    pub const fn replacement(mut self, replacement: &'static str) -> Self {
+        self.replacement = Some(replacement);
+        self
+    }
+
+    /// Value of the `Deprecation` header (RFC 9745): `@` and the Unix time of `since`,
+    /// or `true` without a valid date
+    pub fn deprecation_header(&self) -> String {
+        match self.since.and_then(unix_time) {
+            Some(time) => format!("@{}", time),
+            None => "true".to_string(),
+        }
+    }
+
+    /// Value of the `Sunset` header (RFC 8594), an HTTP date
+    pub fn sunset_header(&self) -> Option<String> {
+        self.sunset.and_then(unix_time).map(http_date)
+    }
+
+    /// Value of the `Link` header pointing at the replacement
+    pub fn link_header(&self) -> Option<String> {
+        self.replacement
+            .map(|replacement| format!("<{}>; rel=\"successor-version\"", replacement))
+    }
+
+    /// The warning the generated clients log for `method path`
+    pub fn warning(&self, method: &str, path: &str) -> String {
+        let mut warning = format!("{} {} is deprecated", method, path);
+        if let Some(since) = self.since {
+            warning.push_str(&format!(" since {}", since));
+        }
+        if let Some(sunset) = self.sunset {
+            warning.push_str(&format!(" and will be removed on {}", sunset));
+        }
+        if let Some(replacement) = self.replacement {
+            warning.push_str(&format!(", use {} instead", replacement));
+        }
+        warning
+    }
+}
+
+thread_local! {
+    static WARNED: RefCell<HashSet<(&'static str, &'static str)>> = RefCell::new(HashSet::new());
+}
+
+/// Logs the [`Deprecation::warning`] of `method path` to the console, the first time only.
+///
+/// Returns whether it was logged. The warning is only shown with the `client` feature.
+pub fn warn_once(method: &'static str, path: &'static str, deprecation: &Deprecation) -> bool {
+    let first = WARNED.with(|warned| warned.borrow_mut().insert((method, path)));
+    if first {
+        log_warning(&deprecation.warning(method, path));
+    }
+    first
+}
+
+#[cfg(feature = "client")]
+fn log_warning(warning: &str) {
+    web_sys::console::warn_1(&warning.into());
+}
+
+#[cfg(not(feature = "client"))]
+fn log_warning(_warning: &str) {}
+
+/// Seconds from the Unix epoch to midnight UTC of a `YYYY-MM-DD` date
+pub fn unix_time(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day) * 86_400)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days from 1970-01-01 to a date of the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Formats a Unix time as an HTTP date, e.g. `Mon, 01 Dec 2025 00:00:00 GMT`
+fn http_date(time: i64) -> String {
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let days = time.div_euclid(86_400);
+    let seconds = time.rem_euclid(86_400);
+
+    // The inverse of `days_from_civil`
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        WEEKDAYS[days.rem_euclid(7) as usize],
+        day,
+        MONTHS[(month - 1) as usize],
+        year,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+pub use server::with_deprecation;
+
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+mod server {
+    use super::Deprecation;
+    use axum::body::Body;
+    use axum::http::header::LINK;
+    use axum::http::{HeaderName, HeaderValue, Response};
+
+    /// Adds the `Deprecation`, `Sunset` and `Link` headers of `deprecation` to `response`
+    pub fn with_deprecation(
+        mut response: Response<Body>,
+        deprecation: &Deprecation,
+    ) -> Response<Body> {
+        let headers = response.headers_mut();
+        let values = [
+            ("deprecation", Some(deprecation.deprecation_header())),
+            ("sunset", deprecation.sunset_header()),
+        ];
+        for (name, value) in values {
+            if let Some(value) = value.and_then(|value| HeaderValue::from_str(&value).ok()) {
+                headers.insert(HeaderName::from_static(name), value);
+            }
+        }
+        if let Some(link) = deprecation.link_header() {
+            if let Ok(link) = HeaderValue::from_str(&link) {
+                headers.append(LINK, link);
+            }
+        }
+        response
+    }
+}
@@ -0,0 +1,63 @@
+//! Interactive documentation for the routes registered by `yew_server_hook`.
+//!
+//! [`docs_routes`] serves a self-contained page at `/api/docs` listing every route of
+//! [`crate::route_registry`], each with a form sending a request to it and showing the
+//! response. Nothing is loaded from a CDN, so the page works offline and behind strict
+//! content security policies that allow inline scripts:
+//!
+//! ```ignore
+//! let app = Router::new()
+//!     .merge(yew_extra::route_registry::routes())
+//!     .merge(yew_extra::docs::docs_routes());
+//! ```
+//!
+//! The page is public to anyone who can reach it, so mount it only where that's fine, e.g.
+//! in debug builds.
+
+use crate::route_registry::registered_routes;
+use axum::response::Html;
+use axum::routing::get;
+use axum::Router;
+use serde::Serialize;
+
+const DOCS_HTML: &str = include_str!("docs.html");
+
+/// Where the page reads the list of routes from
+const ROUTES_PLACEHOLDER: &str = "/*ROUTES*/[]";
+
+/// A route listed on the docs page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DocRoute {
+    pub method: String,
+    pub path: String,
+}
+
+/// Returns every registered route, in the order of the page
+pub fn documented_routes() -> Vec<DocRoute> {
+    registered_routes()
+        .into_iter()
+        .map(|route| DocRoute {
+            method: route.method.to_string(),
+            path: route.path.to_string(),
+        })
+        .collect()
+}
+
+/// Renders the docs page for the registered routes
+pub fn docs_page() -> String {
+    let routes = serde_json::to_string(&documented_routes()).unwrap_or_else(|_| "[]".into());
+    // A path containing `</script>` must not end the script early
+    DOCS_HTML.replace(ROUTES_PLACEHOLDER, &routes.replace("</", "<\\/"))
+}
+
+async fn docs_handler() -> Html<String> {
+    Html(docs_page())
+}
+
+/// Route serving the docs page at `/api/docs`
+pub fn docs_routes<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new().route("/api/docs", get(docs_handler))
+}
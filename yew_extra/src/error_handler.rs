@@ -0,0 +1,92 @@
+//! App-wide handling of failed requests made by the generated hooks and client functions.
+//!
+//! Register one handler with [`set_error_handler`] to react to failures in one place,
+//! e.g. to send the user to the login page on `401` or show a toast on server errors:
+//!
+//! ```
+//! use yew_extra::error_handler::{set_error_handler, RequestFailure};
+//!
+//! set_error_handler(|failure: &RequestFailure| {
+//!     if failure.is_unauthorized() {
+//!         // redirect to /login
+//!     } else if failure.is_server_error() {
+//!         // show a toast
+//!     }
+//! });
+//! ```
+//!
+//! The hook or client function still reports the error to its caller afterwards.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A request made by a generated hook or client function that failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestFailure {
+    /// HTTP method of the endpoint
+    pub method: &'static str,
+    /// Path of the endpoint, as declared in `#[yewserverhook]`
+    pub path: &'static str,
+    /// Status of the response, `None` if the request didn't reach the server
+    pub status: Option<u16>,
+    /// Error reported to the caller
+    pub message: String,
+}
+
+impl RequestFailure {
+    /// Whether the server answered `401 Unauthorized`
+    pub fn is_unauthorized(&self) -> bool {
+        self.status == Some(401)
+    }
+
+    /// Whether the server answered with a 5xx status
+    pub fn is_server_error(&self) -> bool {
+        self.status
+            .is_some_and(|status| (500..600).contains(&status))
+    }
+}
+
+type Handler = Rc<dyn Fn(&RequestFailure)>;
+
+thread_local! {
+    static HANDLER: RefCell<Option<Handler>> = const { RefCell::new(None) };
+}
+
+/// Calls `handler` for every failed request, replacing the previous handler
+pub fn set_error_handler(handler: impl Fn(&RequestFailure) + 'static) {
+    HANDLER.with(|current| *current.borrow_mut() = Some(Rc::new(handler)));
+}
+
+/// Removes the handler registered with [`set_error_handler`]
+pub fn clear_error_handler() {
+    HANDLER.with(|current| current.borrow_mut().take());
+}
+
+/// Passes `failure` to the registered handler, if any
+pub fn report_failure(failure: RequestFailure) {
+    // Cloned out so the handler can replace itself
+    let handler = HANDLER.with(|current| current.borrow().clone());
+    if let Some(handler) = handler {
+        handler(&failure);
+    }
+}
+
+#[cfg(feature = "client")]
+pub use client::redirect_on_unauthorized;
+
+#[cfg(feature = "client")]
+mod client {
+    use super::RequestFailure;
+
+    /// A handler sending the browser to `login_path` when a request gets `401 Unauthorized`
+    pub fn redirect_on_unauthorized(login_path: impl Into<String>) -> impl Fn(&RequestFailure) {
+        let login_path = login_path.into();
+        move |failure| {
+            if failure.is_unauthorized() {
+                if let Some(window) = web_sys::window() {
+                    let _ = window.location().set_href(&login_path);
+                }
+            }
+        }
+    }
+}
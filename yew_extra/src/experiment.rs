@@ -0,0 +1,266 @@
+//! A/B experiments with a stable bucket per visitor.
+//!
+//! An [`Experiment`] names its buckets and their weights. The first time a visitor meets
+//! it, [`bucket`] picks a bucket from a hash of the visitor's id, kept in the
+//! `yew_visitor` cookie, and keeps the bucket in a cookie of its own, so the visitor stays
+//! in it even when the weights change later:
+//!
+//! ```ignore
+//! const CHECKOUT: Experiment = Experiment::new("checkout", &[("control", 90), ("one_page", 10)]);
+//!
+//! #[yewserverhook(path = "/api/checkout", method = "POST")]
+//! pub async fn checkout(cart: Cart) -> Result<Order, AppError> {
+//!     match yew_extra::experiment::bucket(&CHECKOUT).await? {
+//!         "one_page" => one_page_checkout(cart).await,
+//!         _ => checkout_steps(cart).await,
+//!     }
+//! }
+//! ```
+//!
+//! The SSR handler hands the buckets to the app as [`ExperimentBuckets`] context, and the
+//! browser reads the same ones from the cookies when hydrating, so both render the same
+//! variant:
+//!
+//! ```ignore
+//! // SSR handler, with the request parts provided
+//! let buckets = yew_extra::experiment::buckets(&[CHECKOUT]).await?;
+//! let response = render(App { buckets }).await;
+//! yew_extra::apply_response_headers(response).await
+//!
+//! // Browser
+//! let buckets = yew_extra::experiment::current_buckets();
+//!
+//! // Components, under <ContextProvider<ExperimentBuckets> context={buckets}>
+//! let one_page = use_bucket("checkout").as_deref() == Some("one_page");
+//! ```
+//!
+//! The cookies aren't `HttpOnly`, as the browser has to read them.
+
+use crate::util::fnv1a;
+use std::collections::BTreeMap;
+use yew::prelude::*;
+
+/// Name of the cookie carrying the visitor's id
+pub const VISITOR_COOKIE: &str = "yew_visitor";
+
+/// Prefix of the cookies keeping the bucket of each experiment, followed by its name
+pub const BUCKET_COOKIE_PREFIX: &str = "yew_exp_";
+
+/// Seconds the browser keeps the cookies, 180 days
+pub const MAX_AGE: u32 = 180 * 24 * 60 * 60;
+
+/// An experiment and its buckets, with their weights
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Experiment {
+    pub name: &'static str,
+    pub buckets: &'static [(&'static str, u32)],
+}
+
+impl Experiment {
+    pub const fn new(name: &'static str, buckets: &'static [(&'static str, u32)]) -> Self {
+        Self { name, buckets }
+    }
+
+    /// The bucket of `visitor`, the same one every time. `None` without buckets.
+    pub fn assign(&self, visitor: &str) -> Option<&'static str> {
+        let total: u64 = self.buckets.iter().map(|(_, weight)| *weight as u64).sum();
+        if total == 0 {
+            return self.buckets.first().map(|(name, _)| *name);
+        }
+        // A stable hash, so buckets don't change with the platform or the toolchain
+        let key = format!("{}:{}", self.name, visitor);
+        let mut point = fnv1a(key.as_bytes()) % total;
+        for (name, weight) in self.buckets {
+            if point < *weight as u64 {
+                return Some(name);
+            }
+            point -= *weight as u64;
+        }
+        None
+    }
+
+    /// The bucket named `name`, if the experiment has it
+    pub fn find(&self, name: &str) -> Option<&'static str> {
+        self.buckets
+            .iter()
+            .map(|(bucket, _)| *bucket)
+            .find(|bucket| *bucket == name)
+    }
+
+    /// Name of the cookie keeping the bucket
+    pub fn cookie_name(&self) -> String {
+        format!("{}{}", BUCKET_COOKIE_PREFIX, self.name)
+    }
+}
+
+/// The bucket of every experiment, by experiment name, for the components
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExperimentBuckets(pub BTreeMap<String, String>);
+
+impl ExperimentBuckets {
+    pub fn get(&self, experiment: &str) -> Option<&str> {
+        self.0.get(experiment).map(String::as_str)
+    }
+
+    /// Finds the buckets kept in a `Cookie` header
+    pub fn from_cookie_header(header: &str) -> Self {
+        Self(
+            cookies(header)
+                .filter_map(|(name, value)| {
+                    let experiment = name.strip_prefix(BUCKET_COOKIE_PREFIX)?;
+                    Some((experiment.to_string(), value.to_string()))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The bucket of `experiment` from the [`ExperimentBuckets`] in the context
+#[hook]
+pub fn use_bucket(experiment: &str) -> Option<String> {
+    let buckets = use_context::<ExperimentBuckets>();
+    buckets.and_then(|buckets| buckets.get(experiment).map(str::to_string))
+}
+
+/// The `name=value` pairs of a `Cookie` header
+fn cookies(header: &str) -> impl Iterator<Item = (&str, &str)> {
+    header
+        .split(';')
+        .filter_map(|cookie| cookie.trim().split_once('='))
+}
+
+/// The `Set-Cookie` value keeping `value` in the cookie `name`
+pub fn set_cookie(name: &str, value: &str) -> String {
+    format!(
+        "{}={}; Path=/; Max-Age={}; SameSite=Lax",
+        name, value, MAX_AGE
+    )
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::{bucket, buckets};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod server {
+    use super::{cookies, set_cookie, Experiment, ExperimentBuckets, VISITOR_COOKIE};
+    use crate::extract::with_request_parts;
+    use crate::ExtractError;
+    use axum::http::header::{COOKIE, SET_COOKIE};
+    use axum::http::request::Parts;
+    use axum::http::HeaderValue;
+    use sha2::{Digest, Sha256};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// The visitor's id for the request, once it's known or made up
+    #[derive(Clone)]
+    struct Visitor(String);
+
+    fn cookie(parts: &Parts, name: &str) -> Option<String> {
+        parts
+            .headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|header| header.to_str().ok())
+            .find_map(|header| {
+                cookies(header)
+                    .find(|(cookie, _)| *cookie == name)
+                    .map(|(_, value)| value.to_string())
+            })
+    }
+
+    fn new_visitor_id() -> String {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let seed = format!(
+            "{}:{}:{}:{:?}",
+            now,
+            COUNTER.fetch_add(1, Ordering::Relaxed),
+            std::process::id(),
+            std::thread::current().id()
+        );
+        let digest = Sha256::digest(seed.as_bytes());
+        digest[..16]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// The bucket of the visitor sending the request being handled.
+    ///
+    /// The bucket kept in the visitor's cookie is used while the experiment still has it,
+    /// otherwise one is assigned and sent with the response, along with a new visitor id
+    /// if the request had none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the experiment has no buckets.
+    pub async fn bucket(experiment: &Experiment) -> Result<&'static str, ExtractError> {
+        let (bucket, cookies) = with_request_parts(|parts| {
+            let kept = cookie(parts, &experiment.cookie_name());
+            if let Some(bucket) = kept.as_deref().and_then(|kept| experiment.find(kept)) {
+                return (bucket, Vec::new());
+            }
+            let mut cookies = Vec::new();
+            let visitor = match parts.extensions.get::<Visitor>() {
+                Some(visitor) => visitor.0.clone(),
+                None => {
+                    let visitor = cookie(parts, VISITOR_COOKIE).unwrap_or_else(|| {
+                        let visitor = new_visitor_id();
+                        cookies.push(set_cookie(VISITOR_COOKIE, &visitor));
+                        visitor
+                    });
+                    parts.extensions.insert(Visitor(visitor.clone()));
+                    visitor
+                }
+            };
+            let bucket = experiment
+                .assign(&visitor)
+                .unwrap_or_else(|| panic!("The {} experiment has no buckets", experiment.name));
+            cookies.push(set_cookie(&experiment.cookie_name(), bucket));
+            // Later calls in the same request find it like a kept one
+            let kept = format!("{}={}", experiment.cookie_name(), bucket);
+            if let Ok(kept) = HeaderValue::from_str(&kept) {
+                parts.headers.append(COOKIE, kept);
+            }
+            (bucket, cookies)
+        })
+        .await
+        .ok_or_else(|| ExtractError::MissingParts("No request parts provided".to_string()))?;
+
+        for cookie in cookies {
+            let value = HeaderValue::from_str(&cookie)
+                .map_err(|e| ExtractError::ExtractionFailed(e.to_string()))?;
+            crate::append_response_header(SET_COOKIE, value).await?;
+        }
+        Ok(bucket)
+    }
+
+    /// The buckets of the visitor sending the request being handled, for the SSR render
+    pub async fn buckets(experiments: &[Experiment]) -> Result<ExperimentBuckets, ExtractError> {
+        let mut buckets = ExperimentBuckets::default();
+        for experiment in experiments {
+            let bucket = bucket(experiment).await?;
+            buckets
+                .0
+                .insert(experiment.name.to_string(), bucket.to_string());
+        }
+        Ok(buckets)
+    }
+}
+
+/// The buckets kept in the browser's cookies, for hydration
+#[cfg(all(target_arch = "wasm32", feature = "client"))]
+pub fn current_buckets() -> ExperimentBuckets {
+    use wasm_bindgen::JsCast;
+
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.dyn_into::<web_sys::HtmlDocument>().ok())
+        .and_then(|document| document.cookie().ok())
+        .map(|header| ExperimentBuckets::from_cookie_header(&header))
+        .unwrap_or_default()
+}
@@ -2,80 +2,163 @@
 //!
 //! This module provides a way to extract Axum request parts within server functions,
 //! similar to how `leptos_axum::extract()` works.
+//!
+//! The parts belong to the task handling the request: [`request_scope()`] gives a future
+//! storage of its own in a `tokio::task_local!`, which follows it across `.await` points
+//! even when the multi-threaded runtime moves it to another worker thread. The wrappers
+//! generated by `yew_server_hook` run in a scope, handlers written by hand wrap their work
+//! in one.
 
+use axum::body::{to_bytes, Body, Bytes};
 use axum::extract::FromRequestParts;
 use axum::http::request::Parts;
-use dashmap::DashMap;
-use once_cell::sync::Lazy;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, Response, StatusCode, Uri};
+use axum_extra::headers::Header;
+use axum_extra::TypedHeader;
+use serde::de::DeserializeOwned;
 use std::fmt::Debug;
+use std::future::Future;
 use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Request parts shared by every extraction of a request.
+///
+/// Extractors get `&mut Parts` one after the other, like the extractors of an axum handler,
+/// instead of each working on a deep copy of the headers and extensions.
+type SharedParts = Arc<Mutex<Parts>>;
+
+/// The parts and the body buffered by endpoints declared with `raw_body` of a request
+#[derive(Default)]
+struct RequestSlot {
+    parts: std::sync::Mutex<Option<SharedParts>>,
+    body: std::sync::Mutex<Option<Bytes>>,
+}
+
+impl RequestSlot {
+    fn parts(&self) -> Option<SharedParts> {
+        self.parts.lock().ok()?.clone()
+    }
 
-/// Global storage for request Parts, keyed by task ID
-static REQUEST_PARTS_STORAGE: Lazy<DashMap<usize, Parts>> = Lazy::new(DashMap::new);
-
-/// Gets a unique ID for the current task
-fn get_task_id() -> usize {
-    // Use the thread ID as a unique identifier
-    // This works because each request is typically handled on its own thread/task
-    // Note: This is a simplified approach. In production, you might want a more robust solution.
-    let thread_id = std::thread::current().id();
-    // Hash the thread ID to get a usize
-    use std::hash::{Hash, Hasher};
-    let mut hasher = std::collections::hash_map::DefaultHasher::new();
-    thread_id.hash(&mut hasher);
-    hasher.finish() as usize
-}
-
-/// Error type for extraction failures
-#[derive(Debug)]
-pub enum ExtractError {
-    /// No request parts were found in context
-    MissingParts(String),
-    /// Extraction failed
-    ExtractionFailed(String),
-}
-
-impl std::fmt::Display for ExtractError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ExtractError::MissingParts(msg) => write!(f, "Missing request parts: {}", msg),
-            ExtractError::ExtractionFailed(msg) => write!(f, "Extraction failed: {}", msg),
+    fn body(&self) -> Option<Bytes> {
+        self.body.lock().ok()?.clone()
+    }
+
+    fn set_parts(&self, parts: Option<SharedParts>) {
+        if let Ok(mut slot) = self.parts.lock() {
+            *slot = parts;
+        }
+    }
+
+    fn set_body(&self, body: Option<Bytes>) {
+        if let Ok(mut slot) = self.body.lock() {
+            *slot = body;
         }
     }
 }
 
-impl std::error::Error for ExtractError {}
+tokio::task_local! {
+    /// The request of the task running in a [`request_scope()`]
+    static REQUEST: RequestSlot;
+}
+
+/// Runs `f` on the request of the current task, `None` outside a [`request_scope()`]
+fn with_slot<R>(f: impl FnOnce(&RequestSlot) -> R) -> Option<R> {
+    REQUEST.try_with(f).ok()
+}
+
+/// Size limit of the bodies buffered with `raw_body = true`, the same as Axum's default
+pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Runs `future` with request storage of its own, so the parts it provides stay with it
+/// wherever the runtime polls it and never mix with those of concurrent requests.
+///
+/// The storage goes away with the future, so a scoped request needs no
+/// [`clear_request_parts()`]. Nested scopes start empty.
+///
+/// ```ignore
+/// async fn handler(req: Request<Body>) -> Response<Body> {
+///     request_scope(async move {
+///         let (parts, body) = req.into_parts();
+///         provide_request_parts(parts).await;
+///         render_app().await
+///     })
+///     .await
+/// }
+/// ```
+pub fn request_scope<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    REQUEST.scope(RequestSlot::default(), future)
+}
+
+pub use crate::context::ExtractError;
+use crate::response_options::ResponseOptions;
+use crate::ValidationErrors;
 
 /// Provides request parts to the current context.
 ///
-/// This should be called by the server function handler before executing the user's function.
-/// The parts will be stored in task-local storage for the duration of the handler execution.
+/// This should be called by the server function handler before executing the user's function,
+/// inside a [`request_scope()`]. The parts are stored in the scope's task-local storage for
+/// the duration of the handler execution.
+///
+/// The parts live in the scope, so calling this outside one, which used to work, now has
+/// nowhere to keep them: debug builds panic, release builds drop them and [`extract()`]
+/// reports that there is no request scope.
 ///
 /// # Example
 ///
 /// ```ignore
 /// async fn handler(req: Request<Body>) {
-///     let (parts, body) = req.into_parts();
+///     request_scope(async move {
+///         let (parts, body) = req.into_parts();
 ///
-///     provide_request_parts(parts).await;
+///         provide_request_parts(parts).await;
 ///
-///     // Now the user's function can call extract()
-///     let result = user_function().await;
-///
-///     clear_request_parts().await;
+///         // Now the user's function can call extract()
+///         let result = user_function().await;
+///     })
+///     .await
 /// }
 /// ```
 pub async fn provide_request_parts(parts: Parts) {
-    let task_id = get_task_id();
-    REQUEST_PARTS_STORAGE.insert(task_id, parts);
+    let provided = with_slot(|slot| slot.set_parts(Some(Arc::new(Mutex::new(parts)))));
+    debug_assert!(
+        provided.is_some(),
+        "provide_request_parts() called outside a request_scope(), the parts were dropped"
+    );
 }
 
-/// Clears the request parts from context.
+/// Provides the buffered request body to the current context, for [`extract_body()`].
 ///
-/// This should be called after the server function completes to prevent memory leaks.
+/// Called by the handlers of endpoints declared with `raw_body`, next to
+/// [`provide_request_parts()`], and like them it needs a [`request_scope()`]. The body is
+/// cleared along with the parts.
+pub async fn provide_request_body(body: Bytes) {
+    let provided = with_slot(|slot| slot.set_body(Some(body)));
+    debug_assert!(
+        provided.is_some(),
+        "provide_request_body() called outside a request_scope(), the body was dropped"
+    );
+}
+
+/// Reads a request body of at most `limit` bytes, answering `413 Payload Too Large` for
+/// anything bigger
+pub async fn read_request_body(body: Body, limit: usize) -> Result<Bytes, Response<Body>> {
+    to_bytes(body, limit).await.map_err(|e| {
+        Response::builder()
+            .status(StatusCode::PAYLOAD_TOO_LARGE)
+            .body(Body::from(format!("Invalid request body: {}", e)))
+            .unwrap_or_else(|_| Response::new(Body::empty()))
+    })
+}
+
+/// Clears the request parts and body from context.
+///
+/// Scoped requests drop them with their [`request_scope()`] anyway, clearing them earlier
+/// keeps them from the work that runs after the server function in the same scope.
 pub async fn clear_request_parts() {
-    let task_id = get_task_id();
-    REQUEST_PARTS_STORAGE.remove(&task_id);
+    with_slot(|slot| {
+        slot.set_parts(None);
+        slot.set_body(None);
+    });
 }
 
 /// Extracts data from the request using Axum's `FromRequestParts` trait.
@@ -124,6 +207,9 @@ where
 ///
 /// Any error that occurs during extraction is converted to an [`ExtractError`].
 ///
+/// Extractors run one at a time on the same request parts, so an extractor must not call
+/// `extract()` itself.
+///
 /// # Example
 ///
 /// ```ignore
@@ -142,22 +228,290 @@ where
     T: Sized + FromRequestParts<S>,
     T::Rejection: Debug,
 {
-    let task_id = get_task_id();
-
-    // Get the parts from storage
-    let parts_ref = REQUEST_PARTS_STORAGE
-        .get(&task_id)
-        .ok_or_else(|| {
-            ExtractError::MissingParts(
-                "Request parts not found. Make sure provide_request_parts() was called.".to_string()
-            )
-        })?;
-
-    // Clone the Parts (this is cheap as Parts is designed to be cloneable)
-    let mut parts = parts_ref.value().clone();
+    let parts = current_parts()?;
+    let mut parts = parts.lock().await;
 
     // Use from_request_parts to extract the data
     T::from_request_parts(&mut parts, state)
         .await
         .map_err(|e| ExtractError::ExtractionFailed(format!("{:?}", e)))
 }
+
+/// Returns the raw body of the request.
+///
+/// Only endpoints declared with `raw_body` keep their body around, the others hand it to
+/// the parameter parsing and fail with [`ExtractError::MissingBody`]. Useful for payloads
+/// the parameters can't describe, e.g. verifying the signature of a webhook:
+///
+/// ```ignore
+/// #[yewserverhook(path = "/api/webhooks/stripe", method = "POST", raw_body = "1MB")]
+/// pub async fn stripe_webhook() -> Result<(), AppError> {
+///     let signature: TypedHeader<StripeSignature> = extract().await?;
+///     let body = extract_body().await?;
+///     verify(&signature, &body)?;
+///     // ...
+/// }
+/// ```
+pub async fn extract_body() -> Result<Bytes, ExtractError> {
+    with_slot(RequestSlot::body).flatten().ok_or_else(|| {
+        ExtractError::MissingBody(
+            "Request body not found. Declare the endpoint with `raw_body = true`.".to_string(),
+        )
+    })
+}
+
+/// Deserializes the raw body of the request as JSON, see [`extract_body()`].
+///
+/// Like [`extract_query()`], a failure names the field it's about, e.g. `items[2].price`.
+pub async fn extract_json<T>() -> Result<T, ExtractError>
+where
+    T: DeserializeOwned,
+{
+    let body = extract_body().await?;
+    let mut deserializer = serde_json::Deserializer::from_slice(&body);
+    serde_path_to_error::deserialize(&mut deserializer).map_err(|e| {
+        // Malformed JSON isn't about any field
+        let inner = e.inner();
+        if inner.is_syntax() || inner.is_eof() {
+            return ExtractError::ExtractionFailed(inner.to_string());
+        }
+        // The position in the body means little to the user once the field is named
+        let position = format!(" at line {} column {}", inner.line(), inner.column());
+        let message = inner.to_string();
+        let message = message
+            .strip_suffix(&position)
+            .unwrap_or(&message)
+            .to_string();
+        field_error(e.path(), message)
+    })
+}
+
+/// Returns the HTTP method of the request.
+///
+/// Like the other accessors of the request parts ([`uri()`], [`headers()`] and
+/// [`query_string()`]), it reads the parts directly instead of running an extractor:
+///
+/// ```ignore
+/// if yew_extra::method().await? == Method::HEAD {
+///     return Ok(Vec::new());
+/// }
+/// let user_agent = yew_extra::headers().await?.get(USER_AGENT).cloned();
+/// ```
+pub async fn method() -> Result<Method, ExtractError> {
+    Ok(current_parts()?.lock().await.method.clone())
+}
+
+/// Returns the URI of the request, see [`method()`]
+pub async fn uri() -> Result<Uri, ExtractError> {
+    Ok(current_parts()?.lock().await.uri.clone())
+}
+
+/// Returns the headers of the request, see [`method()`]
+pub async fn headers() -> Result<HeaderMap, ExtractError> {
+    Ok(current_parts()?.lock().await.headers.clone())
+}
+
+/// Returns the query string of the request without the `?`, empty if there is none, see
+/// [`method()`]
+pub async fn query_string() -> Result<String, ExtractError> {
+    Ok(uri().await?.query().unwrap_or_default().to_string())
+}
+
+/// Returns a header of the request as a typed value, with axum-extra's [`TypedHeader`].
+///
+/// Fails with [`ExtractError::ExtractionFailed`] when the header is missing or can't be
+/// parsed, naming the header:
+///
+/// ```ignore
+/// use yew_extra::headers::{authorization::Bearer, Authorization, IfNoneMatch};
+///
+/// let Authorization(bearer) = yew_extra::header::<Authorization<Bearer>>().await?;
+/// let if_none_match: Option<IfNoneMatch> = yew_extra::optional_header().await?;
+/// ```
+pub async fn header<H>() -> Result<H, ExtractError>
+where
+    H: Header,
+{
+    let parts = current_parts()?;
+    let mut parts = parts.lock().await;
+    TypedHeader::<H>::from_request_parts(&mut parts, &())
+        .await
+        .map(|TypedHeader(header)| header)
+        .map_err(|e| ExtractError::ExtractionFailed(e.to_string()))
+}
+
+/// Like [`header()`], but `None` when the request doesn't have the header. A header that is
+/// present but invalid still fails.
+pub async fn optional_header<H>() -> Result<Option<H>, ExtractError>
+where
+    H: Header,
+{
+    let present = current_parts()?
+        .lock()
+        .await
+        .headers
+        .contains_key(H::name());
+    if present {
+        header().await.map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// The [`ResponseOptions`] of the request being handled, to set the status, headers and
+/// cookies of its response.
+///
+/// They're kept in the extensions of the shared parts, so they're cleared with them and
+/// survive [`run_blocking()`]. The handlers generated by `yew_server_hook` apply them to the
+/// response of the server function. Handlers written by hand call
+/// [`apply_response_headers()`] themselves.
+pub async fn response_options() -> Result<ResponseOptions, ExtractError> {
+    let parts = current_parts()?;
+    let mut parts = parts.lock().await;
+    Ok(parts
+        .extensions
+        .get_or_insert_with(ResponseOptions::default)
+        .clone())
+}
+
+/// Adds a header to the response of the request being handled, e.g. a `Set-Cookie`, see
+/// [`response_options()`]
+pub async fn append_response_header(
+    name: HeaderName,
+    value: HeaderValue,
+) -> Result<(), ExtractError> {
+    response_options().await?.append_header(name, value);
+    Ok(())
+}
+
+/// Applies the [`ResponseOptions`] of the request to `response`: its status and the
+/// headers of [`append_response_header()`]. Called before [`clear_request_parts()`], which
+/// drops them.
+pub async fn apply_response_headers(response: Response<Body>) -> Response<Body> {
+    let Ok(parts) = current_parts() else {
+        return response;
+    };
+    let options = parts.lock().await.extensions.remove::<ResponseOptions>();
+    match options {
+        Some(options) => options.apply(response),
+        None => response,
+    }
+}
+
+/// Runs `f` on the parts of the request being handled, `None` outside a request
+pub(crate) async fn with_request_parts<R>(f: impl FnOnce(&mut Parts) -> R) -> Option<R> {
+    let parts = current_parts().ok()?;
+    let mut parts = parts.lock().await;
+    Some(f(&mut parts))
+}
+
+/// Returns a header of the request being handled without waiting for the parts, for code
+/// that can't await like `IntoResponse` implementations. `None` while they're in use.
+pub(crate) fn try_request_header(name: HeaderName) -> Option<HeaderValue> {
+    let parts = current_parts().ok()?;
+    let parts = parts.try_lock().ok()?;
+    parts.headers.get(name).cloned()
+}
+
+/// Returns the parts provided for the current task
+fn current_parts() -> Result<SharedParts, ExtractError> {
+    // Release the slot right away, only the parts stay locked while extracting
+    let Some(parts) = with_slot(RequestSlot::parts) else {
+        return Err(ExtractError::MissingParts(
+            "No request scope. Run the handler in request_scope().".to_string(),
+        ));
+    };
+    parts.ok_or_else(|| {
+        ExtractError::MissingParts(
+            "Request parts not found. Make sure provide_request_parts() was called.".to_string(),
+        )
+    })
+}
+
+/// Deserializes the query string of the request.
+///
+/// Unlike `extract::<Query<T>>()`, a failure names the parameter it's about, as
+/// [`ExtractError::InvalidFields`], which answers `400 Bad Request` with a
+/// [`ValidationErrors`] body:
+///
+/// ```ignore
+/// #[derive(Deserialize)]
+/// struct Page {
+///     page: u32,
+///     per_page: Option<u32>,
+/// }
+///
+/// // `?page=two` fails with {"error":"...","fields":{"page":["invalid digit found in string"]}}
+/// let Page { page, per_page } = extract_query().await?;
+/// ```
+pub async fn extract_query<T>() -> Result<T, ExtractError>
+where
+    T: DeserializeOwned,
+{
+    let query = query_string().await?;
+    let deserializer = serde_html_form::Deserializer::new(form_urlencoded::parse(query.as_bytes()));
+    serde_path_to_error::deserialize(deserializer).map_err(|e| {
+        let message = e.inner().to_string();
+        field_error(e.path(), message)
+    })
+}
+
+/// Turns a deserialization failure at `path` into the error of the field it's about
+fn field_error(path: &serde_path_to_error::Path, message: String) -> ExtractError {
+    let mut field = path.to_string();
+    if field == "." {
+        field.clear();
+    }
+
+    // Serde reports missing and unknown fields at the struct containing them
+    let named = ["missing field `", "unknown field `"]
+        .iter()
+        .find_map(|prefix| message.strip_prefix(prefix))
+        .and_then(|rest| rest.split('`').next());
+    if let Some(name) = named {
+        field = if field.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", field, name)
+        };
+    }
+
+    if field.is_empty() {
+        return ExtractError::ExtractionFailed(message);
+    }
+    let mut errors = ValidationErrors::new();
+    errors.add(&field, message);
+    ExtractError::InvalidFields(errors)
+}
+
+/// Runs a future on tokio's blocking thread pool, keeping the request parts available.
+///
+/// CPU-heavy server functions declared with `blocking = true` run through this, so they
+/// don't hold up the runtime's worker threads. `extract()` keeps working inside the future,
+/// which is driven to completion on the blocking thread.
+///
+/// # Panics
+///
+/// Panics if called outside a tokio runtime, and resumes the panic of `future` if it panics.
+pub async fn run_blocking<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    // Task-locals don't cross into the blocking pool, the future gets a scope pointing at
+    // the same parts
+    let slot = with_slot(|slot| RequestSlot {
+        parts: std::sync::Mutex::new(slot.parts()),
+        body: std::sync::Mutex::new(slot.body()),
+    })
+    .unwrap_or_default();
+    let handle = tokio::runtime::Handle::current();
+
+    let result =
+        tokio::task::spawn_blocking(move || handle.block_on(REQUEST.scope(slot, future))).await;
+
+    match result {
+        Ok(output) => output,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
@@ -0,0 +1,106 @@
+//! Endpoints switched on and off at runtime.
+//!
+//! Endpoints declared with `feature_flag = "new_billing"` ask the registered
+//! [`FlagProvider`] whether the flag is on before anything else runs. A flag that's off
+//! answers `404 Not Found`, as if the endpoint didn't exist, and a provider that can't tell
+//! answers `503 Service Unavailable`, so endpoints can be dark-launched and rolled out
+//! without a new build:
+//!
+//! ```ignore
+//! use yew_extra::feature_flags::{set_flag_provider, FlagFuture};
+//!
+//! set_flag_provider(move |flag: String, request: &Parts| -> FlagFuture {
+//!     let client = flags_client.clone();
+//!     let user = user_id(request);
+//!     Box::pin(async move { client.is_enabled(&flag, user).await.map_err(|e| e.to_string()) })
+//! });
+//! ```
+//!
+//! Without a provider every flag is off.
+
+use axum::body::Body;
+use axum::http::request::Parts;
+use axum::http::{Request, Response, StatusCode};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+
+/// The answer of a [`FlagProvider`], `Err` if it couldn't tell
+pub type FlagFuture = Pin<Box<dyn Future<Output = Result<bool, String>> + Send>>;
+
+/// Decides whether a flag is on for a request.
+///
+/// Closures taking the flag and the request parts are providers too.
+pub trait FlagProvider: Send + Sync {
+    fn is_enabled(&self, flag: String, request: &Parts) -> FlagFuture;
+}
+
+impl<F> FlagProvider for F
+where
+    F: Fn(String, &Parts) -> FlagFuture + Send + Sync,
+{
+    fn is_enabled(&self, flag: String, request: &Parts) -> FlagFuture {
+        self(flag, request)
+    }
+}
+
+static PROVIDER: RwLock<Option<Arc<dyn FlagProvider>>> = RwLock::new(None);
+
+/// Asks `provider` about the flags of the endpoints from now on
+pub fn set_flag_provider(provider: impl FlagProvider + 'static) {
+    *PROVIDER.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(provider));
+}
+
+/// Removes the provider, turning every flag off
+pub fn clear_flag_provider() {
+    PROVIDER.write().unwrap_or_else(|e| e.into_inner()).take();
+}
+
+fn rejected(status: StatusCode, message: String) -> Response<Body> {
+    let body = serde_json::json!({ "error": message }).to_string();
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        axum::http::header::CONTENT_TYPE,
+        axum::http::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+/// Checks that `flag` is on for `req`, answering `404 Not Found` if it's off and
+/// `503 Service Unavailable` if the provider failed.
+///
+/// The provider is asked right away, the returned future doesn't borrow `req`.
+pub fn check_flag<B>(
+    req: &Request<B>,
+    flag: &'static str,
+) -> impl Future<Output = Result<(), Response<Body>>> + Send + 'static {
+    let provider = PROVIDER.read().unwrap_or_else(|e| e.into_inner()).clone();
+    let answer = provider.map(|provider| {
+        let (mut parts, ()) = Request::new(()).into_parts();
+        parts.method = req.method().clone();
+        parts.uri = req.uri().clone();
+        parts.version = req.version();
+        parts.headers = req.headers().clone();
+        parts.extensions = req.extensions().clone();
+        provider.is_enabled(flag.to_string(), &parts)
+    });
+    let endpoint = format!("{} {}", req.method(), req.uri().path());
+    async move {
+        let enabled = match answer {
+            Some(answer) => answer.await,
+            None => Ok(false),
+        };
+        match enabled {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(rejected(
+                StatusCode::NOT_FOUND,
+                format!("No endpoint for {}", endpoint),
+            )),
+            Err(e) => Err(rejected(
+                StatusCode::SERVICE_UNAVAILABLE,
+                format!("Couldn't check the {} flag: {}", flag, e),
+            )),
+        }
+    }
+}
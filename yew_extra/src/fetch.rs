@@ -0,0 +1,36 @@
+//! Fetch options and response parsing for client requests.
+//!
+//! `yew_server_hook` applies the `credentials`, `cache`, `mode` and `redirect` options of
+//! an endpoint with these types, so apps don't need to enable the matching `web-sys`
+//! features themselves.
+//!
+//! Endpoints declared with `fast_json = true` parse their responses with
+//! [`parse_json_response`], which lets the browser's `JSON.parse` do the parsing and
+//! converts the result with `serde-wasm-bindgen`. That's faster than `serde_json` for large
+//! payloads and keeps a `serde_json` deserializer per return type out of the wasm bundle.
+
+use serde::de::DeserializeOwned;
+
+pub use web_sys::{RequestCache, RequestCredentials, RequestMode, RequestRedirect};
+
+/// Reads a response body and deserializes it with `JSON.parse` and `serde-wasm-bindgen`
+pub async fn parse_json_response<T: DeserializeOwned>(
+    response: gloo_net::http::Response,
+) -> Result<T, String> {
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+    parse_json_text(&text)
+}
+
+/// Deserializes a JSON document with `JSON.parse` and `serde-wasm-bindgen`
+pub fn parse_json_text<T: DeserializeOwned>(text: &str) -> Result<T, String> {
+    let value = js_sys::JSON::parse(text).map_err(|e| {
+        format!(
+            "Failed to parse response: {}",
+            js_sys::Error::from(e).message()
+        )
+    })?;
+    serde_wasm_bindgen::from_value(value).map_err(|e| format!("Failed to parse response: {}", e))
+}
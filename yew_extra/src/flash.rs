@@ -0,0 +1,201 @@
+//! One-time messages for post-redirect-get flows.
+//!
+//! A mutation calls [`flash`] to leave a message for the next page, e.g. "Profile saved".
+//! It's sent as a short-lived `yew_flash` cookie with the mutation's response, and
+//! [`take_flash`] reads it once, on the server while rendering the page or in the browser
+//! after a client-side navigation, and removes it:
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/profile", method = "PUT")]
+//! pub async fn save_profile(name: String) -> Result<(), AppError> {
+//!     update_profile(&name).await?;
+//!     yew_extra::flash::flash(Flash::success("Profile saved")).await?;
+//!     Ok(())
+//! }
+//!
+//! // SSR handler, with the request parts provided
+//! let flash = yew_extra::flash::take_flash().await;
+//! let response = render(App { flash }).await;
+//! yew_extra::apply_response_headers(response).await
+//! ```
+//!
+//! The cookie isn't `HttpOnly`, as the browser has to read it, so don't flash secrets.
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the cookie carrying the message
+pub const COOKIE_NAME: &str = "yew_flash";
+
+/// Seconds a message waits to be taken before the browser drops it
+pub const MAX_AGE: u32 = 300;
+
+/// How a message should be presented
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlashLevel {
+    Success,
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+impl FlashLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlashLevel::Success => "success",
+            FlashLevel::Info => "info",
+            FlashLevel::Warning => "warning",
+            FlashLevel::Error => "error",
+        }
+    }
+
+    fn parse(level: &str) -> Option<Self> {
+        [
+            FlashLevel::Success,
+            FlashLevel::Info,
+            FlashLevel::Warning,
+            FlashLevel::Error,
+        ]
+        .into_iter()
+        .find(|candidate| candidate.as_str() == level)
+    }
+}
+
+/// A message left for the next page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Flash {
+    pub level: FlashLevel,
+    pub message: String,
+}
+
+impl Flash {
+    pub fn new(level: FlashLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+        }
+    }
+
+    pub fn success(message: impl Into<String>) -> Self {
+        Self::new(FlashLevel::Success, message)
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(FlashLevel::Info, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(FlashLevel::Warning, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(FlashLevel::Error, message)
+    }
+
+    /// The cookie value, `level:message` percent-encoded
+    pub fn to_cookie_value(&self) -> String {
+        let raw = format!("{}:{}", self.level.as_str(), self.message);
+        let mut encoded = String::with_capacity(raw.len());
+        for byte in raw.bytes() {
+            if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+                encoded.push(byte as char);
+            } else {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+        encoded
+    }
+
+    /// Reads a cookie value written by [`Flash::to_cookie_value`]
+    pub fn from_cookie_value(value: &str) -> Option<Self> {
+        let bytes = value.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut index = 0;
+        while index < bytes.len() {
+            if bytes[index] == b'%' {
+                let hex = value.get(index + 1..index + 3)?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                index += 3;
+            } else {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+        let decoded = String::from_utf8(decoded).ok()?;
+        let (level, message) = decoded.split_once(':')?;
+        Some(Self::new(FlashLevel::parse(level)?, message))
+    }
+}
+
+impl From<&str> for Flash {
+    fn from(message: &str) -> Self {
+        Self::info(message)
+    }
+}
+
+impl From<String> for Flash {
+    fn from(message: String) -> Self {
+        Self::info(message)
+    }
+}
+
+/// The `Set-Cookie` value sending `flash`
+pub fn set_cookie(flash: &Flash) -> String {
+    format!(
+        "{}={}; Path=/; Max-Age={}; SameSite=Lax",
+        COOKIE_NAME,
+        flash.to_cookie_value(),
+        MAX_AGE
+    )
+}
+
+/// The `Set-Cookie` value removing the message
+pub fn clear_cookie() -> String {
+    format!("{}=; Path=/; Max-Age=0; SameSite=Lax", COOKIE_NAME)
+}
+
+/// Finds the message in a `Cookie` header
+pub fn from_cookie_header(header: &str) -> Option<Flash> {
+    header
+        .split(';')
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == COOKIE_NAME)
+        .and_then(|(_, value)| Flash::from_cookie_value(value))
+}
+
+/// Leaves `flash` for the next page, sent with the response of the request being handled
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn flash(flash: impl Into<Flash>) -> Result<(), crate::ExtractError> {
+    let value = axum::http::HeaderValue::from_str(&set_cookie(&flash.into()))
+        .map_err(|e| crate::ExtractError::ExtractionFailed(e.to_string()))?;
+    crate::append_response_header(axum::http::header::SET_COOKIE, value).await
+}
+
+/// Takes the message sent with the request being handled, if any, and removes it with the
+/// response
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn take_flash() -> Option<Flash> {
+    let headers = crate::headers().await.ok()?;
+    let flash = headers
+        .get_all(axum::http::header::COOKIE)
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .find_map(from_cookie_header)?;
+    let clear = axum::http::HeaderValue::from_str(&clear_cookie()).ok()?;
+    crate::append_response_header(axum::http::header::SET_COOKIE, clear)
+        .await
+        .ok()?;
+    Some(flash)
+}
+
+/// Takes the message left by the last mutation, if any, and removes it
+#[cfg(all(target_arch = "wasm32", feature = "client"))]
+pub async fn take_flash() -> Option<Flash> {
+    use wasm_bindgen::JsCast;
+
+    let document: web_sys::HtmlDocument = web_sys::window()?.document()?.dyn_into().ok()?;
+    let flash = from_cookie_header(&document.cookie().ok()?)?;
+    let _ = document.set_cookie(&clear_cookie());
+    Some(flash)
+}
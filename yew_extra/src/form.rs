@@ -0,0 +1,39 @@
+//! Support types for the form components generated by `yew_server_hook`.
+//!
+//! Parameters that aren't strings, numbers or booleans are rendered as a `<select>`,
+//! which needs to know the available choices. Implement [`FormSelect`] for those types.
+
+/// Provides the choices for a parameter rendered as a `<select>` in a generated form.
+///
+/// # Example
+///
+/// ```
+/// use yew_extra::FormSelect;
+///
+/// #[derive(Clone, PartialEq, Default)]
+/// enum Role {
+///     #[default]
+///     Member,
+///     Admin,
+/// }
+///
+/// impl FormSelect for Role {
+///     fn options() -> Vec<Self> {
+///         vec![Role::Member, Role::Admin]
+///     }
+///
+///     fn label(&self) -> String {
+///         match self {
+///             Role::Member => "Member".to_string(),
+///             Role::Admin => "Admin".to_string(),
+///         }
+///     }
+/// }
+/// ```
+pub trait FormSelect: Sized + Clone + PartialEq {
+    /// All selectable values, in the order they should be displayed
+    fn options() -> Vec<Self>;
+
+    /// The text shown for this value in the select
+    fn label(&self) -> String;
+}
@@ -0,0 +1,46 @@
+//! Propagating the user's identity to requests made while handling theirs.
+//!
+//! Server functions and SSR code sometimes call another endpoint over HTTP, of this app or
+//! of a service behind the same authentication. Those requests come from the server, so
+//! they carry no cookies unless they are copied over from the request being handled:
+//!
+//! ```ignore
+//! let response = client
+//!     .get("http://billing.internal/api/invoices")
+//!     .headers(yew_extra::forward::identity_headers().await?)
+//!     .send()
+//!     .await?;
+//! ```
+//!
+//! The headers are an `http::HeaderMap`, which reqwest and hyper take as is and which
+//! extends the headers of an `http::Request` sent through the router. Only forward them to
+//! services you trust with the user's credentials.
+
+use crate::{headers, ExtractError};
+use axum::http::header::{AUTHORIZATION, COOKIE};
+use axum::http::{HeaderMap, HeaderName};
+
+/// The headers identifying the user: `Cookie` and `Authorization`
+pub const IDENTITY_HEADERS: [HeaderName; 2] = [COOKIE, AUTHORIZATION];
+
+/// Returns the [`IDENTITY_HEADERS`] of the request being handled
+pub async fn identity_headers() -> Result<HeaderMap, ExtractError> {
+    forwarded_headers(&IDENTITY_HEADERS).await
+}
+
+/// Returns the headers named `names` of the request being handled, e.g. to also forward a
+/// tenant or tracing header
+pub async fn forwarded_headers(names: &[HeaderName]) -> Result<HeaderMap, ExtractError> {
+    Ok(select_headers(&headers().await?, names))
+}
+
+/// Copies the headers named `names` from `from`, keeping repeated headers
+pub fn select_headers(from: &HeaderMap, names: &[HeaderName]) -> HeaderMap {
+    let mut selected = HeaderMap::new();
+    for name in names {
+        for value in from.get_all(name) {
+            selected.append(name.clone(), value.clone());
+        }
+    }
+    selected
+}
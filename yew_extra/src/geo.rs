@@ -0,0 +1,159 @@
+//! Where a request comes from, as told by the CDN in front of the server.
+//!
+//! CDNs add the visitor's country and address to the requests they forward: Cloudflare's
+//! `CF-IPCountry` and `CF-Connecting-IP`, CloudFront's `CloudFront-Viewer-*`, Vercel's
+//! `x-vercel-ip-*`, the `Fastly-Geo-*` headers a Fastly service sets from its `client.geo`
+//! variables, and the `X-Forwarded-For` chain of any proxy. `yew_extra::geo()` reads them
+//! for the request being handled, and [`RequestContext::geo`](crate::RequestContext::geo)
+//! for the request of a context:
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/prices", method = "GET")]
+//! pub async fn get_prices() -> Result<Vec<Price>, AppError> {
+//!     let geo = yew_extra::geo().await?;
+//!     if geo.in_countries(&["CU", "IR", "KP"]) {
+//!         return Err(AppError::Unavailable);
+//!     }
+//!     Ok(prices_for(geo.country.as_deref().unwrap_or("US")).await?)
+//! }
+//! ```
+//!
+//! Clients can send these headers themselves, so only trust them when every request goes
+//! through the CDN, which replaces them.
+
+use axum::http::HeaderMap;
+use std::net::IpAddr;
+
+/// Headers carrying the country, in order of preference
+const COUNTRY_HEADERS: &[&str] = &[
+    "cf-ipcountry",
+    "cloudfront-viewer-country",
+    "x-vercel-ip-country",
+    "fastly-geo-country-code",
+];
+
+/// Headers carrying the region or state
+const REGION_HEADERS: &[&str] = &[
+    "cf-region-code",
+    "cloudfront-viewer-country-region",
+    "x-vercel-ip-country-region",
+    "fastly-geo-region",
+];
+
+/// Headers carrying the city, percent-encoded by Vercel
+const CITY_HEADERS: &[&str] = &[
+    "cf-ipcity",
+    "cloudfront-viewer-city",
+    "x-vercel-ip-city",
+    "fastly-geo-city",
+];
+
+/// Headers carrying only the visitor's address
+const CLIENT_IP_HEADERS: &[&str] = &[
+    "cf-connecting-ip",
+    "fastly-client-ip",
+    "true-client-ip",
+    "x-real-ip",
+];
+
+/// What the CDN tells about the origin of a request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Geo {
+    /// ISO 3166-1 alpha-2 country code, upper case
+    pub country: Option<String>,
+    /// Region or state code, e.g. `CA`
+    pub region: Option<String>,
+    pub city: Option<String>,
+    /// The visitor's address
+    pub client_ip: Option<IpAddr>,
+    /// The addresses of `X-Forwarded-For`, the visitor's first, then each proxy's
+    pub forwarded_for: Vec<IpAddr>,
+}
+
+impl Geo {
+    /// Reads the CDN headers of `headers`
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let forwarded_for = forwarded_for(headers);
+        let client_ip = CLIENT_IP_HEADERS
+            .iter()
+            .find_map(|name| first_value(headers, &[name]).and_then(|ip| parse_ip(&ip)))
+            .or_else(|| forwarded_for.first().copied());
+        Self {
+            country: first_value(headers, COUNTRY_HEADERS)
+                .map(|country| country.to_ascii_uppercase())
+                // Cloudflare's unknown country
+                .filter(|country| country != "XX"),
+            region: first_value(headers, REGION_HEADERS),
+            city: first_value(headers, CITY_HEADERS).map(|city| percent_decode(&city)),
+            client_ip,
+            forwarded_for,
+        }
+    }
+
+    /// Whether the request comes from one of `countries`, given as upper case codes
+    pub fn in_countries(&self, countries: &[&str]) -> bool {
+        self.country
+            .as_deref()
+            .is_some_and(|country| countries.contains(&country))
+    }
+}
+
+/// The first non-empty value of the headers `names`
+fn first_value(headers: &HeaderMap, names: &[&str]) -> Option<String> {
+    names.iter().find_map(|name| {
+        let value = headers.get(*name)?.to_str().ok()?.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// An address as written in the headers, also `[v6]:port` and `v4:port`
+fn parse_ip(value: &str) -> Option<IpAddr> {
+    let value = value.trim().trim_matches('"');
+    if let Ok(ip) = value.parse() {
+        return Some(ip);
+    }
+    if let Some(rest) = value.strip_prefix('[') {
+        return rest.split(']').next()?.parse().ok();
+    }
+    value.split(':').next()?.parse().ok()
+}
+
+/// The addresses of every `X-Forwarded-For` header, in order
+fn forwarded_for(headers: &HeaderMap) -> Vec<IpAddr> {
+    headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(parse_ip)
+        .collect()
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = value
+            .get(index + 1..index + 3)
+            .filter(|_| bytes[index] == b'%')
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| value.to_string())
+}
+
+/// Where the request being handled comes from, see [`Geo`]
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn geo() -> Result<Geo, crate::ExtractError> {
+    Ok(Geo::from_headers(&crate::headers().await?))
+}
@@ -0,0 +1,545 @@
+//! GraphQL facade over the endpoints generated by `yew_server_hook`.
+//!
+//! Endpoints declared with `graphql = true` register the schemas of their parameters and
+//! response type. [`build_schema`] turns them into an `async-graphql` schema with a query
+//! for every GET endpoint and a mutation for the others, named after the server functions in
+//! camelCase. [`router`] serves it for consumers who prefer a single graph endpoint:
+//!
+//! ```ignore
+//! let app = Router::new()
+//!     .merge(yew_extra::route_registry::routes())
+//!     .merge(yew_extra::graphql::router("/graphql"));
+//! ```
+//!
+//! ```graphql
+//! query {
+//!   listUsers(role: "admin") { id name }
+//!   version
+//! }
+//! ```
+//!
+//! Rust structs become object types and the parameters become arguments. Values the schema
+//! has no type for, like enums with data, maps, tuples and structs passed as parameters, use
+//! the `JSON` scalar.
+//!
+//! Fields call the generated wrappers of their endpoints with the headers of the GraphQL
+//! request, so cookies and authorization reach them, but middleware of the app doesn't run
+//! again. An endpoint answering with an error status fails its field with the server's
+//! message and the status in the `status` extension of the error.
+
+use crate::route_registry::Handler;
+use crate::typescript::{camel_case, type_name};
+use async_graphql::dynamic::{
+    Field, FieldFuture, FieldValue, InputValue, Object, ResolverContext, Scalar, Schema,
+    SchemaError, TypeRef,
+};
+use async_graphql::ErrorExtensions;
+use axum::body::Body;
+use axum::http::{header, HeaderMap, Request, Response, StatusCode};
+use axum::response::IntoResponse;
+use axum::Router;
+use schemars::SchemaGenerator;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub use crate::typescript::{schema_of, SchemaFn};
+
+/// Scalar of the values the schema has no type for
+pub const JSON_SCALAR: &str = "JSON";
+
+/// An endpoint, submitted by `yew_server_hook` for `graphql` endpoints
+#[derive(Clone, Copy)]
+pub struct GraphqlEndpoint {
+    /// Name of the server function, `list_users` becomes the field `listUsers`
+    pub name: &'static str,
+    pub path: &'static str,
+    pub method: &'static str,
+    /// Schema of the parameter struct, for endpoints taking parameters
+    pub params: Option<SchemaFn>,
+    /// Schema of the success type
+    pub returns: SchemaFn,
+    /// The generated wrapper the field calls
+    pub handler: Handler,
+}
+
+impl GraphqlEndpoint {
+    pub const fn new(
+        name: &'static str,
+        path: &'static str,
+        method: &'static str,
+        params: Option<SchemaFn>,
+        returns: SchemaFn,
+        handler: Handler,
+    ) -> Self {
+        Self {
+            name,
+            path,
+            method,
+            params,
+            returns,
+            handler,
+        }
+    }
+}
+
+inventory::collect!(GraphqlEndpoint);
+
+/// Returns every registered endpoint, sorted by path and method
+pub fn registered_endpoints() -> Vec<&'static GraphqlEndpoint> {
+    let mut endpoints: Vec<&'static GraphqlEndpoint> =
+        inventory::iter::<GraphqlEndpoint>.into_iter().collect();
+    endpoints.sort_by(|a, b| (a.path, a.method).cmp(&(b.path, b.method)));
+    endpoints
+}
+
+/// Builds the schema of every registered endpoint
+pub fn build_schema() -> Result<Schema, SchemaError> {
+    schema_for(&registered_endpoints())
+}
+
+/// Builds the schema of `endpoints`
+pub fn schema_for(endpoints: &[&GraphqlEndpoint]) -> Result<Schema, SchemaError> {
+    let mut generator = SchemaGenerator::default();
+    let described: Vec<(GraphqlEndpoint, Option<Value>, Value)> = endpoints
+        .iter()
+        .map(|endpoint| {
+            let params = endpoint
+                .params
+                .map(|params| params(&mut generator).to_value());
+            let returns = (endpoint.returns)(&mut generator).to_value();
+            (**endpoint, params, returns)
+        })
+        .collect();
+
+    let mut types = Types {
+        definitions: generator.definitions(),
+        objects: BTreeMap::new(),
+        json: false,
+    };
+    let mut query = Object::new("Query");
+    let mut mutation = Object::new("Mutation");
+    let mut has_queries = false;
+    let mut has_mutations = false;
+    for (endpoint, params, returns) in described {
+        let result_name = format!("{}Result", pascal_case(endpoint.name));
+        let ty = types.output(&returns, &result_name);
+        let mut field = Field::new(camel_case(endpoint.name), ty, move |ctx| {
+            FieldFuture::new(call_endpoint(endpoint, ctx))
+        });
+        for argument in params
+            .map(|params| types.arguments(&params))
+            .unwrap_or_default()
+        {
+            field = field.argument(argument);
+        }
+        if endpoint.method == "GET" {
+            query = query.field(field);
+            has_queries = true;
+        } else {
+            mutation = mutation.field(field);
+            has_mutations = true;
+        }
+    }
+    // GraphQL requires a query type with at least one field
+    if !has_queries {
+        query = query.field(Field::new(
+            "_empty",
+            TypeRef::named(TypeRef::BOOLEAN),
+            |_| FieldFuture::from_value(None),
+        ));
+    }
+
+    let mut builder =
+        Schema::build("Query", has_mutations.then_some("Mutation"), None).register(query);
+    if has_mutations {
+        builder = builder.register(mutation);
+    }
+    if types.json {
+        builder = builder.register(Scalar::new(JSON_SCALAR));
+    }
+    for object in types.objects.into_values().flatten() {
+        builder = builder.register(object);
+    }
+    builder.finish()
+}
+
+/// Builds a router answering GraphQL requests, sent as JSON with `POST`, at `path`.
+///
+/// # Panics
+///
+/// Panics if the schema of the registered endpoints is invalid, e.g. when two functions of
+/// different modules have the same name.
+pub fn router<S>(path: &str) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let schema = build_schema().unwrap_or_else(|e| panic!("Invalid GraphQL schema: {}", e));
+    Router::new().route(
+        path,
+        axum::routing::post(move |req: Request<Body>| {
+            let schema = schema.clone();
+            async move { handle(&schema, req).await }
+        }),
+    )
+}
+
+/// Headers of the GraphQL request, sent to the endpoints
+struct ForwardedHeaders(HeaderMap);
+
+/// Runs `request` against `schema`, calling the endpoints with `headers`
+pub async fn execute(
+    schema: &Schema,
+    request: async_graphql::Request,
+    headers: HeaderMap,
+) -> async_graphql::Response {
+    schema
+        .execute(request.data(ForwardedHeaders(headers)))
+        .await
+}
+
+async fn handle(schema: &Schema, req: Request<Body>) -> Response<Body> {
+    let (parts, body) = req.into_parts();
+    let body = match crate::read_request_body(body, crate::DEFAULT_BODY_LIMIT).await {
+        Ok(body) => body,
+        Err(response) => return response,
+    };
+    let request: async_graphql::Request = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            let error = serde_json::json!({ "error": format!("Invalid GraphQL request: {}", e) });
+            return (StatusCode::BAD_REQUEST, axum::Json(error)).into_response();
+        }
+    };
+    axum::Json(execute(schema, request, parts.headers).await).into_response()
+}
+
+async fn call_endpoint(
+    endpoint: GraphqlEndpoint,
+    ctx: ResolverContext<'_>,
+) -> async_graphql::Result<Option<FieldValue<'_>>> {
+    let mut params = Map::new();
+    for (name, value) in ctx.args.iter() {
+        params.insert(name.to_string(), value.as_value().clone().into_json()?);
+    }
+    let headers = ctx
+        .data_opt::<ForwardedHeaders>()
+        .map(|headers| headers.0.clone())
+        .unwrap_or_default();
+    let request = endpoint_request(&endpoint, params, &headers)?;
+
+    // A scope of its own, so the fields of a document can run side by side
+    let response = crate::request_scope((endpoint.handler)(request)).await;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    if !status.is_success() {
+        return Err(endpoint_error(status, &body));
+    }
+    if body.is_empty() {
+        return Ok(None);
+    }
+    let value: Value = serde_json::from_slice(&body)?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(FieldValue::value(async_graphql::Value::from_json(
+        value,
+    )?)))
+}
+
+/// The request calling `endpoint` with `params`, in the query for GET endpoints and as JSON
+/// for the others
+fn endpoint_request(
+    endpoint: &GraphqlEndpoint,
+    params: Map<String, Value>,
+    headers: &HeaderMap,
+) -> async_graphql::Result<Request<Body>> {
+    let mut builder = Request::builder().method(endpoint.method);
+    for (name, value) in headers {
+        if name != header::CONTENT_LENGTH
+            && name != header::CONTENT_TYPE
+            && name != header::TRANSFER_ENCODING
+        {
+            builder = builder.header(name, value);
+        }
+    }
+
+    let request = if endpoint.method == "GET" {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        for (name, value) in &params {
+            match value {
+                Value::Null => {}
+                Value::String(value) => {
+                    query.append_pair(name, value);
+                }
+                value => {
+                    query.append_pair(name, &value.to_string());
+                }
+            }
+        }
+        let query = query.finish();
+        let uri = if query.is_empty() {
+            endpoint.path.to_string()
+        } else {
+            format!("{}?{}", endpoint.path, query)
+        };
+        builder.uri(uri).body(Body::empty())?
+    } else if endpoint.params.is_some() {
+        builder
+            .uri(endpoint.path)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_vec(&params)?))?
+    } else {
+        builder.uri(endpoint.path).body(Body::empty())?
+    };
+    Ok(request)
+}
+
+/// The field error of an endpoint answering with `status`, with the `error` of a JSON body
+/// or the body itself as its message
+fn endpoint_error(status: StatusCode, body: &[u8]) -> async_graphql::Error {
+    let message = serde_json::from_slice::<Value>(body)
+        .ok()
+        .and_then(|body| body.get("error")?.as_str().map(str::to_string))
+        .or_else(|| {
+            std::str::from_utf8(body)
+                .ok()
+                .filter(|body| !body.trim().is_empty())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| status.to_string());
+    async_graphql::Error::new(message)
+        .extend_with(|_, extensions| extensions.set("status", status.as_u16()))
+}
+
+/// Builds the GraphQL types of JSON schemas
+struct Types<'a> {
+    /// Schemas of the named Rust types
+    definitions: &'a Map<String, Value>,
+    /// Object types by name, `None` while their fields are being built
+    objects: BTreeMap<String, Option<Object>>,
+    /// Whether the `JSON` scalar is used
+    json: bool,
+}
+
+impl Types<'_> {
+    /// The output type of `schema`, an object type described inline is named `name`
+    fn output(&mut self, schema: &Value, name: &str) -> TypeRef {
+        let (schema, nullable) = without_null(schema);
+        let ty = self.output_type(&schema, name);
+        non_null_unless(ty, nullable)
+    }
+
+    fn output_type(&mut self, schema: &Value, name: &str) -> TypeRef {
+        let definitions = self.definitions;
+        if let Some(reference) = reference(schema) {
+            return match definitions.get(reference) {
+                Some(definition) if is_object(definition) => {
+                    self.object(&type_name(reference), definition)
+                }
+                Some(definition) => self.output_type(definition, &type_name(reference)),
+                None => self.json(),
+            };
+        }
+        match schema.get("type").and_then(Value::as_str) {
+            Some("array") if schema.get("prefixItems").is_none() => {
+                let items = schema.get("items").unwrap_or(&Value::Bool(true));
+                TypeRef::List(Box::new(self.output(items, &format!("{}Item", name))))
+            }
+            Some("object") if is_object(schema) => self.object(name, schema),
+            Some(ty) => scalar(ty).map_or_else(|| self.json(), TypeRef::named),
+            None => self.json(),
+        }
+    }
+
+    /// Registers the object type `name` with a field for each property of `schema`
+    fn object(&mut self, name: &str, schema: &Value) -> TypeRef {
+        if !self.objects.contains_key(name) {
+            // Recursive types refer to the object while its fields are being built
+            self.objects.insert(name.to_string(), None);
+            let required = required(schema);
+            let mut object = Object::new(name);
+            for (property, property_schema) in properties(schema) {
+                let property_type = format!("{}{}", name, pascal_case(property));
+                let ty = self.output(property_schema, &property_type);
+                let ty = if required.contains(property.as_str()) {
+                    ty
+                } else {
+                    nullable(ty)
+                };
+                let key = property.clone();
+                object = object.field(Field::new(property, ty, move |ctx| {
+                    FieldFuture::from_value(property_of(ctx.parent_value, &key))
+                }));
+            }
+            self.objects.insert(name.to_string(), Some(object));
+        }
+        TypeRef::named(name)
+    }
+
+    /// The arguments of the parameter struct `params`
+    fn arguments(&mut self, params: &Value) -> Vec<InputValue> {
+        let definitions = self.definitions;
+        let params = reference(params)
+            .and_then(|reference| definitions.get(reference))
+            .unwrap_or(params);
+        let required = required(params);
+        properties(params)
+            .map(|(name, schema)| {
+                let ty = self.input(schema);
+                let ty = if required.contains(name.as_str()) {
+                    ty
+                } else {
+                    nullable(ty)
+                };
+                InputValue::new(name, ty)
+            })
+            .collect()
+    }
+
+    /// The input type of `schema`, objects are passed as JSON
+    fn input(&mut self, schema: &Value) -> TypeRef {
+        let definitions = self.definitions;
+        let (schema, nullable) = without_null(schema);
+        let schema = reference(&schema)
+            .and_then(|reference| definitions.get(reference))
+            .unwrap_or(&schema);
+        let ty = match schema.get("type").and_then(Value::as_str) {
+            Some("array") if schema.get("prefixItems").is_none() => {
+                let items = schema.get("items").unwrap_or(&Value::Bool(true));
+                TypeRef::List(Box::new(self.input(items)))
+            }
+            Some(ty) => scalar(ty).map_or_else(|| self.json(), TypeRef::named),
+            None => self.json(),
+        };
+        non_null_unless(ty, nullable)
+    }
+
+    fn json(&mut self) -> TypeRef {
+        self.json = true;
+        TypeRef::named(JSON_SCALAR)
+    }
+}
+
+/// The built-in scalar of a JSON schema type
+fn scalar(ty: &str) -> Option<&'static str> {
+    match ty {
+        "string" => Some(TypeRef::STRING),
+        "integer" => Some(TypeRef::INT),
+        "number" => Some(TypeRef::FLOAT),
+        "boolean" => Some(TypeRef::BOOLEAN),
+        _ => None,
+    }
+}
+
+/// The name of the definition `schema` refers to, e.g. `User` for `#/$defs/User`
+fn reference(schema: &Value) -> Option<&str> {
+    let reference = schema.get("$ref")?.as_str()?;
+    Some(reference.rsplit('/').next().unwrap_or(reference))
+}
+
+/// Whether `schema` describes a struct, with named fields
+fn is_object(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("object")
+        && properties(schema).next().is_some()
+}
+
+/// The properties of an object schema with a valid GraphQL name
+fn properties(schema: &Value) -> impl Iterator<Item = (&String, &Value)> {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter(|(name, _)| is_name(name))
+}
+
+fn required(schema: &Value) -> BTreeSet<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+/// `schema` without the `null` it allows, e.g. for an `Option`, and whether it allowed it
+fn without_null(schema: &Value) -> (Cow<'_, Value>, bool) {
+    let Some(object) = schema.as_object() else {
+        return (Cow::Borrowed(schema), true);
+    };
+    let is_null = |schema: &Value| schema.get("type").and_then(Value::as_str) == Some("null");
+
+    if let Some(Value::Array(types)) = object.get("type") {
+        let others: Vec<&Value> = types
+            .iter()
+            .filter(|ty| ty.as_str() != Some("null"))
+            .collect();
+        if others.len() < types.len() {
+            let mut schema = object.clone();
+            if let [ty] = others[..] {
+                schema.insert("type".to_string(), ty.clone());
+            }
+            return (Cow::Owned(Value::Object(schema)), true);
+        }
+    }
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(variants)) = object.get(keyword) {
+            if let [a, b] = &variants[..] {
+                if is_null(b) {
+                    return (Cow::Borrowed(a), true);
+                }
+                if is_null(a) {
+                    return (Cow::Borrowed(b), true);
+                }
+            }
+        }
+    }
+    (Cow::Borrowed(schema), is_null(schema))
+}
+
+fn nullable(ty: TypeRef) -> TypeRef {
+    match ty {
+        TypeRef::NonNull(ty) => *ty,
+        ty => ty,
+    }
+}
+
+fn non_null_unless(ty: TypeRef, nullable: bool) -> TypeRef {
+    if nullable {
+        ty
+    } else {
+        TypeRef::NonNull(Box::new(ty))
+    }
+}
+
+/// The property `key` of the JSON object resolved by the parent field
+fn property_of(parent: &FieldValue<'_>, key: &str) -> Option<async_graphql::Value> {
+    match parent.as_value()? {
+        async_graphql::Value::Object(object) => object
+            .get(key)
+            .filter(|value| !matches!(value, async_graphql::Value::Null))
+            .cloned(),
+        _ => None,
+    }
+}
+
+/// Whether `name` can name a GraphQL field or argument
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with("__")
+}
+
+/// `list_users` becomes `ListUsers`
+fn pascal_case(name: &str) -> String {
+    let mut chars = camel_case(&type_name(name)).chars().collect::<Vec<_>>();
+    if let Some(first) = chars.first_mut() {
+        *first = first.to_ascii_uppercase();
+    }
+    chars.into_iter().collect()
+}
@@ -0,0 +1,167 @@
+//! Liveness and readiness probes for deployments.
+//!
+//! [`health_routes!`](crate::health_routes) registers two routes with
+//! [`crate::route_registry`], next to the generated endpoints:
+//!
+//! - `/healthz` answers `200 OK` as long as the server handles requests
+//! - `/readyz` runs every check added with [`register_check`] and answers `200 OK` when
+//!   they all pass, `503 Service Unavailable` otherwise
+//!
+//! ```ignore
+//! yew_extra::health_routes!();
+//!
+//! let pool = PgPool::connect(&url).await?;
+//! let ping_pool = pool.clone();
+//! yew_extra::health::register_check("database", move || {
+//!     let pool = ping_pool.clone();
+//!     async move {
+//!         sqlx::query("SELECT 1").execute(&pool).await.map(|_| ()).map_err(|e| e.to_string())
+//!     }
+//! });
+//! ```
+//!
+//! Both answer with a JSON report, e.g.
+//! `{"status":"unavailable","checks":[{"name":"database","ok":false,"error":"..."}]}`.
+
+use axum::body::Body;
+use axum::http::{header, Request, Response, StatusCode};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+
+type CheckFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+type Check = Arc<dyn Fn() -> CheckFuture + Send + Sync>;
+
+/// Readiness checks by name, in registration order
+static CHECKS: Lazy<RwLock<Vec<(String, Check)>>> = Lazy::new(Default::default);
+
+/// Registers the routes `/healthz` and `/readyz` with the route registry, or the two given
+/// paths:
+///
+/// ```ignore
+/// yew_extra::health_routes!();
+/// yew_extra::health_routes!("/internal/live", "/internal/ready");
+/// ```
+#[macro_export]
+macro_rules! health_routes {
+    () => {
+        $crate::health_routes!("/healthz", "/readyz");
+    };
+    ($health:expr, $ready:expr $(,)?) => {
+        ::inventory::submit! {
+            $crate::route_registry::RouteInfo::new(
+                $health,
+                ::axum::http::Method::GET,
+                $crate::health::health_handler
+            )
+        }
+        ::inventory::submit! {
+            $crate::route_registry::RouteInfo::new(
+                $ready,
+                ::axum::http::Method::GET,
+                $crate::health::ready_handler
+            )
+        }
+    };
+}
+
+/// Adds a check `/readyz` runs, replacing the one registered under the same name
+pub fn register_check<F, Fut>(name: impl Into<String>, check: F)
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), String>> + Send + 'static,
+{
+    let name = name.into();
+    let check: Check = Arc::new(move || Box::pin(check()));
+    let mut checks = CHECKS.write().unwrap_or_else(|e| e.into_inner());
+    match checks.iter_mut().find(|(existing, _)| *existing == name) {
+        Some(entry) => entry.1 = check,
+        None => checks.push((name, check)),
+    }
+}
+
+/// Removes the check registered under `name`
+pub fn unregister_check(name: &str) {
+    CHECKS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|(existing, _)| existing != name);
+}
+
+/// The outcome of a single readiness check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The body of the probe responses
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// `ok` or `unavailable`
+    pub status: String,
+    #[serde(default)]
+    pub checks: Vec<CheckResult>,
+}
+
+impl HealthReport {
+    pub fn is_ok(&self) -> bool {
+        self.status == "ok"
+    }
+}
+
+/// Runs every registered check, one after the other
+pub async fn readiness() -> HealthReport {
+    // Cloned out so the lock isn't held across the checks
+    let checks: Vec<(String, Check)> = CHECKS.read().unwrap_or_else(|e| e.into_inner()).clone();
+
+    let mut results = Vec::with_capacity(checks.len());
+    for (name, check) in checks {
+        let outcome = check().await;
+        results.push(CheckResult {
+            name,
+            ok: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+
+    let ready = results.iter().all(|result| result.ok);
+    HealthReport {
+        status: if ready { "ok" } else { "unavailable" }.to_string(),
+        checks: results,
+    }
+}
+
+fn report_response(report: &HealthReport) -> Response<Body> {
+    let status = if report.is_ok() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let body = serde_json::to_vec(report).unwrap_or_default();
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::CACHE_CONTROL, "no-store")
+        .body(Body::from(body))
+        .unwrap_or_else(|_| Response::new(Body::empty()))
+}
+
+/// Liveness probe, registered by [`health_routes!`](crate::health_routes)
+pub fn health_handler(_req: Request<Body>) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+    Box::pin(async {
+        report_response(&HealthReport {
+            status: "ok".to_string(),
+            checks: Vec::new(),
+        })
+    })
+}
+
+/// Readiness probe, registered by [`health_routes!`](crate::health_routes)
+pub fn ready_handler(_req: Request<Body>) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+    Box::pin(async { report_response(&readiness().await) })
+}
@@ -0,0 +1,182 @@
+//! Hooks rendering the same way on the server and in the browser.
+//!
+//! The hooks generated by `yew_server_hook` are built on these, so their server and
+//! browser builds share one body: the state starts from the same value in both, and only
+//! the effect fetching the data is left out of server builds.
+//!
+//! ```ignore
+//! #[hook]
+//! fn use_clock(format: &'static str) -> String {
+//!     let now = use_server_value(|| format.replace("%T", "--:--:--"));
+//!     {
+//!         let now = now.clone();
+//!         use_isomorphic_effect(format, move |format| {
+//!             #[cfg(not(feature = "ssr"))]
+//!             now.set(current_time(format));
+//!             || ()
+//!         });
+//!     }
+//!     (*now).clone()
+//! }
+//! ```
+
+use std::cell::Cell;
+use std::ops::Deref;
+use std::rc::Rc;
+use yew::functional::TearDown;
+use yew::prelude::*;
+
+/// State starting from `init` in the server render and the first browser render alike.
+///
+/// The server renders what `init` returns and hydration expects the same markup, so it
+/// should only depend on the arguments of the hook, not on browser APIs or the time.
+#[hook]
+pub fn use_server_value<T, F>(init: F) -> UseStateHandle<T>
+where
+    T: 'static,
+    F: FnOnce() -> T,
+{
+    use_state(init)
+}
+
+/// Runs `effect` after the renders where `deps` changed, in the browser only.
+///
+/// Server renders never run it, so they show the state the hook starts with, which is
+/// also the state hydration starts from. The effect still has to compile on the server;
+/// put the parts using browser APIs behind `cfg`.
+#[hook]
+pub fn use_isomorphic_effect<D, F, T>(deps: D, effect: F)
+where
+    D: PartialEq + 'static,
+    F: FnOnce(&D) -> T + 'static,
+    T: TearDown,
+{
+    use_effect_with(deps, effect)
+}
+
+/// Effect dependencies compared by a key instead of by their value.
+///
+/// The hooks of endpoints declared with `key = "..."` keep their parameters in one of
+/// these, so parameters that aren't `PartialEq`, or are too large to compare on every
+/// render, still refetch when the key changes:
+///
+/// ```
+/// use yew_extra::hooks::KeyedDeps;
+///
+/// let first = KeyedDeps::new(1, vec![0.5_f64; 1000]);
+/// let same_key = KeyedDeps::new(1, vec![]);
+/// let other_key = KeyedDeps::new(2, vec![0.5_f64; 1000]);
+///
+/// assert!(first == same_key);
+/// assert!(first != other_key);
+/// assert_eq!(first.len(), 1000);
+/// ```
+pub struct KeyedDeps<K, T> {
+    key: K,
+    value: Rc<T>,
+}
+
+impl<K, T> KeyedDeps<K, T> {
+    pub fn new(key: K, value: T) -> Self {
+        Self {
+            key,
+            value: Rc::new(value),
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K: Clone, T> Clone for KeyedDeps<K, T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl<K: PartialEq, T> PartialEq for KeyedDeps<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K, T> Deref for KeyedDeps<K, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+/// What the `use_*_with_handle` hooks generated by `yew_server_hook` return next to their
+/// `ApiHook`.
+///
+/// `ApiHook` is defined by the app, so anything the generated code adds goes here instead
+/// of breaking its definition.
+#[derive(Clone, PartialEq)]
+pub struct HookHandle {
+    /// Runs the request again, e.g. after a mutation changed the data
+    pub refetch: Callback<()>,
+    /// Whether the last request couldn't be sent because the browser was offline, rather
+    /// than failing on the server
+    pub is_offline: bool,
+}
+
+/// Refetches a hook asked for by its component, e.g. after a mutation changed the data.
+///
+/// The generated hooks add [`requests()`](Self::requests) to the dependencies of their
+/// effect and return [`callback()`](Self::callback) as [`HookHandle::refetch`], so calling
+/// it runs the effect again without new parameters or a remount.
+#[derive(Clone)]
+pub struct Refetch {
+    requests: u64,
+    pending: Rc<Cell<bool>>,
+    callback: Callback<()>,
+}
+
+impl Refetch {
+    /// Number of refetches asked for so far
+    pub fn requests(&self) -> u64 {
+        self.requests
+    }
+
+    /// Asks for a refetch and re-renders the component, the same callback on every render
+    pub fn callback(&self) -> Callback<()> {
+        self.callback.clone()
+    }
+
+    /// Whether a refetch was asked for since the last call, so the effect skips caches
+    pub fn take_pending(&self) -> bool {
+        self.pending.replace(false)
+    }
+}
+
+/// A [`Refetch`] for the hook being run
+#[hook]
+pub fn use_refetch() -> Refetch {
+    let update = use_force_update();
+    let shared = use_memo((), move |_| {
+        let requests = Rc::new(Cell::new(0));
+        let pending = Rc::new(Cell::new(false));
+        let callback = {
+            let requests = requests.clone();
+            let pending = pending.clone();
+            Callback::from(move |()| {
+                requests.set(requests.get() + 1);
+                pending.set(true);
+                update.force_update();
+            })
+        };
+        (requests, pending, callback)
+    });
+    let (requests, pending, callback) = &*shared;
+    Refetch {
+        requests: requests.get(),
+        pending: pending.clone(),
+        callback: callback.clone(),
+    }
+}
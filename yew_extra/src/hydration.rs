@@ -0,0 +1,207 @@
+//! Detection of hook state that differs between the server render and hydration.
+//!
+//! In debug builds, the hooks generated by `yew_server_hook` describe their initial state
+//! with [`use_hydration_check`]. The server adds the descriptions to the
+//! [`HydrationSnapshot`] provided as context while rendering, and the shell embeds it in
+//! the page:
+//!
+//! ```ignore
+//! let snapshot = HydrationSnapshot::new();
+//! let context = snapshot.clone();
+//! let body = ServerRenderer::<Root>::with_props(move || RootProps { snapshot: context })
+//!     .render()
+//!     .await;
+//! format!("<body><div id=\"app\">{}</div>{}</body>", body, snapshot.script_tag())
+//!
+//! // Root renders <ContextProvider<HydrationSnapshot> context={props.snapshot.clone()}>
+//! ```
+//!
+//! The browser compares the first render of each hook with it, when hydration starts with
+//! [`check_hydration`] (`client` feature), and logs a warning for every difference: a
+//! placeholder read from a cache only the browser has, a server function that isn't
+//! deterministic, or a page without the snapshot. Release builds skip all of it.
+
+use crate::util::fnv1a;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use yew::prelude::*;
+
+/// Id of the script element carrying the snapshot
+pub const SNAPSHOT_ID: &str = "yew-hydration-snapshot";
+
+/// The initial states of the hooks rendered on the server, in render order
+#[derive(Clone, Default)]
+pub struct HydrationSnapshot(Arc<Mutex<BTreeMap<String, Vec<String>>>>);
+
+impl PartialEq for HydrationSnapshot {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl HydrationSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&self, hook: &str, state: String) {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(hook.to_string())
+            .or_default()
+            .push(state);
+    }
+
+    /// The states recorded for each hook, in render order
+    pub fn states(&self) -> BTreeMap<String, Vec<String>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// The script element carrying the snapshot to the browser, for the SSR shell, outside
+    /// the element the app is rendered into
+    pub fn script_tag(&self) -> String {
+        let json = serde_json::to_string(&self.states()).unwrap_or_default();
+        format!(
+            r#"<script type="application/json" id="{}">{}</script>"#,
+            SNAPSHOT_ID,
+            json.replace("</", "<\\/")
+        )
+    }
+}
+
+/// Short digest of `value` as JSON, the same on the server and in the browser
+pub fn fingerprint<T: Serialize + ?Sized>(value: &T) -> String {
+    let json = serde_json::to_string(value).unwrap_or_default();
+    // A stable hash, so the digest doesn't depend on the platform or the toolchain
+    format!("{:016x}", fnv1a(json.as_bytes()))
+}
+
+/// Compares the initial state of the hook named `hook` between the server and the
+/// browser, in debug builds.
+///
+/// `state` describes it, e.g. with [`fingerprint`], and is only called on the first
+/// render. The server adds the description to the [`HydrationSnapshot`] in the context;
+/// the browser compares it to the snapshot while [`check_hydration`] is on.
+#[hook]
+pub fn use_hydration_check<F>(hook: &'static str, state: F)
+where
+    F: FnOnce() -> String,
+{
+    let snapshot = use_context::<HydrationSnapshot>();
+    use_memo((), move |_| {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        match snapshot {
+            Some(snapshot) => snapshot.push(hook, state()),
+            None => compare(hook, state),
+        }
+    });
+}
+
+#[cfg(feature = "client")]
+pub use client::check_hydration;
+#[cfg(feature = "client")]
+use client::compare;
+
+/// Only the browser compares states
+#[cfg(not(feature = "client"))]
+fn compare(_hook: &str, _state: impl FnOnce() -> String) {}
+
+#[cfg(feature = "client")]
+mod client {
+    use super::SNAPSHOT_ID;
+    use std::cell::RefCell;
+    use std::collections::{BTreeMap, VecDeque};
+
+    /// A check in progress
+    struct Check {
+        /// The states of the snapshot not compared yet, `None` without a snapshot
+        expected: Option<BTreeMap<String, VecDeque<String>>>,
+        reported_missing: bool,
+    }
+
+    thread_local! {
+        static CHECK: RefCell<Option<Check>> = const { RefCell::new(None) };
+    }
+
+    fn warn(message: &str) {
+        web_sys::console::warn_1(&format!("Hydration mismatch: {}", message).into());
+    }
+
+    fn read_snapshot() -> Option<BTreeMap<String, VecDeque<String>>> {
+        let text = web_sys::window()?
+            .document()?
+            .get_element_by_id(SNAPSHOT_ID)?
+            .text_content()?;
+        serde_json::from_str(&text).ok()
+    }
+
+    /// Compares the hooks rendered while hydrating to the snapshot of the server, in debug
+    /// builds. Call it right before `yew::Renderer::<App>::new().hydrate()`.
+    ///
+    /// The check ends once the first hydration pass is done; parts of the page hydrated
+    /// later, e.g. behind `Suspense`, aren't compared.
+    pub fn check_hydration() {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        CHECK.with(|check| {
+            *check.borrow_mut() = Some(Check {
+                expected: read_snapshot(),
+                reported_missing: false,
+            })
+        });
+        // Yew hydrates in a task of its own, this one runs after it
+        wasm_bindgen_futures::spawn_local(async {
+            crate::browser::sleep(0).await;
+            let check = CHECK.with(|check| check.borrow_mut().take());
+            let expected = check.and_then(|check| check.expected).unwrap_or_default();
+            for (hook, states) in expected {
+                if !states.is_empty() {
+                    warn(&format!(
+                        "{} was rendered {} more time(s) on the server than while hydrating",
+                        hook,
+                        states.len()
+                    ));
+                }
+            }
+        });
+    }
+
+    /// Compares the first render of `hook` to the next state the server recorded for it
+    pub(super) fn compare(hook: &str, state: impl FnOnce() -> String) {
+        CHECK.with(|check| {
+            let mut check = check.borrow_mut();
+            let Some(check) = check.as_mut() else {
+                return;
+            };
+            let Some(expected) = check.expected.as_mut() else {
+                if !check.reported_missing {
+                    check.reported_missing = true;
+                    warn(&format!(
+                        "the page has no #{} element, render HydrationSnapshot::script_tag() \
+                         in the SSR shell and provide the snapshot as context while rendering",
+                        SNAPSHOT_ID
+                    ));
+                }
+                return;
+            };
+            let state = state();
+            match expected.get_mut(hook).and_then(VecDeque::pop_front) {
+                None => warn(&format!(
+                    "{} was rendered while hydrating but not on the server",
+                    hook
+                )),
+                Some(server) if server != state => warn(&format!(
+                    "{} started as `{}` on the server but `{}` in the browser; its placeholder \
+                     data and initial state must not depend on the browser",
+                    hook, server, state
+                )),
+                Some(_) => {}
+            }
+        });
+    }
+}
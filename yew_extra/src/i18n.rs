@@ -0,0 +1,297 @@
+//! Error messages in the user's language.
+//!
+//! Server functions return a [`LocalizedError`], a message key with interpolation
+//! arguments, instead of an English sentence. The translations are registered with
+//! [`add_translations`] on the server and in the browser:
+//!
+//! ```
+//! use yew_extra::i18n::{add_translations, LocalizedError};
+//!
+//! add_translations("en", [("todo.not_found", "Todo {id} doesn't exist")]);
+//! add_translations("de", [("todo.not_found", "Aufgabe {id} existiert nicht")]);
+//!
+//! let error = LocalizedError::new("todo.not_found").status(404).arg("id", 7);
+//! assert_eq!(error.message(&["de-CH".to_string()]), "Aufgabe 7 existiert nicht");
+//! ```
+//!
+//! The response carries the message rendered for the request's `Accept-Language` in
+//! `error`, next to `key` and `args`. The clients generated by `yew_server_hook` render the
+//! key again with the translations registered in the browser, in the languages of
+//! [`client_languages`], and keep `error` when they have none for it.
+
+use serde::de::{Deserializer, Visitor};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::RwLock;
+
+/// Templates by language and key, languages in lowercase
+static TRANSLATIONS: RwLock<BTreeMap<String, BTreeMap<String, String>>> =
+    RwLock::new(BTreeMap::new());
+
+/// Language used when none of the preferred ones has a translation
+static DEFAULT_LANGUAGE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Languages set with [`set_client_languages`]
+static CLIENT_LANGUAGES: RwLock<Option<Vec<String>>> = RwLock::new(None);
+
+/// Adds the templates of `language`, replacing those already registered under the same
+/// keys. `{name}` in a template is replaced by the argument `name`.
+pub fn add_translations<K, T>(language: &str, templates: impl IntoIterator<Item = (K, T)>)
+where
+    K: Into<String>,
+    T: Into<String>,
+{
+    let mut translations = TRANSLATIONS.write().unwrap_or_else(|e| e.into_inner());
+    let language = translations
+        .entry(language.trim().to_lowercase())
+        .or_default();
+    for (key, template) in templates {
+        language.insert(key.into(), template.into());
+    }
+}
+
+/// Sets the language tried after the preferred ones, `en` unless set
+pub fn set_default_language(language: &str) {
+    *DEFAULT_LANGUAGE.write().unwrap_or_else(|e| e.into_inner()) =
+        Some(language.trim().to_lowercase());
+}
+
+fn default_language() -> String {
+    DEFAULT_LANGUAGE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| "en".to_string())
+}
+
+/// Renders `key` in the first of `languages` with a translation for it, falling back to
+/// the default language. A regional language like `de-CH` also tries `de`.
+pub fn translate(
+    key: &str,
+    args: &BTreeMap<String, String>,
+    languages: &[String],
+) -> Option<String> {
+    render(key, args, languages, Some(default_language()))
+}
+
+fn render(
+    key: &str,
+    args: &BTreeMap<String, String>,
+    languages: &[String],
+    fallback: Option<String>,
+) -> Option<String> {
+    let translations = TRANSLATIONS.read().unwrap_or_else(|e| e.into_inner());
+    let template = languages
+        .iter()
+        .map(|language| language.to_lowercase())
+        .flat_map(|language| {
+            let primary = language.split('-').next().unwrap_or_default().to_string();
+            [language, primary]
+        })
+        .chain(fallback)
+        .find_map(|language| translations.get(&language)?.get(key))?;
+
+    let mut message = template.clone();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), value);
+    }
+    Some(message)
+}
+
+/// The languages of an `Accept-Language` header, most preferred first
+pub fn parse_accept_language(header: &str) -> Vec<String> {
+    let mut languages: Vec<(String, f32)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut pieces = entry.split(';');
+            let language = pieces.next()?.trim();
+            let quality = pieces
+                .find_map(|piece| piece.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |quality| quality.trim().parse::<f32>().ok())?;
+            (!language.is_empty() && language != "*" && quality > 0.0)
+                .then(|| (language.to_string(), quality))
+        })
+        .collect();
+    // Stable, so equally preferred languages keep their order
+    languages.sort_by(|a, b| b.1.total_cmp(&a.1));
+    languages
+        .into_iter()
+        .map(|(language, _)| language)
+        .collect()
+}
+
+/// An error message identified by a key, rendered in the user's language
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalizedError {
+    /// Status of the response, `400 Bad Request` unless set
+    #[serde(skip, default = "default_status")]
+    pub status: u16,
+    pub key: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<String, String>,
+}
+
+fn default_status() -> u16 {
+    400
+}
+
+impl LocalizedError {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            status: default_status(),
+            key: key.into(),
+            args: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the status of the response
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Sets the value of `{name}` in the message
+    pub fn arg(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.args.insert(name.into(), value.to_string());
+        self
+    }
+
+    /// The message in the first of `languages` with a translation, or the key without any
+    pub fn message(&self, languages: &[String]) -> String {
+        translate(&self.key, &self.args, languages).unwrap_or_else(|| self.key.clone())
+    }
+}
+
+/// The message in the default language
+impl fmt::Display for LocalizedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message(&[]))
+    }
+}
+
+impl std::error::Error for LocalizedError {}
+
+/// Answers with the message rendered for the `Accept-Language` of the request being handled
+#[cfg(not(target_arch = "wasm32"))]
+impl axum::response::IntoResponse for LocalizedError {
+    fn into_response(self) -> axum::response::Response {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            error: String,
+            key: &'a str,
+            #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+            args: &'a BTreeMap<String, String>,
+        }
+
+        let languages = crate::extract::try_request_header(axum::http::header::ACCEPT_LANGUAGE)
+            .and_then(|header| header.to_str().map(parse_accept_language).ok())
+            .unwrap_or_default();
+        let status = axum::http::StatusCode::from_u16(self.status)
+            .unwrap_or(axum::http::StatusCode::BAD_REQUEST);
+        let body = Body {
+            error: self.message(&languages),
+            key: &self.key,
+            args: &self.args,
+        };
+        (status, axum::Json(body)).into_response()
+    }
+}
+
+/// Makes the generated clients render errors in `languages` instead of the browser's, e.g.
+/// after the user picked a language in the app
+pub fn set_client_languages(languages: Vec<String>) {
+    *CLIENT_LANGUAGES.write().unwrap_or_else(|e| e.into_inner()) = Some(languages);
+}
+
+/// The languages the generated clients render errors in: those of
+/// [`set_client_languages`], or the browser's (`navigator.languages`)
+pub fn client_languages() -> Vec<String> {
+    if let Some(languages) = CLIENT_LANGUAGES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+    {
+        return languages;
+    }
+    browser_languages()
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "client"))]
+fn browser_languages() -> Vec<String> {
+    crate::browser::navigator_property("languages")
+        .map(|languages| {
+            js_sys::Array::from(&languages)
+                .iter()
+                .filter_map(|language| language.as_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(not(all(target_arch = "wasm32", feature = "client")))]
+fn browser_languages() -> Vec<String> {
+    Vec::new()
+}
+
+/// The `key` and `args` of an error response, if it has them
+#[derive(Deserialize)]
+struct ErrorBody {
+    key: Option<String>,
+    #[serde(default)]
+    args: BTreeMap<String, Arg>,
+}
+
+/// An argument of any JSON scalar type, as text
+struct Arg(String);
+
+impl<'de> Deserialize<'de> for Arg {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ArgVisitor;
+
+        impl Visitor<'_> for ArgVisitor {
+            type Value = Arg;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string, number or boolean")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Arg, E> {
+                Ok(Arg(value.to_string()))
+            }
+
+            fn visit_bool<E>(self, value: bool) -> Result<Arg, E> {
+                Ok(Arg(value.to_string()))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Arg, E> {
+                Ok(Arg(value.to_string()))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Arg, E> {
+                Ok(Arg(value.to_string()))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Arg, E> {
+                Ok(Arg(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(ArgVisitor)
+    }
+}
+
+/// Renders a localized error response in the [`client_languages`], used by the generated
+/// clients. `None` if it isn't one or none of the languages has a translation for it, the
+/// `error` the server rendered for the same languages is better than the default language.
+///
+/// Takes the parsed body, e.g. a `&serde_json::Value`.
+pub fn localize_error<'de, D: Deserializer<'de>>(body: D) -> Option<String> {
+    let body = ErrorBody::deserialize(body).ok()?;
+    let args = body
+        .args
+        .into_iter()
+        .map(|(name, Arg(value))| (name, value))
+        .collect();
+    render(&body.key?, &args, &client_languages(), None)
+}
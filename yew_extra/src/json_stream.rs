@@ -0,0 +1,83 @@
+//! Incremental JSON serialization of large list responses.
+//!
+//! [`StreamingJson`] writes a `Vec<T>` as a JSON array in chunks while the response body is
+//! being sent, instead of building the whole document in memory first. The generated
+//! wrappers of endpoints declared with `stream_json = true` respond with it; clients read
+//! the same JSON as before.
+
+use axum::body::{Body, Bytes};
+use axum::http::{header, HeaderValue, Response, StatusCode};
+use axum::response::IntoResponse;
+use serde::Serialize;
+
+/// Chunks are flushed once they reach this size
+const CHUNK_SIZE: usize = 16 * 1024;
+
+/// A JSON array response serialized while it is sent
+#[derive(Debug, Clone)]
+pub struct StreamingJson<T>(pub Vec<T>);
+
+impl<T> StreamingJson<T> {
+    pub fn new(items: Vec<T>) -> Self {
+        Self(items)
+    }
+}
+
+impl<T> IntoResponse for StreamingJson<T>
+where
+    T: Serialize + Send + 'static,
+{
+    fn into_response(self) -> Response<Body> {
+        let chunks = Chunks {
+            items: self.0.into_iter(),
+            started: false,
+            finished: false,
+        };
+        let mut response = Response::new(Body::from_stream(futures_util::stream::iter(chunks)));
+        *response.status_mut() = StatusCode::OK;
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        response
+    }
+}
+
+/// Serializes the items lazily, one chunk of the array at a time
+struct Chunks<T> {
+    items: std::vec::IntoIter<T>,
+    started: bool,
+    finished: bool,
+}
+
+impl<T: Serialize> Iterator for Chunks<T> {
+    type Item = Result<Bytes, serde_json::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+        if !self.started {
+            chunk.push(b'[');
+        }
+        while chunk.len() < CHUNK_SIZE {
+            let Some(item) = self.items.next() else {
+                chunk.push(b']');
+                self.finished = true;
+                break;
+            };
+            if self.started {
+                chunk.push(b',');
+            }
+            self.started = true;
+            if let Err(e) = serde_json::to_writer(&mut chunk, &item) {
+                // The status is already sent, ending the body early is all that's left
+                self.finished = true;
+                return Some(Err(e));
+            }
+        }
+        Some(Ok(Bytes::from(chunk)))
+    }
+}
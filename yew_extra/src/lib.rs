@@ -7,12 +7,144 @@
 
 #![cfg_attr(not(target_arch = "wasm32"), allow(unused_imports))]
 
+// The generated wrappers and the app share one Axum, picked with the `axum-08` (default)
+// or `axum-07` feature. The renamed dependency is brought in as `axum`, so the rest of the
+// crate names it the same way for either version.
+#[cfg(all(feature = "axum-07", not(feature = "axum-08"), not(target_arch = "wasm32")))]
+extern crate axum07 as axum;
+#[cfg(all(feature = "axum-07", not(feature = "axum-08"), not(target_arch = "wasm32")))]
+extern crate axum_extra09 as axum_extra;
+
+#[cfg(all(not(feature = "axum-07"), not(feature = "axum-08"), not(target_arch = "wasm32")))]
+compile_error!("yew_extra needs the `axum-08` or `axum-07` feature on the server");
+
 #[cfg(not(target_arch = "wasm32"))]
 mod extract;
+#[cfg(not(target_arch = "wasm32"))]
+mod response_options;
+
+#[cfg(feature = "client")]
+mod browser;
+
+pub mod action;
+#[cfg(all(feature = "actix", not(target_arch = "wasm32")))]
+pub mod actix;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod audit;
+pub mod background_sync;
+pub mod base_path;
+pub mod cache;
+pub mod channel;
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+pub mod content_type;
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+mod context;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod db;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod deferred;
+pub mod deprecation;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod docs;
+pub mod error_handler;
+pub mod experiment;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod coalesce;
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+pub mod feature_flags;
+#[cfg(feature = "client")]
+pub mod fetch;
+pub mod flash;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod forward;
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+pub mod geo;
+#[cfg(all(feature = "graphql", not(target_arch = "wasm32")))]
+pub mod graphql;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod health;
+pub mod hooks;
+pub mod hydration;
+pub mod i18n;
+mod form;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod json_stream;
+#[cfg(feature = "client")]
+pub mod mock;
+pub mod ndjson;
+pub mod network;
+pub mod nonce;
+#[cfg(feature = "client")]
+pub mod offload;
+pub mod path_params;
+pub mod poll;
+pub mod query;
+pub mod recording;
+pub mod redirect;
+mod request;
+pub mod resource;
+pub mod retry;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod route_registry;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod server;
+pub mod shared_worker;
+pub mod status;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod subscription;
+pub mod sw;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod test;
+pub mod timeout;
+pub mod transform;
+pub mod transport;
+#[cfg(all(feature = "schema", not(target_arch = "wasm32")))]
+pub mod typescript;
+pub mod upload;
+mod util;
+pub mod validate;
+pub mod ws;
 
 #[cfg(not(target_arch = "wasm32"))]
-pub use extract::{extract, extract_with_state, provide_request_parts, clear_request_parts};
+pub use extract::{extract, extract_with_state, provide_request_parts, clear_request_parts, request_scope, run_blocking};
+#[cfg(not(target_arch = "wasm32"))]
+pub use extract::{
+    append_response_header, apply_response_headers, extract_body, extract_json, extract_query,
+    header, headers, method, optional_header, provide_request_body, query_string,
+    read_request_body, response_options, uri, DEFAULT_BODY_LIMIT,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use response_options::ResponseOptions;
+#[cfg(not(target_arch = "wasm32"))]
+pub use redirect::redirect;
+
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+pub use context::{ExtractError, RequestContext};
+#[cfg(all(feature = "sqlx", not(target_arch = "wasm32")))]
+pub use db::tx;
+#[cfg(not(target_arch = "wasm32"))]
+pub use deferred::{after_response, run_after_response};
+#[cfg(not(target_arch = "wasm32"))]
+pub use geo::geo;
+pub use form::FormSelect;
+pub use request::{RequestOptions, ResponseMeta, SettleCallbacks};
+pub use status::WithStatus;
+#[cfg(not(target_arch = "wasm32"))]
+pub use timeout::remaining_time;
+#[cfg(not(target_arch = "wasm32"))]
+pub use upload::UploadedFile;
+pub use validate::{ValidateLength, ValidateRange, ValidateRegex, ValidationErrors};
+
+/// Whether the generated clients report failed requests by their status only.
+///
+/// Enabled by the `minimal_errors` feature, which trades the server's error messages for a
+/// smaller wasm bundle in apps with many endpoints.
+pub const MINIMAL_ERRORS: bool = cfg!(feature = "minimal_errors");
 
 // Re-export commonly used types for convenience
 #[cfg(not(target_arch = "wasm32"))]
 pub use axum::http::request::Parts;
+/// The typed headers [`header()`] returns, e.g. `headers::Authorization<Bearer>`
+#[cfg(not(target_arch = "wasm32"))]
+pub use axum_extra::headers;
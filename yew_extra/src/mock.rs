@@ -0,0 +1,6 @@
+//! Helpers for the `mock` builds of endpoints declared with `mock = "..."`.
+
+/// Waits for `ms` milliseconds, simulating the latency of a real request
+pub async fn delay(ms: u32) {
+    crate::browser::sleep(ms.min(i32::MAX as u32) as i32).await;
+}
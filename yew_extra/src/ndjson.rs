@@ -0,0 +1,258 @@
+//! Streaming responses of server functions as newline-delimited JSON.
+//!
+//! Server functions returning `Result<impl Stream<Item = T>, E>` answer with one JSON
+//! document per line, written as the stream yields the items:
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/jobs/{id}/logs", method = "GET")]
+//! pub async fn job_logs(id: u32) -> Result<impl Stream<Item = LogLine>, AppError> {
+//!     let lines = open_log(id).await?;
+//!     Ok(lines)
+//! }
+//! ```
+//!
+//! The generated `use_job_logs_stream` hook appends the items to its
+//! `DataState::Data(StreamItems<T>)` as the chunks arrive, and `job_logs()` in the browser
+//! collects all of them. The request is over once the function has returned its stream, so read what
+//! the stream needs from the request (headers, `extract()`) before returning it.
+
+use serde::de::DeserializeOwned;
+use std::cell::{Ref, RefCell};
+use std::fmt;
+use std::rc::Rc;
+
+/// Media type of the streamed responses
+pub const CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// The items a `use_*_stream` hook received so far.
+///
+/// The chunks are appended to one buffer that every snapshot shares, so a long stream
+/// isn't copied for each chunk. A snapshot keeps the items it had, and differs from the
+/// later ones, so components taking it as a prop render the new items:
+///
+/// ```
+/// use yew_extra::ndjson::StreamItems;
+///
+/// let mut items = StreamItems::default();
+/// items.extend([1, 2]);
+/// let first = items.clone();
+/// items.extend([3]);
+///
+/// assert_eq!(*first.borrow(), [1, 2]);
+/// assert_eq!(*items.borrow(), [1, 2, 3]);
+/// assert!(first != items);
+/// ```
+pub struct StreamItems<T> {
+    buffer: Rc<RefCell<Vec<T>>>,
+    len: usize,
+}
+
+impl<T> StreamItems<T> {
+    /// The items of this snapshot
+    pub fn borrow(&self) -> Ref<'_, [T]> {
+        Ref::map(self.buffer.borrow(), |buffer| &buffer[..self.len])
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends `items`. An older snapshot copies its items into a buffer of its own first,
+    /// leaving the later ones as they are.
+    pub fn extend(&mut self, items: impl IntoIterator<Item = T>)
+    where
+        T: Clone,
+    {
+        if self.buffer.borrow().len() != self.len {
+            let own = self.to_vec();
+            self.buffer = Rc::new(RefCell::new(own));
+        }
+        let mut buffer = self.buffer.borrow_mut();
+        buffer.extend(items);
+        self.len = buffer.len();
+    }
+
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.borrow().to_vec()
+    }
+}
+
+impl<T> Default for StreamItems<T> {
+    fn default() -> Self {
+        Self {
+            buffer: Rc::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> Clone for StreamItems<T> {
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for StreamItems<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && (Rc::ptr_eq(&self.buffer, &other.buffer) || *self.borrow() == *other.borrow())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for StreamItems<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.borrow().iter()).finish()
+    }
+}
+
+/// Splits the received chunks of a streamed body into lines, parsing the complete ones
+#[derive(Debug, Default)]
+pub struct LineDecoder {
+    /// The start of a line whose end hasn't arrived yet
+    pending: Vec<u8>,
+}
+
+impl LineDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a chunk of the body, returning the items of the lines it completed
+    pub fn push<T: DeserializeOwned>(&mut self, chunk: &[u8]) -> Result<Vec<T>, String> {
+        self.pending.extend_from_slice(chunk);
+        let Some(end) = self.pending.iter().rposition(|byte| *byte == b'\n') else {
+            return Ok(Vec::new());
+        };
+        let rest = self.pending.split_off(end + 1);
+        let complete = std::mem::replace(&mut self.pending, rest);
+        parse_lines(&complete)
+    }
+
+    /// Parses the last line once the body has ended, in case it had no newline
+    pub fn finish<T: DeserializeOwned>(self) -> Result<Vec<T>, String> {
+        parse_lines(&self.pending)
+    }
+}
+
+fn parse_lines<T: DeserializeOwned>(bytes: &[u8]) -> Result<Vec<T>, String> {
+    bytes
+        .split(|byte| *byte == b'\n')
+        .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+        .map(|line| {
+            serde_json::from_slice(line)
+                .map_err(|e| format!("Failed to parse streamed item: {}", e))
+        })
+        .collect()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::NdJson;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod server {
+    use super::CONTENT_TYPE;
+    use axum::body::{Body, Bytes};
+    use axum::http::{header, HeaderValue, Response, StatusCode};
+    use axum::response::IntoResponse;
+    use futures_util::{Stream, StreamExt};
+    use serde::Serialize;
+
+    /// A response writing the items of a stream as newline-delimited JSON while they come
+    #[derive(Debug, Clone)]
+    pub struct NdJson<S>(pub S);
+
+    impl<S> NdJson<S> {
+        pub fn new(items: S) -> Self {
+            Self(items)
+        }
+    }
+
+    impl<S> IntoResponse for NdJson<S>
+    where
+        S: Stream + Send + 'static,
+        S::Item: Serialize,
+    {
+        fn into_response(self) -> Response<Body> {
+            // The status is already sent when an item fails to serialize, ending the body
+            // early is all that's left
+            let lines = self.0.map(|item| {
+                let mut line = serde_json::to_vec(&item)?;
+                line.push(b'\n');
+                Ok::<_, serde_json::Error>(Bytes::from(line))
+            });
+            let mut response = Response::new(Body::from_stream(lines));
+            *response.status_mut() = StatusCode::OK;
+            response
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, HeaderValue::from_static(CONTENT_TYPE));
+            response
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+pub use client::read_items;
+
+#[cfg(feature = "client")]
+mod client {
+    use super::LineDecoder;
+    use serde::de::DeserializeOwned;
+    use std::ops::ControlFlow;
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    /// Reads the newline-delimited JSON body of `response`, passing the items of every
+    /// chunk to `on_items` as it arrives. Returning `ControlFlow::Break` stops reading and
+    /// cancels the rest of the body.
+    pub async fn read_items<T: DeserializeOwned>(
+        response: gloo_net::http::Response,
+        mut on_items: impl FnMut(Vec<T>) -> ControlFlow<()>,
+    ) -> Result<(), String> {
+        let Some(body) = response.body() else {
+            return Ok(());
+        };
+        let reader = web_sys::ReadableStreamDefaultReader::new(&body)
+            .map_err(|e| format!("Failed to read the response: {:?}", e))?;
+        let mut decoder = LineDecoder::new();
+
+        loop {
+            let chunk = JsFuture::from(reader.read())
+                .await
+                .map_err(|e| format!("Failed to read the response: {:?}", e))?;
+            let done = field(&chunk, "done").as_bool().unwrap_or(true);
+            if done {
+                break;
+            }
+            let bytes = field(&chunk, "value")
+                .dyn_into::<js_sys::Uint8Array>()
+                .map_err(|_| "The response body isn't made of bytes".to_string())?
+                .to_vec();
+
+            let items = decoder.push(&bytes)?;
+            if !items.is_empty() && on_items(items).is_break() {
+                let _ = reader.cancel();
+                return Ok(());
+            }
+        }
+
+        let items = decoder.finish()?;
+        if !items.is_empty() {
+            let _ = on_items(items);
+        }
+        Ok(())
+    }
+
+    fn field(target: &JsValue, name: &str) -> JsValue {
+        js_sys::Reflect::get(target, &JsValue::from_str(name)).unwrap_or(JsValue::UNDEFINED)
+    }
+}
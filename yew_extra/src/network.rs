@@ -0,0 +1,92 @@
+//! Offline detection for the hooks and client functions generated by `yew_server_hook`.
+//!
+//! When a request can't be sent while the browser reports being offline
+//! (`navigator.onLine`), the generated hooks set `HookHandle::is_offline`, so UIs can tell a
+//! lost connection from a failing server without looking at the error message. Client
+//! functions fail with a message saying so; check [`is_online`] to tell it apart.
+//! [`watch_online`] (`client` feature) reports when the connection comes and goes, e.g. to
+//! show a banner or refetch once it is back.
+
+use std::fmt::Display;
+
+/// Error reported by the generated code when the browser is offline. Only a message for
+/// the user, the hooks report the state itself in `HookHandle::is_offline`.
+const OFFLINE_ERROR: &str = "You are offline. Check your connection and try again";
+
+/// Whether the browser reports a network connection.
+///
+/// Always `true` without the `client` feature or outside the browser.
+pub fn is_online() -> bool {
+    #[cfg(all(feature = "client", target_arch = "wasm32"))]
+    {
+        crate::browser::navigator_property("onLine")
+            .and_then(|on_line| on_line.as_bool())
+            .unwrap_or(true)
+    }
+    #[cfg(not(all(feature = "client", target_arch = "wasm32")))]
+    {
+        true
+    }
+}
+
+/// Message for a request that couldn't be sent: a notice that the browser is offline, the
+/// fetch error otherwise
+pub fn fetch_error(error: impl Display) -> String {
+    if is_online() {
+        format!("Failed to fetch data: {}", error)
+    } else {
+        OFFLINE_ERROR.to_string()
+    }
+}
+
+#[cfg(feature = "client")]
+pub use client::{watch_online, OnlineWatcher};
+
+#[cfg(feature = "client")]
+mod client {
+    use wasm_bindgen::prelude::*;
+
+    type Listener = Closure<dyn FnMut()>;
+
+    /// Keeps the `online` and `offline` listeners registered until it is dropped
+    pub struct OnlineWatcher {
+        online: Listener,
+        offline: Listener,
+    }
+
+    impl Drop for OnlineWatcher {
+        fn drop(&mut self) {
+            if let Some(window) = web_sys::window() {
+                let _ = window.remove_event_listener_with_callback(
+                    "online",
+                    self.online.as_ref().unchecked_ref(),
+                );
+                let _ = window.remove_event_listener_with_callback(
+                    "offline",
+                    self.offline.as_ref().unchecked_ref(),
+                );
+            }
+        }
+    }
+
+    /// Calls `on_change` with the new state whenever the browser goes online or offline,
+    /// until the returned watcher is dropped
+    pub fn watch_online<F>(on_change: F) -> Result<OnlineWatcher, String>
+    where
+        F: Fn(bool) + Clone + 'static,
+    {
+        let window = web_sys::window().ok_or_else(|| "No window available".to_string())?;
+
+        let on_online = on_change.clone();
+        let online: Listener = Closure::new(move || on_online(true));
+        let offline: Listener = Closure::new(move || on_change(false));
+
+        for (event, listener) in [("online", &online), ("offline", &offline)] {
+            window
+                .add_event_listener_with_callback(event, listener.as_ref().unchecked_ref())
+                .map_err(|e| format!("Failed to listen to {} events: {:?}", event, e))?;
+        }
+
+        Ok(OnlineWatcher { online, offline })
+    }
+}
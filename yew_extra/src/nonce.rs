@@ -0,0 +1,206 @@
+//! Replay protection for high-value mutations.
+//!
+//! Client functions and hooks of endpoints declared with `nonce = true` send a fresh
+//! [`NONCE_HEADER`] and the time of the request in [`TIMESTAMP_HEADER`]. The server rejects
+//! a request whose timestamp is further than the allowed age from its clock, or whose nonce
+//! it has seen before, so a captured request can't be sent again. Endpoints verifying a
+//! request signature should sign both headers.
+//!
+//! Seen nonces are kept in memory by default. Servers running several instances share them
+//! through a [`NonceStore`] of their own, e.g. on Redis:
+//!
+//! ```ignore
+//! use yew_extra::nonce::{set_nonce_store, InsertFuture, NonceStore};
+//!
+//! struct RedisNonces(redis::Client);
+//!
+//! impl NonceStore for RedisNonces {
+//!     fn insert(&self, key: String, ttl: Duration) -> InsertFuture {
+//!         let client = self.0.clone();
+//!         Box::pin(async move {
+//!             // SET key 1 NX PX ttl, `true` if the key was set
+//!             set_if_absent(&client, &key, ttl).await
+//!         })
+//!     }
+//! }
+//!
+//! set_nonce_store(RedisNonces(client));
+//! ```
+
+use std::time::Duration;
+
+/// Header carrying the nonce of a request
+pub const NONCE_HEADER: &str = "x-yew-nonce";
+
+/// Header carrying the time a request was made, in seconds since the Unix epoch
+pub const TIMESTAMP_HEADER: &str = "x-yew-timestamp";
+
+/// How far the timestamp of a request may be from the server's clock, unless the endpoint
+/// sets its own with `nonce = "..."`
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::{
+    check_nonce, clear_nonce_store, set_nonce_store, InsertFuture, MemoryNonceStore, NonceStore,
+};
+
+#[cfg(feature = "client")]
+pub use client::{new_nonce, timestamp};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod server {
+    use super::{NONCE_HEADER, TIMESTAMP_HEADER};
+    use axum::body::Body;
+    use axum::http::{Request, Response, StatusCode};
+    use once_cell::sync::Lazy;
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashSet};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex, RwLock};
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+    /// The answer of a [`NonceStore`]
+    pub type InsertFuture = Pin<Box<dyn Future<Output = bool> + Send>>;
+
+    /// Remembers the nonces that were used.
+    ///
+    /// Closures taking the key and how long to keep it are stores too.
+    pub trait NonceStore: Send + Sync {
+        /// Keeps `key` for `ttl`, `false` if it's already kept
+        fn insert(&self, key: String, ttl: Duration) -> InsertFuture;
+    }
+
+    impl<F> NonceStore for F
+    where
+        F: Fn(String, Duration) -> InsertFuture + Send + Sync,
+    {
+        fn insert(&self, key: String, ttl: Duration) -> InsertFuture {
+            self(key, ttl)
+        }
+    }
+
+    /// Keeps the nonces in the memory of this server
+    #[derive(Default)]
+    pub struct MemoryNonceStore {
+        nonces: Mutex<Nonces>,
+    }
+
+    /// The kept nonces, and their expiries in the order they're due, so an insert only
+    /// looks at the nonces that expired instead of all of them
+    #[derive(Default)]
+    struct Nonces {
+        keys: HashSet<String>,
+        expiries: BinaryHeap<Reverse<(Instant, String)>>,
+    }
+
+    impl NonceStore for MemoryNonceStore {
+        fn insert(&self, key: String, ttl: Duration) -> InsertFuture {
+            let now = Instant::now();
+            let mut nonces = self.nonces.lock().unwrap_or_else(|e| e.into_inner());
+            let Nonces { keys, expiries } = &mut *nonces;
+            while expiries
+                .peek()
+                .is_some_and(|Reverse((expiry, _))| *expiry <= now)
+            {
+                if let Some(Reverse((_, expired))) = expiries.pop() {
+                    keys.remove(&expired);
+                }
+            }
+            let inserted = keys.insert(key.clone());
+            if inserted {
+                expiries.push(Reverse((now + ttl, key)));
+            }
+            Box::pin(async move { inserted })
+        }
+    }
+
+    static MEMORY_STORE: Lazy<Arc<MemoryNonceStore>> = Lazy::new(Default::default);
+    static STORE: RwLock<Option<Arc<dyn NonceStore>>> = RwLock::new(None);
+
+    /// Keeps the nonces in `store` from now on
+    pub fn set_nonce_store(store: impl NonceStore + 'static) {
+        *STORE.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(store));
+    }
+
+    /// Goes back to the [`MemoryNonceStore`]
+    pub fn clear_nonce_store() {
+        STORE.write().unwrap_or_else(|e| e.into_inner()).take();
+    }
+
+    fn store() -> Arc<dyn NonceStore> {
+        STORE
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .unwrap_or_else(|| MEMORY_STORE.clone())
+    }
+
+    fn rejected(status: StatusCode, message: &'static str) -> Response<Body> {
+        let mut response = Response::new(Body::from(message));
+        *response.status_mut() = status;
+        response
+    }
+
+    /// Checks the nonce and timestamp of `req`, answering `400 Bad Request` if they're
+    /// missing or the timestamp is further than `max_age` from now, and `409 Conflict` if
+    /// the nonce was used before. Nonces are scoped to the method and path.
+    ///
+    /// The headers are read right away, the returned future doesn't borrow `req`.
+    pub fn check_nonce<B>(
+        req: &Request<B>,
+        max_age: Duration,
+    ) -> impl Future<Output = Result<(), Response<Body>>> + Send + 'static {
+        let key = nonce_key(req, max_age);
+        async move {
+            let key = key.map_err(|message| rejected(StatusCode::BAD_REQUEST, message))?;
+            // A nonce only has to be kept while its timestamp is accepted, in either direction
+            if !store().insert(key, max_age * 2).await {
+                return Err(rejected(
+                    StatusCode::CONFLICT,
+                    "The request was already received",
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    /// The key the nonce of `req` is kept under, or why it has no fresh one
+    fn nonce_key<B>(req: &Request<B>, max_age: Duration) -> Result<String, &'static str> {
+        let header = |name: &str| {
+            req.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .filter(|value| !value.is_empty())
+        };
+        let (Some(nonce), Some(timestamp)) = (header(NONCE_HEADER), header(TIMESTAMP_HEADER))
+        else {
+            return Err("The request has no nonce or timestamp");
+        };
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let fresh = timestamp
+            .parse::<u64>()
+            .is_ok_and(|timestamp| now.abs_diff(timestamp) <= max_age.as_secs());
+        if !fresh {
+            return Err("The request has expired, check the clock of the device");
+        }
+        Ok(format!("{} {} {}", req.method(), req.uri().path(), nonce))
+    }
+}
+
+#[cfg(feature = "client")]
+mod client {
+    /// Generates a new nonce for a request
+    pub fn new_nonce() -> String {
+        crate::background_sync::new_idempotency_key()
+    }
+
+    /// The current time in seconds since the Unix epoch, for [`super::TIMESTAMP_HEADER`]
+    pub fn timestamp() -> String {
+        ((js_sys::Date::now() / 1000.0) as u64).to_string()
+    }
+}
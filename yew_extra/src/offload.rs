@@ -0,0 +1,337 @@
+//! Heavy post-processing of responses in a dedicated worker, off the main thread.
+//!
+//! The worker is a second wasm build of the app's crate (e.g. a trunk
+//! `<link data-trunk rel="rust" data-type="worker">` binary), which registers its tasks
+//! and then serves them:
+//!
+//! ```ignore
+//! // Worker binary
+//! fn main() {
+//!     yew_extra::offload::register("summarize", |orders: Vec<Order>| summarize(&orders));
+//!     yew_extra::offload::serve();
+//! }
+//!
+//! // App
+//! let offloader = Offloader::spawn("/worker.js")?;
+//! let orders = list_orders(params).await?;
+//! let summary: Summary = offloader.run("summarize", &orders).await?;
+//! ```
+//!
+//! Inputs and outputs cross to the worker as JSON. The generated client functions run in
+//! a worker as well, given an absolute API base with
+//! [`set_api_base`](crate::base_path::set_api_base), so a task can fetch its own data.
+//!
+//! Endpoints returning multi-megabyte payloads can be fetched by the worker as a whole,
+//! with [`Offloader::fetch`]. The worker downloads and parses the response, and posts the
+//! parsed value back, so the main thread never parses the JSON text:
+//!
+//! ```ignore
+//! let orders: Vec<Order> = offloader
+//!     .fetch("/api/orders?year=2024", &RequestOptions::new())
+//!     .await?;
+//! ```
+
+use crate::RequestOptions;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// A task run by the worker, as JSON in and out
+type Task = Rc<dyn Fn(&str) -> Result<String, String>>;
+
+thread_local! {
+    static TASKS: RefCell<HashMap<String, Task>> = RefCell::new(HashMap::new());
+}
+
+/// A task posted to the worker
+#[derive(Serialize, Deserialize)]
+struct TaskRequest {
+    id: u32,
+    task: String,
+    input: String,
+}
+
+/// A request the worker fetches, posted by [`Offloader::fetch`]
+#[derive(Serialize, Deserialize)]
+struct FetchRequest {
+    id: u32,
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+/// The worker's answer to a [`TaskRequest`]
+#[derive(Serialize, Deserialize)]
+struct TaskAnswer {
+    id: u32,
+    output: Option<String>,
+    error: Option<String>,
+}
+
+/// Registers `task` under `name`, in the worker
+pub fn register<I, O>(name: &str, task: impl Fn(I) -> O + 'static)
+where
+    I: DeserializeOwned,
+    O: Serialize,
+{
+    let task: Task = Rc::new(move |input| {
+        let input = serde_json::from_str(input).map_err(|e| format!("Invalid input: {}", e))?;
+        serde_json::to_string(&task(input)).map_err(|e| format!("Invalid output: {}", e))
+    });
+    TASKS.with(|tasks| tasks.borrow_mut().insert(name.to_string(), task));
+}
+
+/// Answers the tasks sent by an [`Offloader`] with those of [`register`], in the worker.
+///
+/// Does nothing outside a dedicated worker.
+pub fn serve() {
+    let Ok(scope) = js_sys::global().dyn_into::<web_sys::DedicatedWorkerGlobalScope>() else {
+        return;
+    };
+    let reply_scope = scope.clone();
+    let on_message =
+        Closure::<dyn FnMut(web_sys::MessageEvent)>::new(move |event: web_sys::MessageEvent| {
+            if let Ok(request) = serde_wasm_bindgen::from_value::<FetchRequest>(event.data()) {
+                let scope = reply_scope.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let answer = fetch_answer(&scope, &request).await;
+                    let _ = scope.post_message(&answer);
+                });
+                return;
+            }
+            let Ok(request) = serde_wasm_bindgen::from_value::<TaskRequest>(event.data()) else {
+                return;
+            };
+            let task = TASKS.with(|tasks| tasks.borrow().get(&request.task).cloned());
+            let result = match task {
+                Some(task) => task(&request.input),
+                None => Err(format!("No task named {}", request.task)),
+            };
+            let (output, error) = match result {
+                Ok(output) => (Some(output), None),
+                Err(error) => (None, Some(error)),
+            };
+            let answer = TaskAnswer {
+                id: request.id,
+                output,
+                error,
+            };
+            if let Ok(answer) = serde_wasm_bindgen::to_value(&answer) {
+                let _ = reply_scope.post_message(&answer);
+            }
+        });
+    scope.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    // Serves for the lifetime of the worker
+    on_message.forget();
+}
+
+/// Fetches `request` in the worker and answers with `{ id, value }` or `{ id, error }`.
+///
+/// The parsed value is posted as is, the browser copies it to the main thread without
+/// going through JSON text.
+async fn fetch_answer(
+    scope: &web_sys::DedicatedWorkerGlobalScope,
+    request: &FetchRequest,
+) -> JsValue {
+    let answer = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&answer, &"id".into(), &request.id.into());
+    match fetch_json(scope, request).await {
+        Ok(value) => {
+            let _ = js_sys::Reflect::set(&answer, &"value".into(), &value);
+        }
+        Err(error) => {
+            let _ = js_sys::Reflect::set(&answer, &"error".into(), &error.into());
+        }
+    }
+    answer.into()
+}
+
+async fn fetch_json(
+    scope: &web_sys::DedicatedWorkerGlobalScope,
+    request: &FetchRequest,
+) -> Result<JsValue, String> {
+    let headers =
+        web_sys::Headers::new().map_err(|e| format!("Failed to build the request: {:?}", e))?;
+    for (name, value) in &request.headers {
+        headers
+            .append(name, value)
+            .map_err(|e| format!("Invalid header {}: {:?}", name, e))?;
+    }
+    let init = web_sys::RequestInit::new();
+    init.set_headers(&headers);
+
+    let response = JsFuture::from(scope.fetch_with_str_and_init(&request.url, &init))
+        .await
+        .map_err(|e| format!("Request failed: {:?}", e))?;
+    let response: web_sys::Response = response.unchecked_into();
+    let body = response
+        .text()
+        .map_err(|e| format!("Failed to read response: {:?}", e))?;
+    let text = JsFuture::from(body)
+        .await
+        .ok()
+        .and_then(|text| text.as_string())
+        .unwrap_or_default();
+
+    if !response.ok() {
+        // The server's message, like the generated clients report it
+        let message = serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .and_then(|json| {
+                json.get("error")
+                    .or_else(|| json.get("message"))
+                    .and_then(|msg| msg.as_str())
+                    .map(str::to_string)
+            });
+        return Err(match message {
+            Some(message) => message,
+            None if text.is_empty() => {
+                format!("Request failed with status {}", response.status())
+            }
+            None => text,
+        });
+    }
+    js_sys::JSON::parse(&text).map_err(|e| format!("Failed to parse response: {:?}", e))
+}
+
+/// Resolvers of the tasks waiting for the worker, by id
+type Pending = Rc<RefCell<HashMap<u32, js_sys::Function>>>;
+
+/// A dedicated worker running the tasks of [`register`], terminated when dropped
+pub struct Offloader {
+    worker: web_sys::Worker,
+    pending: Pending,
+    next_id: Cell<u32>,
+    _on_message: Closure<dyn FnMut(web_sys::MessageEvent)>,
+}
+
+impl Offloader {
+    /// Starts the worker script at `script_url`
+    pub fn spawn(script_url: &str) -> Result<Self, String> {
+        let worker = web_sys::Worker::new(script_url)
+            .map_err(|e| format!("Failed to start the worker: {:?}", e))?;
+        let pending = Pending::default();
+        let on_message = Closure::<dyn FnMut(web_sys::MessageEvent)>::new({
+            let pending = pending.clone();
+            move |event: web_sys::MessageEvent| {
+                let data = event.data();
+                let id = js_sys::Reflect::get(&data, &"id".into())
+                    .ok()
+                    .and_then(|id| id.as_f64());
+                let resolve = id.and_then(|id| pending.borrow_mut().remove(&(id as u32)));
+                if let Some(resolve) = resolve {
+                    let _ = resolve.call1(&JsValue::NULL, &data);
+                }
+            }
+        });
+        worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        Ok(Self {
+            worker,
+            pending,
+            next_id: Cell::new(0),
+            _on_message: on_message,
+        })
+    }
+
+    fn next_id(&self) -> u32 {
+        let id = self.next_id.get();
+        self.next_id.set(id.wrapping_add(1));
+        id
+    }
+
+    /// Posts `message` to the worker and waits for the answer with the same `id`
+    async fn post(&self, id: u32, message: &JsValue) -> Result<JsValue, String> {
+        let answer = js_sys::Promise::new(&mut |resolve, _reject| {
+            self.pending.borrow_mut().insert(id, resolve);
+        });
+        if let Err(e) = self.worker.post_message(message) {
+            self.pending.borrow_mut().remove(&id);
+            return Err(format!("Failed to reach the worker: {:?}", e));
+        }
+        JsFuture::from(answer)
+            .await
+            .map_err(|e| format!("The task failed: {:?}", e))
+    }
+
+    /// Runs the task `name` on `input` in the worker and returns its output
+    pub async fn run<I, O>(&self, name: &str, input: &I) -> Result<O, String>
+    where
+        I: Serialize,
+        O: DeserializeOwned,
+    {
+        let id = self.next_id();
+        let request = TaskRequest {
+            id,
+            task: name.to_string(),
+            input: serde_json::to_string(input).map_err(|e| format!("Invalid input: {}", e))?,
+        };
+        let message = serde_wasm_bindgen::to_value(&request)
+            .map_err(|e| format!("Failed to encode the task: {}", e))?;
+
+        let answer = self.post(id, &message).await?;
+        let answer: TaskAnswer = serde_wasm_bindgen::from_value(answer)
+            .map_err(|e| format!("Invalid answer from the worker: {}", e))?;
+        match (answer.output, answer.error) {
+            (Some(output), _) => {
+                serde_json::from_str(&output).map_err(|e| format!("Invalid output: {}", e))
+            }
+            (None, error) => Err(error.unwrap_or_else(|| "The task failed".to_string())),
+        }
+    }
+
+    /// Fetches the JSON response of a GET request to `path` in the worker and returns it
+    /// decoded as `T`.
+    ///
+    /// `path` goes under the API base of the generated clients, or `options.base_url`, and
+    /// the headers and query parameters of `options` are added to the request.
+    pub async fn fetch<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        options: &RequestOptions,
+    ) -> Result<T, String> {
+        let id = self.next_id();
+        let request = FetchRequest {
+            id,
+            url: fetch_url(path, options)?,
+            headers: options.headers.clone(),
+        };
+        let message = serde_wasm_bindgen::to_value(&request)
+            .map_err(|e| format!("Failed to encode the request: {}", e))?;
+
+        let answer = self.post(id, &message).await?;
+        let error = js_sys::Reflect::get(&answer, &"error".into()).unwrap_or(JsValue::UNDEFINED);
+        if let Some(error) = error.as_string() {
+            return Err(error);
+        }
+        let value = js_sys::Reflect::get(&answer, &"value".into()).unwrap_or(JsValue::UNDEFINED);
+        serde_wasm_bindgen::from_value(value)
+            .map_err(|e| format!("Failed to parse response: {}", e))
+    }
+}
+
+/// The absolute URL of `path`, since the worker resolves relative ones against its script
+fn fetch_url(path: &str, options: &RequestOptions) -> Result<String, String> {
+    let base = match &options.base_url {
+        Some(base_url) => base_url.trim_end_matches('/').to_string(),
+        None => crate::base_path::api_base(None),
+    };
+    let page = web_sys::window()
+        .and_then(|window| window.location().href().ok())
+        .unwrap_or_default();
+    let url = web_sys::Url::new_with_base(&format!("{}{}", base, path), &page)
+        .map_err(|e| format!("Invalid URL {}: {:?}", path, e))?;
+    for (name, value) in &options.query {
+        url.search_params().append(name, value);
+    }
+    Ok(url.href())
+}
+
+impl Drop for Offloader {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
@@ -0,0 +1,241 @@
+//! Parameters carried in the path of an endpoint, e.g. the `{id}` of `/api/users/{id}`.
+//!
+//! Arguments named like a segment of the endpoint's path are sent in the URL instead of
+//! the query string or the JSON body:
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/users/{id}", method = "GET")]
+//! pub async fn get_user(id: u32) -> Result<User, AppError> { ... }
+//!
+//! // GET /api/users/42
+//! let user = get_user(42).await?;
+//! ```
+//!
+//! The generated clients fill the path with [`fill_path`] and the server reads it back
+//! with [`extract_path`]. Segments are written `{name}` or `{*name}` (Axum 0.8) or
+//! `:name` and `*name` (Axum 0.7), a wildcard takes the rest of the path. Either syntax
+//! works with either version, the router gets the paths through [`router_path`].
+
+use serde::Serialize;
+
+/// The name of the parameter a path segment holds, and whether it's a wildcard
+fn parameter(segment: &str) -> Option<(&str, bool)> {
+    if let Some(name) = segment
+        .strip_prefix("{*")
+        .and_then(|name| name.strip_suffix('}'))
+    {
+        Some((name, true))
+    } else if let Some(name) = segment
+        .strip_prefix('{')
+        .and_then(|name| name.strip_suffix('}'))
+    {
+        Some((name, false))
+    } else if let Some(name) = segment.strip_prefix('*') {
+        Some((name, true))
+    } else {
+        segment.strip_prefix(':').map(|name| (name, false))
+    }
+}
+
+/// The names of the parameters in a path, in order
+pub fn parameter_names(template: &str) -> Vec<&str> {
+    template
+        .split('/')
+        .filter_map(parameter)
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// A path parameter as written in the URL: strings as they are, other values as JSON, so
+/// numbers and booleans read back
+pub fn segment<T: Serialize + ?Sized>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(value)) => value,
+        Ok(value) => value.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+/// Replaces the parameters of `template` with `values`, percent-encoded. Wildcards keep
+/// the slashes of their value.
+///
+/// ```
+/// use yew_extra::path_params::fill_path;
+///
+/// let path = fill_path("/api/files/{owner}/{*path}", &[
+///     ("owner", "Ada Lovelace".to_string()),
+///     ("path", "notes/2024.md".to_string()),
+/// ]);
+/// assert_eq!(path, "/api/files/Ada%20Lovelace/notes/2024.md");
+/// ```
+pub fn fill_path(template: &str, values: &[(&str, String)]) -> String {
+    template
+        .split('/')
+        .map(|segment| {
+            let Some((name, wildcard)) = parameter(segment) else {
+                return segment.to_string();
+            };
+            let value = values
+                .iter()
+                .find(|(value_name, _)| *value_name == name)
+                .map(|(_, value)| value.as_str())
+                .unwrap_or_default();
+            if wildcard {
+                value.split('/').map(encode).collect::<Vec<_>>().join("/")
+            } else {
+                encode(value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Percent-encodes everything but the unreserved characters of RFC 3986
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Decodes the percent-encoded bytes of a path segment
+fn decode(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = segment.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// The parameters of `template` in `path`, decoded, or `None` if it doesn't match.
+///
+/// `path` may have a prefix `template` doesn't, e.g. the one a router was nested under.
+pub fn match_path(template: &str, path: &str) -> Option<Vec<(String, String)>> {
+    let template: Vec<&str> = template.trim_start_matches('/').split('/').collect();
+    let path: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    (0..path.len()).find_map(|start| match_segments(&template, &path[start..]))
+}
+
+fn match_segments(template: &[&str], path: &[&str]) -> Option<Vec<(String, String)>> {
+    let mut values = Vec::new();
+    for (i, segment) in template.iter().enumerate() {
+        match parameter(segment) {
+            Some((name, true)) => {
+                let rest = path.get(i..).filter(|rest| !rest.is_empty())?;
+                let rest = rest
+                    .iter()
+                    .map(|part| decode(part))
+                    .collect::<Option<Vec<_>>>()?;
+                values.push((name.to_string(), rest.join("/")));
+                return Some(values);
+            }
+            Some((name, false)) => {
+                let part = path.get(i).filter(|part| !part.is_empty())?;
+                values.push((name.to_string(), decode(part)?));
+            }
+            None => {
+                if path.get(i) != Some(segment) {
+                    return None;
+                }
+            }
+        }
+    }
+    (path.len() == template.len()).then_some(values)
+}
+
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+pub use server::{extract_path, router_path};
+
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+mod server {
+    use super::{match_path, parameter};
+    use axum::body::Body;
+    use axum::extract::rejection::PathRejection;
+    use axum::extract::{FromRequestParts, Path};
+    use axum::http::request::Parts;
+    use axum::http::{Response, StatusCode};
+    use axum::response::IntoResponse;
+    use serde::de::DeserializeOwned;
+
+    /// Reads the parameters of `template` from the request, answering `400 Bad Request`
+    /// if they don't parse.
+    ///
+    /// Behind axum's router they come from [`Path`]. Requests handed to a wrapper
+    /// directly, e.g. by [`call_handler`](crate::test::call_handler) or the actix and
+    /// Lambda adapters, have their path matched against `template` instead.
+    pub async fn extract_path<T>(parts: &mut Parts, template: &str) -> Result<T, Response<Body>>
+    where
+        T: DeserializeOwned + Send,
+    {
+        match Path::<T>::from_request_parts(parts, &()).await {
+            Ok(Path(params)) => Ok(params),
+            Err(PathRejection::MissingPathParams(_)) => {
+                let params = match_path(template, parts.uri.path())
+                    .ok_or_else(|| bad_request(format!("The path doesn't match {}", template)))?;
+                let query =
+                    serde_html_form::to_string(params).map_err(|e| bad_request(e.to_string()))?;
+                serde_html_form::from_str(&query).map_err(|e| bad_request(e.to_string()))
+            }
+            Err(rejection) => Err(rejection.into_response()),
+        }
+    }
+
+    /// `template` in the syntax of the Axum the crate is built against, so paths written for
+    /// either version can be handed to the router:
+    ///
+    /// ```
+    /// use yew_extra::path_params::router_path;
+    ///
+    /// // With the default `axum-08` feature
+    /// assert_eq!(router_path("/api/:org/files/*path"), "/api/{org}/files/{*path}");
+    /// ```
+    pub fn router_path(template: &str) -> String {
+        template
+            .split('/')
+            .map(|segment| match parameter(segment) {
+                Some((name, wildcard)) => router_segment(name, wildcard),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    #[cfg(not(all(feature = "axum-07", not(feature = "axum-08"))))]
+    fn router_segment(name: &str, wildcard: bool) -> String {
+        if wildcard {
+            format!("{{*{}}}", name)
+        } else {
+            format!("{{{}}}", name)
+        }
+    }
+
+    #[cfg(all(feature = "axum-07", not(feature = "axum-08")))]
+    fn router_segment(name: &str, wildcard: bool) -> String {
+        if wildcard {
+            format!("*{}", name)
+        } else {
+            format!(":{}", name)
+        }
+    }
+
+    fn bad_request(message: String) -> Response<Body> {
+        Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(format!("Invalid path parameters: {}", message)))
+            .unwrap_or_else(|_| Response::new(Body::empty()))
+    }
+}
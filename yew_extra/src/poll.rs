@@ -0,0 +1,114 @@
+//! Refetching hooks on an interval, for data changing on the server like a job's status.
+//!
+//! The hooks of GET endpoints declared with `poll_interval` call [`use_poll`] with their
+//! refetch callback:
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/status", method = "GET", poll_interval = 5000)]
+//! pub async fn get_status() -> Result<Status, AppError> { ... }
+//! ```
+//!
+//! Polls are skipped while the page is hidden, and a missed one is made as soon as it's
+//! visible again. The interval is cleared when the component unmounts.
+
+use crate::hooks::use_isomorphic_effect;
+use yew::prelude::*;
+
+/// Emits `refetch` every `interval_ms` milliseconds while the page is visible, in the
+/// browser only (`client` feature)
+#[hook]
+pub fn use_poll(interval_ms: u32, refetch: Callback<()>) {
+    use_isomorphic_effect(interval_ms, move |interval_ms| {
+        #[cfg(feature = "client")]
+        let poller = client::Poller::start(*interval_ms, refetch).ok();
+        #[cfg(not(feature = "client"))]
+        let _ = (interval_ms, refetch);
+
+        move || {
+            #[cfg(feature = "client")]
+            drop(poller);
+        }
+    });
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use wasm_bindgen::prelude::*;
+    use yew::Callback;
+
+    type Listener = Closure<dyn FnMut()>;
+
+    /// Keeps the interval and the `visibilitychange` listener until it is dropped
+    pub struct Poller {
+        interval: i32,
+        /// Called by the interval, kept alive until it is cleared
+        _tick: Listener,
+        on_visibility_change: Listener,
+    }
+
+    impl Poller {
+        pub fn start(interval_ms: u32, refetch: Callback<()>) -> Result<Self, String> {
+            let window = web_sys::window().ok_or_else(|| "No window available".to_string())?;
+            let document = window
+                .document()
+                .ok_or_else(|| "No document available".to_string())?;
+            let missed = Rc::new(Cell::new(false));
+
+            let tick: Listener = Closure::new({
+                let document = document.clone();
+                let missed = missed.clone();
+                let refetch = refetch.clone();
+                move || {
+                    if document.hidden() {
+                        missed.set(true);
+                    } else {
+                        refetch.emit(());
+                    }
+                }
+            });
+            let on_visibility_change: Listener = Closure::new({
+                let document = document.clone();
+                move || {
+                    if !document.hidden() && missed.replace(false) {
+                        refetch.emit(());
+                    }
+                }
+            });
+
+            document
+                .add_event_listener_with_callback(
+                    "visibilitychange",
+                    on_visibility_change.as_ref().unchecked_ref(),
+                )
+                .map_err(|e| format!("Failed to listen to visibilitychange events: {:?}", e))?;
+            let interval = window
+                .set_interval_with_callback_and_timeout_and_arguments_0(
+                    tick.as_ref().unchecked_ref(),
+                    i32::try_from(interval_ms).unwrap_or(i32::MAX),
+                )
+                .map_err(|e| format!("Failed to start polling: {:?}", e))?;
+
+            Ok(Self {
+                interval,
+                _tick: tick,
+                on_visibility_change,
+            })
+        }
+    }
+
+    impl Drop for Poller {
+        fn drop(&mut self) {
+            if let Some(window) = web_sys::window() {
+                window.clear_interval_with_handle(self.interval);
+                if let Some(document) = window.document() {
+                    let _ = document.remove_event_listener_with_callback(
+                        "visibilitychange",
+                        self.on_visibility_change.as_ref().unchecked_ref(),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Query strings with repeated keys.
+//!
+//! `serde_urlencoded` has no way to write or read `?tag=a&tag=b`, so `Vec` parameters of GET
+//! endpoints couldn't be sent at all. The generated client and server use these functions
+//! instead: a sequence is written as one pair per item, in order, and a key found several
+//! times is read back into a sequence in the same order. A key found once fills a `Vec`
+//! too, so `?tag=a` is `vec!["a"]`:
+//!
+//! ```
+//! #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+//! struct Search {
+//!     q: String,
+//!     tag: Vec<String>,
+//! }
+//!
+//! let search = Search { q: "boots".into(), tag: vec!["red".into(), "sale".into()] };
+//! let query = yew_extra::query::to_query_string(&search).unwrap();
+//! assert_eq!(query, "q=boots&tag=red&tag=sale");
+//! assert_eq!(yew_extra::query::from_query_str::<Search>(&query).unwrap(), search);
+//! ```
+//!
+//! Other values are written like `serde_urlencoded` does, except that an empty value reads
+//! as `None` for an `Option`.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub use serde_html_form::de::Error as DecodeError;
+pub use serde_html_form::ser::Error as EncodeError;
+
+/// Writes `params` as a query string, without the leading `?`
+pub fn to_query_string<T: Serialize + ?Sized>(params: &T) -> Result<String, EncodeError> {
+    serde_html_form::to_string(params)
+}
+
+/// Reads `query`, without the leading `?`, into `T`
+pub fn from_query_str<T: DeserializeOwned>(query: &str) -> Result<T, DecodeError> {
+    serde_html_form::from_str(query)
+}
@@ -0,0 +1,355 @@
+//! Record and replay HTTP interactions for deterministic UI tests.
+//!
+//! On the server, the [`record`] middleware captures every request/response pair passing
+//! through it into a [`Recorder`], which can be saved as a JSON [`Cassette`]. In wasm
+//! tests, `replay()` (with the `client` feature) replaces `window.fetch` with a stub
+//! answering from the cassette, so the generated hooks and client functions run
+//! unchanged without a live server.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::{record, Recorder};
+
+#[cfg(feature = "client")]
+pub use client::{replay, Replay};
+
+/// A request as it was received by the server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path and query string, e.g. `/api/users?role=admin`
+    pub uri: String,
+    /// Request body, omitted when empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// The response the server sent back
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+/// A recorded request/response pair
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// A list of recorded interactions, stored as JSON
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Parses a cassette, typically included with `include_str!`
+    #[cfg(any(not(target_arch = "wasm32"), feature = "client"))]
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid cassette: {}", e))
+    }
+
+    /// Serializes the cassette as pretty-printed JSON
+    #[cfg(any(not(target_arch = "wasm32"), feature = "client"))]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Finds the recorded response for a request.
+    ///
+    /// Bodies are compared as JSON when both sides are valid JSON, so key order and
+    /// whitespace don't matter.
+    pub fn find(&self, method: &str, uri: &str, body: Option<&str>) -> Option<&Interaction> {
+        self.interactions
+            .iter()
+            .find(|interaction| interaction.matches(method, uri, body))
+    }
+}
+
+impl Interaction {
+    fn matches(&self, method: &str, uri: &str, body: Option<&str>) -> bool {
+        let request = &self.request;
+        if !request.method.eq_ignore_ascii_case(method) || request.uri != uri {
+            return false;
+        }
+
+        let recorded = request.body.as_deref().unwrap_or_default();
+        let body = body.unwrap_or_default();
+        recorded == body || same_json(recorded, body)
+    }
+}
+
+#[cfg(any(not(target_arch = "wasm32"), feature = "client"))]
+fn same_json(a: &str, b: &str) -> bool {
+    match (
+        serde_json::from_str::<serde_json::Value>(a),
+        serde_json::from_str::<serde_json::Value>(b),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(not(any(not(target_arch = "wasm32"), feature = "client")))]
+fn same_json(_a: &str, _b: &str) -> bool {
+    false
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod server {
+    use super::{Cassette, Interaction, RecordedRequest, RecordedResponse};
+    use axum::body::{to_bytes, Body};
+    use axum::extract::State;
+    use axum::http::{Request, Response};
+    use axum::middleware::Next;
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+
+    /// Collects the interactions captured by the [`record`] middleware
+    #[derive(Debug, Clone, Default)]
+    pub struct Recorder {
+        interactions: Arc<Mutex<Vec<Interaction>>>,
+    }
+
+    impl Recorder {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Returns everything recorded so far
+        pub fn cassette(&self) -> Cassette {
+            Cassette {
+                interactions: self
+                    .interactions
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            }
+        }
+
+        /// Writes everything recorded so far to a JSON file
+        pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+            std::fs::write(path, self.cassette().to_json())
+        }
+
+        fn push(&self, interaction: Interaction) {
+            self.interactions
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(interaction);
+        }
+    }
+
+    /// Middleware recording every request/response pair into a [`Recorder`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let recorder = Recorder::new();
+    /// let app = Router::new()
+    ///     .merge(your_generated_routes())
+    ///     .layer(axum::middleware::from_fn_with_state(recorder.clone(), yew_extra::recording::record));
+    ///
+    /// // ... exercise the app, then
+    /// recorder.save("tests/fixtures/users.json")?;
+    /// ```
+    pub async fn record(
+        State(recorder): State<Recorder>,
+        req: Request<Body>,
+        next: Next,
+    ) -> Response<Body> {
+        let (parts, body) = req.into_parts();
+        let body = match to_bytes(body, usize::MAX).await {
+            Ok(body) => body,
+            Err(e) => {
+                return Response::builder()
+                    .status(axum::http::StatusCode::BAD_REQUEST)
+                    .body(Body::from(format!("Failed to read request body: {}", e)))
+                    .unwrap();
+            }
+        };
+
+        let request = RecordedRequest {
+            method: parts.method.to_string(),
+            uri: parts
+                .uri
+                .path_and_query()
+                .map(|path_and_query| path_and_query.to_string())
+                .unwrap_or_else(|| parts.uri.path().to_string()),
+            body: (!body.is_empty()).then(|| String::from_utf8_lossy(&body).into_owned()),
+        };
+
+        let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+        let (parts, body) = response.into_parts();
+        let body = match to_bytes(body, usize::MAX).await {
+            Ok(body) => body,
+            Err(e) => {
+                return Response::builder()
+                    .status(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from(format!("Failed to read response body: {}", e)))
+                    .unwrap();
+            }
+        };
+
+        recorder.push(Interaction {
+            request,
+            response: RecordedResponse {
+                status: parts.status.as_u16(),
+                headers: parts
+                    .headers
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_string()))
+                    })
+                    .collect(),
+                body: String::from_utf8_lossy(&body).into_owned(),
+            },
+        });
+
+        Response::from_parts(parts, Body::from(body))
+    }
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use super::{Cassette, Interaction};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+    type FetchStub = Closure<dyn FnMut(JsValue, JsValue) -> js_sys::Promise>;
+
+    /// Keeps `window.fetch` replaced by the cassette stub until it is dropped
+    pub struct Replay {
+        original_fetch: JsValue,
+        _stub: FetchStub,
+    }
+
+    impl Drop for Replay {
+        fn drop(&mut self) {
+            if let Some(window) = web_sys::window() {
+                let _ = js_sys::Reflect::set(&window, &"fetch".into(), &self.original_fetch);
+            }
+        }
+    }
+
+    /// Answers every `fetch` from `cassette` until the returned guard is dropped.
+    ///
+    /// Identical requests get the matching interactions in recorded order, the last one
+    /// being repeated once they are used up. Requests without a recording fail with an
+    /// error naming the request.
+    pub fn replay(cassette: Cassette) -> Result<Replay, String> {
+        let window = web_sys::window().ok_or_else(|| "No window available".to_string())?;
+        let original_fetch = js_sys::Reflect::get(&window, &"fetch".into())
+            .map_err(|e| format!("Failed to read window.fetch: {:?}", e))?;
+
+        // Each interaction is marked once it has been replayed
+        let interactions: Rc<RefCell<Vec<(Interaction, bool)>>> = Rc::new(RefCell::new(
+            cassette
+                .interactions
+                .into_iter()
+                .map(|interaction| (interaction, false))
+                .collect(),
+        ));
+
+        let stub: FetchStub = Closure::new(move |input: JsValue, init: JsValue| {
+            let interactions = interactions.clone();
+            future_to_promise(async move {
+                let request = to_request(input, init)?;
+                let (method, uri, body) = describe(&request).await?;
+                let response =
+                    take(&interactions, &method, &uri, body.as_deref()).ok_or_else(|| {
+                        JsValue::from(js_sys::Error::new(&format!(
+                            "No recorded response for {} {}",
+                            method, uri
+                        )))
+                    })?;
+                to_response(&response).map(JsValue::from)
+            })
+        });
+
+        js_sys::Reflect::set(&window, &"fetch".into(), stub.as_ref())
+            .map_err(|e| format!("Failed to replace window.fetch: {:?}", e))?;
+
+        Ok(Replay {
+            original_fetch,
+            _stub: stub,
+        })
+    }
+
+    fn to_request(input: JsValue, init: JsValue) -> Result<web_sys::Request, JsValue> {
+        if let Ok(request) = input.clone().dyn_into::<web_sys::Request>() {
+            return Ok(request);
+        }
+        let url = input
+            .as_string()
+            .ok_or_else(|| JsValue::from_str("Unsupported fetch input"))?;
+        if init.is_undefined() {
+            web_sys::Request::new_with_str(&url)
+        } else {
+            web_sys::Request::new_with_str_and_init(&url, init.unchecked_ref())
+        }
+    }
+
+    /// Returns the method, path with query and body of a request
+    async fn describe(
+        request: &web_sys::Request,
+    ) -> Result<(String, String, Option<String>), JsValue> {
+        let url = web_sys::Url::new(&request.url())?;
+        let uri = format!("{}{}", url.pathname(), url.search());
+        let body = JsFuture::from(request.text()?)
+            .await?
+            .as_string()
+            .filter(|body| !body.is_empty());
+        Ok((request.method(), uri, body))
+    }
+
+    fn take(
+        interactions: &RefCell<Vec<(Interaction, bool)>>,
+        method: &str,
+        uri: &str,
+        body: Option<&str>,
+    ) -> Option<super::RecordedResponse> {
+        let mut interactions = interactions.borrow_mut();
+        let matching: Vec<usize> = interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, (interaction, _))| interaction.matches(method, uri, body))
+            .map(|(index, _)| index)
+            .collect();
+
+        let index = matching
+            .iter()
+            .copied()
+            .find(|&index| !interactions[index].1)
+            .or_else(|| matching.last().copied())?;
+        interactions[index].1 = true;
+        Some(interactions[index].0.response.clone())
+    }
+
+    fn to_response(recorded: &super::RecordedResponse) -> Result<web_sys::Response, JsValue> {
+        let headers = web_sys::Headers::new()?;
+        for (name, value) in &recorded.headers {
+            headers.set(name, value)?;
+        }
+
+        let init = web_sys::ResponseInit::new();
+        init.set_status(recorded.status);
+        init.set_headers(&headers);
+
+        // Responses with these statuses can't have a body
+        let body = match recorded.status {
+            101 | 204 | 205 | 304 => None,
+            _ => Some(recorded.body.as_str()),
+        };
+        web_sys::Response::new_with_opt_str_and_init(body, &init)
+    }
+}
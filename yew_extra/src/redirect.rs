@@ -0,0 +1,118 @@
+//! Redirects from server functions, e.g. to the login page after signing out.
+//!
+//! [`redirect()`] sends the caller of the server function elsewhere once it has answered:
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/logout", method = "POST")]
+//! pub async fn logout() -> Result<(), AppError> {
+//!     end_session().await?;
+//!     yew_extra::redirect("/login").await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Browsers navigating to the endpoint, e.g. a form posted without JavaScript, get a
+//! `302 Found` (`303 See Other` for other methods than GET) to follow. The generated hooks
+//! and client functions get their response as usual with the location in the
+//! [`REDIRECT_HEADER`], since fetch would follow a real redirect out of sight, and pass it
+//! to [`follow()`] through [`follow_header()`]. That loads the page, or calls the handler registered with
+//! [`set_redirect_handler()`] to navigate within the app.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Header carrying the location the generated clients are redirected to
+pub const REDIRECT_HEADER: &str = "x-yew-redirect";
+
+type Handler = Rc<dyn Fn(&str)>;
+
+thread_local! {
+    static HANDLER: RefCell<Option<Handler>> = const { RefCell::new(None) };
+}
+
+/// Follows redirects with `handler` instead of loading the page, replacing the previous
+/// handler, e.g. to push the location to `yew_router`'s navigator
+pub fn set_redirect_handler(handler: impl Fn(&str) + 'static) {
+    HANDLER.with(|current| *current.borrow_mut() = Some(Rc::new(handler)));
+}
+
+/// Removes the handler registered with [`set_redirect_handler()`]
+pub fn clear_redirect_handler() {
+    HANDLER.with(|current| current.borrow_mut().take());
+}
+
+/// Sends the user to `location`, the redirect a generated client received, through the
+/// registered handler or by loading the page (`client` feature)
+pub fn follow(location: &str) {
+    // Cloned out so the handler can replace itself
+    let handler = HANDLER.with(|current| current.borrow().clone());
+    match handler {
+        Some(handler) => handler(location),
+        None => navigate(location),
+    }
+}
+
+/// Follows the redirect of a response a generated client or hook received, if `header`
+/// reads a [`REDIRECT_HEADER`] from it. Returns whether there was one.
+pub fn follow_header(header: impl FnOnce(&str) -> Option<String>) -> bool {
+    match header(REDIRECT_HEADER) {
+        Some(location) => {
+            follow(&location);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(feature = "client")]
+fn navigate(location: &str) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.location().set_href(location);
+    }
+}
+
+#[cfg(not(feature = "client"))]
+fn navigate(_location: &str) {}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::redirect;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod server {
+    use super::REDIRECT_HEADER;
+    use crate::ExtractError;
+    use axum::http::header::{ACCEPT, LOCATION};
+    use axum::http::{HeaderName, HeaderValue, Method, StatusCode};
+
+    /// Redirects the caller of the server function being handled to `location`, a path or
+    /// URL, once the function has answered.
+    ///
+    /// Requests accepting HTML get a `302 Found`, or `303 See Other` for other methods than
+    /// GET, with a `Location` header. Other requests, like those of the generated clients,
+    /// keep the status of the function's result and get the [`REDIRECT_HEADER`] instead.
+    pub async fn redirect(location: &str) -> Result<(), ExtractError> {
+        let value = HeaderValue::from_str(location).map_err(|e| {
+            ExtractError::ExtractionFailed(format!("Invalid redirect location: {}", e))
+        })?;
+        let accepts_html = crate::headers()
+            .await?
+            .get(ACCEPT)
+            .and_then(|accept| accept.to_str().ok())
+            .is_some_and(|accept| accept.contains("text/html"));
+
+        let response = crate::response_options().await?;
+        if accepts_html {
+            let method = crate::method().await?;
+            let status = if method == Method::GET || method == Method::HEAD {
+                StatusCode::FOUND
+            } else {
+                StatusCode::SEE_OTHER
+            };
+            response.set_status(status);
+            response.insert_header(LOCATION, value);
+        } else {
+            response.insert_header(HeaderName::from_static(REDIRECT_HEADER), value);
+        }
+        Ok(())
+    }
+}
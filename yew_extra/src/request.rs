@@ -0,0 +1,171 @@
+//! Per-call request options and response metadata for the hooks and client functions
+//! generated by `yew_server_hook`.
+
+use std::fmt;
+use std::rc::Rc;
+
+/// One-off additions to a single request, passed to the `*_with_options` variants of the
+/// generated hooks and client functions.
+///
+/// # Example
+///
+/// ```
+/// use yew_extra::RequestOptions;
+///
+/// let options = RequestOptions::new()
+///     .header("If-Match", "\"v42\"")
+///     .query("trace", "1")
+///     .base_url("https://api.example.com");
+///
+/// assert_eq!(options.headers, vec![("If-Match".to_string(), "\"v42\"".to_string())]);
+/// assert_eq!(options.base_url.as_deref(), Some("https://api.example.com"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestOptions {
+    /// Extra headers, applied after the generated ones so they can replace them
+    pub headers: Vec<(String, String)>,
+    /// Extra query parameters appended to the URL
+    pub query: Vec<(String, String)>,
+    /// Origin (and optional prefix) used instead of the page's origin
+    pub base_url: Option<String>,
+    /// Callbacks the `*_with_options` hooks fire when their fetch settles
+    pub callbacks: SettleCallbacks,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a header to the request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Appends a query parameter to the URL
+    pub fn query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sends the request to `base_url` followed by the endpoint path
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Calls `f` when a fetch of the hook succeeds
+    pub fn on_success(mut self, f: impl Fn() + 'static) -> Self {
+        self.callbacks.on_success = Some(Rc::new(f));
+        self
+    }
+
+    /// Calls `f` with the error message when a fetch of the hook fails
+    pub fn on_error(mut self, f: impl Fn(&str) + 'static) -> Self {
+        self.callbacks.on_error = Some(Rc::new(f));
+        self
+    }
+}
+
+/// Side effects a hook runs when its fetch settles, set with
+/// [`RequestOptions::on_success`] and [`RequestOptions::on_error`].
+///
+/// Callbacks always compare equal, so passing new closures on every render doesn't make
+/// the hook refetch. The hook calls the ones passed with its latest render.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use yew_extra::RequestOptions;
+///
+/// let failures = Rc::new(Cell::new(0));
+/// let counter = failures.clone();
+/// let options = RequestOptions::new().on_error(move |_| counter.set(counter.get() + 1));
+///
+/// options.callbacks.error("Request failed with status 500");
+/// options.callbacks.success();
+/// assert_eq!(failures.get(), 1);
+/// assert_eq!(options, RequestOptions::new());
+/// ```
+#[derive(Clone, Default)]
+pub struct SettleCallbacks {
+    pub on_success: Option<Rc<dyn Fn()>>,
+    pub on_error: Option<ErrorCallback>,
+}
+
+/// Callback receiving the error message of a failed fetch
+pub type ErrorCallback = Rc<dyn Fn(&str)>;
+
+impl SettleCallbacks {
+    /// Runs the `on_success` callback, if any
+    pub fn success(&self) {
+        if let Some(on_success) = &self.on_success {
+            on_success();
+        }
+    }
+
+    /// Runs the `on_error` callback, if any
+    pub fn error(&self, error: &str) {
+        if let Some(on_error) = &self.on_error {
+            on_error(error);
+        }
+    }
+}
+
+impl PartialEq for SettleCallbacks {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for SettleCallbacks {}
+
+impl fmt::Debug for SettleCallbacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SettleCallbacks")
+            .field("on_success", &self.on_success.is_some())
+            .field("on_error", &self.on_error.is_some())
+            .finish()
+    }
+}
+
+/// Status and headers of a successful response, returned by the `*_with_response`
+/// variants of the generated client functions.
+///
+/// # Example
+///
+/// ```
+/// use yew_extra::ResponseMeta;
+///
+/// let meta = ResponseMeta::new(200, vec![("etag".to_string(), "\"v42\"".to_string())]);
+///
+/// assert_eq!(meta.header("ETag"), Some("\"v42\""));
+/// assert_eq!(meta.header("Link"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers as (name, value) pairs
+    pub headers: Vec<(String, String)>,
+}
+
+impl ResponseMeta {
+    pub fn new(status: u16, headers: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self {
+            status,
+            headers: headers.into_iter().collect(),
+        }
+    }
+
+    /// Returns the value of a header, matching its name case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
@@ -0,0 +1,121 @@
+//! Server data read and refetched outside components.
+//!
+//! A [`Resource`] is the cached response of a GET endpoint, under the same key its hook
+//! reads, for code that can't call hooks: agents, router guards, event listeners set up
+//! at startup. `yew_server_hook` generates a `resource_<function>()` for GET endpoints:
+//!
+//! ```ignore
+//! let user = resource_get_user(user_id);
+//!
+//! // A router guard
+//! let allowed = match user.load().await {
+//!     Ok(user) => user.is_admin,
+//!     Err(_) => false,
+//! };
+//!
+//! // Mounted hooks showing the user rerender with the new response
+//! user.refetch().await?;
+//!
+//! let _subscription = user.subscribe(|user| log::info!("user changed: {:?}", user));
+//! ```
+
+use crate::cache::{CacheScope, CacheSubscription, Cached};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+type ResourceFetch<T> = Rc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<T, String>>>>>;
+
+/// A cached server response, with the request fetching it
+pub struct Resource<T> {
+    key: String,
+    scope: CacheScope,
+    fetch: ResourceFetch<T>,
+}
+
+impl<T: Clone + 'static> Resource<T> {
+    /// A resource stored under `key` in the global cache, fetched by `fetch`
+    pub fn new<F, Fut>(key: impl Into<String>, fetch: F) -> Self
+    where
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = Result<T, String>> + 'static,
+    {
+        Self {
+            key: key.into(),
+            scope: CacheScope::global(),
+            fetch: Rc::new(move || Box::pin(fetch())),
+        }
+    }
+
+    /// The same resource in the cache of `scope` instead of the global one
+    pub fn in_scope(mut self, scope: CacheScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Key the response is stored under, see [`request_key`](crate::cache::request_key)
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The cached response, if any, without fetching it
+    pub fn get(&self) -> Option<T> {
+        self.cached().map(|cached| cached.value)
+    }
+
+    /// The cached response and whether it is stale
+    pub fn cached(&self) -> Option<Cached<T>> {
+        self.scope.get(&self.key)
+    }
+
+    /// The cached response if it is still fresh, otherwise a new one
+    pub async fn load(&self) -> Result<T, String> {
+        match self.cached() {
+            Some(cached) if !cached.is_stale => Ok(cached.value),
+            _ => self.refetch().await,
+        }
+    }
+
+    /// Fetches the response, stores it and notifies the subscribers, including the
+    /// mounted hooks showing it
+    pub async fn refetch(&self) -> Result<T, String> {
+        let value = (self.fetch)().await?;
+        self.scope.insert(self.key.clone(), value.clone());
+        self.scope.notify(&self.key);
+        Ok(value)
+    }
+
+    /// Calls `f` with the cached response whenever it changes, until the returned
+    /// subscription is dropped
+    pub fn subscribe(&self, f: impl Fn(Option<T>) + 'static) -> CacheSubscription {
+        let scope = self.scope.clone();
+        let key = self.key.clone();
+        self.scope.subscribe(self.key.clone(), move || {
+            f(scope.get::<T>(&key).map(|cached| cached.value))
+        })
+    }
+}
+
+impl<T> Clone for Resource<T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            scope: self.scope.clone(),
+            fetch: self.fetch.clone(),
+        }
+    }
+}
+
+/// Resources are equal when they read the same key of the same cache
+impl<T> PartialEq for Resource<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.scope == other.scope
+    }
+}
+
+impl<T> fmt::Debug for Resource<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resource").field("key", &self.key).finish()
+    }
+}
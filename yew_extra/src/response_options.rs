@@ -0,0 +1,103 @@
+//! Status, headers and cookies a server function sets on its response.
+//!
+//! [`response_options()`](crate::response_options) returns the options of the request
+//! being handled. The handlers generated by `yew_server_hook` apply them to the response of
+//! the server function, whatever it returned:
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/session", method = "POST")]
+//! pub async fn sign_in(credentials: Credentials) -> Result<User, AppError> {
+//!     let user = authenticate(&credentials).await?;
+//!     let response = yew_extra::response_options().await?;
+//!     response.set_status(StatusCode::CREATED);
+//!     response.insert_header(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+//!     response.add_cookie(format!("session={}; HttpOnly; Secure; Path=/", user.session))?;
+//!     Ok(user)
+//! }
+//! ```
+
+use axum::body::Body;
+use axum::http::header::{InvalidHeaderValue, SET_COOKIE};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Response, StatusCode};
+use std::fmt::Display;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// The status and headers set for the response of a request.
+///
+/// Clones share the same options, like `leptos_axum::ResponseOptions`.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseOptions(Arc<Mutex<ResponseParts>>);
+
+#[derive(Debug, Default)]
+struct ResponseParts {
+    status: Option<StatusCode>,
+    /// Headers replacing those of the response
+    inserted: HeaderMap,
+    /// Headers added to those of the response
+    appended: HeaderMap,
+}
+
+impl ResponseOptions {
+    fn lock(&self) -> MutexGuard<'_, ResponseParts> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Sets the status of the response, replacing the one of the function's result
+    pub fn set_status(&self, status: StatusCode) {
+        self.lock().status = Some(status);
+    }
+
+    /// The status set with [`set_status()`](Self::set_status)
+    pub fn status(&self) -> Option<StatusCode> {
+        self.lock().status
+    }
+
+    /// Sets a header of the response, replacing its other values
+    pub fn insert_header(&self, name: HeaderName, value: HeaderValue) {
+        let mut parts = self.lock();
+        parts.appended.remove(&name);
+        parts.inserted.insert(name, value);
+    }
+
+    /// Adds a value to a header of the response
+    pub fn append_header(&self, name: HeaderName, value: HeaderValue) {
+        self.lock().appended.append(name, value);
+    }
+
+    /// Adds a `Set-Cookie` header with `cookie` as its value, e.g. `id=7; HttpOnly` or a
+    /// `cookie::Cookie`
+    pub fn add_cookie(&self, cookie: impl Display) -> Result<(), InvalidHeaderValue> {
+        let value = HeaderValue::from_str(&cookie.to_string())?;
+        self.append_header(SET_COOKIE, value);
+        Ok(())
+    }
+
+    /// The headers set so far
+    pub fn headers(&self) -> HeaderMap {
+        let parts = self.lock();
+        let mut headers = parts.inserted.clone();
+        for (name, value) in &parts.appended {
+            headers.append(name.clone(), value.clone());
+        }
+        headers
+    }
+
+    /// Sets the status and headers on `response`, and clears them so they're only applied
+    /// once
+    pub fn apply(&self, mut response: Response<Body>) -> Response<Body> {
+        let parts = std::mem::take(&mut *self.lock());
+        if let Some(status) = parts.status {
+            *response.status_mut() = status;
+        }
+        let headers = response.headers_mut();
+        for (name, value) in parts.inserted {
+            if let Some(name) = name {
+                headers.insert(name, value);
+            }
+        }
+        for (name, value) in &parts.appended {
+            headers.append(name.clone(), value.clone());
+        }
+        response
+    }
+}
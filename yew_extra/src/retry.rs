@@ -0,0 +1,85 @@
+//! Automatic retries for the clients of endpoints declared with `retry = N`.
+//!
+//! Requests that fail to reach the server, or that are answered with a status that
+//! signals a temporary failure, are sent again after an exponential backoff. Retrying a
+//! mutation could apply it twice, so `yew_server_hook` only accepts `retry` on mutations
+//! that send an `Idempotency-Key`; the server then answers repeated attempts with the
+//! stored response instead of running the function again.
+
+/// Delay before the first retry, doubled after every failed attempt
+pub const RETRY_DELAY_MS: i32 = 250;
+
+/// Longest delay between two attempts
+pub const MAX_RETRY_DELAY_MS: i32 = 8_000;
+
+/// Whether a response with `status` is worth retrying.
+///
+/// `409 Conflict` is what an idempotent mutation gets while an earlier attempt with the
+/// same key is still running, so it is retried for those requests only.
+pub fn is_retryable_status(status: u16, idempotent: bool) -> bool {
+    matches!(status, 408 | 429 | 500 | 502 | 503 | 504) || (idempotent && status == 409)
+}
+
+/// Delay before retry number `attempt` (starting at 1)
+pub fn retry_delay_ms(attempt: u32) -> i32 {
+    RETRY_DELAY_MS
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(MAX_RETRY_DELAY_MS)
+}
+
+#[cfg(feature = "client")]
+pub use client::{send_with_retries, IntoRetryRequest};
+
+#[cfg(feature = "client")]
+mod client {
+    use super::{is_retryable_status, retry_delay_ms};
+    use crate::background_sync::IDEMPOTENCY_HEADER;
+    use gloo_net::http::{Request, RequestBuilder, Response};
+
+    /// Requests [`send_with_retries`] and [`crate::transport::send`] accept, finished or
+    /// still being built
+    pub trait IntoRetryRequest {
+        fn into_request(self) -> Result<Request, gloo_net::Error>;
+    }
+
+    impl IntoRetryRequest for Request {
+        fn into_request(self) -> Result<Request, gloo_net::Error> {
+            Ok(self)
+        }
+    }
+
+    impl IntoRetryRequest for RequestBuilder {
+        fn into_request(self) -> Result<Request, gloo_net::Error> {
+            self.build()
+        }
+    }
+
+    /// Sends `request`, retrying it up to `retries` times while it fails temporarily.
+    ///
+    /// Returns the last response or error once the retries are used up.
+    pub async fn send_with_retries(
+        request: impl IntoRetryRequest,
+        retries: u32,
+    ) -> Result<Response, gloo_net::Error> {
+        let request: web_sys::Request = request.into_request()?.into();
+        let idempotent = request.headers().has(IDEMPOTENCY_HEADER).unwrap_or(false);
+
+        let mut attempt = 0;
+        loop {
+            // The body of a sent request is consumed, so every attempt sends a copy
+            let copy = web_sys::Request::clone(&request).map_err(|e| {
+                gloo_net::Error::GlooError(format!("Failed to copy request: {:?}", e))
+            })?;
+            let result = crate::transport::send(Request::from(copy)).await;
+            let retry = match &result {
+                Ok(response) => is_retryable_status(response.status(), idempotent),
+                Err(_) => true,
+            };
+            if !retry || attempt >= retries {
+                return result;
+            }
+            attempt += 1;
+            crate::browser::sleep(retry_delay_ms(attempt)).await;
+        }
+    }
+}
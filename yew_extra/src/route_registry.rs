@@ -0,0 +1,523 @@
+//! Registry of the routes generated by `yew_server_hook`.
+//!
+//! The macro submits a [`RouteInfo`] for every endpoint to `crate::route_registry` of the
+//! crate using it. Re-exporting this module there lets [`routes()`] (and
+//! [`test::test_router()`](crate::test::test_router)) mount all of them:
+//!
+//! ```ignore
+//! // src/lib.rs
+//! pub use yew_extra::route_registry;
+//!
+//! // src/main.rs
+//! let app = Router::new()
+//!     .merge(yew_extra::route_registry::routes())
+//!     .with_state(state);
+//! ```
+//!
+//! Endpoints declared with `state = AppState` need the app state, so mount them with
+//! [`build_router_with_state()`] instead.
+//!
+//! [`into_lambda_service()`] serves the same routes on AWS Lambda through `lambda_http`,
+//! and [`normalize_paths()`] makes the app accept `/api/users/` or `//api/users` for
+//! `/api/users`. Middleware finds the [`RouteMetadata`] of the route a request was routed
+//! to with [`matched_route()`].
+
+use crate::deprecation::Deprecation;
+use crate::path_params::router_path;
+use axum::body::{Body, Bytes, HttpBody};
+use axum::extract::MatchedPath;
+use axum::http::uri::PathAndQuery;
+use axum::http::{header, Method, Request, Response, StatusCode, Uri};
+use axum::routing::{MethodFilter, MethodRouter};
+use axum::{BoxError, Router};
+use once_cell::sync::Lazy;
+use std::any::Any;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::ServiceExt;
+
+/// A generated `*_handler_wrapper` function
+pub type Handler = fn(Request<Body>) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>>;
+
+/// A generated `*_handler_state_wrapper` function, called with the state the router was
+/// built with. It answers `500 Internal Server Error` for a state of another type.
+pub type StateHandler = fn(
+    Request<Body>,
+    &(dyn Any + Send + Sync),
+) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>>;
+
+/// Who may call a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Auth {
+    /// Only signed-in users
+    Required,
+    /// Anyone, with more for signed-in users
+    Optional,
+    /// Anyone
+    Public,
+}
+
+/// What an endpoint declares about itself with the `auth`, `tags`, `owner` and `deprecated`
+/// options of `#[yewserverhook]`, for middleware to act on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RouteMetadata {
+    /// `None` when the endpoint doesn't say, leaving it to the app's default
+    pub auth: Option<Auth>,
+    pub tags: &'static [&'static str],
+    /// The team owning the endpoint, e.g. for alerts
+    pub owner: Option<&'static str>,
+    /// Set for endpoints being retired
+    pub deprecation: Option<Deprecation>,
+}
+
+impl RouteMetadata {
+    pub const fn new() -> Self {
+        Self {
+            auth: None,
+            tags: &[],
+            owner: None,
+            deprecation: None,
+        }
+    }
+
+    pub const fn auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    pub const fn tags(mut self, tags: &'static [&'static str]) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub const fn owner(mut self, owner: &'static str) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    pub const fn deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.deprecation = Some(deprecation);
+        self
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&tag)
+    }
+}
+
+/// A registered endpoint
+pub struct RouteInfo {
+    pub path: &'static str,
+    pub method: Method,
+    /// Without the app state, the wrapper of an endpoint declared with `state` answers
+    /// `500 Internal Server Error`
+    pub handler: Handler,
+    /// Set for endpoints declared with `state`
+    pub state_handler: Option<StateHandler>,
+    pub metadata: RouteMetadata,
+}
+
+impl RouteInfo {
+    pub const fn new(path: &'static str, method: Method, handler: Handler) -> Self {
+        Self {
+            path,
+            method,
+            handler,
+            state_handler: None,
+            metadata: RouteMetadata::new(),
+        }
+    }
+
+    pub const fn state_handler(mut self, state_handler: StateHandler) -> Self {
+        self.state_handler = Some(state_handler);
+        self
+    }
+
+    pub const fn metadata(mut self, metadata: RouteMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
+
+inventory::collect!(RouteInfo);
+
+/// Returns every registered route, sorted by path and method
+pub fn registered_routes() -> Vec<&'static RouteInfo> {
+    let mut routes: Vec<&'static RouteInfo> = inventory::iter::<RouteInfo>.into_iter().collect();
+    routes.sort_by(|a, b| (a.path, a.method.as_str()).cmp(&(b.path, b.method.as_str())));
+    routes
+}
+
+/// The registered routes by their path as the router has it
+static ROUTES_BY_PATH: Lazy<HashMap<String, Vec<&'static RouteInfo>>> = Lazy::new(|| {
+    let mut by_path: HashMap<String, Vec<&'static RouteInfo>> = HashMap::new();
+    for route in inventory::iter::<RouteInfo> {
+        by_path
+            .entry(router_path(route.path))
+            .or_default()
+            .push(route);
+    }
+    by_path
+});
+
+/// Returns the route registered for `method` on `path`, written like the registered paths
+/// (`/api/users/{id}` or `/api/users/:id`, not `/api/users/42`). HEAD requests find the
+/// GET route.
+pub fn find_route(method: &Method, path: &str) -> Option<&'static RouteInfo> {
+    let method = if method == Method::HEAD {
+        &Method::GET
+    } else {
+        method
+    };
+    ROUTES_BY_PATH
+        .get(&router_path(path))?
+        .iter()
+        .find(|route| route.method == *method)
+        .copied()
+}
+
+/// Returns the registered route `request` was routed to, so middleware can branch on its
+/// metadata:
+///
+/// ```ignore
+/// async fn require_auth(request: Request, next: Next) -> Response {
+///     let public = matched_route(&request)
+///         .is_some_and(|route| route.metadata.auth == Some(Auth::Public));
+///     if !public && !is_signed_in(&request) {
+///         return StatusCode::UNAUTHORIZED.into_response();
+///     }
+///     next.run(request).await
+/// }
+///
+/// let app = Router::new()
+///     .merge(routes())
+///     .layer(axum::middleware::from_fn(require_auth));
+/// ```
+///
+/// Only middleware added with `Router::layer` sees the matched route, wrapping the whole
+/// app runs before routing. Routes nested under a prefix aren't found.
+pub fn matched_route<B>(request: &Request<B>) -> Option<&'static RouteInfo> {
+    let path = request.extensions().get::<MatchedPath>()?;
+    find_route(request.method(), path.as_str())
+}
+
+/// Builds a router mounting every registered route.
+///
+/// A request for a registered path with another method is answered with
+/// `405 Method Not Allowed`, listing the methods of the path in the `Allow` header, rather
+/// than reaching the fallback of the app (e.g. the SSR handler).
+///
+/// # Panics
+///
+/// Panics if two endpoints are registered for the same path and method, or if a method
+/// isn't supported by axum's router.
+pub fn routes<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    build_routes(None)
+}
+
+/// Builds a router mounting every registered route like [`routes()`], passing `state` to
+/// the endpoints declared with `state = AppState`:
+///
+/// ```ignore
+/// let app = Router::new()
+///     .merge(yew_extra::route_registry::build_router_with_state(app_state.clone()))
+///     .route("/health", get(health))
+///     .with_state(app_state);
+/// ```
+///
+/// The endpoints read it as `State<AppState>`, so `state` must be an `AppState`. An endpoint
+/// declared with another type answers `500 Internal Server Error`.
+///
+/// # Panics
+///
+/// Panics like [`routes()`].
+pub fn build_router_with_state<T, S>(state: T) -> Router<S>
+where
+    T: Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    build_routes(Some(Arc::new(state)))
+}
+
+fn build_routes<S>(state: Option<Arc<dyn Any + Send + Sync>>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let mut router = Router::new();
+    // The paths in the syntax of the router, sorted so the methods of a path are next to
+    // each other
+    let mut routes: Vec<(String, &'static RouteInfo)> = registered_routes()
+        .into_iter()
+        .map(|route| (router_path(route.path), route))
+        .collect();
+    routes.sort_by(|a, b| a.0.cmp(&b.0));
+    for same_path in routes.chunk_by(|a, b| a.0 == b.0) {
+        let mut method_router = MethodRouter::new();
+        let mut allowed = Vec::new();
+        for (_, route) in same_path {
+            let filter = MethodFilter::try_from(route.method.clone()).unwrap_or_else(|e| {
+                panic!(
+                    "Unsupported method for {} {}: {}",
+                    route.method, route.path, e
+                )
+            });
+            let handler = route.handler;
+            method_router = match (route.state_handler, &state) {
+                (Some(state_handler), Some(state)) => {
+                    let state = state.clone();
+                    method_router.on(filter, move |req: Request<Body>| {
+                        state_handler(req, &*state)
+                    })
+                }
+                _ => method_router.on(filter, move |req: Request<Body>| handler(req)),
+            };
+            allowed.push(route.method.as_str());
+            // Axum answers HEAD requests with the GET route
+            if route.method == Method::GET {
+                allowed.push("HEAD");
+            }
+        }
+        let allow = allowed.join(", ");
+        method_router = method_router.fallback(move |req: Request<Body>| {
+            let allow = allow.clone();
+            async move { method_not_allowed(req.method(), &allow) }
+        });
+        router = router.route(&same_path[0].0, method_router);
+    }
+    router
+}
+
+fn json_error(status: StatusCode, message: String) -> Response<Body> {
+    let body = serde_json::json!({ "error": message }).to_string();
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+fn method_not_allowed(method: &Method, allow: &str) -> Response<Body> {
+    let mut response = json_error(
+        StatusCode::METHOD_NOT_ALLOWED,
+        format!("Method {} not allowed, expected {}", method, allow),
+    );
+    if let Ok(allow) = header::HeaderValue::from_str(allow) {
+        response.headers_mut().insert(header::ALLOW, allow);
+    }
+    response
+}
+
+/// Answers every request under `prefix` that no route handles with a JSON
+/// `404 Not Found`, so API calls to unknown paths don't get the HTML of the SSR fallback:
+///
+/// ```ignore
+/// let app = Router::new()
+///     .merge(yew_extra::route_registry::routes())
+///     .merge(yew_extra::route_registry::api_fallback("/api"))
+///     .fallback(render_app);
+/// ```
+///
+/// The body is `{"error": "No endpoint for GET /api/..."}`, which the generated clients
+/// report as the error message.
+///
+/// # Panics
+///
+/// Panics if `prefix` is empty or `/`; use a fallback for the whole app instead.
+pub fn api_fallback<S>(prefix: &str) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let prefix = prefix.trim_end_matches('/');
+    assert!(
+        !prefix.is_empty(),
+        "The API prefix can't be the root, use Router::fallback instead"
+    );
+    Router::new().nest(
+        prefix,
+        Router::new().fallback(|req: Request<Body>| async move {
+            let path = req
+                .extensions()
+                .get::<axum::extract::OriginalUri>()
+                .map(|uri| uri.path().to_string())
+                .unwrap_or_else(|| req.uri().path().to_string());
+            json_error(
+                StatusCode::NOT_FOUND,
+                format!("No endpoint for {} {}", req.method(), path),
+            )
+        }),
+    )
+}
+
+/// Builds a service running every registered route for `lambda_http::run()`:
+///
+/// ```ignore
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() -> Result<(), lambda_http::Error> {
+///     lambda_http::run(yew_extra::route_registry::into_lambda_service()).await
+/// }
+/// ```
+///
+/// Every invocation runs in a [`request_scope()`](crate::request_scope), so a warm Lambda
+/// container never hands the request parts of an invocation to the next one, even when it
+/// panicked.
+pub fn into_lambda_service() -> LambdaService {
+    LambdaService { router: routes() }
+}
+
+/// The registered routes as a service accepting requests with any body, e.g. the
+/// `lambda_http::Request`s of AWS Lambda
+#[derive(Clone)]
+pub struct LambdaService {
+    router: Router,
+}
+
+impl<B> tower::Service<Request<B>> for LambdaService
+where
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<Body>, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let router = self.router.clone();
+        Box::pin(crate::request_scope(router.oneshot(req.map(Body::new))))
+    }
+}
+
+/// How [`normalize_paths()`] treats a trailing slash
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrailingSlash {
+    /// `/api/users/` and `/api/users` are different paths
+    #[default]
+    Strict,
+    /// `/api/users/` is handled as `/api/users`
+    Merge,
+    /// `/api/users/` is redirected to `/api/users` with `308 Permanent Redirect`
+    Redirect,
+}
+
+/// Options of [`normalize_paths()`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PathOptions {
+    pub trailing_slash: TrailingSlash,
+    /// Handle `//api///users` as `/api/users`, or redirect it with
+    /// [`TrailingSlash::Redirect`]
+    pub merge_slashes: bool,
+}
+
+impl PathOptions {
+    pub fn trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+
+    pub fn merge_slashes(mut self, merge_slashes: bool) -> Self {
+        self.merge_slashes = merge_slashes;
+        self
+    }
+
+    /// The path `path` is handled as
+    fn normalize(&self, path: &str) -> String {
+        let mut normalized = String::with_capacity(path.len());
+        for c in path.chars() {
+            if !(self.merge_slashes && c == '/' && normalized.ends_with('/')) {
+                normalized.push(c);
+            }
+        }
+        if self.trailing_slash != TrailingSlash::Strict {
+            while normalized.len() > 1 && normalized.ends_with('/') {
+                normalized.pop();
+            }
+        }
+        normalized
+    }
+}
+
+/// Normalizes the path of every request before `service` routes it.
+///
+/// Routing happens before middleware added with `Router::layer` runs, so the app is wrapped
+/// instead and served as a service:
+///
+/// ```ignore
+/// use tower::ServiceExt;
+/// use yew_extra::route_registry::{normalize_paths, PathOptions, TrailingSlash};
+///
+/// let app = normalize_paths(
+///     Router::new().merge(routes()).fallback(render_app),
+///     PathOptions::default()
+///         .trailing_slash(TrailingSlash::Merge)
+///         .merge_slashes(true),
+/// );
+/// axum::serve(listener, ServiceExt::<Request<Body>>::into_make_service(app)).await?;
+/// ```
+///
+/// Registered paths should then be written without a trailing slash, the form requests
+/// are normalized to.
+pub fn normalize_paths<S>(service: S, options: PathOptions) -> NormalizePath<S> {
+    NormalizePath { service, options }
+}
+
+/// A service normalizing request paths, see [`normalize_paths()`]
+#[derive(Debug, Clone)]
+pub struct NormalizePath<S> {
+    service: S,
+    options: PathOptions,
+}
+
+impl<S, B> tower::Service<Request<B>> for NormalizePath<S>
+where
+    S: tower::Service<Request<B>, Response = Response<Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<Body>, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let path = req.uri().path();
+        let normalized = self.options.normalize(path);
+        if normalized != path {
+            let path_and_query = match req.uri().query() {
+                Some(query) => format!("{}?{}", normalized, query),
+                None => normalized,
+            };
+            if self.options.trailing_slash == TrailingSlash::Redirect {
+                // `//host/` would redirect to another site
+                let location = format!("/{}", path_and_query.trim_start_matches('/'));
+                let response = Response::builder()
+                    .status(StatusCode::PERMANENT_REDIRECT)
+                    .header(header::LOCATION, location)
+                    .body(Body::empty())
+                    .unwrap_or_else(|_| Response::new(Body::empty()));
+                return Box::pin(async move { Ok(response) });
+            }
+            if let Ok(path_and_query) = PathAndQuery::try_from(path_and_query) {
+                let mut parts = req.uri().clone().into_parts();
+                parts.path_and_query = Some(path_and_query);
+                if let Ok(uri) = Uri::from_parts(parts) {
+                    *req.uri_mut() = uri;
+                }
+            }
+        }
+        Box::pin(self.service.call(req))
+    }
+}
@@ -0,0 +1,231 @@
+//! Checks JSON responses against the JSON Schema of the expected type.
+//!
+//! Endpoints declared with `schema_check = true` run [`validate_json`] on every response in
+//! debug builds, before deserializing it. A mismatch is reported with the path of the
+//! offending value and what was expected there, which makes drift between the server and
+//! client types much easier to spot than serde's error.
+//!
+//! Only the keywords schemars generates for Rust types are checked (`type`, `enum`,
+//! `const`, `properties`, `required`, `additionalProperties`, `items`, `prefixItems`,
+//! `anyOf`, `oneOf`, `allOf`, `minimum`, `maximum` and local `$ref`s).
+
+use serde_json::Value;
+
+pub use schemars;
+pub use schemars::JsonSchema;
+
+/// Validates `json` against the schema of `T`.
+///
+/// # Example
+///
+/// ```
+/// use yew_extra::schema::{validate_json, JsonSchema};
+///
+/// #[derive(JsonSchema)]
+/// #[schemars(crate = "yew_extra::schema::schemars")]
+/// struct Item {
+///     id: u32,
+///     tags: Vec<String>,
+/// }
+///
+/// assert!(validate_json::<Item>(r#"{"id": 1, "tags": ["a"]}"#).is_ok());
+/// assert_eq!(
+///     validate_json::<Item>(r#"{"id": 1, "tags": ["a", 2]}"#).unwrap_err(),
+///     "Response doesn't match the expected type at $.tags[1]: expected string, found number 2",
+/// );
+/// ```
+pub fn validate_json<T: JsonSchema>(json: &str) -> Result<(), String> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|e| format!("Response is not valid JSON: {}", e))?;
+    let schema = schemars::schema_for!(T);
+
+    Validator {
+        root: schema.as_value(),
+    }
+    .validate(schema.as_value(), &value, "$")
+    .map_err(|e| {
+        format!(
+            "Response doesn't match the expected type at {}: {}",
+            e.path, e.message
+        )
+    })
+}
+
+struct SchemaError {
+    path: String,
+    message: String,
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+}
+
+impl<'a> Validator<'a> {
+    fn validate(&self, schema: &'a Value, value: &Value, path: &str) -> Result<(), SchemaError> {
+        let error = |message: String| SchemaError {
+            path: path.to_string(),
+            message,
+        };
+
+        let schema = match schema {
+            Value::Bool(true) => return Ok(()),
+            Value::Bool(false) => return Err(error("no value is allowed here".to_string())),
+            Value::Object(schema) => schema,
+            _ => return Ok(()),
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let target = self
+                .resolve(reference)
+                .ok_or_else(|| error(format!("unresolved schema reference {}", reference)))?;
+            self.validate(target, value, path)?;
+        }
+
+        if let Some(types) = schema.get("type") {
+            let allowed: Vec<&str> = match types {
+                Value::String(single) => vec![single.as_str()],
+                Value::Array(many) => many.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !allowed.is_empty() && !allowed.iter().any(|ty| has_type(value, ty)) {
+                return Err(error(format!(
+                    "expected {}, found {}",
+                    allowed.join(" or "),
+                    describe(value)
+                )));
+            }
+        }
+
+        if let Some(expected) = schema.get("const") {
+            if expected != value {
+                return Err(error(format!("expected {}, found {}", expected, value)));
+            }
+        }
+
+        if let Some(Value::Array(variants)) = schema.get("enum") {
+            if !variants.contains(value) {
+                let variants: Vec<String> = variants.iter().map(Value::to_string).collect();
+                return Err(error(format!(
+                    "expected one of {}, found {}",
+                    variants.join(", "),
+                    value
+                )));
+            }
+        }
+
+        if let Some(number) = value.as_f64() {
+            if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < minimum {
+                    return Err(error(format!("{} is less than {}", value, minimum)));
+                }
+            }
+            if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > maximum {
+                    return Err(error(format!("{} is greater than {}", value, maximum)));
+                }
+            }
+        }
+
+        if let Value::Object(object) = value {
+            let properties = schema.get("properties").and_then(Value::as_object);
+
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for field in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(field) {
+                        return Err(error(format!("missing field `{}`", field)));
+                    }
+                }
+            }
+
+            for (field, field_value) in object {
+                let field_path = format!("{}.{}", path, field);
+                match properties.and_then(|properties| properties.get(field)) {
+                    Some(field_schema) => self.validate(field_schema, field_value, &field_path)?,
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            return Err(error(format!("unknown field `{}`", field)));
+                        }
+                        Some(additional) => self.validate(additional, field_value, &field_path)?,
+                        None => {}
+                    },
+                }
+            }
+        }
+
+        if let Value::Array(elements) = value {
+            let prefix = schema
+                .get("prefixItems")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+
+            for (index, element) in elements.iter().enumerate() {
+                let element_path = format!("{}[{}]", path, index);
+                if let Some(element_schema) = prefix.get(index).or_else(|| schema.get("items")) {
+                    self.validate(element_schema, element, &element_path)?;
+                }
+            }
+        }
+
+        if let Some(Value::Array(all)) = schema.get("allOf") {
+            for sub_schema in all {
+                self.validate(sub_schema, value, path)?;
+            }
+        }
+
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(Value::Array(variants)) = schema.get(keyword) {
+                let mut errors = Vec::new();
+                for variant in variants {
+                    match self.validate(variant, value, path) {
+                        Ok(()) => break,
+                        Err(e) => errors.push(e),
+                    }
+                }
+                if !variants.is_empty() && errors.len() == variants.len() {
+                    // Report the error that got the furthest into the value
+                    return Err(errors
+                        .into_iter()
+                        .max_by_key(|e| e.path.len())
+                        .unwrap_or_else(|| error("no variant matches".to_string())));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolves a local reference such as `#/$defs/Item`
+    fn resolve(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        self.root.pointer(pointer)
+    }
+}
+
+fn has_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().is_some_and(|number| number.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(_) => format!("boolean {}", value),
+        Value::Number(_) => format!("number {}", value),
+        Value::String(_) => format!("string {}", value),
+        Value::Array(_) => "array".to_string(),
+        Value::Object(_) => "object".to_string(),
+    }
+}
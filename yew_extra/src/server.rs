@@ -0,0 +1,143 @@
+//! Runs the routes registered by `yew_server_hook` from other server frameworks.
+//!
+//! The generated wrappers are axum handlers, taking and returning the `http` types axum
+//! uses. This module is an adapter in front of them, not an interface the macro generates
+//! code for: backends convert their requests with [`ServerRequest`], let [`handle`] route
+//! them through the axum router, and build their responses with [`ServerResponse`]. The
+//! app still depends on axum, and responses are buffered on the way back. The actix-web
+//! adapter (`actix` feature) and the Tauri IPC [`dispatch`](crate::transport::dispatch) are
+//! built this way:
+//!
+//! ```ignore
+//! impl ServerRequest for MyRequest {
+//!     fn into_request(self) -> Result<Request<Body>, String> {
+//!         build_request(&self.method, &self.uri, self.headers.iter(), self.body)
+//!     }
+//! }
+//!
+//! impl ServerResponse for MyResponse {
+//!     fn from_parts(status: u16, headers: Vec<(String, Vec<u8>)>, body: Bytes) -> Self {
+//!         MyResponse { status, headers, body }
+//!     }
+//! }
+//!
+//! let response: MyResponse = yew_extra::server::handle(routes(), request).await;
+//! ```
+
+use crate::route_registry::Handler;
+use axum::body::{to_bytes, Body, Bytes};
+use axum::http::{Method, Request, Response, StatusCode};
+use axum::Router;
+use tower::ServiceExt;
+
+/// A request of a server framework, converted to the request the generated wrappers take
+pub trait ServerRequest {
+    fn into_request(self) -> Result<Request<Body>, String>;
+}
+
+/// A response of a server framework, built from the response of a generated wrapper
+pub trait ServerResponse: Sized {
+    fn from_parts(status: u16, headers: Vec<(String, Vec<u8>)>, body: Bytes) -> Self;
+}
+
+impl ServerRequest for Request<Body> {
+    fn into_request(self) -> Result<Request<Body>, String> {
+        Ok(self)
+    }
+}
+
+impl ServerResponse for Response<Body> {
+    fn from_parts(status: u16, headers: Vec<(String, Vec<u8>)>, body: Bytes) -> Self {
+        let mut builder = Response::builder().status(status);
+        for (name, value) in headers {
+            builder = builder.header(name, value);
+        }
+        builder.body(Body::from(body)).unwrap_or_else(|_| {
+            let mut response = Response::new(Body::empty());
+            *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+            response
+        })
+    }
+}
+
+/// Builds the request a generated wrapper takes from the pieces of another framework's one
+pub fn build_request<N, V>(
+    method: &str,
+    uri: &str,
+    headers: impl IntoIterator<Item = (N, V)>,
+    body: impl Into<Body>,
+) -> Result<Request<Body>, String>
+where
+    N: AsRef<str>,
+    V: AsRef<[u8]>,
+{
+    let method =
+        Method::from_bytes(method.as_bytes()).map_err(|e| format!("Invalid method: {}", e))?;
+    let mut builder = Request::builder().method(method).uri(uri);
+    for (name, value) in headers {
+        builder = builder.header(name.as_ref(), value.as_ref());
+    }
+    builder
+        .body(body.into())
+        .map_err(|e| format!("Invalid request: {}", e))
+}
+
+fn error<R: ServerResponse>(status: StatusCode, message: String) -> R {
+    R::from_parts(
+        status.as_u16(),
+        vec![("content-type".to_string(), b"text/plain".to_vec())],
+        Bytes::from(message),
+    )
+}
+
+async fn into_server_response<R: ServerResponse>(response: Response<Body>) -> R {
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to read response body: {}", e),
+            )
+        }
+    };
+    let headers = parts
+        .headers
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.as_bytes().to_vec()))
+        .collect();
+    R::from_parts(parts.status.as_u16(), headers, body)
+}
+
+/// Routes `request` through `router`, e.g. [`routes()`](crate::route_registry::routes),
+/// and converts the response.
+///
+/// Requests that can't be converted are answered with `400 Bad Request`. The response body
+/// is buffered, so streamed responses arrive in one piece.
+pub async fn handle<Req, Res>(router: Router, request: Req) -> Res
+where
+    Req: ServerRequest,
+    Res: ServerResponse,
+{
+    let request = match request.into_request() {
+        Ok(request) => request,
+        Err(message) => return error(StatusCode::BAD_REQUEST, message),
+    };
+    let response = match router.oneshot(request).await {
+        Ok(response) => response,
+        Err(e) => match e {},
+    };
+    into_server_response(response).await
+}
+
+/// Runs a single generated wrapper, for backends doing their own routing
+pub async fn call<Req, Res>(handler: Handler, request: Req) -> Res
+where
+    Req: ServerRequest,
+    Res: ServerResponse,
+{
+    match request.into_request() {
+        Ok(request) => into_server_response(handler(request).await).await,
+        Err(message) => error(StatusCode::BAD_REQUEST, message),
+    }
+}
@@ -0,0 +1,80 @@
+// Shared worker sending the GET requests of the generated clients for every tab of the
+// app, so identical requests are made once and their responses are cached for all tabs.
+//
+// Served by yew_extra::shared_worker::shared_worker_routes() at /shared-worker.js.
+
+const MAX_ENTRIES = 500;
+
+// Key of a request -> { response, time }
+const cache = new Map();
+// Key of a request -> promise of its response
+const inFlight = new Map();
+// Bumped by every invalidation, so responses fetched before it aren't cached
+let generation = 0;
+
+function keyOf(request) {
+  return request.method + " " + request.uri + "\n" + JSON.stringify(request.headers);
+}
+
+async function send(request) {
+  const response = await fetch(request.uri, {
+    method: request.method,
+    headers: request.headers,
+    body: request.body ?? undefined,
+  });
+  return {
+    status: response.status,
+    headers: [...response.headers],
+    body: await response.text(),
+  };
+}
+
+function cacheResponse(key, response) {
+  cache.delete(key);
+  cache.set(key, { response, time: Date.now() });
+  // Maps iterate in insertion order, the first entry is the oldest
+  if (cache.size > MAX_ENTRIES) {
+    cache.delete(cache.keys().next().value);
+  }
+}
+
+async function answer({ request, stale_ms }) {
+  const key = keyOf(request);
+  const cached = cache.get(key);
+  if (cached && Date.now() - cached.time < stale_ms) {
+    return cached.response;
+  }
+
+  // Requests sent before an invalidation aren't shared with those after it
+  const sentIn = generation;
+  const flightKey = sentIn + " " + key;
+  let pending = inFlight.get(flightKey);
+  if (!pending) {
+    pending = send(request)
+      .then((response) => {
+        if (sentIn === generation && response.status >= 200 && response.status < 300) {
+          cacheResponse(key, response);
+        }
+        return response;
+      })
+      .finally(() => inFlight.delete(flightKey));
+    inFlight.set(flightKey, pending);
+  }
+  return pending;
+}
+
+self.onconnect = (event) => {
+  const port = event.ports[0];
+  port.onmessage = async ({ data }) => {
+    if (data.invalidate) {
+      generation += 1;
+      cache.clear();
+      return;
+    }
+    try {
+      port.postMessage({ id: data.id, response: await answer(data) });
+    } catch (e) {
+      port.postMessage({ id: data.id, error: String(e) });
+    }
+  };
+};
@@ -0,0 +1,236 @@
+//! Requests shared by every tab of the app through a SharedWorker.
+//!
+//! With a [`SharedWorkerBackend`] installed (`client` feature, and the `transport` feature
+//! of `yew_server_hook`), the GET requests of the generated clients are sent by the bundled
+//! shared worker instead of the tab. The worker sends identical requests only once, while
+//! they're in flight, and answers from its cache while a response is fresh, so ten open
+//! tabs make one request and read the same data. Mutations are sent from the tab and clear
+//! the worker's cache once they succeed.
+//!
+//! Serve the worker from your server:
+//!
+//! ```ignore
+//! let app = Router::new()
+//!     .merge(your_generated_routes())
+//!     .merge(yew_extra::shared_worker::shared_worker_routes()); // /shared-worker.js
+//! ```
+//!
+//! and install the backend when the app starts, which does nothing in browsers without
+//! shared workers:
+//!
+//! ```ignore
+//! yew_extra::shared_worker::install_shared_worker("/shared-worker.js", Duration::from_secs(30));
+//! ```
+//!
+//! The worker sends requests to the origin it's served from, with text bodies.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::{shared_worker_routes, SHARED_WORKER_JS};
+
+#[cfg(feature = "client")]
+pub use client::{install_shared_worker, shared_workers_supported, SharedWorkerBackend};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod server {
+    use axum::http::header;
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::Router;
+
+    /// The bundled shared worker script
+    pub const SHARED_WORKER_JS: &str = include_str!("shared_worker.js");
+
+    async fn shared_worker_handler() -> impl IntoResponse {
+        (
+            [
+                (header::CONTENT_TYPE, "application/javascript"),
+                (header::CACHE_CONTROL, "no-cache"),
+            ],
+            SHARED_WORKER_JS,
+        )
+    }
+
+    /// Route serving the shared worker (`/shared-worker.js`)
+    pub fn shared_worker_routes<S>() -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        Router::new().route("/shared-worker.js", get(shared_worker_handler))
+    }
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use crate::transport::{
+        ipc_error, ipc_request, set_backend, to_response, FetchBackend, HttpBackend, IpcRequest,
+        IpcResponse, SendFuture,
+    };
+    use gloo_net::http::{Method, Request, Response};
+    use serde::{Deserialize, Serialize};
+    use std::cell::{Cell, RefCell};
+    use std::collections::HashMap;
+    use std::rc::Rc;
+    use std::time::Duration;
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    /// A request posted to the worker
+    #[derive(Serialize)]
+    struct WorkerRequest {
+        id: u32,
+        request: IpcRequest,
+        stale_ms: u64,
+    }
+
+    /// The worker's answer to a [`WorkerRequest`]
+    #[derive(Deserialize)]
+    struct WorkerAnswer {
+        response: Option<IpcResponse>,
+        error: Option<String>,
+    }
+
+    #[derive(Serialize)]
+    struct Invalidate {
+        invalidate: bool,
+    }
+
+    /// Resolvers of the requests waiting for the worker, by id
+    type Pending = Rc<RefCell<HashMap<u32, js_sys::Function>>>;
+
+    struct Connection {
+        _worker: web_sys::SharedWorker,
+        port: web_sys::MessagePort,
+        pending: Pending,
+        next_id: Cell<u32>,
+        stale_time: Cell<Duration>,
+        _on_message: Closure<dyn FnMut(web_sys::MessageEvent)>,
+    }
+
+    /// Whether the browser has shared workers
+    pub fn shared_workers_supported() -> bool {
+        js_sys::Reflect::has(&js_sys::global(), &"SharedWorker".into()).unwrap_or(false)
+    }
+
+    /// An [`HttpBackend`] sending GET requests through the shared worker
+    #[derive(Clone)]
+    pub struct SharedWorkerBackend(Rc<Connection>);
+
+    impl SharedWorkerBackend {
+        /// Starts or joins the shared worker served at `script_url` (usually
+        /// `/shared-worker.js`)
+        pub fn connect(script_url: &str) -> Result<Self, String> {
+            let worker = web_sys::SharedWorker::new(script_url)
+                .map_err(|e| format!("Failed to start the shared worker: {:?}", e))?;
+            let port = worker.port();
+            let pending = Pending::default();
+
+            let on_message = Closure::<dyn FnMut(web_sys::MessageEvent)>::new({
+                let pending = pending.clone();
+                move |event: web_sys::MessageEvent| {
+                    let data = event.data();
+                    let id = js_sys::Reflect::get(&data, &"id".into())
+                        .ok()
+                        .and_then(|id| id.as_f64());
+                    let resolve = id.and_then(|id| pending.borrow_mut().remove(&(id as u32)));
+                    if let Some(resolve) = resolve {
+                        let _ = resolve.call1(&JsValue::NULL, &data);
+                    }
+                }
+            });
+            // Setting the handler starts the port
+            port.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+            Ok(Self(Rc::new(Connection {
+                _worker: worker,
+                port,
+                pending,
+                next_id: Cell::new(0),
+                stale_time: Cell::new(Duration::ZERO),
+                _on_message: on_message,
+            })))
+        }
+
+        /// Sets how long the worker answers with a cached response, by default only
+        /// requests in flight are shared
+        pub fn stale_time(self, stale_time: Duration) -> Self {
+            self.0.stale_time.set(stale_time);
+            self
+        }
+
+        /// Clears the worker's cache, for every tab
+        pub fn invalidate(&self) {
+            if let Ok(message) = serde_wasm_bindgen::to_value(&Invalidate { invalidate: true }) {
+                let _ = self.0.port.post_message(&message);
+            }
+        }
+
+        async fn request(&self, request: IpcRequest) -> Result<IpcResponse, gloo_net::Error> {
+            let connection = &self.0;
+            let id = connection.next_id.get();
+            connection.next_id.set(id.wrapping_add(1));
+
+            let message = serde_wasm_bindgen::to_value(&WorkerRequest {
+                id,
+                request,
+                stale_ms: connection.stale_time.get().as_millis() as u64,
+            })
+            .map_err(|e| ipc_error("Failed to encode request", e))?;
+            let answer = js_sys::Promise::new(&mut |resolve, _reject| {
+                connection.pending.borrow_mut().insert(id, resolve);
+            });
+            if let Err(e) = connection.port.post_message(&message) {
+                connection.pending.borrow_mut().remove(&id);
+                return Err(ipc_error("Failed to reach the shared worker", e));
+            }
+
+            let answer = JsFuture::from(answer)
+                .await
+                .map_err(|e| ipc_error("Shared worker request failed", e))?;
+            let answer: WorkerAnswer = serde_wasm_bindgen::from_value(answer)
+                .map_err(|e| ipc_error("Invalid shared worker response", e))?;
+            match (answer.response, answer.error) {
+                (Some(response), _) => Ok(response),
+                (None, error) => Err(ipc_error(
+                    "Shared worker request failed",
+                    error.unwrap_or_default(),
+                )),
+            }
+        }
+    }
+
+    impl HttpBackend for SharedWorkerBackend {
+        fn send(&self, request: Request) -> SendFuture {
+            let backend = self.clone();
+            Box::pin(async move {
+                if request.method() != Method::GET {
+                    let response = FetchBackend.send(request).await?;
+                    if response.ok() {
+                        backend.invalidate();
+                    }
+                    return Ok(response);
+                }
+                let answer = backend.request(ipc_request(request.into()).await?).await?;
+                to_response(&answer)
+                    .map(Response::from)
+                    .map_err(|e| ipc_error("Failed to build response", e))
+            })
+        }
+    }
+
+    /// Sends the requests of the generated clients through the shared worker served at
+    /// `script_url`, with responses fresh for `stale_time`. Returns `false`, leaving the
+    /// backend as is, if the browser has no shared workers or the worker can't start.
+    pub fn install_shared_worker(script_url: &str, stale_time: Duration) -> bool {
+        if !shared_workers_supported() {
+            return false;
+        }
+        match SharedWorkerBackend::connect(script_url) {
+            Ok(backend) => {
+                set_backend(backend.stale_time(stale_time));
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Success statuses other than `200 OK` for server functions.
+//!
+//! A server function returning `WithStatus<T>` or `(StatusCode, T)` answers with that
+//! status and `T` as the body. The generated client still decodes `T`, and the
+//! `*_with_response` variants report the status in [`ResponseMeta`](crate::ResponseMeta):
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/orders", method = "POST")]
+//! pub async fn place_order(order: NewOrder) -> Result<WithStatus<Order>, AppError> {
+//!     Ok(WithStatus::created(insert_order(order).await?))
+//! }
+//!
+//! let (order, meta) = place_order_with_response(order, RequestOptions::new()).await?;
+//! assert_eq!(meta.status, 201);
+//! ```
+
+/// A successful result sent with `status` instead of `200 OK`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WithStatus<T> {
+    /// HTTP status code
+    pub status: u16,
+    pub data: T,
+}
+
+impl<T> WithStatus<T> {
+    pub fn new(status: u16, data: T) -> Self {
+        Self { status, data }
+    }
+
+    /// `201 Created`
+    pub fn created(data: T) -> Self {
+        Self::new(201, data)
+    }
+
+    /// `202 Accepted`
+    pub fn accepted(data: T) -> Self {
+        Self::new(202, data)
+    }
+
+    /// `206 Partial Content`
+    pub fn partial_content(data: T) -> Self {
+        Self::new(206, data)
+    }
+}
+
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+mod server {
+    use super::WithStatus;
+    use axum::http::StatusCode;
+    use axum::response::{IntoResponse, Response};
+    use axum::Json;
+    use serde::Serialize;
+
+    impl<T> From<(StatusCode, T)> for WithStatus<T> {
+        fn from((status, data): (StatusCode, T)) -> Self {
+            Self::new(status.as_u16(), data)
+        }
+    }
+
+    impl<T> WithStatus<T> {
+        /// The status and the JSON body of the response. A status that isn't a valid
+        /// code answers `500 Internal Server Error`.
+        pub fn into_parts(self) -> (StatusCode, Json<T>) {
+            let status =
+                StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            (status, Json(self.data))
+        }
+    }
+
+    impl<T: Serialize> IntoResponse for WithStatus<T> {
+        fn into_response(self) -> Response {
+            self.into_parts().into_response()
+        }
+    }
+}
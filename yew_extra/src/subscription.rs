@@ -0,0 +1,171 @@
+//! Transport selection for subscription endpoints, with a long-polling fallback.
+//!
+//! Subscriptions prefer a streaming transport (EventSource or WebSocket). When neither is
+//! available, or a streaming connection has failed (old proxies and some corporate networks
+//! drop long-lived connections), the client falls back to long polling: repeated GET
+//! requests carrying the cursor of the last batch received.
+//!
+//! A long-polling endpoint responds with a [`PollBatch`]. The server may hold the request
+//! open until new items arrive (or a timeout passes) and then return them with the cursor
+//! to send on the next request.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "client")]
+pub use client::{disable_streaming, long_poll, preferred_transport, PollHandle, Transport};
+
+/// One response of a long-polling endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PollBatch<T> {
+    /// Items published since the cursor sent with the request
+    pub items: Vec<T>,
+    /// Cursor to send with the next request (`None` keeps the previous cursor)
+    pub cursor: Option<String>,
+}
+
+impl<T> PollBatch<T> {
+    /// Creates a batch with the cursor the client should continue from
+    pub fn new(items: Vec<T>, cursor: impl Into<String>) -> Self {
+        Self {
+            items,
+            cursor: Some(cursor.into()),
+        }
+    }
+
+    /// A batch without new items, telling the client to poll again with the same cursor
+    pub fn empty() -> Self {
+        Self {
+            items: Vec::new(),
+            cursor: None,
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use super::PollBatch;
+    use crate::browser::sleep;
+    use serde::de::DeserializeOwned;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Longest wait between retries after failed polls
+    const MAX_BACKOFF_MS: i32 = 30_000;
+
+    thread_local! {
+        static STREAMING_DISABLED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// How a subscription receives updates
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Transport {
+        EventSource,
+        WebSocket,
+        LongPoll,
+    }
+
+    /// Picks the best transport available in the current environment.
+    ///
+    /// Returns [`Transport::LongPoll`] when the browser lacks EventSource and WebSocket,
+    /// or after [`disable_streaming`] was called.
+    pub fn preferred_transport() -> Transport {
+        if STREAMING_DISABLED.with(Cell::get) {
+            return Transport::LongPoll;
+        }
+
+        let global = js_sys::global();
+        let has = |name: &str| js_sys::Reflect::has(&global, &name.into()).unwrap_or(false);
+
+        if has("EventSource") {
+            Transport::EventSource
+        } else if has("WebSocket") {
+            Transport::WebSocket
+        } else {
+            Transport::LongPoll
+        }
+    }
+
+    /// Makes every following subscription use long polling.
+    ///
+    /// Call this when a streaming connection fails to establish, so the rest of the
+    /// session doesn't keep retrying a transport the network doesn't support.
+    pub fn disable_streaming() {
+        STREAMING_DISABLED.with(|disabled| disabled.set(true));
+    }
+
+    /// Stops the long-polling loop when cancelled or dropped
+    pub struct PollHandle {
+        cancelled: Rc<Cell<bool>>,
+    }
+
+    impl PollHandle {
+        pub fn cancel(&self) {
+            self.cancelled.set(true);
+        }
+    }
+
+    impl Drop for PollHandle {
+        fn drop(&mut self) {
+            self.cancel();
+        }
+    }
+
+    /// Polls `url` repeatedly, calling `on_items` with every non-empty batch.
+    ///
+    /// Each request carries the cursor of the previous batch as a `cursor` query parameter.
+    /// Failed requests are retried with exponential backoff. The loop runs until the
+    /// returned handle is cancelled or dropped.
+    pub fn long_poll<T, F>(url: String, on_items: F) -> PollHandle
+    where
+        T: DeserializeOwned + 'static,
+        F: Fn(Vec<T>) + 'static,
+    {
+        let cancelled = Rc::new(Cell::new(false));
+        let handle = PollHandle {
+            cancelled: cancelled.clone(),
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let mut cursor: Option<String> = None;
+            let mut backoff_ms = 500;
+
+            while !cancelled.get() {
+                let request_url = match &cursor {
+                    Some(cursor) => {
+                        let separator = if url.contains('?') { '&' } else { '?' };
+                        let encoded = String::from(js_sys::encode_uri_component(cursor));
+                        format!("{}{}cursor={}", url, separator, encoded)
+                    }
+                    None => url.clone(),
+                };
+
+                let batch = match gloo_net::http::Request::get(&request_url).send().await {
+                    Ok(response) if response.ok() => response.json::<PollBatch<T>>().await.ok(),
+                    _ => None,
+                };
+
+                if cancelled.get() {
+                    break;
+                }
+
+                match batch {
+                    Some(batch) => {
+                        backoff_ms = 500;
+                        if batch.cursor.is_some() {
+                            cursor = batch.cursor;
+                        }
+                        if !batch.items.is_empty() {
+                            on_items(batch.items);
+                        }
+                    }
+                    None => {
+                        sleep(backoff_ms).await;
+                        backoff_ms = (backoff_ms * 2).min(MAX_BACKOFF_MS);
+                    }
+                }
+            }
+        });
+
+        handle
+    }
+}
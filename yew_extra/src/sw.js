@@ -0,0 +1,274 @@
+// Service worker caching the GET endpoints listed in the yew_extra manifest and
+// queueing the background sync mutations while offline.
+//
+// Served by yew_extra::sw::service_worker_routes() at /sw.js.
+
+const MANIFEST_URL = "/sw-manifest.json";
+const CACHE_NAME = "yew-extra-api-v1";
+const SYNC_TAG = "yew-extra-mutations";
+const QUEUE_DB = "yew-extra-sync";
+const QUEUE_STORE = "requests";
+
+let manifestPromise = null;
+
+// The manifest is cached too, so routes are known offline and after the worker restarts
+async function loadManifest() {
+  const cache = await caches.open(CACHE_NAME);
+  try {
+    const response = await fetch(MANIFEST_URL, { cache: "no-store" });
+    if (response.ok) {
+      await cache.put(MANIFEST_URL, response.clone());
+      return await response.json();
+    }
+  } catch (e) {
+    // Offline, fall back to the cached manifest
+  }
+  const cached = await cache.match(MANIFEST_URL);
+  return cached ? await cached.json() : { routes: [] };
+}
+
+function manifest() {
+  if (!manifestPromise) {
+    manifestPromise = loadManifest();
+  }
+  return manifestPromise;
+}
+
+async function routes() {
+  return (await manifest()).routes;
+}
+
+async function syncRoutes() {
+  return (await manifest()).background_sync || [];
+}
+
+// Whether a pathname is one of a route path, whose `{id}` or `:id` segments match any
+// segment and whose `{*rest}` or `*rest` segment matches the rest of the pathname
+function matchesPath(path, pathname) {
+  const expected = path.split("/");
+  const actual = pathname.split("/");
+  for (let i = 0; i < expected.length; i++) {
+    const segment = expected[i];
+    if (segment.startsWith("{*") || segment.startsWith("*")) {
+      return actual.length > i && actual[i] !== "";
+    }
+    if ((segment.startsWith("{") && segment.endsWith("}")) || segment.startsWith(":")) {
+      if (actual[i] === undefined || actual[i] === "") {
+        return false;
+      }
+    } else if (segment !== actual[i]) {
+      return false;
+    }
+  }
+  return expected.length === actual.length;
+}
+
+// Queued mutations are kept in IndexedDB, in the order they were made
+
+function openQueue() {
+  return new Promise((resolve, reject) => {
+    const open = indexedDB.open(QUEUE_DB, 1);
+    open.onupgradeneeded = () =>
+      open.result.createObjectStore(QUEUE_STORE, { autoIncrement: true });
+    open.onsuccess = () => resolve(open.result);
+    open.onerror = () => reject(open.error);
+  });
+}
+
+async function withQueue(mode, action) {
+  const db = await openQueue();
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction(QUEUE_STORE, mode);
+    const request = action(transaction.objectStore(QUEUE_STORE));
+    transaction.oncomplete = () => resolve(request.result);
+    transaction.onerror = () => reject(transaction.error);
+  });
+}
+
+// Used when the browser has no Background Sync API: replay on the next request instead
+let replayPending = false;
+let replaying = null;
+
+async function scheduleReplay() {
+  if (self.registration.sync) {
+    try {
+      await self.registration.sync.register(SYNC_TAG);
+      return;
+    } catch (e) {
+      // Background Sync is unavailable or not permitted
+    }
+  }
+  replayPending = true;
+}
+
+// Sends the queued requests in order. Stops at the first one that can't be delivered,
+// so the browser retries the rest later. The idempotency key of each request makes
+// sending it twice harmless.
+async function replayQueue() {
+  const keys = await withQueue("readonly", (store) => store.getAllKeys());
+  for (const key of keys) {
+    const entry = await withQueue("readonly", (store) => store.get(key));
+    if (entry) {
+      const response = await fetch(entry.url, {
+        method: entry.method,
+        headers: entry.headers,
+        body: entry.body,
+      });
+      if (response.status >= 500) {
+        throw new Error(`Replaying ${entry.method} ${entry.url} failed with status ${response.status}`);
+      }
+    }
+    await withQueue("readwrite", (store) => store.delete(key));
+  }
+}
+
+function replay() {
+  if (!replaying) {
+    replaying = replayQueue().finally(() => {
+      replaying = null;
+    });
+  }
+  return replaying;
+}
+
+async function sendOrQueue(request) {
+  const body = await request.clone().text();
+  try {
+    return await fetch(request);
+  } catch (e) {
+    await withQueue("readwrite", (store) =>
+      store.add({
+        url: request.url,
+        method: request.method,
+        headers: [...request.headers.entries()],
+        body,
+      })
+    );
+    await scheduleReplay();
+    return new Response(JSON.stringify({ queued: true }), {
+      status: 202,
+      headers: { "Content-Type": "application/json", "X-Yew-Queued": "true" },
+    });
+  }
+}
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    (async () => {
+      const cache = await caches.open(CACHE_NAME);
+      const precached = (await routes()).filter((route) => route.precache);
+      await Promise.all(
+        precached.map((route) => cache.add(route.path).catch(() => undefined))
+      );
+      await self.skipWaiting();
+    })()
+  );
+});
+
+self.addEventListener("activate", (event) => {
+  event.waitUntil(
+    (async () => {
+      await self.clients.claim();
+      // Deliver whatever was left in the queue by a previous worker
+      await replay().catch(() => scheduleReplay());
+    })()
+  );
+});
+
+self.addEventListener("sync", (event) => {
+  if (event.tag === SYNC_TAG) {
+    event.waitUntil(replay());
+  }
+});
+
+async function cacheFirst(request) {
+  const cache = await caches.open(CACHE_NAME);
+  const cached = await cache.match(request);
+  if (cached) {
+    return cached;
+  }
+  const response = await fetch(request);
+  if (response.ok) {
+    await cache.put(request, response.clone());
+  }
+  return response;
+}
+
+async function networkFirst(request) {
+  const cache = await caches.open(CACHE_NAME);
+  try {
+    const response = await fetch(request);
+    if (response.ok) {
+      await cache.put(request, response.clone());
+    }
+    return response;
+  } catch (e) {
+    const cached = await cache.match(request);
+    if (cached) {
+      return cached;
+    }
+    throw e;
+  }
+}
+
+async function staleWhileRevalidate(event) {
+  const cache = await caches.open(CACHE_NAME);
+  const cached = await cache.match(event.request);
+  const network = fetch(event.request).then(async (response) => {
+    if (response.ok) {
+      await cache.put(event.request, response.clone());
+    }
+    return response;
+  });
+  if (cached) {
+    event.waitUntil(network.catch(() => undefined));
+    return cached;
+  }
+  return network;
+}
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  const url = new URL(request.url);
+  if (url.origin !== self.location.origin) {
+    return;
+  }
+
+  if (replayPending) {
+    replayPending = false;
+    event.waitUntil(
+      replay().catch(() => {
+        replayPending = true;
+      })
+    );
+  }
+
+  if (request.method !== "GET") {
+    event.respondWith(
+      (async () => {
+        const queued = (await syncRoutes()).some(
+          (route) => matchesPath(route.path, url.pathname) && route.method === request.method
+        );
+        return queued ? sendOrQueue(request) : fetch(request);
+      })()
+    );
+    return;
+  }
+
+  event.respondWith(
+    (async () => {
+      const route = (await routes()).find((route) => matchesPath(route.path, url.pathname));
+      if (!route) {
+        return fetch(request);
+      }
+      switch (route.strategy) {
+        case "cache-first":
+          return cacheFirst(request);
+        case "network-first":
+          return networkFirst(request);
+        default:
+          return staleWhileRevalidate(event);
+      }
+    })()
+  );
+});
@@ -0,0 +1,184 @@
+//! Service worker support for offline reads of GET endpoints.
+//!
+//! Endpoints declared with `sw_cache = "..."` are listed in a manifest served at
+//! `/sw-manifest.json`. The bundled service worker (served at `/sw.js`) reads the
+//! manifest, precaches the endpoints without parameters and answers matching requests
+//! with the declared caching strategy.
+//!
+//! Mutations declared with `background_sync = true` are listed as well. When they can't
+//! reach the server, the service worker queues them and replays them through the
+//! Background Sync API (see [`crate::background_sync`]).
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::{
+    precache_manifest, service_worker_routes, BackgroundSyncRoute, CacheableRoute,
+    SERVICE_WORKER_JS,
+};
+
+#[cfg(feature = "client")]
+pub use client::register_service_worker;
+
+/// How the service worker answers requests for a cached endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CacheStrategy {
+    /// Serve from the cache immediately and refresh it in the background
+    StaleWhileRevalidate,
+    /// Serve from the cache, only going to the network on a miss
+    CacheFirst,
+    /// Go to the network, falling back to the cache when offline
+    NetworkFirst,
+}
+
+/// A route listed in the service worker manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestRoute {
+    pub path: String,
+    pub strategy: CacheStrategy,
+    /// Whether the service worker fetches this route when it's installed
+    pub precache: bool,
+}
+
+/// A mutation the service worker queues while offline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncRoute {
+    pub path: String,
+    pub method: String,
+}
+
+/// The document served at `/sw-manifest.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrecacheManifest {
+    pub routes: Vec<ManifestRoute>,
+    #[serde(default)]
+    pub background_sync: Vec<SyncRoute>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod server {
+    use super::{CacheStrategy, ManifestRoute, PrecacheManifest, SyncRoute};
+    use axum::http::header;
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::{Json, Router};
+
+    /// The bundled service worker script
+    pub const SERVICE_WORKER_JS: &str = include_str!("sw.js");
+
+    /// A cacheable GET endpoint, submitted by `yew_server_hook` for `sw_cache` endpoints
+    pub struct CacheableRoute {
+        pub path: &'static str,
+        pub strategy: CacheStrategy,
+        pub precache: bool,
+    }
+
+    impl CacheableRoute {
+        pub const fn new(path: &'static str, strategy: CacheStrategy, precache: bool) -> Self {
+            Self {
+                path,
+                strategy,
+                precache,
+            }
+        }
+    }
+
+    inventory::collect!(CacheableRoute);
+
+    /// A mutation endpoint, submitted by `yew_server_hook` for `background_sync` endpoints
+    pub struct BackgroundSyncRoute {
+        pub path: &'static str,
+        pub method: &'static str,
+    }
+
+    impl BackgroundSyncRoute {
+        pub const fn new(path: &'static str, method: &'static str) -> Self {
+            Self { path, method }
+        }
+    }
+
+    inventory::collect!(BackgroundSyncRoute);
+
+    /// Builds the manifest from every registered cacheable and background sync route
+    pub fn precache_manifest() -> PrecacheManifest {
+        let mut routes: Vec<ManifestRoute> = inventory::iter::<CacheableRoute>
+            .into_iter()
+            .map(|route| ManifestRoute {
+                path: route.path.to_string(),
+                strategy: route.strategy,
+                precache: route.precache,
+            })
+            .collect();
+        routes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut background_sync: Vec<SyncRoute> = inventory::iter::<BackgroundSyncRoute>
+            .into_iter()
+            .map(|route| SyncRoute {
+                path: route.path.to_string(),
+                method: route.method.to_string(),
+            })
+            .collect();
+        background_sync.sort_by(|a, b| (&a.path, &a.method).cmp(&(&b.path, &b.method)));
+
+        PrecacheManifest {
+            routes,
+            background_sync,
+        }
+    }
+
+    async fn manifest_handler() -> Json<PrecacheManifest> {
+        Json(precache_manifest())
+    }
+
+    async fn service_worker_handler() -> impl IntoResponse {
+        (
+            [
+                (header::CONTENT_TYPE, "application/javascript"),
+                (header::CACHE_CONTROL, "no-cache"),
+            ],
+            SERVICE_WORKER_JS,
+        )
+    }
+
+    /// Routes serving the manifest (`/sw-manifest.json`) and the service worker (`/sw.js`).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let app = Router::new()
+    ///     .merge(your_generated_routes())
+    ///     .merge(yew_extra::sw::service_worker_routes());
+    /// ```
+    pub fn service_worker_routes<S>() -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        Router::new()
+            .route("/sw-manifest.json", get(manifest_handler))
+            .route("/sw.js", get(service_worker_handler))
+    }
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use wasm_bindgen_futures::JsFuture;
+
+    /// Registers the service worker served at `script_url` (usually `/sw.js`).
+    ///
+    /// Does nothing in browsers without service worker support.
+    pub async fn register_service_worker(script_url: &str) -> Result<(), String> {
+        let window = web_sys::window().ok_or_else(|| "No window available".to_string())?;
+        let navigator = window.navigator();
+
+        let supported = js_sys::Reflect::has(&navigator, &"serviceWorker".into()).unwrap_or(false);
+        if !supported {
+            return Ok(());
+        }
+
+        JsFuture::from(navigator.service_worker().register(script_url))
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Failed to register service worker: {:?}", e))
+    }
+}
@@ -0,0 +1,222 @@
+//! Helpers for unit testing server functions without running a server.
+//!
+//! [`call_handler`] sends a synthetic [`TestRequest`] through the `*_handler_wrapper`
+//! function `yew_server_hook` generates for every endpoint. The wrapper provides the
+//! request parts to [`extract()`](crate::extract), runs validation and the function body,
+//! and maps errors exactly as it does behind the real router. The wrappers aren't
+//! generated in `cfg(test)` builds, so call them from integration tests in `tests/`.
+//!
+//! [`test_router()`] mounts every registered endpoint instead, for tests going through
+//! routing as well, e.g. with `tower::ServiceExt::oneshot` or `axum-test`. Endpoints
+//! declared with `state = AppState` go through their `*_handler_state_wrapper` function or
+//! [`test_router_with_state()`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use yew_extra::test::{call_handler, TestRequest};
+//!
+//! #[tokio::test]
+//! async fn admins_can_list_users() {
+//!     let response = call_handler::<Vec<User>, _, _>(
+//!         get_users_handler_wrapper,
+//!         TestRequest::get("/api/users")
+//!             .query(&GetUsersParams { role: "admin".to_string() })
+//!             .cookie("session_token", "admin-session"),
+//!     )
+//!     .await;
+//!
+//!     assert_eq!(response.status, 200);
+//!     assert_eq!(response.data.unwrap().len(), 2);
+//! }
+//! ```
+
+use axum::body::{to_bytes, Body};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, Request, Response, StatusCode};
+use axum::Router;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::future::Future;
+
+/// A synthetic request passed to [`call_handler`]
+#[derive(Debug, Clone)]
+pub struct TestRequest {
+    method: Method,
+    uri: String,
+    headers: HeaderMap,
+    cookies: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Default for TestRequest {
+    fn default() -> Self {
+        Self::new(Method::GET, "/")
+    }
+}
+
+impl TestRequest {
+    pub fn new(method: Method, uri: impl Into<String>) -> Self {
+        Self {
+            method,
+            uri: uri.into(),
+            headers: HeaderMap::new(),
+            cookies: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    pub fn get(uri: impl Into<String>) -> Self {
+        Self::new(Method::GET, uri)
+    }
+
+    pub fn post(uri: impl Into<String>) -> Self {
+        Self::new(Method::POST, uri)
+    }
+
+    pub fn put(uri: impl Into<String>) -> Self {
+        Self::new(Method::PUT, uri)
+    }
+
+    pub fn patch(uri: impl Into<String>) -> Self {
+        Self::new(Method::PATCH, uri)
+    }
+
+    pub fn delete(uri: impl Into<String>) -> Self {
+        Self::new(Method::DELETE, uri)
+    }
+
+    /// Adds a header, panicking if the name or value is invalid
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        let name = HeaderName::try_from(name)
+            .unwrap_or_else(|e| panic!("Invalid header name {:?}: {}", name, e));
+        let value = HeaderValue::try_from(value)
+            .unwrap_or_else(|e| panic!("Invalid header value {:?}: {}", value, e));
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Adds a cookie, sent in a single `Cookie` header with the others
+    pub fn cookie(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.cookies.push((name.into(), value.into()));
+        self
+    }
+
+    /// Appends `params` to the query string, as the generated client does for GET endpoints
+    pub fn query<T: Serialize>(mut self, params: &T) -> Self {
+        let query = crate::query::to_query_string(params)
+            .unwrap_or_else(|e| panic!("Failed to encode query parameters: {}", e));
+        if !query.is_empty() {
+            let separator = if self.uri.contains('?') { '&' } else { '?' };
+            self.uri = format!("{}{}{}", self.uri, separator, query);
+        }
+        self
+    }
+
+    /// Sets a JSON body, as the generated client sends for mutation endpoints
+    pub fn json<T: Serialize>(self, body: &T) -> Self {
+        let body = serde_json::to_vec(body)
+            .unwrap_or_else(|e| panic!("Failed to serialize request body: {}", e));
+        self.header("content-type", "application/json").body(body)
+    }
+
+    /// Sets a raw body
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Builds the `Request<Body>` handed to the handler
+    pub fn into_request(self) -> Request<Body> {
+        let mut request = Request::builder()
+            .method(self.method)
+            .uri(&self.uri)
+            .body(Body::from(self.body))
+            .unwrap_or_else(|e| panic!("Invalid test request for {}: {}", self.uri, e));
+
+        *request.headers_mut() = self.headers;
+        if !self.cookies.is_empty() {
+            let cookies = self
+                .cookies
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join("; ");
+            let cookies = HeaderValue::try_from(cookies)
+                .unwrap_or_else(|e| panic!("Invalid cookie value: {}", e));
+            request
+                .headers_mut()
+                .insert(axum::http::header::COOKIE, cookies);
+        }
+        request
+    }
+}
+
+/// The response returned by [`call_handler`]
+#[derive(Debug)]
+pub struct TestResponse<T> {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    /// The raw response body
+    pub body: String,
+    /// The decoded success value, or the error body for non-2xx responses
+    pub data: Result<T, String>,
+}
+
+/// Sends `request` through `handler` and decodes the response.
+///
+/// `handler` is usually a generated `*_handler_wrapper` function, but any function
+/// taking a `Request<Body>` works. It runs in a [`request_scope()`](crate::request_scope),
+/// so hand-written handlers can provide the parts to `extract()` like the generated ones.
+pub async fn call_handler<T, H, Fut>(handler: H, request: TestRequest) -> TestResponse<T>
+where
+    T: DeserializeOwned,
+    H: FnOnce(Request<Body>) -> Fut,
+    Fut: Future<Output = Response<Body>>,
+{
+    let response = crate::request_scope(handler(request.into_request())).await;
+    let (parts, body) = response.into_parts();
+
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => String::from_utf8_lossy(&body).into_owned(),
+        Err(e) => format!("Failed to read response body: {}", e),
+    };
+
+    let data = if parts.status.is_success() {
+        serde_json::from_str(&body).map_err(|e| format!("Failed to parse response: {}", e))
+    } else {
+        Err(body.clone())
+    };
+
+    TestResponse {
+        status: parts.status,
+        headers: parts.headers,
+        body,
+        data,
+    }
+}
+
+/// Builds a router with every endpoint registered through [`crate::route_registry`].
+///
+/// The crate under test has to re-export the registry (`pub use yew_extra::route_registry;`)
+/// and be linked into the test, which happens once any of its items is used.
+///
+/// # Example
+///
+/// ```ignore
+/// use tower::ServiceExt;
+/// use yew_extra::test::{test_router, TestRequest};
+///
+/// let response = test_router()
+///     .oneshot(TestRequest::get("/api/users").into_request())
+///     .await
+///     .unwrap();
+/// ```
+pub fn test_router() -> Router {
+    crate::route_registry::routes()
+}
+
+/// Builds a router like [`test_router()`] for endpoints declared with `state = AppState`,
+/// see [`build_router_with_state()`](crate::route_registry::build_router_with_state)
+pub fn test_router_with_state<T: Send + Sync + 'static>(state: T) -> Router {
+    crate::route_registry::build_router_with_state(state)
+}
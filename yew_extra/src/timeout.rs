@@ -0,0 +1,96 @@
+//! Server-side deadlines for server functions.
+//!
+//! The generated wrappers of endpoints declared with `server_timeout = "..."` run their
+//! handler through [`with_timeout`], which answers with `504 Gateway Timeout` and a
+//! [`TimeoutError`] body once the deadline passes.
+//!
+//! The function itself can see the deadline coming with [`remaining_time`] and return a
+//! partial result in time, instead of being cut off:
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/search", method = "GET", server_timeout = "10s")]
+//! pub async fn search(query: String) -> Result<Vec<Hit>, AppError> {
+//!     let mut hits = Vec::new();
+//!     for shard in shards() {
+//!         if remaining_time().await.is_some_and(|left| left < Duration::from_secs(1)) {
+//!             break;
+//!         }
+//!         hits.extend(shard.search(&query).await?);
+//!     }
+//!     Ok(hits)
+//! }
+//! ```
+//!
+//! A client that disconnects doesn't need to be watched for: the server drops the
+//! function's future along with the connection.
+
+use serde::{Deserialize, Serialize};
+
+/// Body of the `504` response sent when a server function runs out of time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeoutError {
+    /// Always `"timeout"`
+    pub error: String,
+    pub message: String,
+    pub timeout_ms: u64,
+}
+
+impl TimeoutError {
+    pub fn new(timeout_ms: u64) -> Self {
+        Self {
+            error: "timeout".to_string(),
+            message: format!("The server function didn't finish within {} ms", timeout_ms),
+            timeout_ms,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::{remaining_time, with_timeout};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod server {
+    use super::TimeoutError;
+    use axum::body::Body;
+    use axum::http::{Response, StatusCode};
+    use axum::response::IntoResponse;
+    use std::future::Future;
+    use std::time::{Duration, Instant};
+
+    /// When the request being handled times out, kept in the extensions of its parts
+    #[derive(Clone, Copy)]
+    struct Deadline(Instant);
+
+    /// Runs `handler`, answering with `504 Gateway Timeout` if it takes longer than `timeout`.
+    ///
+    /// The handler is dropped at the deadline. Work it handed off elsewhere, such as the
+    /// body of a `blocking = true` function, still runs to completion.
+    pub async fn with_timeout<F>(timeout: Duration, handler: F) -> Response<Body>
+    where
+        F: Future<Output = Response<Body>>,
+    {
+        let deadline = Deadline(Instant::now() + timeout);
+        crate::extract::with_request_parts(|parts| parts.extensions.insert(deadline)).await;
+
+        match tokio::time::timeout(timeout, handler).await {
+            Ok(response) => response,
+            Err(_) => {
+                let timeout_ms = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+                (
+                    StatusCode::GATEWAY_TIMEOUT,
+                    axum::Json(TimeoutError::new(timeout_ms)),
+                )
+                    .into_response()
+            }
+        }
+    }
+
+    /// Time left before the request being handled times out, `None` if its endpoint has no
+    /// `server_timeout` or outside a request. Zero once the deadline has passed.
+    pub async fn remaining_time() -> Option<Duration> {
+        let Deadline(deadline) =
+            crate::extract::with_request_parts(|parts| parts.extensions.get::<Deadline>().copied())
+                .await??;
+        Some(deadline.saturating_duration_since(Instant::now()))
+    }
+}
@@ -0,0 +1,160 @@
+//! Transforms of the request and response bodies of an endpoint, on both sides.
+//!
+//! Endpoints declared with `transform = "MyTransform"` pass their bodies through a
+//! [`PayloadTransform`]: the client encodes the JSON body it sends and the server decodes
+//! it before parsing, the server encodes the JSON body of successful responses and the
+//! client decodes it before parsing. Field-level encryption, compression or envelope
+//! signing plug in without changing the generated request pipeline:
+//!
+//! ```
+//! use yew_extra::transform::PayloadTransform;
+//!
+//! /// Wraps bodies in an envelope carrying their length
+//! pub struct Envelope;
+//!
+//! impl PayloadTransform for Envelope {
+//!     fn encode(body: Vec<u8>) -> Result<Vec<u8>, String> {
+//!         let mut envelope = format!("{}:", body.len()).into_bytes();
+//!         envelope.extend(body);
+//!         Ok(envelope)
+//!     }
+//!
+//!     fn decode(body: Vec<u8>) -> Result<Vec<u8>, String> {
+//!         let text = String::from_utf8(body).map_err(|e| e.to_string())?;
+//!         let (length, body) = text.split_once(':').ok_or("Missing envelope")?;
+//!         if length.parse::<usize>() != Ok(body.len()) {
+//!             return Err("Truncated body".to_string());
+//!         }
+//!         Ok(body.as_bytes().to_vec())
+//!     }
+//! }
+//!
+//! let sent = Envelope::encode_request(br#"{"id":1}"#.to_vec()).unwrap();
+//! assert_eq!(sent, br#"8:{"id":1}"#);
+//! assert_eq!(Envelope::decode_request(sent).unwrap(), br#"{"id":1}"#);
+//! ```
+//!
+//! ```ignore
+//! #[yewserverhook(path = "/api/notes", method = "POST", transform = "Envelope")]
+//! pub async fn save_note(note: Note) -> Result<Note, AppError> { ... }
+//! ```
+//!
+//! Error responses aren't transformed, so every client can read their message. Client
+//! builds need the `client` feature to send transformed bodies.
+
+/// Pre-send and post-receive steps for the bodies of an endpoint.
+///
+/// `encode` and `decode` apply to both directions, override the directional methods when
+/// requests and responses differ, e.g. with keys of their own.
+pub trait PayloadTransform {
+    /// Turns a JSON body into the bytes sent
+    fn encode(body: Vec<u8>) -> Result<Vec<u8>, String> {
+        Ok(body)
+    }
+
+    /// Turns the bytes received back into the JSON body
+    fn decode(body: Vec<u8>) -> Result<Vec<u8>, String> {
+        Ok(body)
+    }
+
+    /// The client's request body, before it's sent
+    fn encode_request(body: Vec<u8>) -> Result<Vec<u8>, String> {
+        Self::encode(body)
+    }
+
+    /// The request body the server received, before its JSON is parsed
+    fn decode_request(body: Vec<u8>) -> Result<Vec<u8>, String> {
+        Self::decode(body)
+    }
+
+    /// The server's successful response body, before it's sent
+    fn encode_response(body: Vec<u8>) -> Result<Vec<u8>, String> {
+        Self::encode(body)
+    }
+
+    /// The successful response body the client received, before its JSON is parsed
+    fn decode_response(body: Vec<u8>) -> Result<Vec<u8>, String> {
+        Self::decode(body)
+    }
+}
+
+/// The JSON text of a response body the client received, for the generated clients
+pub fn response_text<T: PayloadTransform>(body: Vec<u8>) -> Result<String, String> {
+    let body = T::decode_response(body).map_err(|e| format!("Failed to decode response: {}", e))?;
+    String::from_utf8(body).map_err(|e| format!("Failed to decode response: {}", e))
+}
+
+/// The body of a client request, encoded for sending, for the generated clients
+#[cfg(feature = "client")]
+pub fn request_body<T: PayloadTransform>(body: String) -> Result<wasm_bindgen::JsValue, String> {
+    let body = T::encode_request(body.into_bytes())
+        .map_err(|e| format!("Failed to encode request: {}", e))?;
+    Ok(js_sys::Uint8Array::from(body.as_slice()).into())
+}
+
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+pub use server::{decode_request, encode_response};
+
+#[cfg(any(not(target_arch = "wasm32"), feature = "workers"))]
+mod server {
+    use super::PayloadTransform;
+    use axum::body::Body;
+    use axum::http::header::CONTENT_LENGTH;
+    use axum::http::{Request, Response, StatusCode};
+
+    /// Bodies read without a limit of their own, the limit of axum's `Json` extractor
+    const DEFAULT_LIMIT: usize = 2 * 1024 * 1024;
+
+    /// Decodes the body of `request` of at most `limit` bytes (2 MiB by default),
+    /// answering `400 Bad Request` if that fails and `413 Payload Too Large` for bigger
+    /// bodies. Requests without a body are returned as they are.
+    pub async fn decode_request<T: PayloadTransform>(
+        request: Request<Body>,
+        limit: Option<usize>,
+    ) -> Result<Request<Body>, Response<Body>> {
+        let (mut parts, body) = request.into_parts();
+        let limit = limit.unwrap_or(DEFAULT_LIMIT);
+        let body = axum::body::to_bytes(body, limit).await.map_err(|e| {
+            Response::builder()
+                .status(StatusCode::PAYLOAD_TOO_LARGE)
+                .body(Body::from(format!("Invalid request body: {}", e)))
+                .unwrap_or_else(|_| Response::new(Body::empty()))
+        })?;
+        if body.is_empty() {
+            return Ok(Request::from_parts(parts, Body::empty()));
+        }
+        match T::decode_request(body.to_vec()) {
+            Ok(body) => {
+                parts.headers.remove(CONTENT_LENGTH);
+                Ok(Request::from_parts(parts, Body::from(body)))
+            }
+            Err(e) => Err(Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(format!("Invalid request: {}", e)))
+                .unwrap_or_else(|_| Response::new(Body::empty()))),
+        }
+    }
+
+    /// Encodes the body of a successful `response`, answering
+    /// `500 Internal Server Error` if that fails. Other responses are returned as they are.
+    pub async fn encode_response<T: PayloadTransform>(response: Response<Body>) -> Response<Body> {
+        if !response.status().is_success() {
+            return response;
+        }
+        let (mut parts, body) = response.into_parts();
+        let encoded = match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(body) => T::encode_response(body.to_vec()),
+            Err(e) => Err(e.to_string()),
+        };
+        match encoded {
+            Ok(body) => {
+                parts.headers.remove(CONTENT_LENGTH);
+                Response::from_parts(parts, Body::from(body))
+            }
+            Err(e) => Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(format!("Failed to encode response: {}", e)))
+                .unwrap_or_else(|_| Response::new(Body::empty())),
+        }
+    }
+}
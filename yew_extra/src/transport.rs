@@ -0,0 +1,341 @@
+//! Pluggable transport for the clients generated by `yew_server_hook`.
+//!
+//! With the `transport` (or `tauri`) feature of `yew_server_hook`, the generated hooks and
+//! client functions send their requests through [`send`] (`client` feature). It hands them
+//! to the [`HttpBackend`] installed with [`set_backend`] or an `<ApiProvider>`, e.g. a
+//! fetch with middleware or a test double. Without one, requests go to the Tauri command
+//! [`IPC_COMMAND`] as an [`IpcRequest`] inside a Tauri webview, and over HTTP with
+//! gloo-net everywhere else.
+//!
+//! The desktop backend answers the command by running the request through the app's
+//! router with [`dispatch`], so the same server functions run without an embedded HTTP
+//! server:
+//!
+//! ```ignore
+//! #[tauri::command]
+//! async fn yew_server_fn(request: IpcRequest) -> IpcResponse {
+//!     yew_extra::transport::dispatch(yew_extra::route_registry::routes(), request).await
+//! }
+//!
+//! tauri::Builder::default()
+//!     .invoke_handler(tauri::generate_handler![yew_server_fn])
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the Tauri command the client invokes
+pub const IPC_COMMAND: &str = "yew_server_fn";
+
+/// A request sent over Tauri IPC
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpcRequest {
+    pub method: String,
+    /// Path and query string, e.g. `/api/users?role=admin`
+    pub uri: String,
+    pub headers: Vec<(String, String)>,
+    /// Text body; binary bodies, e.g. upload chunks, aren't sent over IPC
+    pub body: Option<String>,
+}
+
+/// The answer to an [`IpcRequest`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpcResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::dispatch;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod server {
+    use super::{IpcRequest, IpcResponse};
+    use crate::server::{build_request, handle, ServerRequest, ServerResponse};
+    use axum::body::{Body, Bytes};
+    use axum::http::Request;
+
+    impl ServerRequest for IpcRequest {
+        fn into_request(self) -> Result<Request<Body>, String> {
+            build_request(
+                &self.method,
+                &self.uri,
+                self.headers,
+                self.body.unwrap_or_default(),
+            )
+        }
+    }
+
+    impl ServerResponse for IpcResponse {
+        fn from_parts(status: u16, headers: Vec<(String, Vec<u8>)>, body: Bytes) -> Self {
+            IpcResponse {
+                status,
+                headers: headers
+                    .into_iter()
+                    .filter_map(|(name, value)| Some((name, String::from_utf8(value).ok()?)))
+                    .collect(),
+                body: String::from_utf8_lossy(&body).into_owned(),
+            }
+        }
+    }
+
+    /// Runs `request` through `router` in-process and returns its response
+    pub async fn dispatch(router: axum::Router, request: IpcRequest) -> IpcResponse {
+        handle(router, request).await
+    }
+}
+
+#[cfg(feature = "client")]
+pub use client::{
+    clear_backend, is_tauri, send, set_backend, ApiProvider, ApiProviderProps, Backend,
+    DefaultBackend, FetchBackend, HttpBackend, SendFuture,
+};
+#[cfg(feature = "client")]
+pub(crate) use client::{ipc_error, ipc_request, to_response};
+
+#[cfg(feature = "client")]
+mod client {
+    use super::{IpcRequest, IpcResponse, IPC_COMMAND};
+    use crate::retry::IntoRetryRequest;
+    use gloo_net::http::{Request, Response};
+    use std::cell::RefCell;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::rc::Rc;
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use yew::prelude::*;
+
+    const TAURI_GLOBAL: &str = "__TAURI_INTERNALS__";
+
+    /// The response of an [`HttpBackend`]
+    pub type SendFuture = Pin<Box<dyn Future<Output = Result<Response, gloo_net::Error>>>>;
+
+    /// Sends the requests built by the generated clients.
+    ///
+    /// Closures taking a request and returning a [`SendFuture`] are backends too, so a
+    /// middleware can wrap another backend:
+    ///
+    /// ```ignore
+    /// set_backend(|request: Request| -> SendFuture {
+    ///     log::debug!("{} {}", request.method(), request.url());
+    ///     DefaultBackend.send(request)
+    /// });
+    /// ```
+    pub trait HttpBackend {
+        fn send(&self, request: Request) -> SendFuture;
+    }
+
+    impl<F> HttpBackend for F
+    where
+        F: Fn(Request) -> SendFuture,
+    {
+        fn send(&self, request: Request) -> SendFuture {
+            self(request)
+        }
+    }
+
+    /// Sends requests over HTTP with gloo-net
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct FetchBackend;
+
+    impl HttpBackend for FetchBackend {
+        fn send(&self, request: Request) -> SendFuture {
+            Box::pin(request.send())
+        }
+    }
+
+    /// Sends requests over Tauri IPC inside Tauri, over HTTP otherwise
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct DefaultBackend;
+
+    impl HttpBackend for DefaultBackend {
+        fn send(&self, request: Request) -> SendFuture {
+            match tauri_internals() {
+                Some(internals) => {
+                    Box::pin(async move { invoke(&internals, request.into()).await })
+                }
+                None => FetchBackend.send(request),
+            }
+        }
+    }
+
+    thread_local! {
+        static BACKEND: RefCell<Option<Rc<dyn HttpBackend>>> = const { RefCell::new(None) };
+    }
+
+    /// Sends the requests of the generated clients through `backend` from now on
+    pub fn set_backend(backend: impl HttpBackend + 'static) {
+        BACKEND.with(|current| *current.borrow_mut() = Some(Rc::new(backend)));
+    }
+
+    /// Goes back to the [`DefaultBackend`]
+    pub fn clear_backend() {
+        BACKEND.with(|current| current.borrow_mut().take());
+    }
+
+    pub(crate) fn ipc_error(context: &str, e: impl std::fmt::Debug) -> gloo_net::Error {
+        gloo_net::Error::GlooError(format!("{}: {:?}", context, e))
+    }
+
+    fn tauri_internals() -> Option<JsValue> {
+        let window = web_sys::window()?;
+        js_sys::Reflect::get(&window, &JsValue::from_str(TAURI_GLOBAL))
+            .ok()
+            .filter(|internals| internals.is_object())
+    }
+
+    /// Whether the app runs inside a Tauri webview
+    pub fn is_tauri() -> bool {
+        tauri_internals().is_some()
+    }
+
+    /// Sends `request` through the installed backend, or the [`DefaultBackend`]
+    pub async fn send(request: impl IntoRetryRequest) -> Result<Response, gloo_net::Error> {
+        let request = request.into_request()?;
+        // Cloned out so the backend can replace itself
+        let backend = BACKEND.with(|current| current.borrow().clone());
+        match backend {
+            Some(backend) => backend.send(request).await,
+            None => DefaultBackend.send(request).await,
+        }
+    }
+
+    /// An [`HttpBackend`] to pass to an [`ApiProvider`], compared by identity
+    #[derive(Clone)]
+    pub struct Backend(Rc<dyn HttpBackend>);
+
+    impl Backend {
+        pub fn new(backend: impl HttpBackend + 'static) -> Self {
+            Self(Rc::new(backend))
+        }
+    }
+
+    impl PartialEq for Backend {
+        fn eq(&self, other: &Self) -> bool {
+            Rc::ptr_eq(&self.0, &other.0)
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    pub struct ApiProviderProps {
+        pub backend: Backend,
+        #[prop_or_default]
+        pub children: Html,
+    }
+
+    /// Installs `backend` for the generated clients while it is mounted:
+    ///
+    /// ```ignore
+    /// html! {
+    ///     <ApiProvider backend={Backend::new(FetchBackend)}>
+    ///         <App />
+    ///     </ApiProvider>
+    /// }
+    /// ```
+    ///
+    /// Client functions aren't components, so the backend is installed globally rather
+    /// than for the subtree.
+    #[function_component(ApiProvider)]
+    pub fn api_provider(props: &ApiProviderProps) -> Html {
+        // Installed while rendering, so the effects of the children already use it
+        use_memo(props.backend.clone(), |backend| {
+            let backend = backend.0.clone();
+            BACKEND.with(|current| *current.borrow_mut() = Some(backend));
+        });
+        use_effect_with((), |_| clear_backend);
+
+        props.children.clone()
+    }
+
+    async fn invoke(
+        internals: &JsValue,
+        request: web_sys::Request,
+    ) -> Result<Response, gloo_net::Error> {
+        let ipc_request = ipc_request(request).await?;
+        let args = js_sys::Object::new();
+        let ipc_request = serde_wasm_bindgen::to_value(&ipc_request)
+            .map_err(|e| ipc_error("Failed to encode request", e))?;
+        js_sys::Reflect::set(&args, &JsValue::from_str("request"), &ipc_request)
+            .map_err(|e| ipc_error("Failed to encode request", e))?;
+
+        let invoke: js_sys::Function =
+            js_sys::Reflect::get(internals, &JsValue::from_str("invoke"))
+                .map_err(|e| ipc_error("Tauri invoke unavailable", e))?
+                .dyn_into()
+                .map_err(|e| ipc_error("Tauri invoke unavailable", e))?;
+        let promise: js_sys::Promise = invoke
+            .call2(internals, &JsValue::from_str(IPC_COMMAND), &args)
+            .map_err(|e| ipc_error("Tauri invoke failed", e))?
+            .dyn_into()
+            .map_err(|e| ipc_error("Tauri invoke failed", e))?;
+        let answer = JsFuture::from(promise)
+            .await
+            .map_err(|e| ipc_error("Tauri invoke failed", e))?;
+        let answer: IpcResponse = serde_wasm_bindgen::from_value(answer)
+            .map_err(|e| ipc_error("Invalid IPC response", e))?;
+
+        to_response(&answer)
+            .map(Response::from)
+            .map_err(|e| ipc_error("Failed to build response", e))
+    }
+
+    /// Reads `request` into an [`IpcRequest`], keeping the path and query of its URL
+    pub(crate) async fn ipc_request(
+        request: web_sys::Request,
+    ) -> Result<IpcRequest, gloo_net::Error> {
+        // The router only sees the path, whatever origin the webview is served from
+        let url = web_sys::Url::new(&request.url()).map_err(|e| ipc_error("Invalid URL", e))?;
+        let mut headers = Vec::new();
+        if let Some(entries) = js_sys::try_iter(&request.headers())
+            .map_err(|e| ipc_error("Failed to read headers", e))?
+        {
+            for entry in entries {
+                let entry: js_sys::Array = entry
+                    .map_err(|e| ipc_error("Failed to read headers", e))?
+                    .unchecked_into();
+                headers.push((
+                    entry.get(0).as_string().unwrap_or_default(),
+                    entry.get(1).as_string().unwrap_or_default(),
+                ));
+            }
+        }
+        let body = if !matches!(request.method().as_str(), "GET" | "HEAD") {
+            let text = request
+                .text()
+                .map_err(|e| ipc_error("Failed to read body", e))?;
+            JsFuture::from(text)
+                .await
+                .map_err(|e| ipc_error("Failed to read body", e))?
+                .as_string()
+        } else {
+            None
+        };
+
+        Ok(IpcRequest {
+            method: request.method(),
+            uri: format!("{}{}", url.pathname(), url.search()),
+            headers,
+            body,
+        })
+    }
+
+    /// Builds the response described by an [`IpcResponse`]
+    pub(crate) fn to_response(answer: &IpcResponse) -> Result<web_sys::Response, JsValue> {
+        let headers = web_sys::Headers::new()?;
+        for (name, value) in &answer.headers {
+            headers.append(name, value)?;
+        }
+
+        let init = web_sys::ResponseInit::new();
+        init.set_status(answer.status);
+        init.set_headers(&headers);
+
+        // Responses with these statuses can't have a body
+        let body = match answer.status {
+            101 | 204 | 205 | 304 => None,
+            _ => Some(answer.body.as_str()),
+        };
+        web_sys::Response::new_with_opt_str_and_init(body, &init)
+    }
+}
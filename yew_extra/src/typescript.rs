@@ -0,0 +1,350 @@
+//! Standalone TypeScript client for the endpoints generated by `yew_server_hook`.
+//!
+//! Endpoints declared with `typescript = true` register the schemas of their parameters and
+//! response type. [`generate_client`] turns them into a single module with a type for every
+//! Rust type involved and one typed `fetch` wrapper per endpoint, so a frontend that isn't
+//! written in Rust, a browser extension or an E2E suite calls the API the same way the Yew
+//! clients do:
+//!
+//! ```ignore
+//! // e.g. in a `cargo run --bin ts-client` of the server crate
+//! yew_extra::typescript::write_client("frontend/src/api.ts")?;
+//! ```
+//!
+//! ```ts
+//! import { config, listUsers } from "./api";
+//!
+//! config.baseUrl = "https://example.com";
+//! const users = await listUsers({ role: "admin" });
+//! ```
+//!
+//! Failed requests reject with an `ApiError` carrying the status and the server's message.
+
+use crate::schema::JsonSchema;
+use schemars::{Schema, SchemaGenerator};
+use serde_json::{Map, Value};
+use std::io;
+use std::path::Path;
+
+/// Produces the schema of a type, registering the types it refers to with the generator
+pub type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+/// An endpoint, submitted by `yew_server_hook` for `typescript` endpoints
+pub struct TsEndpoint {
+    /// Name of the server function, `list_users` becomes `listUsers`
+    pub name: &'static str,
+    pub path: &'static str,
+    pub method: &'static str,
+    /// Schema of the parameter struct, for endpoints taking parameters
+    pub params: Option<SchemaFn>,
+    /// Schema of the success type
+    pub returns: SchemaFn,
+}
+
+impl TsEndpoint {
+    pub const fn new(
+        name: &'static str,
+        path: &'static str,
+        method: &'static str,
+        params: Option<SchemaFn>,
+        returns: SchemaFn,
+    ) -> Self {
+        Self {
+            name,
+            path,
+            method,
+            params,
+            returns,
+        }
+    }
+}
+
+inventory::collect!(TsEndpoint);
+
+/// The [`SchemaFn`] of `T`
+pub fn schema_of<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    generator.subschema_for::<T>()
+}
+
+/// Returns every registered endpoint, sorted by path and method
+pub fn registered_endpoints() -> Vec<&'static TsEndpoint> {
+    let mut endpoints: Vec<&'static TsEndpoint> =
+        inventory::iter::<TsEndpoint>.into_iter().collect();
+    endpoints.sort_by(|a, b| (a.path, a.method).cmp(&(b.path, b.method)));
+    endpoints
+}
+
+/// Generates the TypeScript client for every registered endpoint
+pub fn generate_client() -> String {
+    client_for(&registered_endpoints())
+}
+
+/// Writes the client of [`generate_client`] to `path`
+pub fn write_client(path: impl AsRef<Path>) -> io::Result<()> {
+    std::fs::write(path, generate_client())
+}
+
+/// Generates the TypeScript client for `endpoints`
+pub fn client_for(endpoints: &[&TsEndpoint]) -> String {
+    let mut generator = SchemaGenerator::default();
+    let functions: Vec<String> = endpoints
+        .iter()
+        .map(|endpoint| {
+            let params = endpoint
+                .params
+                .map(|params| ts_type(params(&mut generator).as_value()));
+            let returns = ts_type((endpoint.returns)(&mut generator).as_value());
+            client_function(endpoint, params.as_deref(), &returns)
+        })
+        .collect();
+
+    let mut out = String::from(PRELUDE);
+    let mut definitions: Vec<(&String, &Value)> = generator.definitions().iter().collect();
+    definitions.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, schema) in definitions {
+        out.push_str(&format!(
+            "\nexport type {} = {};\n",
+            type_name(name),
+            ts_type(schema)
+        ));
+    }
+    for function in functions {
+        out.push('\n');
+        out.push_str(&function);
+    }
+    out
+}
+
+const PRELUDE: &str = r#"// Generated by yew_extra::typescript. Do not edit.
+
+export class ApiError extends Error {
+  readonly status: number;
+  readonly body: unknown;
+
+  constructor(status: number, message: string, body: unknown) {
+    super(message);
+    this.name = "ApiError";
+    this.status = status;
+    this.body = body;
+  }
+}
+
+/** Prepended to every path, and merged into every request */
+export const config: { baseUrl: string; init: RequestInit } = { baseUrl: "", init: {} };
+
+async function request<T>(
+  method: string,
+  path: string,
+  params: object | undefined,
+  init: RequestInit | undefined,
+): Promise<T> {
+  let url = config.baseUrl + path;
+  const options: RequestInit = { ...config.init, ...init, method };
+  if (params !== undefined && method === "GET") {
+    const query = new URLSearchParams();
+    for (const [key, value] of Object.entries(params)) {
+      if (value !== undefined && value !== null) query.append(key, String(value));
+    }
+    const search = query.toString();
+    if (search) url += "?" + search;
+  } else if (params !== undefined) {
+    const headers = new Headers(options.headers);
+    headers.set("Content-Type", "application/json");
+    options.headers = headers;
+    options.body = JSON.stringify(params);
+  }
+
+  const response = await fetch(url, options);
+  const text = await response.text();
+  let body: unknown = text;
+  try {
+    body = text ? JSON.parse(text) : null;
+  } catch {
+    // Plain text error messages
+  }
+  if (!response.ok) {
+    const fields = (typeof body === "object" && body !== null ? body : {}) as {
+      error?: unknown;
+      message?: unknown;
+    };
+    const message = fields.error ?? fields.message ?? (typeof body === "string" ? body : "");
+    throw new ApiError(
+      response.status,
+      String(message || `Request failed with status ${response.status}`),
+      body,
+    );
+  }
+  return body as T;
+}
+"#;
+
+fn client_function(endpoint: &TsEndpoint, params: Option<&str>, returns: &str) -> String {
+    let path = Value::String(endpoint.path.to_string());
+    let method = Value::String(endpoint.method.to_string());
+    let (params_arg, params_value) = match params {
+        Some(params) => (format!("params: {}, ", params), "params"),
+        None => (String::new(), "undefined"),
+    };
+    format!(
+        "/** {method_name} {path_name} */\nexport function {name}({params_arg}init?: RequestInit): Promise<{returns}> {{\n  return request<{returns}>({method}, {path}, {params_value}, init);\n}}\n",
+        method_name = endpoint.method,
+        path_name = endpoint.path,
+        name = camel_case(endpoint.name),
+    )
+}
+
+/// The TypeScript type accepting the JSON values `schema` describes
+fn ts_type(schema: &Value) -> String {
+    let schema = match schema {
+        Value::Object(schema) => schema,
+        Value::Bool(false) => return "never".to_string(),
+        _ => return "unknown".to_string(),
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return type_name(reference.rsplit('/').next().unwrap_or(reference));
+    }
+    // JSON literals are TypeScript literal types
+    if let Some(value) = schema.get("const") {
+        return value.to_string();
+    }
+    if let Some(Value::Array(values)) = schema.get("enum") {
+        return union(values.iter().map(Value::to_string));
+    }
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(variants)) = schema.get(keyword) {
+            return union(variants.iter().map(ts_type));
+        }
+    }
+    if let Some(Value::Array(parts)) = schema.get("allOf") {
+        return parts
+            .iter()
+            .map(|part| parenthesize(ts_type(part)))
+            .collect::<Vec<_>>()
+            .join(" & ");
+    }
+
+    match schema.get("type") {
+        Some(Value::String(ty)) => type_for(ty, schema),
+        Some(Value::Array(types)) => union(
+            types
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|ty| type_for(ty, schema)),
+        ),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn type_for(ty: &str, schema: &Map<String, Value>) -> String {
+    match ty {
+        "string" => "string".to_string(),
+        "integer" | "number" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => {
+            if let Some(Value::Array(items)) = schema.get("prefixItems") {
+                let items: Vec<String> = items.iter().map(ts_type).collect();
+                format!("[{}]", items.join(", "))
+            } else {
+                let item = schema.get("items").map(ts_type);
+                format!(
+                    "{}[]",
+                    parenthesize(item.unwrap_or_else(|| "unknown".to_string()))
+                )
+            }
+        }
+        "object" => match schema.get("properties") {
+            Some(Value::Object(properties)) => {
+                let required: Vec<&str> = match schema.get("required") {
+                    Some(Value::Array(required)) => {
+                        required.iter().filter_map(Value::as_str).collect()
+                    }
+                    _ => Vec::new(),
+                };
+                let fields: Vec<String> = properties
+                    .iter()
+                    .map(|(name, property)| {
+                        let optional = if required.contains(&name.as_str()) {
+                            ""
+                        } else {
+                            "?"
+                        };
+                        format!("{}{}: {}", property_name(name), optional, ts_type(property))
+                    })
+                    .collect();
+                if fields.is_empty() {
+                    "{}".to_string()
+                } else {
+                    format!("{{ {} }}", fields.join("; "))
+                }
+            }
+            _ => match schema.get("additionalProperties") {
+                Some(values @ Value::Object(_)) => format!("Record<string, {}>", ts_type(values)),
+                _ => "Record<string, unknown>".to_string(),
+            },
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+fn union(types: impl Iterator<Item = String>) -> String {
+    let mut variants: Vec<String> = Vec::new();
+    for ty in types {
+        if !variants.contains(&ty) {
+            variants.push(ty);
+        }
+    }
+    match variants.len() {
+        0 => "never".to_string(),
+        _ => variants.join(" | "),
+    }
+}
+
+/// Wraps unions and intersections so they can be used as an array item or intersection part
+fn parenthesize(ty: String) -> String {
+    if ty.contains(" | ") || ty.contains(" & ") {
+        format!("({})", ty)
+    } else {
+        ty
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn property_name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        Value::String(name.to_string()).to_string()
+    }
+}
+
+/// Schema names of generic types contain characters like `<`, e.g. `Page_for_User`
+pub(crate) fn type_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+pub(crate) fn camel_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = !out.is_empty();
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,219 @@
+//! Client side of the chunked upload protocol.
+//!
+//! Splits a browser `File` into chunks, retries failed chunks and remembers the upload
+//! ID in `localStorage` so an upload interrupted by a reload picks up where it left off.
+
+use super::{CreateUpload, UploadStatus};
+use crate::browser::sleep;
+use gloo_net::http::Request;
+
+pub use web_sys::File;
+
+/// Tuning for [`upload_file_with_options`]
+#[derive(Debug, Clone)]
+pub struct UploadOptions {
+    /// Size of each chunk in bytes
+    pub chunk_size: u64,
+    /// How many times a failed chunk is retried before giving up
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled after every failed attempt
+    pub retry_delay_ms: i32,
+}
+
+impl Default for UploadOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: 1024 * 1024,
+            max_retries: 3,
+            retry_delay_ms: 500,
+        }
+    }
+}
+
+/// Uploads a file to a `upload = true` endpoint using the default options.
+///
+/// Returns the upload ID to pass to the completion request.
+pub async fn upload_file(path: &str, file: &File) -> Result<String, String> {
+    upload_file_with_options(path, file, &UploadOptions::default()).await
+}
+
+/// Uploads a file to a `upload = true` endpoint.
+///
+/// Returns the upload ID to pass to the completion request.
+pub async fn upload_file_with_options(
+    path: &str,
+    file: &File,
+    options: &UploadOptions,
+) -> Result<String, String> {
+    let size = file.size() as u64;
+    let storage_key = format!(
+        "yew_extra_upload:{}:{}:{}:{}",
+        path,
+        file.name(),
+        size,
+        file.last_modified()
+    );
+
+    // Resume a previous upload of the same file if the server still knows about it
+    let mut status = match load_upload_id(&storage_key) {
+        Some(upload_id) => fetch_offset(path, &upload_id).await.ok(),
+        None => None,
+    };
+
+    if status.is_none() {
+        let created = create_upload(path, file, size).await?;
+        save_upload_id(&storage_key, &created.upload_id);
+        status = Some(created);
+    }
+
+    let mut status = status.expect("upload status is set above");
+
+    while status.offset < status.size {
+        let end = (status.offset + options.chunk_size).min(status.size);
+        status = append_with_retries(path, file, &status, end, options).await?;
+    }
+
+    remove_upload_id(&storage_key);
+    Ok(status.upload_id)
+}
+
+async fn append_with_retries(
+    path: &str,
+    file: &File,
+    status: &UploadStatus,
+    end: u64,
+    options: &UploadOptions,
+) -> Result<UploadStatus, String> {
+    let mut attempt = 0;
+    let mut delay = options.retry_delay_ms;
+
+    loop {
+        match append_chunk(path, file, status, end).await {
+            Ok(next) => return Ok(next),
+            Err(ChunkError::Conflict) => {
+                // The server has a different offset (e.g. an earlier attempt did arrive)
+                return fetch_offset(path, &status.upload_id).await;
+            }
+            Err(ChunkError::Failed(message)) => {
+                attempt += 1;
+                if attempt > options.max_retries {
+                    return Err(message);
+                }
+                sleep(delay).await;
+                delay = delay.saturating_mul(2);
+            }
+        }
+    }
+}
+
+enum ChunkError {
+    Conflict,
+    Failed(String),
+}
+
+async fn append_chunk(
+    path: &str,
+    file: &File,
+    status: &UploadStatus,
+    end: u64,
+) -> Result<UploadStatus, ChunkError> {
+    let chunk = file
+        .slice_with_f64_and_f64(status.offset as f64, end as f64)
+        .map_err(|_| ChunkError::Failed("Failed to read file chunk".to_string()))?;
+
+    let url = format!(
+        "{}/append?upload_id={}&offset={}",
+        path, status.upload_id, status.offset
+    );
+    let request = Request::patch(&url)
+        .header("Content-Type", "application/octet-stream")
+        .body(chunk)
+        .map_err(|e| ChunkError::Failed(format!("Failed to create request: {}", e)))?;
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| ChunkError::Failed(format!("Failed to upload chunk: {}", e)))?;
+
+    match response.status() {
+        409 => Err(ChunkError::Conflict),
+        _ if response.ok() => response
+            .json::<UploadStatus>()
+            .await
+            .map_err(|e| ChunkError::Failed(format!("Failed to parse response: {}", e))),
+        status => Err(ChunkError::Failed(format!(
+            "Chunk upload failed with status {}",
+            status
+        ))),
+    }
+}
+
+async fn create_upload(path: &str, file: &File, size: u64) -> Result<UploadStatus, String> {
+    let content_type = file.type_();
+    let create = CreateUpload {
+        file_name: file.name(),
+        size,
+        content_type: (!content_type.is_empty()).then_some(content_type),
+    };
+    let body =
+        serde_json::to_string(&create).map_err(|e| format!("Failed to serialize upload: {}", e))?;
+
+    let response = Request::post(&format!("{}/create", path))
+        .header("Content-Type", "application/json")
+        .body(body)
+        .map_err(|e| format!("Failed to create request: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to start upload: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Starting upload failed with status {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json::<UploadStatus>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+async fn fetch_offset(path: &str, upload_id: &str) -> Result<UploadStatus, String> {
+    let response = Request::get(&format!("{}/offset?upload_id={}", path, upload_id))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch upload offset: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Fetching upload offset failed with status {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json::<UploadStatus>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+fn load_upload_id(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok()?
+}
+
+fn save_upload_id(key: &str, upload_id: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(key, upload_id);
+    }
+}
+
+fn remove_upload_id(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
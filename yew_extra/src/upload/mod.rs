@@ -0,0 +1,50 @@
+//! Resumable chunked uploads for server functions declared with `upload = true`.
+//!
+//! The protocol is offset based (similar to tus):
+//!
+//! 1. `POST {path}/create` with a [`CreateUpload`] body starts an upload session
+//! 2. `GET {path}/offset?upload_id=..` reports how many bytes the server has received
+//! 3. `PATCH {path}/append?upload_id=..&offset=..` appends a chunk of raw bytes
+//! 4. `POST {path}` with the function parameters and the `upload_id` completes the upload
+//!    and runs the server function with the received [`UploadedFile`]
+//!
+//! `yew_server_hook` registers all four routes. On the client, the generated function
+//! drives the protocol through `upload_file` (with the `client` feature enabled).
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod server;
+
+#[cfg(feature = "client")]
+mod client;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use server::{
+    handle_append, handle_create, handle_offset, set_upload_dir, set_upload_limits, take_upload,
+    UploadLimits, UploadedFile, MAX_CHUNK_SIZE,
+};
+
+#[cfg(feature = "client")]
+pub use client::{upload_file, upload_file_with_options, File, UploadOptions};
+
+/// Body of the request that starts an upload session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateUpload {
+    /// Original name of the file
+    pub file_name: String,
+    /// Total size of the file in bytes
+    pub size: u64,
+    /// MIME type reported by the browser, if any
+    pub content_type: Option<String>,
+}
+
+/// Progress of an upload session, returned by the create, offset and append routes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadStatus {
+    pub upload_id: String,
+    /// Number of bytes received so far
+    pub offset: u64,
+    /// Total size of the file in bytes
+    pub size: u64,
+}
@@ -0,0 +1,420 @@
+//! Server side of the chunked upload protocol.
+//!
+//! Upload sessions are kept in memory and their bytes are written to a temporary
+//! directory, so a client can resume an interrupted upload for as long as the
+//! server process is running.
+
+use super::{CreateUpload, UploadStatus};
+use axum::body::{to_bytes, Body};
+use axum::extract::Query;
+use axum::http::{Request, Response, StatusCode};
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Largest chunk accepted by a single append request
+pub const MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Sessions that haven't received data for this long are discarded
+const SESSION_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Limits on the upload sessions the server accepts, so clients can't fill its disk by
+/// declaring huge files or opening sessions without end. Requests beyond them are
+/// answered with `413 Payload Too Large`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadLimits {
+    /// Largest file size a session can declare, 1 GiB by default
+    pub max_file_size: u64,
+    /// Most sessions open at once, 1000 by default
+    pub max_sessions: usize,
+    /// Most bytes the open sessions can declare together, 10 GiB by default
+    pub max_total_size: u64,
+}
+
+impl Default for UploadLimits {
+    fn default() -> Self {
+        Self {
+            max_file_size: 1024 * 1024 * 1024,
+            max_sessions: 1000,
+            max_total_size: 10 * 1024 * 1024 * 1024,
+        }
+    }
+}
+
+struct UploadSession {
+    file_name: String,
+    content_type: Option<String>,
+    size: u64,
+    offset: u64,
+    path: PathBuf,
+    updated: Instant,
+}
+
+/// Global storage for upload sessions, keyed by upload ID
+static UPLOAD_SESSIONS: Lazy<DashMap<String, Arc<Mutex<UploadSession>>>> = Lazy::new(DashMap::new);
+
+/// Directory the uploaded bytes are written to
+static UPLOAD_DIR: Lazy<RwLock<PathBuf>> =
+    Lazy::new(|| RwLock::new(std::env::temp_dir().join("yew_extra_uploads")));
+
+static UPLOAD_LIMITS: Lazy<RwLock<UploadLimits>> = Lazy::new(Default::default);
+
+/// Bytes declared by the open sessions, counted against [`UploadLimits::max_total_size`]
+static DECLARED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+static UPLOAD_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Sets the directory used to store partially uploaded files.
+///
+/// Defaults to `yew_extra_uploads` inside the system temp directory.
+pub fn set_upload_dir(dir: impl Into<PathBuf>) {
+    *UPLOAD_DIR.write().unwrap_or_else(|e| e.into_inner()) = dir.into();
+}
+
+fn upload_dir() -> PathBuf {
+    UPLOAD_DIR.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Sets the limits new upload sessions are checked against
+pub fn set_upload_limits(limits: UploadLimits) {
+    *UPLOAD_LIMITS.write().unwrap_or_else(|e| e.into_inner()) = limits;
+}
+
+fn upload_limits() -> UploadLimits {
+    *UPLOAD_LIMITS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Removes a session declaring `size` bytes, returning them to the total
+fn remove_session(upload_id: &str, size: u64) {
+    if UPLOAD_SESSIONS.remove(upload_id).is_some() {
+        DECLARED_BYTES.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+/// Generates an unguessable upload ID
+fn new_upload_id() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let count = UPLOAD_COUNTER.fetch_add(1, Ordering::Relaxed);
+    // RandomState is seeded randomly per instance, which makes the hashes unpredictable
+    let first = RandomState::new().hash_one((nanos, count));
+    let second = RandomState::new().hash_one((count, nanos));
+    format!("{:016x}{:016x}", first, second)
+}
+
+/// A file received through the chunked upload protocol.
+///
+/// The bytes live in a temporary file which is removed when this value is dropped,
+/// unless it was moved somewhere else with [`UploadedFile::persist`].
+#[derive(Debug)]
+pub struct UploadedFile {
+    file_name: String,
+    content_type: Option<String>,
+    size: u64,
+    path: PathBuf,
+}
+
+impl UploadedFile {
+    /// Original name of the file
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// MIME type reported by the browser, if any
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Size of the file in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Location of the temporary file holding the bytes
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the whole file into memory
+    pub async fn bytes(&self) -> std::io::Result<Vec<u8>> {
+        tokio::fs::read(&self.path).await
+    }
+
+    /// Moves the file to a permanent location
+    pub async fn persist(self, destination: impl AsRef<Path>) -> std::io::Result<()> {
+        let destination = destination.as_ref();
+        if tokio::fs::rename(&self.path, destination).await.is_err() {
+            // Renaming fails across filesystems, so fall back to copying
+            tokio::fs::copy(&self.path, destination).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for UploadedFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Takes a completed upload out of the session storage.
+///
+/// Returns `None` if the upload doesn't exist or hasn't received all of its bytes yet.
+pub async fn take_upload(upload_id: &str) -> Option<UploadedFile> {
+    let session = UPLOAD_SESSIONS.get(upload_id)?.value().clone();
+    let session = session.lock().await;
+
+    if session.offset != session.size {
+        return None;
+    }
+
+    remove_session(upload_id, session.size);
+
+    Some(UploadedFile {
+        file_name: session.file_name.clone(),
+        content_type: session.content_type.clone(),
+        size: session.size,
+        path: session.path.clone(),
+    })
+}
+
+/// Removes sessions that haven't been touched within the session TTL
+async fn remove_expired_sessions() {
+    let sessions: Vec<(String, Arc<Mutex<UploadSession>>)> = UPLOAD_SESSIONS
+        .iter()
+        .map(|entry| (entry.key().clone(), entry.value().clone()))
+        .collect();
+
+    for (upload_id, session) in sessions {
+        if let Ok(session) = session.try_lock() {
+            if session.updated.elapsed() > SESSION_TTL {
+                remove_session(&upload_id, session.size);
+                let _ = tokio::fs::remove_file(&session.path).await;
+            }
+        }
+    }
+}
+
+fn json_response<T: serde::Serialize>(status: StatusCode, value: &T) -> Response<Body> {
+    match serde_json::to_vec(value) {
+        Ok(body) => Response::builder()
+            .status(status)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body))
+            .unwrap(),
+        Err(e) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to serialize response: {}", e),
+        ),
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Body> {
+    json_response(status, &serde_json::json!({ "error": message }))
+}
+
+/// Query string of the offset and append routes
+#[derive(serde::Deserialize)]
+struct UploadQuery {
+    upload_id: String,
+    offset: Option<u64>,
+}
+
+fn upload_query(req: &Request<Body>) -> Option<UploadQuery> {
+    Query::<UploadQuery>::try_from_uri(req.uri())
+        .ok()
+        .map(|Query(query)| query)
+}
+
+/// Handles `POST {path}/create`, starting a new upload session
+pub async fn handle_create(req: Request<Body>) -> Response<Body> {
+    let body = match to_bytes(req.into_body(), 64 * 1024).await {
+        Ok(body) => body,
+        Err(e) => {
+            return error_response(StatusCode::BAD_REQUEST, &format!("Invalid request: {}", e))
+        }
+    };
+    let create: CreateUpload = match serde_json::from_slice(&body) {
+        Ok(create) => create,
+        Err(e) => {
+            return error_response(StatusCode::BAD_REQUEST, &format!("Invalid request: {}", e))
+        }
+    };
+
+    remove_expired_sessions().await;
+
+    let limits = upload_limits();
+    if create.size > limits.max_file_size {
+        return error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            &format!(
+                "File exceeds the upload limit of {} bytes",
+                limits.max_file_size
+            ),
+        );
+    }
+    if UPLOAD_SESSIONS.len() >= limits.max_sessions {
+        return error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "Too many uploads in progress",
+        );
+    }
+    // Reserve the bytes right away, so concurrent sessions can't pass the limit together
+    let reserved = DECLARED_BYTES.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |total| {
+        total
+            .checked_add(create.size)
+            .filter(|total| *total <= limits.max_total_size)
+    });
+    if reserved.is_err() {
+        return error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "Too many bytes being uploaded",
+        );
+    }
+
+    let dir = upload_dir();
+    if let Err(e) = tokio::fs::create_dir_all(&dir).await {
+        DECLARED_BYTES.fetch_sub(create.size, Ordering::Relaxed);
+        return error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to create upload directory: {}", e),
+        );
+    }
+
+    let upload_id = new_upload_id();
+    let path = dir.join(&upload_id);
+    if let Err(e) = tokio::fs::File::create(&path).await {
+        DECLARED_BYTES.fetch_sub(create.size, Ordering::Relaxed);
+        return error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to create upload file: {}", e),
+        );
+    }
+
+    let status = UploadStatus {
+        upload_id: upload_id.clone(),
+        offset: 0,
+        size: create.size,
+    };
+
+    UPLOAD_SESSIONS.insert(
+        upload_id,
+        Arc::new(Mutex::new(UploadSession {
+            file_name: create.file_name,
+            content_type: create.content_type,
+            size: create.size,
+            offset: 0,
+            path,
+            updated: Instant::now(),
+        })),
+    );
+
+    json_response(StatusCode::CREATED, &status)
+}
+
+/// Handles `GET {path}/offset?upload_id=..`, reporting how much has been received
+pub async fn handle_offset(req: Request<Body>) -> Response<Body> {
+    let Some(UploadQuery { upload_id, .. }) = upload_query(&req) else {
+        return error_response(StatusCode::BAD_REQUEST, "Missing upload_id");
+    };
+    let Some(session) = UPLOAD_SESSIONS
+        .get(&upload_id)
+        .map(|entry| entry.value().clone())
+    else {
+        return error_response(StatusCode::NOT_FOUND, "Upload not found");
+    };
+
+    let session = session.lock().await;
+    json_response(
+        StatusCode::OK,
+        &UploadStatus {
+            upload_id,
+            offset: session.offset,
+            size: session.size,
+        },
+    )
+}
+
+/// Handles `PATCH {path}/append?upload_id=..&offset=..`, appending the request body.
+///
+/// The offset must match the number of bytes already received, otherwise the chunk is
+/// rejected with `409 Conflict` and the client should ask for the current offset.
+pub async fn handle_append(req: Request<Body>) -> Response<Body> {
+    let Some(UploadQuery {
+        upload_id,
+        offset: Some(offset),
+    }) = upload_query(&req)
+    else {
+        return error_response(StatusCode::BAD_REQUEST, "Missing upload_id or offset");
+    };
+    let Some(session) = UPLOAD_SESSIONS
+        .get(&upload_id)
+        .map(|entry| entry.value().clone())
+    else {
+        return error_response(StatusCode::NOT_FOUND, "Upload not found");
+    };
+
+    let chunk = match to_bytes(req.into_body(), MAX_CHUNK_SIZE).await {
+        Ok(chunk) => chunk,
+        Err(e) => {
+            return error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                &format!("Invalid chunk: {}", e),
+            )
+        }
+    };
+
+    let mut session = session.lock().await;
+
+    if offset != session.offset {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!(
+                "Offset mismatch: expected {}, got {}",
+                session.offset, offset
+            ),
+        );
+    }
+    if session.offset + chunk.len() as u64 > session.size {
+        return error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "Chunk exceeds the declared file size",
+        );
+    }
+
+    let write = async {
+        let mut file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&session.path)
+            .await?;
+        file.write_all(&chunk).await?;
+        file.flush().await
+    };
+    if let Err(e) = write.await {
+        return error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to write chunk: {}", e),
+        );
+    }
+
+    session.offset += chunk.len() as u64;
+    session.updated = Instant::now();
+
+    json_response(
+        StatusCode::OK,
+        &UploadStatus {
+            upload_id,
+            offset: session.offset,
+            size: session.size,
+        },
+    )
+}
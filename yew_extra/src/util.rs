@@ -0,0 +1,10 @@
+//! Small helpers shared by modules that have nothing else in common.
+
+/// 64-bit FNV-1a hash of `bytes`. Unlike `std`'s hashers it's the same on every platform
+/// and toolchain, so values derived from it can be compared between the server and the
+/// browser, or across releases.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -0,0 +1,168 @@
+//! Validation support for parameters declared with `#[validate(...)]`.
+//!
+//! `yew_server_hook` generates a `validate()` method on the parameter struct which is
+//! run by the server wrapper (rejecting invalid requests with `422 Unprocessable Entity`)
+//! and by the generated client before the request is sent. Both sides report failures
+//! as [`ValidationErrors`], so the client sees the same errors either way.
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// The regex type `#[validate(regex = "...")]` compiles its pattern into, re-exported so the
+/// generated code doesn't need a `regex` dependency in the user's crate.
+pub use regex::Regex;
+
+/// Field-level validation errors.
+///
+/// Serializes to the body of a `422` response:
+///
+/// ```json
+/// {
+///   "error": "Validation failed: name: length must be at least 1",
+///   "fields": { "name": ["length must be at least 1"] }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ValidationErrors {
+    /// Error messages keyed by parameter name
+    pub fields: BTreeMap<String, Vec<String>>,
+}
+
+impl ValidationErrors {
+    /// Creates an empty set of errors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an error message for a field
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.fields
+            .entry(field.to_string())
+            .or_default()
+            .push(message.into());
+    }
+
+    /// Returns true when no errors were recorded
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Converts into `Ok(())` when empty, or `Err(self)` otherwise
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Validation failed")?;
+        let mut separator = ": ";
+        for (field, messages) in &self.fields {
+            for message in messages {
+                write!(f, "{}{}: {}", separator, field, message)?;
+                separator = "; ";
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+impl Serialize for ValidationErrors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Include the summary as "error" so clients that only read the message still get it
+        let mut state = serializer.serialize_struct("ValidationErrors", 2)?;
+        state.serialize_field("error", &self.to_string())?;
+        state.serialize_field("fields", &self.fields)?;
+        state.end()
+    }
+}
+
+/// Values that can be checked with `#[validate(length(...))]`.
+///
+/// Returns `None` to skip the check (e.g. for `Option::None`).
+pub trait ValidateLength {
+    fn validate_length(&self) -> Option<usize>;
+}
+
+impl ValidateLength for String {
+    fn validate_length(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
+}
+
+impl ValidateLength for &str {
+    fn validate_length(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
+}
+
+impl<T> ValidateLength for Vec<T> {
+    fn validate_length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T: ValidateLength> ValidateLength for Option<T> {
+    fn validate_length(&self) -> Option<usize> {
+        self.as_ref().and_then(ValidateLength::validate_length)
+    }
+}
+
+/// Values that can be checked with `#[validate(range(...))]`.
+///
+/// Returns `None` to skip the check (e.g. for `Option::None`).
+pub trait ValidateRange {
+    fn validate_range(&self) -> Option<f64>;
+}
+
+macro_rules! impl_validate_range {
+    ($($ty:ty),*) => {
+        $(
+            impl ValidateRange for $ty {
+                fn validate_range(&self) -> Option<f64> {
+                    Some(*self as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_validate_range!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+impl<T: ValidateRange> ValidateRange for Option<T> {
+    fn validate_range(&self) -> Option<f64> {
+        self.as_ref().and_then(ValidateRange::validate_range)
+    }
+}
+
+/// Values that can be checked with `#[validate(regex = "...")]`.
+///
+/// Returns `None` to skip the check (e.g. for `Option::None`).
+pub trait ValidateRegex {
+    fn validate_regex(&self) -> Option<&str>;
+}
+
+impl ValidateRegex for String {
+    fn validate_regex(&self) -> Option<&str> {
+        Some(self.as_str())
+    }
+}
+
+impl ValidateRegex for &str {
+    fn validate_regex(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl<T: ValidateRegex> ValidateRegex for Option<T> {
+    fn validate_regex(&self) -> Option<&str> {
+        self.as_ref().and_then(ValidateRegex::validate_regex)
+    }
+}
@@ -0,0 +1,478 @@
+//! WebSocket endpoints exchanging typed messages, declared with `#[yewserverws]`.
+//!
+//! The function takes the messages the client sends as an [`Inbound`] stream and returns
+//! the stream of messages to send back. Both are JSON text frames:
+//!
+//! ```ignore
+//! #[yewserverws(path = "/ws/chat")]
+//! pub async fn chat(inbound: Inbound<ChatIn>) -> Result<impl Stream<Item = ChatOut>, AppError> {
+//!     let user = current_user().await?;
+//!     Ok(inbound.map(move |message| ChatOut { from: user.name.clone(), text: message.text }))
+//! }
+//! ```
+//!
+//! The function runs while the upgrade request is handled, so `extract()` and an error
+//! return, e.g. for a missing login, work as in other server functions. The socket stays
+//! open until the client leaves or the returned stream ends. The server side needs the
+//! `ws` feature.
+//!
+//! In the browser, `use_chat_ws()` connects on mount and returns a [`WsHook`] with the
+//! messages received so far and a `send` callback. Lost connections are opened again
+//! with a growing delay, and messages sent in the meantime go out once it's back.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew::prelude::*;
+
+use crate::hooks::{use_isomorphic_effect, use_server_value};
+
+/// The connection state of a [`use_ws`] hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WsStatus {
+    /// Opening the first connection, also the state of server renders
+    Connecting,
+    Open,
+    /// Waiting to open the connection again after it was lost
+    Reconnecting,
+}
+
+/// What the hooks of `#[yewserverws]` endpoints return
+pub struct WsHook<I, O> {
+    /// The messages received since the component mounted, oldest first
+    pub messages: Rc<Vec<O>>,
+    pub status: WsStatus,
+    /// Sends a message, or queues it until the connection is open
+    pub send: Callback<I>,
+}
+
+impl<I, O> Clone for WsHook<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            messages: self.messages.clone(),
+            status: self.status,
+            send: self.send.clone(),
+        }
+    }
+}
+
+impl<I, O: PartialEq> PartialEq for WsHook<I, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.messages == other.messages && self.status == other.status && self.send == other.send
+    }
+}
+
+/// The `ws://` or `wss://` URL of `path` on the API `base`, which is either a URL or a
+/// path on the page's `origin`
+pub fn websocket_url(base: &str, path: &str, origin: &str) -> String {
+    let base = if base.contains("://") {
+        base.to_string()
+    } else {
+        format!("{}{}", origin.trim_end_matches('/'), base)
+    };
+    let base = match base.split_once("://") {
+        Some(("https", rest)) => format!("wss://{}", rest),
+        Some(("http", rest)) => format!("ws://{}", rest),
+        _ => base,
+    };
+    format!("{}{}", base.trim_end_matches('/'), path)
+}
+
+/// Messages waiting for the connection, shared by `send` and the socket
+#[derive(Default)]
+struct Outbox {
+    queued: Vec<String>,
+    #[cfg(feature = "client")]
+    socket: Option<web_sys::WebSocket>,
+}
+
+impl Outbox {
+    fn send(&mut self, text: String) {
+        #[cfg(feature = "client")]
+        if let Some(socket) = &self.socket {
+            if socket.send_with_str(&text).is_ok() {
+                return;
+            }
+        }
+        self.queued.push(text);
+    }
+}
+
+/// The messages received by a [`use_ws`] hook
+struct Received<O> {
+    messages: Rc<Vec<O>>,
+}
+
+impl<O> Default for Received<O> {
+    fn default() -> Self {
+        Self {
+            messages: Rc::default(),
+        }
+    }
+}
+
+impl<O: Clone> Reducible for Received<O> {
+    type Action = O;
+
+    fn reduce(self: Rc<Self>, message: O) -> Rc<Self> {
+        let mut messages = self.messages.clone();
+        Rc::make_mut(&mut messages).push(message);
+        Rc::new(Self { messages })
+    }
+}
+
+/// Connects to the WebSocket endpoint at `path` on the API `base` while the component is
+/// mounted, in the browser only (`client` feature). The hooks generated by
+/// `#[yewserverws]` call it.
+#[hook]
+pub fn use_ws<I, O>(base: String, path: &'static str) -> WsHook<I, O>
+where
+    I: Serialize + 'static,
+    O: DeserializeOwned + Clone + 'static,
+{
+    let status = use_server_value(|| WsStatus::Connecting);
+    let received = use_reducer(Received::<O>::default);
+    let shared = use_memo((), |_| {
+        let outbox = Rc::new(RefCell::new(Outbox::default()));
+        let send = {
+            let outbox = outbox.clone();
+            Callback::from(move |message: I| match serde_json::to_string(&message) {
+                Ok(text) => outbox.borrow_mut().send(text),
+                Err(e) => warn(&format!("Failed to serialize a WebSocket message: {}", e)),
+            })
+        };
+        (outbox, send)
+    });
+    let (outbox, send) = &*shared;
+
+    {
+        let status = status.clone();
+        let received = received.dispatcher();
+        let outbox = outbox.clone();
+        use_isomorphic_effect((base, path), move |(base, path)| {
+            #[cfg(feature = "client")]
+            let connection = {
+                let origin = web_sys::window()
+                    .and_then(|window| window.location().origin().ok())
+                    .unwrap_or_default();
+                client::Connection::open(
+                    websocket_url(base, path, &origin),
+                    outbox,
+                    move |text| match serde_json::from_str::<O>(&text) {
+                        Ok(message) => received.dispatch(message),
+                        Err(e) => warn(&format!("Failed to parse a WebSocket message: {}", e)),
+                    },
+                    move |current| status.set(current),
+                )
+            };
+            #[cfg(not(feature = "client"))]
+            let _ = (base, path, status, received, outbox);
+
+            move || {
+                #[cfg(feature = "client")]
+                drop(connection);
+            }
+        });
+    }
+
+    WsHook {
+        messages: received.messages.clone(),
+        status: *status,
+        send: send.clone(),
+    }
+}
+
+#[cfg(feature = "client")]
+fn warn(message: &str) {
+    web_sys::console::warn_1(&message.into());
+}
+
+#[cfg(not(feature = "client"))]
+fn warn(_message: &str) {}
+
+#[cfg(feature = "client")]
+mod client {
+    use super::{Outbox, WsStatus};
+    use crate::browser::sleep;
+    use std::cell::{Cell, RefCell};
+    use std::rc::{Rc, Weak};
+    use wasm_bindgen::prelude::*;
+
+    /// Delay before the first reconnection, doubled after every failed one
+    const INITIAL_BACKOFF_MS: i32 = 500;
+    /// Longest wait between reconnections
+    const MAX_BACKOFF_MS: i32 = 30_000;
+
+    /// Keeps a WebSocket open, reconnecting until it is dropped
+    pub struct Connection {
+        shared: Rc<Shared>,
+    }
+
+    struct Shared {
+        url: String,
+        outbox: Rc<RefCell<Outbox>>,
+        on_message: Box<dyn Fn(String)>,
+        on_status: Box<dyn Fn(WsStatus)>,
+        closed: Cell<bool>,
+        backoff_ms: Cell<i32>,
+        socket: RefCell<Option<Socket>>,
+    }
+
+    /// A socket with its handlers, kept alive as long as it is in use
+    struct Socket {
+        socket: web_sys::WebSocket,
+        _on_open: Closure<dyn FnMut()>,
+        _on_message: Closure<dyn FnMut(web_sys::MessageEvent)>,
+        _on_close: Closure<dyn FnMut()>,
+    }
+
+    impl Socket {
+        fn close(self) {
+            self.socket.set_onopen(None);
+            self.socket.set_onmessage(None);
+            self.socket.set_onclose(None);
+            let _ = self.socket.close();
+        }
+    }
+
+    impl Connection {
+        pub fn open(
+            url: String,
+            outbox: Rc<RefCell<Outbox>>,
+            on_message: impl Fn(String) + 'static,
+            on_status: impl Fn(WsStatus) + 'static,
+        ) -> Self {
+            let shared = Rc::new(Shared {
+                url,
+                outbox,
+                on_message: Box::new(on_message),
+                on_status: Box::new(on_status),
+                closed: Cell::new(false),
+                backoff_ms: Cell::new(INITIAL_BACKOFF_MS),
+                socket: RefCell::new(None),
+            });
+            connect(&shared);
+            Self { shared }
+        }
+    }
+
+    impl Drop for Connection {
+        fn drop(&mut self) {
+            self.shared.closed.set(true);
+            self.shared.outbox.borrow_mut().socket = None;
+            if let Some(socket) = self.shared.socket.borrow_mut().take() {
+                socket.close();
+            }
+        }
+    }
+
+    fn connect(shared: &Rc<Shared>) {
+        let socket = match web_sys::WebSocket::new(&shared.url) {
+            Ok(socket) => socket,
+            Err(_) => {
+                reconnect_later(shared);
+                return;
+            }
+        };
+
+        // The handlers only hold on to the connection weakly, so dropping it ends them
+        let on_open = Closure::<dyn FnMut()>::new({
+            let shared = Rc::downgrade(shared);
+            let socket = socket.clone();
+            move || {
+                let Some(shared) = shared.upgrade() else {
+                    return;
+                };
+                shared.backoff_ms.set(INITIAL_BACKOFF_MS);
+                {
+                    let mut outbox = shared.outbox.borrow_mut();
+                    outbox.socket = Some(socket.clone());
+                    for text in std::mem::take(&mut outbox.queued) {
+                        outbox.send(text);
+                    }
+                }
+                (shared.on_status)(WsStatus::Open);
+            }
+        });
+        let on_message = Closure::<dyn FnMut(web_sys::MessageEvent)>::new({
+            let shared = Rc::downgrade(shared);
+            move |event: web_sys::MessageEvent| {
+                if let (Some(shared), Some(text)) = (shared.upgrade(), event.data().as_string()) {
+                    (shared.on_message)(text);
+                }
+            }
+        });
+        let on_close = Closure::<dyn FnMut()>::new({
+            let shared = Rc::downgrade(shared);
+            move || {
+                let Some(shared) = shared.upgrade() else {
+                    return;
+                };
+                shared.outbox.borrow_mut().socket = None;
+                if !shared.closed.get() {
+                    (shared.on_status)(WsStatus::Reconnecting);
+                    reconnect_later(&shared);
+                }
+            }
+        });
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+        let previous = shared.socket.borrow_mut().replace(Socket {
+            socket,
+            _on_open: on_open,
+            _on_message: on_message,
+            _on_close: on_close,
+        });
+        if let Some(previous) = previous {
+            previous.close();
+        }
+    }
+
+    fn reconnect_later(shared: &Rc<Shared>) {
+        let delay_ms = shared.backoff_ms.get();
+        shared
+            .backoff_ms
+            .set(delay_ms.saturating_mul(2).min(MAX_BACKOFF_MS));
+        let shared: Weak<Shared> = Rc::downgrade(shared);
+        wasm_bindgen_futures::spawn_local(async move {
+            sleep(delay_ms).await;
+            if let Some(shared) = shared.upgrade() {
+                if !shared.closed.get() {
+                    connect(&shared);
+                }
+            }
+        });
+    }
+}
+
+/// The messages the client sends, parsed from JSON, ending when it disconnects. Messages
+/// that don't parse are dropped.
+///
+/// It is a `Stream` on the server (`ws` feature), and only names the message type elsewhere.
+pub struct Inbound<T> {
+    #[cfg(all(feature = "ws", not(target_arch = "wasm32")))]
+    receiver: tokio::sync::mpsc::UnboundedReceiver<T>,
+    #[cfg(not(all(feature = "ws", not(target_arch = "wasm32"))))]
+    _message: std::marker::PhantomData<T>,
+}
+
+#[cfg(all(feature = "ws", not(target_arch = "wasm32")))]
+pub use server::handle;
+
+#[cfg(all(feature = "ws", not(target_arch = "wasm32")))]
+mod server {
+    use super::Inbound;
+    use axum::body::Body;
+    use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+    use axum::extract::FromRequestParts;
+    use axum::http::{Request, Response};
+    use axum::response::IntoResponse;
+    use futures_util::future::{select, Either};
+    use futures_util::{Stream, StreamExt};
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+    use std::future::Future;
+    use std::pin::{pin, Pin};
+    use std::task::{Context, Poll};
+    use tokio::sync::mpsc;
+
+    impl<T> Inbound<T> {
+        /// An inbound stream and the sender feeding it, e.g. to call the function of an
+        /// endpoint in a test
+        pub fn channel() -> (Self, mpsc::UnboundedSender<T>) {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            (Self { receiver }, sender)
+        }
+    }
+
+    impl<T> Stream for Inbound<T> {
+        type Item = T;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+            self.receiver.poll_recv(cx)
+        }
+    }
+
+    /// Upgrades `req` to a WebSocket connected to the function `handler`, the wrapper of
+    /// `#[yewserverws]` endpoints.
+    ///
+    /// Requests that aren't WebSocket upgrades are rejected before the function runs. An
+    /// error the function returns answers the upgrade request instead.
+    pub async fn handle<I, O, S, E, F, Fut>(req: Request<Body>, handler: F) -> Response<Body>
+    where
+        I: DeserializeOwned + Send + 'static,
+        O: Serialize + Send + 'static,
+        S: Stream<Item = O> + Send + 'static,
+        E: IntoResponse,
+        F: FnOnce(Inbound<I>) -> Fut,
+        Fut: Future<Output = Result<S, E>>,
+    {
+        let (mut parts, _body) = req.into_parts();
+        let upgrade = match WebSocketUpgrade::from_request_parts(&mut parts, &()).await {
+            Ok(upgrade) => upgrade,
+            Err(rejection) => return rejection.into_response(),
+        };
+
+        crate::provide_request_parts(parts).await;
+        let (inbound, sender) = Inbound::channel();
+        let response = match handler(inbound).await {
+            Ok(outbound) => upgrade.on_upgrade(move |socket| relay(socket, sender, outbound)),
+            Err(e) => e.into_response(),
+        };
+        let response = crate::apply_response_headers(response).await;
+        crate::clear_request_parts().await;
+        response
+    }
+
+    /// Passes the messages of the client to `inbound` and sends the items of `outbound`,
+    /// until either side is done
+    async fn relay<I, O, S>(mut socket: WebSocket, inbound: mpsc::UnboundedSender<I>, outbound: S)
+    where
+        I: DeserializeOwned,
+        O: Serialize,
+        S: Stream<Item = O>,
+    {
+        let mut outbound = pin!(outbound);
+        loop {
+            // Both sides are cancel safe, the one that lost the race is polled again next time
+            let next = match select(pin!(socket.recv()), outbound.next()).await {
+                Either::Left((message, _)) => Either::Left(message),
+                Either::Right((item, _)) => Either::Right(item),
+            };
+            match next {
+                Either::Left(Some(Ok(message))) => {
+                    let parsed = match message {
+                        Message::Text(text) => serde_json::from_str(text.as_str()),
+                        Message::Binary(bytes) => serde_json::from_slice(&bytes),
+                        Message::Close(_) => break,
+                        // Pings are answered by axum
+                        _ => continue,
+                    };
+                    if let Ok(message) = parsed {
+                        let _ = inbound.send(message);
+                    }
+                }
+                // The client left or the connection broke
+                Either::Left(_) => break,
+                Either::Right(Some(item)) => {
+                    let Ok(text) = serde_json::to_string(&item) else {
+                        continue;
+                    };
+                    // Axum 0.8 sends `Utf8Bytes`, 0.7 the `String` itself
+                    #[allow(clippy::useless_conversion)]
+                    let message = Message::Text(text.into());
+                    if socket.send(message).await.is_err() {
+                        break;
+                    }
+                }
+                Either::Right(None) => {
+                    let _ = socket.send(Message::Close(None)).await;
+                    break;
+                }
+            }
+        }
+    }
+}
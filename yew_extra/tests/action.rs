@@ -0,0 +1,108 @@
+// Checks the state actions keep across their calls
+use std::cell::RefCell;
+use std::rc::Rc;
+use tokio::sync::oneshot;
+use tokio::task::LocalSet;
+use yew::prelude::*;
+use yew::ServerRenderer;
+use yew_extra::action::{use_action, Action, Mutation};
+
+async fn double(n: u32) -> Result<u32, String> {
+    if n == 0 {
+        Err("Nothing to double".to_string())
+    } else {
+        Ok(n * 2)
+    }
+}
+
+#[tokio::test]
+async fn test_run_keeps_the_latest_result() {
+    let action = Action::new(double);
+    assert!(!action.pending());
+    assert_eq!(action.value(), None);
+    assert_eq!(action.version(), 0);
+
+    assert_eq!(action.run(2).await, Ok(4));
+    assert_eq!(action.value(), Some(Ok(4)));
+    assert_eq!(action.run(0).await, Err("Nothing to double".to_string()));
+    assert_eq!(action.value(), Some(Err("Nothing to double".to_string())));
+    assert_eq!(action.version(), 2);
+    assert_eq!(action.input(), None);
+}
+
+#[tokio::test]
+async fn test_dispatch_tracks_pending_calls_and_ignores_stale_results() {
+    // Each call waits for its own reply, so the test decides the order they settle in
+    let replies: Rc<RefCell<Vec<oneshot::Sender<u32>>>> = Rc::default();
+    let action = Action::new({
+        let replies = replies.clone();
+        move |_: &'static str| {
+            let (sender, receiver) = oneshot::channel();
+            replies.borrow_mut().push(sender);
+            async move { receiver.await.map_err(|e| e.to_string()) }
+        }
+    });
+
+    LocalSet::new()
+        .run_until(async {
+            action.dispatch("first");
+            action.dispatch("second");
+            tokio::task::yield_now().await;
+            assert!(action.pending());
+            assert_eq!(action.input(), Some("second"));
+
+            let first = replies.borrow_mut().remove(0);
+            let second = replies.borrow_mut().remove(0);
+            second.send(2).unwrap();
+            tokio::task::yield_now().await;
+            assert_eq!(action.value(), Some(Ok(2)));
+            assert!(action.pending());
+
+            first.send(1).unwrap();
+            tokio::task::yield_now().await;
+            assert_eq!(action.value(), Some(Ok(2)));
+            assert!(!action.pending());
+            assert_eq!(action.version(), 2);
+        })
+        .await;
+}
+
+#[test]
+fn test_clones_are_the_same_action() {
+    let action = Action::new(double);
+    assert_eq!(action.clone(), action);
+    assert_ne!(Action::new(double), action);
+}
+
+#[tokio::test]
+async fn test_mutations_show_the_latest_result() {
+    let action = Action::new(double);
+    let mutation = Mutation::new(action.clone());
+    assert!(!mutation.is_pending);
+    assert_eq!(mutation.data, None);
+    assert_eq!(mutation.error, None);
+
+    action.run(3).await.unwrap();
+    assert_eq!(Mutation::new(action.clone()).data, Some(6));
+    action.run(0).await.unwrap_err();
+    let mutation = Mutation::new(action);
+    assert_eq!(mutation.data, None);
+    assert_eq!(mutation.error, Some("Nothing to double".to_string()));
+}
+
+#[function_component(Save)]
+fn save() -> Html {
+    let action = use_action(double);
+    html! {
+        <button disabled={action.pending()}>{ format!("Saved {} times", action.version()) }</button>
+    }
+}
+
+#[tokio::test]
+async fn test_actions_start_idle_in_renders() {
+    let body = ServerRenderer::<Save>::new()
+        .hydratable(false)
+        .render()
+        .await;
+    assert_eq!(body, "<button>Saved 0 times</button>");
+}
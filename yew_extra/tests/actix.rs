@@ -0,0 +1,66 @@
+// Checks that the registered routes run behind actix-web
+#![cfg(all(feature = "actix", feature = "axum-08"))]
+
+use actix_web::{test as actix_test, App};
+use axum::body::Body;
+use axum::http::{Method, Request, Response, StatusCode};
+use std::future::Future;
+use std::pin::Pin;
+use yew_extra::actix::{actix_path, configure};
+use yew_extra::route_registry::RouteInfo;
+
+fn echo_handler_wrapper(
+    req: Request<Body>,
+) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+    Box::pin(yew_extra::request_scope(async move {
+        let (parts, body) = req.into_parts();
+        yew_extra::provide_request_parts(parts).await;
+        let method: Method = yew_extra::extract().await.unwrap();
+        let uri: axum::http::Uri = yew_extra::extract().await.unwrap();
+        yew_extra::clear_request_parts().await;
+
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        Response::builder()
+            .status(StatusCode::CREATED)
+            .header("x-echo", "yes")
+            .body(Body::from(format!(
+                "{} {} {}",
+                method,
+                uri,
+                String::from_utf8_lossy(&body)
+            )))
+            .unwrap()
+    }))
+}
+
+inventory::submit! {
+    RouteInfo::new("/api/echo", Method::POST, echo_handler_wrapper)
+}
+
+#[actix_web::test]
+async fn test_routes_run_behind_actix() {
+    let app = actix_test::init_service(App::new().configure(configure)).await;
+
+    let request = actix_test::TestRequest::post()
+        .uri("/api/echo?loud=1")
+        .set_payload("hello")
+        .to_request();
+    let response = actix_test::call_service(&app, request).await;
+    assert_eq!(response.status().as_u16(), 201);
+    assert_eq!(response.headers().get("x-echo").unwrap(), "yes");
+    let body = actix_test::read_body(response).await;
+    assert_eq!(body, "POST /api/echo?loud=1 hello");
+
+    // Only the registered method is routed
+    let request = actix_test::TestRequest::get().uri("/api/echo").to_request();
+    let response = actix_test::call_service(&app, request).await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[test]
+fn test_wildcards_use_actix_syntax() {
+    assert_eq!(actix_path("/api/users/{id}"), "/api/users/{id}");
+    assert_eq!(actix_path("/files/{*path}"), "/files/{path:.*}");
+    assert_eq!(actix_path("/api/users/:id"), "/api/users/{id}");
+    assert_eq!(actix_path("/files/*path"), "/files/{path:.*}");
+}
@@ -0,0 +1,76 @@
+// Checks that routes declared with `state` are handed the state the router was built with
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::{Method, Request, Response, StatusCode};
+use std::any::Any;
+use std::future::Future;
+use std::pin::Pin;
+use tower::ServiceExt;
+use yew_extra::route_registry::RouteInfo;
+use yew_extra::test::{test_router, test_router_with_state};
+
+#[derive(Clone)]
+struct AppState {
+    greeting: &'static str,
+}
+
+fn greet_handler_wrapper(
+    _req: Request<Body>,
+) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+    Box::pin(async {
+        Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("GET /api/greet needs the app state"))
+            .unwrap()
+    })
+}
+
+fn greet_handler_state_wrapper(
+    _req: Request<Body>,
+    state: &(dyn Any + Send + Sync),
+) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+    let state = state.downcast_ref::<AppState>().cloned();
+    Box::pin(async move {
+        match state {
+            Some(state) => Response::new(Body::from(state.greeting)),
+            None => Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::empty())
+                .unwrap(),
+        }
+    })
+}
+
+inventory::submit! {
+    RouteInfo::new("/api/greet", Method::GET, greet_handler_wrapper)
+        .state_handler(greet_handler_state_wrapper)
+}
+
+async fn get(router: axum::Router) -> (StatusCode, String) {
+    let request = Request::get("/api/greet").body(Body::empty()).unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_state_is_passed_to_the_endpoint() {
+    let router = test_router_with_state(AppState { greeting: "hello" });
+    assert_eq!(get(router).await, (StatusCode::OK, "hello".to_string()));
+}
+
+#[tokio::test]
+async fn test_state_of_another_type() {
+    let (status, _) = get(test_router_with_state(42_u32)).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn test_router_without_state() {
+    let (status, body) = get(test_router()).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(body.contains("needs the app state"));
+}
@@ -0,0 +1,90 @@
+// Checks the records sent to the audit sink
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::{Request, Response, StatusCode};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use yew_extra::audit::{
+    begin, clear_audit_sink, finish, params_digest, record_params, set_audit_sink,
+    set_principal_resolver, AuditRecord,
+};
+use yew_extra::{clear_request_parts, provide_request_parts, request_scope};
+
+#[derive(Serialize)]
+struct Params {
+    title: String,
+}
+
+async fn handle(mut request: Request<Body>, status: StatusCode) -> Response<Body> {
+    request_scope(async move {
+        let audit = begin("/api/todos", &mut request);
+        let (parts, _) = request.into_parts();
+        provide_request_parts(parts).await;
+        record_params(&Params {
+            title: "Buy milk".to_string(),
+        })
+        .await;
+        clear_request_parts().await;
+
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = status;
+        finish(audit, response).await
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_sink_gets_a_record_per_call() {
+    let request = || {
+        Request::post("/api/todos")
+            .header("x-user", "alice")
+            .header("x-request-id", "req-42")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    // Nothing is recorded without a sink
+    assert!(begin("/api/todos", &mut request()).is_none());
+
+    let records: Arc<Mutex<Vec<AuditRecord>>> = Arc::default();
+    let sink = records.clone();
+    set_audit_sink(move |record| {
+        sink.lock().unwrap().push(record);
+        async {}
+    });
+    set_principal_resolver(|request| {
+        let user = request.headers().get("x-user")?;
+        user.to_str().ok().map(str::to_string)
+    });
+
+    handle(request(), StatusCode::CREATED).await;
+    handle(request(), StatusCode::FORBIDDEN).await;
+    clear_audit_sink();
+    handle(request(), StatusCode::CREATED).await;
+
+    let records = records.lock().unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[0],
+        AuditRecord {
+            principal: Some("alice".to_string()),
+            method: "POST".to_string(),
+            path: "/api/todos".to_string(),
+            params_digest: params_digest(&Params {
+                title: "Buy milk".to_string()
+            }),
+            status: 201,
+            request_id: Some("req-42".to_string()),
+        }
+    );
+    assert!(records[0].succeeded());
+    assert!(!records[1].succeeded());
+}
+
+#[test]
+fn test_digest_is_the_sha256_of_the_json() {
+    assert_eq!(
+        params_digest(&serde_json::json!({})).unwrap(),
+        "44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+    );
+}
@@ -0,0 +1,46 @@
+// Checks that mutations sent with an idempotency key only run once
+#![cfg(feature = "axum-08")]
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, Response, StatusCode};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use yew_extra::background_sync::{idempotency_key, run_idempotent, IDEMPOTENCY_HEADER};
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+async fn create_todo() -> Response<Body> {
+    let call = CALLS.fetch_add(1, Ordering::SeqCst) + 1;
+    let mut response = Response::new(Body::from(format!("todo {}", call)));
+    *response.status_mut() = StatusCode::CREATED;
+    response
+}
+
+fn request(key: Option<&str>) -> Request<Body> {
+    let mut builder = Request::post("/api/todos");
+    if let Some(key) = key {
+        builder = builder.header(IDEMPOTENCY_HEADER, key);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+async fn body_of(response: Response<Body>) -> String {
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_repeated_key_returns_stored_response() {
+    let first = run_idempotent(idempotency_key(&request(Some("abc"))), create_todo()).await;
+    assert_eq!(first.status(), StatusCode::CREATED);
+    assert_eq!(body_of(first).await, "todo 1");
+
+    // The replay gets the first response without running the handler again
+    let replay = run_idempotent(idempotency_key(&request(Some("abc"))), create_todo()).await;
+    assert_eq!(replay.status(), StatusCode::CREATED);
+    assert_eq!(replay.headers()["idempotent-replayed"], "true");
+    assert_eq!(body_of(replay).await, "todo 1");
+
+    // Requests without a key always run
+    let unkeyed = run_idempotent(idempotency_key(&request(None)), create_todo()).await;
+    assert_eq!(body_of(unkeyed).await, "todo 2");
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+}
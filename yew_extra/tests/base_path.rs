@@ -0,0 +1,62 @@
+// Checks the base path of apps deployed under a sub-path
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use tower::ServiceExt;
+use yew_extra::base_path::{
+    api_base, base_path, clear_api_base, meta_tag, mount, set_api_base, set_base_path, url, ENV_VAR,
+};
+
+#[tokio::test]
+async fn test_base_path_prefixes_urls_and_routes() {
+    // Read from the environment until set explicitly
+    std::env::set_var(ENV_VAR, "myapp/");
+    assert_eq!(base_path(), "/myapp");
+    assert_eq!(url("/pkg/app.js"), "/myapp/pkg/app.js");
+    assert_eq!(
+        meta_tag(),
+        r#"<meta name="yew-base-path" content="/myapp">"#
+    );
+
+    let app = mount(Router::new().route("/api/ping", get(|| async { "pong" })));
+    let response = app
+        .clone()
+        .oneshot(Request::get("/myapp/api/ping").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = app
+        .oneshot(Request::get("/api/ping").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // The API base a client was built with wins over the base path
+    assert_eq!(api_base(None), "/myapp");
+    assert_eq!(api_base(Some("")), "/myapp");
+    assert_eq!(
+        api_base(Some("http://localhost:4000/")),
+        "http://localhost:4000"
+    );
+
+    // A base set at runtime wins over both
+    set_api_base("https://api.example.com/");
+    assert_eq!(
+        api_base(Some("http://localhost:4000")),
+        "https://api.example.com"
+    );
+    clear_api_base();
+    assert_eq!(api_base(None), "/myapp");
+
+    set_base_path("/");
+    assert_eq!(base_path(), "");
+    assert_eq!(url("pkg/app.js"), "/pkg/app.js");
+
+    set_base_path("/a\"b/");
+    assert_eq!(
+        meta_tag(),
+        r#"<meta name="yew-base-path" content="/a&quot;b">"#
+    );
+}
@@ -0,0 +1,320 @@
+// Checks the stale time, cache time and size limit of the hooks' shared cache
+use std::time::Duration;
+use yew_extra::cache::{self, CacheConfig, QueryCache};
+use yew_extra::RequestOptions;
+
+#[test]
+fn test_values_go_stale_after_stale_time() {
+    let mut cache = QueryCache::new(CacheConfig::new().stale_time(Duration::from_millis(30)));
+    cache.insert("users", vec!["ada".to_string()]);
+
+    let cached = cache.get::<Vec<String>>("users").unwrap();
+    assert_eq!(cached.value, vec!["ada".to_string()]);
+    assert!(!cached.is_stale);
+
+    std::thread::sleep(Duration::from_millis(40));
+    assert!(cache.get::<Vec<String>>("users").unwrap().is_stale);
+
+    // Values are only returned as the type they were stored with
+    assert!(cache.get::<String>("users").is_none());
+}
+
+#[test]
+fn test_unused_entries_expire_after_cache_time() {
+    let mut cache = QueryCache::new(CacheConfig::new().cache_time(Duration::from_millis(30)));
+    cache.insert("kept", 1u32);
+    cache.insert("dropped", 2u32);
+
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(cache.get::<u32>("kept").is_some());
+    std::thread::sleep(Duration::from_millis(20));
+
+    cache.collect_garbage();
+    assert!(cache.get::<u32>("dropped").is_none());
+    assert_eq!(cache.get::<u32>("kept").unwrap().value, 1);
+}
+
+#[test]
+fn test_least_recently_used_entries_are_evicted() {
+    let mut cache = QueryCache::new(CacheConfig::new().max_entries(2));
+    cache.insert("a", 1u32);
+    std::thread::sleep(Duration::from_millis(2));
+    cache.insert("b", 2u32);
+    std::thread::sleep(Duration::from_millis(2));
+    cache.get::<u32>("a");
+    std::thread::sleep(Duration::from_millis(2));
+    cache.insert("c", 3u32);
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get::<u32>("b").is_none());
+    assert!(cache.get::<u32>("a").is_some());
+    assert!(cache.get::<u32>("c").is_some());
+
+    cache.set_config(CacheConfig::new().max_entries(1));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_shared_cache_keys_include_extra_query() {
+    let plain = cache::request_key("/api/users?page=1", &RequestOptions::new());
+    let traced = cache::request_key(
+        "/api/users?page=1",
+        &RequestOptions::new().query("trace", "1"),
+    );
+    assert_ne!(plain, traced);
+
+    cache::insert(plain.clone(), 42u32);
+    assert_eq!(cache::get::<u32>(&plain).unwrap().value, 42);
+    cache::remove(&plain);
+    assert!(cache::get::<u32>(&plain).is_none());
+}
+
+#[test]
+fn test_optimistic_updates_roll_back_and_notify() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use yew_extra::cache::OptimisticUpdate;
+
+    let key = "GET /api/todos?list=optimistic";
+    cache::insert(key, vec!["write docs".to_string()]);
+
+    let notified = Rc::new(Cell::new(0));
+    let counter = notified.clone();
+    let subscription = cache::subscribe(key, move || counter.set(counter.get() + 1));
+
+    let mut optimistic = OptimisticUpdate::new();
+    assert!(optimistic.update::<Vec<String>>(key, |todos| todos.push("ship".to_string())));
+    assert!(optimistic.update::<Vec<String>>(key, |todos| todos.push("celebrate".to_string())));
+    // A key without a cached value is left alone
+    assert!(!optimistic.update::<Vec<String>>("GET /api/missing", |todos| todos.clear()));
+    assert_eq!(cache::get::<Vec<String>>(key).unwrap().value.len(), 3);
+
+    optimistic.settle(false);
+    assert_eq!(
+        cache::get::<Vec<String>>(key).unwrap().value,
+        vec!["write docs".to_string()]
+    );
+    assert!(cache::get::<Vec<String>>("GET /api/missing").is_none());
+    assert_eq!(notified.get(), 3);
+
+    // Committed changes stay, and dropped subscriptions aren't called anymore
+    drop(subscription);
+    let mut optimistic = OptimisticUpdate::new();
+    optimistic.update::<Vec<String>>(key, |todos| todos.clear());
+    optimistic.settle(true);
+    assert!(cache::get::<Vec<String>>(key).unwrap().value.is_empty());
+    assert_eq!(notified.get(), 3);
+}
+
+#[test]
+fn test_scopes_are_isolated() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use yew_extra::cache::{CacheConfig, CacheScope, OptimisticUpdate};
+
+    let key = "GET /api/tenant";
+    let scope = CacheScope::new(CacheConfig::new().max_entries(1));
+    let other = CacheScope::new(CacheConfig::default());
+    assert_ne!(scope, other);
+    assert_eq!(scope, scope.clone());
+    assert_eq!(CacheScope::global(), CacheScope::global());
+
+    scope.insert(key, "acme".to_string());
+    assert_eq!(scope.get::<String>(key).unwrap().value, "acme");
+    assert!(other.get::<String>(key).is_none());
+    assert!(cache::get::<String>(key).is_none());
+    assert_eq!(scope.with(|cache| cache.config().max_entries), 1);
+
+    // Subscribers only hear about their own scope
+    let notified = Rc::new(Cell::new(0));
+    let counter = notified.clone();
+    let _subscription = scope.subscribe(key, move || counter.set(counter.get() + 1));
+    cache::insert(key, "global".to_string());
+    cache::update::<String>(key, |tenant| tenant.push('!'));
+    assert_eq!(notified.get(), 0);
+
+    let mut optimistic = OptimisticUpdate::in_scope(scope.clone());
+    optimistic.update::<String>(key, |tenant| tenant.make_ascii_uppercase());
+    assert_eq!(scope.get::<String>(key).unwrap().value, "ACME");
+    optimistic.rollback();
+    assert_eq!(scope.get::<String>(key).unwrap().value, "acme");
+    assert_eq!(cache::get::<String>(key).unwrap().value, "global!");
+    assert_eq!(notified.get(), 2);
+
+    cache::remove(key);
+}
+
+#[tokio::test]
+async fn test_warm_cache_skips_fresh_keys() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use yew_extra::cache::Prefetch;
+
+    cache::configure(CacheConfig::new().stale_time(Duration::from_secs(60)));
+    cache::insert("GET /api/warm/fresh", 1u32);
+
+    let fetched = Rc::new(Cell::new(0));
+    let prefetch = |key: &'static str, result: Result<(), String>| {
+        let fetched = fetched.clone();
+        Prefetch::new(key, async move {
+            fetched.set(fetched.get() + 1);
+            cache::insert(key, 2u32);
+            result
+        })
+    };
+    let results = cache::warm_cache([
+        prefetch("GET /api/warm/fresh", Ok(())),
+        prefetch("GET /api/warm/user", Ok(())),
+        prefetch("GET /api/warm/broken", Err("500".to_string())),
+    ])
+    .await;
+
+    assert_eq!(
+        results,
+        vec![
+            ("GET /api/warm/fresh".to_string(), Ok(())),
+            ("GET /api/warm/user".to_string(), Ok(())),
+            ("GET /api/warm/broken".to_string(), Err("500".to_string())),
+        ]
+    );
+    assert_eq!(fetched.get(), 2);
+    assert_eq!(cache::get::<u32>("GET /api/warm/fresh").unwrap().value, 1);
+    assert_eq!(cache::get::<u32>("GET /api/warm/user").unwrap().value, 2);
+}
+
+#[tokio::test]
+async fn test_identical_requests_share_the_running_one() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use tokio::sync::oneshot;
+    use yew_extra::cache::CacheScope;
+
+    let scope = CacheScope::new(CacheConfig::default());
+    let key = "GET /api/dashboard";
+    let sent = Rc::new(Cell::new(0));
+    let (reply, response) = oneshot::channel::<Result<u32, String>>();
+
+    let leader = scope.dedupe(key, {
+        let sent = sent.clone();
+        async move {
+            sent.set(sent.get() + 1);
+            response.await.unwrap()
+        }
+    });
+    let follower = scope.dedupe(key, {
+        let sent = sent.clone();
+        async move {
+            sent.set(sent.get() + 1);
+            Ok(0u32)
+        }
+    });
+    let (first, second, _) = tokio::join!(leader, follower, async {
+        tokio::task::yield_now().await;
+        assert!(scope.is_fetching(key));
+        reply.send(Ok(7)).unwrap();
+    });
+
+    assert_eq!((first, second), (Ok(7), Ok(7)));
+    assert_eq!(sent.get(), 1);
+    assert!(!scope.is_fetching(key));
+
+    // A finished request isn't reused
+    assert_eq!(scope.dedupe(key, async { Ok(2u32) }).await, Ok(2));
+}
+
+#[tokio::test]
+async fn test_waiting_requests_run_when_the_running_one_is_dropped() {
+    use futures_util::FutureExt;
+    use yew_extra::cache::CacheScope;
+
+    let scope = CacheScope::new(CacheConfig::default());
+    let key = "GET /api/report";
+    let mut leader = Box::pin(scope.dedupe(key, std::future::pending::<Result<u32, String>>()));
+    assert!((&mut leader).now_or_never().is_none());
+    assert!(scope.is_fetching(key));
+
+    let follower = scope.dedupe(key, async { Ok(3u32) });
+    let (result, _) = tokio::join!(follower, async {
+        tokio::task::yield_now().await;
+        drop(leader);
+    });
+    assert_eq!(result, Ok(3));
+    assert!(!scope.is_fetching(key));
+}
+
+#[test]
+fn test_invalidation_marks_the_path_stale_and_notifies_hooks() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use yew_extra::cache::CacheScope;
+
+    let scope = CacheScope::new(CacheConfig::new().stale_time(Duration::from_secs(60)));
+    scope.insert("GET /api/todos?page=1", 1u32);
+    scope.insert("GET https://api.example.com/api/todos?page=2", 2u32);
+    scope.insert("GET /api/todos/7", 7u32);
+    scope.insert("GET /api/users", 0u32);
+
+    let invalidated = Rc::new(RefCell::new(Vec::new()));
+    let listen = |key: &'static str| {
+        let invalidated = invalidated.clone();
+        scope.subscribe_invalidation(key, move || invalidated.borrow_mut().push(key))
+    };
+    // Hooks are notified whether or not a response is cached
+    let _subscriptions = [
+        listen("GET /api/todos?page=1"),
+        listen("GET /api/todos?page=3 tenant=acme"),
+        listen("GET /api/users"),
+    ];
+
+    scope.invalidate("/api/todos");
+    assert!(scope.get::<u32>("GET /api/todos?page=1").unwrap().is_stale);
+    assert!(scope.with(|cache| !cache.is_fresh("GET https://api.example.com/api/todos?page=2")));
+    assert!(!scope.get::<u32>("GET /api/todos/7").unwrap().is_stale);
+    assert!(!scope.get::<u32>("GET /api/users").unwrap().is_stale);
+    invalidated.borrow_mut().sort();
+    assert_eq!(
+        *invalidated.borrow(),
+        ["GET /api/todos?page=1", "GET /api/todos?page=3 tenant=acme"]
+    );
+
+    // A new response is fresh again
+    scope.insert("GET /api/todos?page=1", 1u32);
+    assert!(!scope.get::<u32>("GET /api/todos?page=1").unwrap().is_stale);
+
+    invalidated.borrow_mut().clear();
+    scope.invalidate_matching("/api/todos");
+    assert!(scope.get::<u32>("GET /api/todos/7").unwrap().is_stale);
+    assert_eq!(invalidated.borrow().len(), 2);
+    assert!(!scope.get::<u32>("GET /api/users").unwrap().is_stale);
+}
+
+#[test]
+fn test_invalidation_ignores_the_base_path() {
+    use yew_extra::base_path::{api_base, set_base_path};
+    use yew_extra::cache::CacheScope;
+
+    set_base_path("/myapp");
+    let scope = CacheScope::new(CacheConfig::new().stale_time(Duration::from_secs(60)));
+    let todos = cache::request_key(
+        &format!("{}/api/todos", api_base(None)),
+        &RequestOptions::new(),
+    );
+    assert_eq!(todos, "GET /myapp/api/todos");
+    scope.insert(&todos, 1u32);
+    scope.insert("GET /myapp/api/users", 2u32);
+
+    scope.invalidate("/api/todos");
+    assert!(scope.get::<u32>(&todos).unwrap().is_stale);
+    assert!(!scope.get::<u32>("GET /myapp/api/users").unwrap().is_stale);
+
+    scope.invalidate_matching("/api");
+    assert!(scope.get::<u32>("GET /myapp/api/users").unwrap().is_stale);
+    set_base_path("/");
+
+    // The path of a `YEW_API_BASE` is ignored as well
+    let base = api_base(Some("https://api.example.com/v1/"));
+    let users = format!("GET {}/api/users", base);
+    scope.insert(&users, 3u32);
+    scope.invalidate("/api/users");
+    assert!(scope.get::<u32>(&users).unwrap().is_stale);
+}
@@ -0,0 +1,24 @@
+// Checks the framing of messages on WebTransport streams
+use yew_extra::channel::{encode_frame, FrameDecoder};
+
+#[test]
+fn test_frames_are_length_prefixed() {
+    assert_eq!(encode_frame(b"hi"), vec![0, 0, 0, 2, b'h', b'i']);
+    assert_eq!(encode_frame(b""), vec![0, 0, 0, 0]);
+}
+
+#[test]
+fn test_decoder_splits_chunks_into_messages() {
+    let mut stream = encode_frame(b"first");
+    stream.extend(encode_frame(b""));
+    stream.extend(encode_frame(b"second message"));
+
+    let mut decoder = FrameDecoder::new();
+    let (head, tail) = stream.split_at(7);
+    assert_eq!(decoder.push(head), Vec::<Vec<u8>>::new());
+    assert!(decoder.has_partial());
+    let (middle, tail) = tail.split_at(10);
+    assert_eq!(decoder.push(middle), vec![b"first".to_vec(), Vec::new()]);
+    assert_eq!(decoder.push(tail), vec![b"second message".to_vec()]);
+    assert!(!decoder.has_partial());
+}
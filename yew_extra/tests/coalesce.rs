@@ -0,0 +1,158 @@
+// Checks that identical concurrent requests share one run of the handler
+#![cfg(feature = "axum-08")]
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Request, Response};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::Notify;
+use yew_extra::coalesce::{coalesce_key, run_coalesced, COALESCED_HEADER, MAX_SHARED_BODY};
+
+async fn handler(runs: &AtomicUsize, release: &Notify) -> Response<Body> {
+    runs.fetch_add(1, Ordering::SeqCst);
+    release.notified().await;
+    Response::new(Body::from("report"))
+}
+
+async fn body(response: Response<Body>) -> String {
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+fn key(uri: &str) -> String {
+    coalesce_key(&Request::get(uri).body(()).unwrap())
+}
+
+#[test]
+fn test_coalesce_key_includes_query() {
+    assert_eq!(key("/api/report?year=2024"), "GET /api/report?year=2024");
+    assert_ne!(key("/api/report?year=2024"), key("/api/report?year=2025"));
+}
+
+#[tokio::test]
+async fn test_identical_requests_share_one_run() {
+    let runs = AtomicUsize::new(0);
+    let release = Notify::new();
+    let key = key("/api/shared?year=2024");
+
+    let (first, second, third, ()) = tokio::join!(
+        run_coalesced(key.clone(), handler(&runs, &release)),
+        run_coalesced(key.clone(), handler(&runs, &release)),
+        run_coalesced(key.clone(), handler(&runs, &release)),
+        async { release.notify_waiters() },
+    );
+
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    assert!(first.headers().get(COALESCED_HEADER).is_none());
+    assert_eq!(second.headers()[COALESCED_HEADER], "true");
+    assert_eq!(body(first).await, "report");
+    assert_eq!(body(second).await, "report");
+    assert_eq!(body(third).await, "report");
+}
+
+#[tokio::test]
+async fn test_later_requests_run_again() {
+    let runs = AtomicUsize::new(0);
+    let key = key("/api/sequential");
+
+    for _ in 0..2 {
+        let response = run_coalesced(key.clone(), async {
+            runs.fetch_add(1, Ordering::SeqCst);
+            Response::new(Body::from("fresh"))
+        })
+        .await;
+        assert!(response.headers().get(COALESCED_HEADER).is_none());
+    }
+
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_waiting_request_runs_when_leader_is_dropped() {
+    let runs = AtomicUsize::new(0);
+    let release = Notify::new();
+    let key = key("/api/dropped");
+
+    let mut leader = Box::pin(run_coalesced(key.clone(), handler(&runs, &release)));
+    // Start the leading request, then give up on it
+    assert!(poll_once(leader.as_mut()).await.is_none());
+
+    let mut follower = Box::pin(run_coalesced(key.clone(), async {
+        runs.fetch_add(1, Ordering::SeqCst);
+        Response::new(Body::from("own run"))
+    }));
+    // The follower waits for the leader until it is dropped
+    assert!(poll_once(follower.as_mut()).await.is_none());
+    drop(leader);
+
+    assert_eq!(body(follower.await).await, "own run");
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+}
+
+// Runs a leader answering with `response` next to a follower, returning the follower's
+// response and the number of handler runs
+async fn follow(key: String, response: fn() -> Response<Body>) -> (Response<Body>, usize) {
+    let runs = AtomicUsize::new(0);
+    let release = Notify::new();
+    let leader = async {
+        runs.fetch_add(1, Ordering::SeqCst);
+        release.notified().await;
+        response()
+    };
+    let follower = async {
+        runs.fetch_add(1, Ordering::SeqCst);
+        Response::new(Body::from("own run"))
+    };
+
+    let (first, second, ()) = tokio::join!(
+        run_coalesced(key.clone(), leader),
+        run_coalesced(key, follower),
+        async { release.notify_waiters() },
+    );
+    assert!(first.headers().get(COALESCED_HEADER).is_none());
+    (second, runs.load(Ordering::SeqCst))
+}
+
+#[tokio::test]
+async fn test_responses_for_one_caller_are_not_shared() {
+    let (response, runs) = follow(key("/api/session"), || {
+        Response::builder()
+            .header(header::SET_COOKIE, "session=leader")
+            .body(Body::from("leader"))
+            .unwrap()
+    })
+    .await;
+    assert!(response.headers().get(header::SET_COOKIE).is_none());
+    assert_eq!(body(response).await, "own run");
+    assert_eq!(runs, 2);
+
+    let (response, runs) = follow(key("/api/account"), || {
+        Response::builder()
+            .header(header::CACHE_CONTROL, "no-store, private")
+            .body(Body::from("leader"))
+            .unwrap()
+    })
+    .await;
+    assert_eq!(body(response).await, "own run");
+    assert_eq!(runs, 2);
+}
+
+#[tokio::test]
+async fn test_large_bodies_are_not_shared() {
+    let large = || Response::new(Body::from(vec![b'x'; MAX_SHARED_BODY + 1]));
+    let (response, runs) = follow(key("/api/export"), large).await;
+    assert_eq!(body(response).await, "own run");
+    assert_eq!(runs, 2);
+
+    // The leading request still gets all of it
+    let response = run_coalesced(key("/api/export"), async { large() }).await;
+    assert_eq!(body(response).await.len(), MAX_SHARED_BODY + 1);
+}
+
+async fn poll_once<F: std::future::Future + Unpin>(mut future: F) -> Option<F::Output> {
+    std::future::poll_fn(|cx| {
+        std::task::Poll::Ready(match std::pin::Pin::new(&mut future).poll(cx) {
+            std::task::Poll::Ready(output) => Some(output),
+            std::task::Poll::Pending => None,
+        })
+    })
+    .await
+}
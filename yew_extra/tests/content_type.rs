@@ -0,0 +1,32 @@
+// Checks the media type stamped on the responses of endpoints declared with `content_type`
+#![cfg(feature = "axum-08")]
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use yew_extra::content_type::with_content_type;
+
+const MEDIA_TYPE: &str = "application/vnd.shop.v2+json";
+
+#[test]
+fn test_successful_json_responses_are_stamped() {
+    let response = with_content_type(Json(vec![1, 2]).into_response(), MEDIA_TYPE);
+    assert_eq!(response.headers()[CONTENT_TYPE], MEDIA_TYPE);
+
+    let created = (StatusCode::CREATED, Json("done")).into_response();
+    let response = with_content_type(created, MEDIA_TYPE);
+    assert_eq!(response.headers()[CONTENT_TYPE], MEDIA_TYPE);
+}
+
+#[test]
+fn test_errors_and_other_bodies_are_left_alone() {
+    let error = (StatusCode::BAD_REQUEST, Json("invalid")).into_response();
+    let response = with_content_type(error, MEDIA_TYPE);
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+
+    let response = with_content_type("plain".into_response(), MEDIA_TYPE);
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        "text/plain; charset=utf-8"
+    );
+}
@@ -0,0 +1,78 @@
+// Checks the pool provided to the router and the connection shared by a request
+#![cfg(feature = "axum-08")]
+use axum::body::{to_bytes, Body};
+use axum::extract::Request;
+use axum::routing::get;
+use axum::Router;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tower::ServiceExt;
+use yew_extra::db::{db_connection, provide_db, use_db};
+use yew_extra::{clear_request_parts, provide_request_parts, request_scope, ExtractError};
+
+#[derive(Clone, Default)]
+struct Pool {
+    acquired: Arc<AtomicUsize>,
+}
+
+impl Pool {
+    async fn acquire(&self) -> Result<usize, Infallible> {
+        Ok(self.acquired.fetch_add(1, Ordering::SeqCst))
+    }
+}
+
+async fn handler(request: Request) -> String {
+    request_scope(async move {
+        let (parts, _) = request.into_parts();
+        provide_request_parts(parts).await;
+        let pool: Pool = use_db().await.unwrap();
+        let first = *db_connection(|| pool.acquire()).await.unwrap();
+        let second = *db_connection(|| pool.acquire()).await.unwrap();
+        clear_request_parts().await;
+        format!("{} {}", first, second)
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_requests_share_the_pool_and_keep_their_connection() {
+    let pool = Pool::default();
+    let app = provide_db(Router::new().route("/", get(handler)), pool.clone());
+
+    for expected in ["0 0", "1 1"] {
+        let response = app
+            .clone()
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], expected.as_bytes());
+    }
+    assert_eq!(pool.acquired.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_missing_pool() {
+    request_scope(async {
+        let (parts, _) = Request::new(()).into_parts();
+        provide_request_parts(parts).await;
+        let pool = use_db::<Pool>().await;
+        clear_request_parts().await;
+
+        let Err(ExtractError::MissingParts(message)) = pool else {
+            panic!("expected a missing pool");
+        };
+        assert!(message.contains("provide_db()"), "{}", message);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_outside_a_request_every_call_acquires() {
+    clear_request_parts().await;
+    let pool = Pool::default();
+    assert_eq!(*db_connection(|| pool.acquire()).await.unwrap(), 0);
+    assert_eq!(*db_connection(|| pool.acquire()).await.unwrap(), 1);
+    assert!(use_db::<Pool>().await.is_err());
+}
@@ -0,0 +1,60 @@
+// Checks that work queued with after_response waits for the response to be sent
+#![cfg(feature = "axum-08")]
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, Response};
+use tokio::sync::oneshot;
+use yew_extra::{
+    after_response, clear_request_parts, provide_request_parts, request_scope, run_after_response,
+    uri,
+};
+
+#[tokio::test]
+async fn test_tasks_run_after_the_body_is_sent() {
+    request_scope(async {
+        let (parts, _) = Request::get("/api/signup?plan=pro")
+            .body(())
+            .unwrap()
+            .into_parts();
+        provide_request_parts(parts).await;
+
+        let (sender, mut receiver) = oneshot::channel();
+        after_response(async move {
+            let uri = uri().await.map(|uri| uri.to_string());
+            let _ = sender.send(uri);
+        })
+        .await
+        .unwrap();
+
+        let response = run_after_response(Response::new(Body::from("welcome"))).await;
+        clear_request_parts().await;
+        tokio::task::yield_now().await;
+        assert!(
+            receiver.try_recv().is_err(),
+            "ran before the response was sent"
+        );
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"welcome");
+        assert_eq!(receiver.await.unwrap().unwrap(), "/api/signup?plan=pro");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_nothing_queued_keeps_the_response() {
+    request_scope(async {
+        let (parts, _) = Request::new(()).into_parts();
+        provide_request_parts(parts).await;
+        let response = run_after_response(Response::new(Body::from("done"))).await;
+        clear_request_parts().await;
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"done");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_queueing_needs_a_request() {
+    assert!(after_response(async {}).await.is_err());
+}
@@ -0,0 +1,73 @@
+// Checks the headers and console warnings of deprecated endpoints
+#![cfg(feature = "axum-08")]
+use axum::http::header::LINK;
+use axum::response::IntoResponse;
+use axum::Json;
+use yew_extra::deprecation::{unix_time, warn_once, with_deprecation, Deprecation};
+
+const DEPRECATION: Deprecation = Deprecation::new()
+    .since("2025-06-01")
+    .sunset("2025-12-01")
+    .replacement("/api/v2/users");
+
+#[test]
+fn test_header_values() {
+    assert_eq!(DEPRECATION.deprecation_header(), "@1748736000");
+    assert_eq!(
+        DEPRECATION.sunset_header().unwrap(),
+        "Mon, 01 Dec 2025 00:00:00 GMT"
+    );
+    assert_eq!(
+        DEPRECATION.link_header().unwrap(),
+        "</api/v2/users>; rel=\"successor-version\""
+    );
+
+    let bare = Deprecation::new();
+    assert_eq!(bare.deprecation_header(), "true");
+    assert_eq!(bare.sunset_header(), None);
+    assert_eq!(bare.link_header(), None);
+}
+
+#[test]
+fn test_dates() {
+    assert_eq!(unix_time("1970-01-01"), Some(0));
+    assert_eq!(unix_time("2000-02-29"), Some(951_782_400));
+    assert_eq!(unix_time("2023-02-29"), None);
+    assert_eq!(unix_time("2025-13-01"), None);
+    assert_eq!(unix_time("next year"), None);
+
+    let leap_day = Deprecation::new().sunset("2024-02-29");
+    assert_eq!(
+        leap_day.sunset_header().unwrap(),
+        "Thu, 29 Feb 2024 00:00:00 GMT"
+    );
+}
+
+#[test]
+fn test_responses_carry_the_headers() {
+    let response = (
+        [(LINK, "</api/users?page=2>; rel=\"next\"")],
+        Json(vec![1, 2]),
+    )
+        .into_response();
+    let response = with_deprecation(response, &DEPRECATION);
+
+    let headers = response.headers();
+    assert_eq!(headers["deprecation"], "@1748736000");
+    assert_eq!(headers["sunset"], "Mon, 01 Dec 2025 00:00:00 GMT");
+    // Links of the function are kept
+    let links: Vec<_> = headers.get_all(LINK).iter().collect();
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[1], "</api/v2/users>; rel=\"successor-version\"");
+}
+
+#[test]
+fn test_clients_warn_once_per_endpoint() {
+    assert_eq!(
+        DEPRECATION.warning("GET", "/api/users"),
+        "GET /api/users is deprecated since 2025-06-01 and will be removed on 2025-12-01, use /api/v2/users instead"
+    );
+    assert!(warn_once("GET", "/api/users", &DEPRECATION));
+    assert!(!warn_once("GET", "/api/users", &DEPRECATION));
+    assert!(warn_once("DELETE", "/api/users", &DEPRECATION));
+}
@@ -0,0 +1,72 @@
+// Checks the docs page built from registered routes
+#![cfg(feature = "axum-08")]
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Method, Request, Response, StatusCode};
+use std::future::Future;
+use std::pin::Pin;
+use tower::ServiceExt;
+use yew_extra::docs::{docs_page, docs_routes, documented_routes, DocRoute};
+use yew_extra::route_registry::RouteInfo;
+
+fn ok_handler_wrapper(_req: Request<Body>) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+    Box::pin(async { Response::new(Body::empty()) })
+}
+
+inventory::submit! {
+    RouteInfo::new("/api/todos", Method::GET, ok_handler_wrapper)
+}
+
+inventory::submit! {
+    RouteInfo::new("/api/todos", Method::POST, ok_handler_wrapper)
+}
+
+inventory::submit! {
+    RouteInfo::new("/api/</script>", Method::GET, ok_handler_wrapper)
+}
+
+#[test]
+fn test_registered_routes_are_listed() {
+    let routes = documented_routes();
+    assert_eq!(
+        routes,
+        vec![
+            DocRoute {
+                method: "GET".to_string(),
+                path: "/api/</script>".to_string(),
+            },
+            DocRoute {
+                method: "GET".to_string(),
+                path: "/api/todos".to_string(),
+            },
+            DocRoute {
+                method: "POST".to_string(),
+                path: "/api/todos".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_page_embeds_the_routes() {
+    let page = docs_page();
+    assert!(page.contains(r#"{"method":"POST","path":"/api/todos"}"#));
+    // The path can't close the script element
+    assert!(page.contains(r#""path":"/api/<\/script>""#));
+    assert_eq!(page.matches("</script>").count(), 1);
+}
+
+#[tokio::test]
+async fn test_page_is_served_at_api_docs() {
+    let response = docs_routes::<()>()
+        .oneshot(Request::get("/api/docs").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/html; charset=utf-8"
+    );
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(String::from_utf8_lossy(&body).contains("/api/todos"));
+}
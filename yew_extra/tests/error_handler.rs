@@ -0,0 +1,38 @@
+// Checks that failed requests reach the app's error handler
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew_extra::error_handler::{
+    clear_error_handler, report_failure, set_error_handler, RequestFailure,
+};
+
+fn failure(status: Option<u16>) -> RequestFailure {
+    RequestFailure {
+        method: "GET",
+        path: "/api/account",
+        status,
+        message: "Unauthorized".to_string(),
+    }
+}
+
+#[test]
+fn test_failures_reach_the_registered_handler() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let recorded = seen.clone();
+    set_error_handler(move |failure: &RequestFailure| recorded.borrow_mut().push(failure.clone()));
+
+    report_failure(failure(Some(401)));
+    report_failure(failure(None));
+    assert_eq!(*seen.borrow(), vec![failure(Some(401)), failure(None)]);
+
+    clear_error_handler();
+    report_failure(failure(Some(500)));
+    assert_eq!(seen.borrow().len(), 2);
+}
+
+#[test]
+fn test_failures_are_classified_by_status() {
+    assert!(failure(Some(401)).is_unauthorized());
+    assert!(!failure(Some(403)).is_unauthorized());
+    assert!(failure(Some(503)).is_server_error());
+    assert!(!failure(None).is_server_error());
+}
@@ -0,0 +1,143 @@
+// Checks the bucket assigned to visitors and kept in their cookies
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::header::{COOKIE, SET_COOKIE};
+use axum::http::{Request, Response};
+use yew::prelude::*;
+use yew::ServerRenderer;
+use yew_extra::experiment::{bucket, buckets, use_bucket, Experiment, ExperimentBuckets};
+use yew_extra::{
+    apply_response_headers, clear_request_parts, provide_request_parts, request_scope,
+};
+
+const CHECKOUT: Experiment = Experiment::new("checkout", &[("control", 50), ("one_page", 50)]);
+const BANNER: Experiment = Experiment::new("banner", &[("off", 1), ("on", 0)]);
+
+async fn provide_request(cookie: Option<&str>) {
+    let mut request = Request::get("/cart");
+    if let Some(cookie) = cookie {
+        request = request.header(COOKIE, cookie);
+    }
+    let (parts, _body) = request.body(()).unwrap().into_parts();
+    provide_request_parts(parts).await;
+}
+
+async fn set_cookies() -> Vec<String> {
+    let response: Response<Body> = apply_response_headers(Response::new(Body::empty())).await;
+    response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .map(|value| value.to_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_assignment_is_deterministic_and_weighted() {
+    assert_eq!(CHECKOUT.assign("visitor-1"), CHECKOUT.assign("visitor-1"));
+    let one_page = (0..1000)
+        .filter(|visitor| CHECKOUT.assign(&visitor.to_string()) == Some("one_page"))
+        .count();
+    assert!((400..600).contains(&one_page), "{}", one_page);
+    assert!((0..100).all(|visitor| BANNER.assign(&visitor.to_string()) == Some("off")));
+    assert_eq!(Experiment::new("empty", &[]).assign("visitor-1"), None);
+}
+
+#[tokio::test]
+async fn test_new_visitors_get_an_id_and_a_bucket() {
+    request_scope(async {
+        provide_request(None).await;
+        let first = bucket(&CHECKOUT).await.unwrap();
+        let second = bucket(&CHECKOUT).await.unwrap();
+        bucket(&BANNER).await.unwrap();
+        let cookies = set_cookies().await;
+        clear_request_parts().await;
+
+        assert_eq!(first, second);
+        // One visitor id for the request, then the bucket of each experiment
+        assert_eq!(cookies.len(), 3, "{:?}", cookies);
+        assert!(cookies[0].starts_with("yew_visitor="));
+        assert_eq!(
+            cookies[1],
+            format!(
+                "yew_exp_checkout={}; Path=/; Max-Age=15552000; SameSite=Lax",
+                first
+            )
+        );
+        assert!(cookies[2].starts_with("yew_exp_banner=off;"));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_kept_buckets_win_over_the_assignment() {
+    request_scope(async {
+        let visitor = (0..)
+            .map(|visitor: u32| visitor.to_string())
+            .find(|visitor| CHECKOUT.assign(visitor) == Some("control"))
+            .unwrap();
+        provide_request(Some(&format!(
+            "yew_visitor={}; yew_exp_checkout=one_page",
+            visitor
+        )))
+        .await;
+        assert_eq!(bucket(&CHECKOUT).await.unwrap(), "one_page");
+        assert!(set_cookies().await.is_empty());
+        clear_request_parts().await;
+
+        // A bucket the experiment no longer has is assigned again, for the same visitor
+        provide_request(Some(&format!(
+            "yew_visitor={}; yew_exp_checkout=three_step",
+            visitor
+        )))
+        .await;
+        assert_eq!(bucket(&CHECKOUT).await.unwrap(), "control");
+        let cookies = set_cookies().await;
+        clear_request_parts().await;
+        assert_eq!(cookies.len(), 1);
+        assert!(cookies[0].starts_with("yew_exp_checkout=control;"));
+    })
+    .await;
+}
+
+#[function_component(Banner)]
+fn banner() -> Html {
+    let bucket = use_bucket("banner");
+    html! { <p>{ bucket.unwrap_or_else(|| "none".to_string()) }</p> }
+}
+
+#[derive(Properties, PartialEq)]
+struct RootProps {
+    buckets: ExperimentBuckets,
+}
+
+#[function_component(Root)]
+fn root(props: &RootProps) -> Html {
+    html! {
+        <ContextProvider<ExperimentBuckets> context={props.buckets.clone()}>
+            <Banner />
+        </ContextProvider<ExperimentBuckets>>
+    }
+}
+
+#[tokio::test]
+async fn test_render_and_cookies_agree() {
+    request_scope(async {
+        provide_request(Some("yew_exp_banner=on")).await;
+        let server = buckets(&[BANNER]).await.unwrap();
+        clear_request_parts().await;
+        assert_eq!(server.get("banner"), Some("on"));
+        // The browser reads the same buckets from its cookies
+        assert_eq!(
+            ExperimentBuckets::from_cookie_header("theme=dark; yew_exp_banner=on"),
+            server
+        );
+
+        let body = ServerRenderer::<Root>::with_props(move || RootProps { buckets: server })
+            .hydratable(false)
+            .render()
+            .await;
+        assert_eq!(body, "<p>on</p>");
+    })
+    .await;
+}
@@ -0,0 +1,388 @@
+// Checks that extractions within a request share the same request parts
+#![cfg(feature = "axum-08")]
+use axum::body::{to_bytes, Body, Bytes};
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::http::{HeaderMap, Method, Request};
+use axum::response::IntoResponse;
+use serde::Deserialize;
+use yew_extra::headers::authorization::Bearer;
+use yew_extra::headers::{Authorization, ContentLength, ContentType};
+use yew_extra::{
+    clear_request_parts, extract, extract_body, extract_json, extract_query, header, headers,
+    method, optional_header, provide_request_body, provide_request_parts, query_string,
+    read_request_body, request_scope, run_blocking, uri, ExtractError, RequestContext,
+};
+
+/// Counts how often it has been extracted from the request, like extractors caching
+/// their result in the extensions do
+struct Extractions(u32);
+
+impl<S: Send + Sync> FromRequestParts<S> for Extractions {
+    type Rejection = ();
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let count = parts.extensions.get::<u32>().copied().unwrap_or_default() + 1;
+        parts.extensions.insert(count);
+        Ok(Extractions(count))
+    }
+}
+
+fn request_parts() -> Parts {
+    let (parts, _body) = Request::post("/api/items")
+        .header("x-request-id", "42")
+        .body(())
+        .unwrap()
+        .into_parts();
+    parts
+}
+
+#[tokio::test]
+async fn test_extractors_share_request_parts() {
+    request_scope(async {
+        provide_request_parts(request_parts()).await;
+
+        let Extractions(first) = extract().await.unwrap();
+        let Extractions(second) = extract().await.unwrap();
+        let method: Method = extract().await.unwrap();
+
+        clear_request_parts().await;
+
+        assert_eq!((first, second), (1, 2));
+        assert_eq!(method, Method::POST);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_concurrent_extractions() {
+    request_scope(async {
+        provide_request_parts(request_parts()).await;
+
+        let (headers, method) = tokio::join!(extract::<HeaderMap>(), extract::<Method>());
+
+        clear_request_parts().await;
+
+        assert_eq!(headers.unwrap()["x-request-id"], "42");
+        assert_eq!(method.unwrap(), Method::POST);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_missing_parts() {
+    clear_request_parts().await;
+
+    assert!(extract::<Method>().await.is_err());
+}
+
+#[tokio::test]
+async fn test_extract_outside_a_scope() {
+    // There is no request to read, and nothing is shared between tasks
+    let error = extract::<Method>().await.unwrap_err();
+    assert!(error.to_string().contains("request_scope()"), "{}", error);
+    assert!(matches!(
+        extract_body().await,
+        Err(ExtractError::MissingBody(_))
+    ));
+}
+
+#[cfg(debug_assertions)]
+#[tokio::test]
+#[should_panic(expected = "outside a request_scope()")]
+async fn test_parts_provided_outside_a_scope_panic_in_debug_builds() {
+    provide_request_parts(request_parts()).await;
+}
+
+#[tokio::test]
+async fn test_run_blocking_keeps_request_parts() {
+    request_scope(async {
+        provide_request_parts(request_parts()).await;
+
+        let method = run_blocking(async {
+            // Deliberately blocking work, on a thread of its own
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            extract::<Method>().await
+        })
+        .await;
+
+        clear_request_parts().await;
+
+        assert_eq!(method.unwrap(), Method::POST);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_request_context_extracts_without_provided_parts() {
+    let mut cx = RequestContext::new(request_parts());
+
+    let Extractions(first) = cx.extract().await.unwrap();
+    let Extractions(second) = cx.extract().await.unwrap();
+    let headers: HeaderMap = cx.extract().await.unwrap();
+
+    assert_eq!((first, second), (1, 2));
+    assert_eq!(headers.get("x-request-id").unwrap(), "42");
+    assert_eq!(cx.parts().method, Method::POST);
+
+    // Nothing was stored for extract()
+    assert!(extract::<Method>().await.is_err());
+}
+
+#[tokio::test]
+async fn test_extract_body() {
+    request_scope(async {
+        provide_request_parts(request_parts()).await;
+        provide_request_body(Bytes::from_static(br#"{"event":"paid","amount":42}"#)).await;
+
+        let body = extract_body().await.unwrap();
+        let event: serde_json::Value = extract_json().await.unwrap();
+        let invalid = extract_json::<Vec<u32>>().await;
+        let blocking_body = run_blocking(async { extract_body().await }).await;
+
+        clear_request_parts().await;
+
+        assert_eq!(body.len(), 28);
+        assert_eq!(event["amount"], 42);
+        assert!(matches!(invalid, Err(ExtractError::ExtractionFailed(_))));
+        assert_eq!(blocking_body.unwrap(), body);
+        assert!(matches!(
+            extract_body().await,
+            Err(ExtractError::MissingBody(_))
+        ));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_read_request_body_limit() {
+    let body = read_request_body(Body::from("0123456789"), 10)
+        .await
+        .unwrap();
+    assert_eq!(body, "0123456789");
+
+    let response = read_request_body(Body::from("0123456789"), 9)
+        .await
+        .unwrap_err();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[derive(Debug, Deserialize)]
+struct Page {
+    page: u32,
+    per_page: Option<u32>,
+}
+
+// Only deserialized to see which field fails
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Order {
+    customer: String,
+    items: Vec<Item>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Item {
+    name: String,
+    price: u32,
+}
+
+async fn provide_query(uri: &str) {
+    let (parts, _body) = Request::get(uri).body(()).unwrap().into_parts();
+    provide_request_parts(parts).await;
+}
+
+fn invalid_fields(error: ExtractError) -> Vec<(String, Vec<String>)> {
+    match error {
+        ExtractError::InvalidFields(errors) => errors.fields.into_iter().collect(),
+        other => panic!("expected field errors, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_extract_query() {
+    request_scope(async {
+        provide_query("/api/items?page=2").await;
+        let page: Result<Page, _> = extract_query().await;
+        provide_query("/api/items?page=two&per_page=10").await;
+        let invalid: Result<Page, _> = extract_query().await;
+        provide_query("/api/items?per_page=10").await;
+        let missing: Result<Page, _> = extract_query().await;
+        clear_request_parts().await;
+
+        let page = page.unwrap();
+        assert_eq!((page.page, page.per_page), (2, None));
+        assert_eq!(
+            invalid_fields(invalid.unwrap_err()),
+            [(
+                "page".to_string(),
+                vec!["invalid digit found in string".to_string()]
+            )]
+        );
+        assert_eq!(
+            invalid_fields(missing.unwrap_err()),
+            [("page".to_string(), vec!["missing field `page`".to_string()])]
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_extract_json_names_the_field() {
+    request_scope(async {
+        provide_request_parts(request_parts()).await;
+        provide_request_body(Bytes::from_static(
+            br#"{"customer":"Ada","items":[{"name":"tea","price":3},{"name":"cake","price":"4"}]}"#,
+        ))
+        .await;
+        let wrong_type = extract_json::<Order>().await;
+        provide_request_body(Bytes::from_static(
+            br#"{"customer":"Ada","items":[{"price":3}]}"#,
+        ))
+        .await;
+        let missing = extract_json::<Order>().await;
+        provide_request_body(Bytes::from_static(b"{not json")).await;
+        let syntax = extract_json::<Order>().await;
+        clear_request_parts().await;
+
+        assert_eq!(
+            invalid_fields(wrong_type.unwrap_err()),
+            [(
+                "items[1].price".to_string(),
+                vec!["invalid type: string \"4\", expected u32".to_string()]
+            )]
+        );
+        assert_eq!(
+            invalid_fields(missing.unwrap_err()),
+            [(
+                "items[0].name".to_string(),
+                vec!["missing field `name`".to_string()]
+            )]
+        );
+        assert!(matches!(syntax, Err(ExtractError::ExtractionFailed(_))));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_field_errors_answer_bad_request() {
+    request_scope(async {
+        provide_query("/api/items?page=-1").await;
+        let error = extract_query::<Page>().await.unwrap_err();
+        clear_request_parts().await;
+
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["fields"]["page"][0], "invalid digit found in string");
+
+        let response = extract_query::<Page>().await.unwrap_err().into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_typed_headers() {
+    request_scope(async {
+        let (parts, _body) = Request::get("/api/items")
+            .header("authorization", "Bearer secret-token")
+            .header("content-length", "not a number")
+            .body(())
+            .unwrap()
+            .into_parts();
+        provide_request_parts(parts).await;
+
+        let bearer = header::<Authorization<Bearer>>().await;
+        let missing = header::<ContentType>().await;
+        let optional = optional_header::<ContentType>().await;
+        let invalid = optional_header::<ContentLength>().await;
+
+        clear_request_parts().await;
+
+        assert_eq!(bearer.unwrap().token(), "secret-token");
+        assert!(missing.unwrap_err().to_string().contains("content-type"));
+        assert_eq!(optional.unwrap(), None);
+        assert!(invalid.unwrap_err().to_string().contains("content-length"));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_request_accessors() {
+    request_scope(async {
+        let (parts, _body) = Request::put("/api/items/7?notify=true&by=ada")
+            .header("x-request-id", "42")
+            .body(())
+            .unwrap()
+            .into_parts();
+        provide_request_parts(parts).await;
+
+        let method = method().await.unwrap();
+        let uri = uri().await.unwrap();
+        let headers = headers().await.unwrap();
+        let query = query_string().await.unwrap();
+
+        provide_request_parts(request_parts()).await;
+        let no_query = query_string().await.unwrap();
+        clear_request_parts().await;
+
+        assert_eq!(method, Method::PUT);
+        assert_eq!(uri.path(), "/api/items/7");
+        assert_eq!(headers["x-request-id"], "42");
+        assert_eq!(query, "notify=true&by=ada");
+        assert_eq!(no_query, "");
+        assert!(matches!(
+            query_string().await,
+            Err(ExtractError::MissingParts(_))
+        ));
+    })
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_scoped_requests_keep_their_parts_across_threads() {
+    let requests: Vec<_> = (0..32)
+        .map(|id| {
+            tokio::spawn(request_scope(async move {
+                let (parts, _body) = Request::get(format!("/api/items/{}", id))
+                    .body(())
+                    .unwrap()
+                    .into_parts();
+                provide_request_parts(parts).await;
+                let mut paths = Vec::new();
+                for _ in 0..10 {
+                    // Give the runtime a chance to move the task to another worker
+                    tokio::task::yield_now().await;
+                    paths.push(uri().await.unwrap().path().to_string());
+                }
+                (id, paths)
+            }))
+        })
+        .collect();
+
+    for request in requests {
+        let (id, paths) = request.await.unwrap();
+        assert!(paths
+            .iter()
+            .all(|path| *path == format!("/api/items/{}", id)));
+    }
+}
+
+#[tokio::test]
+async fn test_scopes_drop_their_parts() {
+    let outer = request_scope(async {
+        provide_request_parts(request_parts()).await;
+        // A nested scope starts empty
+        let nested = request_scope(async { method().await }).await;
+        assert!(matches!(nested, Err(ExtractError::MissingParts(_))));
+        method().await.unwrap()
+    })
+    .await;
+
+    assert_eq!(outer, Method::POST);
+    assert!(matches!(method().await, Err(ExtractError::MissingParts(_))));
+}
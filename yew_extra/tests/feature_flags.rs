@@ -0,0 +1,51 @@
+// Checks the answers of flagged endpoints for each answer of the provider
+#![cfg(feature = "axum-08")]
+use axum::body::{to_bytes, Body};
+use axum::http::request::Parts;
+use axum::http::{Request, Response, StatusCode};
+use yew_extra::feature_flags::{check_flag, clear_flag_provider, set_flag_provider, FlagFuture};
+
+async fn error_of(response: Response<Body>) -> (StatusCode, String) {
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    (status, body["error"].as_str().unwrap().to_string())
+}
+
+// One test, the provider is global
+#[tokio::test]
+async fn test_flags_gate_the_endpoint() {
+    let request = Request::get("/api/billing")
+        .header("x-beta", "1")
+        .body(())
+        .unwrap();
+
+    // Without a provider, every flag is off
+    clear_flag_provider();
+    let (status, error) = error_of(check_flag(&request, "new_billing").await.unwrap_err()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(error, "No endpoint for GET /api/billing");
+
+    // The provider sees the flag and the request
+    set_flag_provider(|flag: String, request: &Parts| -> FlagFuture {
+        let beta = request.headers.contains_key("x-beta");
+        Box::pin(async move {
+            match flag.as_str() {
+                "new_billing" => Ok(beta),
+                _ => Err("flag service down".to_string()),
+            }
+        })
+    });
+    assert!(check_flag(&request, "new_billing").await.is_ok());
+    let other = Request::get("/api/billing").body(()).unwrap();
+    let (status, _) = error_of(check_flag(&other, "new_billing").await.unwrap_err()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, error) = error_of(check_flag(&request, "search_v2").await.unwrap_err()).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        error,
+        "Couldn't check the search_v2 flag: flag service down"
+    );
+    clear_flag_provider();
+}
@@ -0,0 +1,93 @@
+// Checks the flash cookie sent by a mutation and taken by the next page
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::header::{COOKIE, SET_COOKIE};
+use axum::http::{Request, Response};
+use yew_extra::flash::{flash, from_cookie_header, take_flash, Flash, FlashLevel};
+use yew_extra::{
+    apply_response_headers, clear_request_parts, provide_request_parts, request_scope, run_blocking,
+};
+
+async fn provide_request(cookie: Option<&str>) {
+    let mut request = Request::get("/profile");
+    if let Some(cookie) = cookie {
+        request = request.header(COOKIE, cookie);
+    }
+    let (parts, _body) = request.body(()).unwrap().into_parts();
+    provide_request_parts(parts).await;
+}
+
+fn set_cookies(response: &Response<Body>) -> Vec<String> {
+    response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .map(|value| value.to_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_cookie_value_round_trip() {
+    let flash = Flash::error("Couldn't save: 50% done; try again ✓");
+    let value = flash.to_cookie_value();
+    assert!(value
+        .bytes()
+        .all(|byte| byte.is_ascii_alphanumeric() || b"%-_.~".contains(&byte)));
+    assert_eq!(Flash::from_cookie_value(&value), Some(flash));
+
+    assert_eq!(Flash::from_cookie_value("nonsense"), None);
+    assert_eq!(Flash::from_cookie_value("info%3"), None);
+    assert_eq!(
+        from_cookie_header("theme=dark; yew_flash=success%3ASaved"),
+        Some(Flash::success("Saved"))
+    );
+}
+
+#[tokio::test]
+async fn test_mutation_sends_the_flash_cookie() {
+    request_scope(async {
+        provide_request(None).await;
+        flash("Profile saved").await.unwrap();
+        run_blocking(async { flash(Flash::warning("Almost full")).await })
+            .await
+            .unwrap();
+        let response = apply_response_headers(Response::new(Body::empty())).await;
+        clear_request_parts().await;
+
+        assert_eq!(
+            set_cookies(&response),
+            [
+                "yew_flash=info%3AProfile%20saved; Path=/; Max-Age=300; SameSite=Lax",
+                "yew_flash=warning%3AAlmost%20full; Path=/; Max-Age=300; SameSite=Lax",
+            ]
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_next_page_takes_the_flash_once() {
+    request_scope(async {
+        provide_request(Some("theme=dark; yew_flash=success%3AProfile%20saved")).await;
+        let taken = take_flash().await;
+        let response = apply_response_headers(Response::new(Body::empty())).await;
+        clear_request_parts().await;
+
+        let taken = taken.unwrap();
+        assert_eq!(taken.level, FlashLevel::Success);
+        assert_eq!(taken.message, "Profile saved");
+        assert_eq!(
+            set_cookies(&response),
+            ["yew_flash=; Path=/; Max-Age=0; SameSite=Lax"]
+        );
+
+        provide_request(Some("theme=dark")).await;
+        let taken = take_flash().await;
+        let response = apply_response_headers(Response::new(Body::empty())).await;
+        clear_request_parts().await;
+
+        assert_eq!(taken, None);
+        assert!(set_cookies(&response).is_empty());
+    })
+    .await;
+}
@@ -0,0 +1,47 @@
+// Checks the headers forwarded to requests made while handling another
+#![cfg(feature = "axum-08")]
+use axum::http::header::{AUTHORIZATION, COOKIE};
+use axum::http::{HeaderName, Request};
+use yew_extra::forward::{forwarded_headers, identity_headers};
+use yew_extra::{clear_request_parts, provide_request_parts, request_scope, ExtractError};
+
+#[tokio::test]
+async fn test_identity_headers_are_copied() {
+    request_scope(async {
+        let (parts, _body) = Request::get("/dashboard")
+            .header("cookie", "session=abc")
+            .header("cookie", "theme=dark")
+            .header("authorization", "Bearer token")
+            .header("x-tenant", "acme")
+            .header("accept", "text/html")
+            .body(())
+            .unwrap()
+            .into_parts();
+        provide_request_parts(parts).await;
+
+        let identity = identity_headers().await.unwrap();
+        let with_tenant = forwarded_headers(&[COOKIE, HeaderName::from_static("x-tenant")])
+            .await
+            .unwrap();
+
+        clear_request_parts().await;
+
+        assert_eq!(identity.len(), 3);
+        let cookies: Vec<_> = identity.get_all(COOKIE).iter().collect();
+        assert_eq!(cookies, ["session=abc", "theme=dark"]);
+        assert_eq!(identity[AUTHORIZATION], "Bearer token");
+        assert_eq!(with_tenant.len(), 3);
+        assert_eq!(with_tenant["x-tenant"], "acme");
+        assert!(!with_tenant.contains_key(AUTHORIZATION));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_outside_a_request() {
+    clear_request_parts().await;
+    assert!(matches!(
+        identity_headers().await,
+        Err(ExtractError::MissingParts(_))
+    ));
+}
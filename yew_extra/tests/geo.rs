@@ -0,0 +1,91 @@
+// Checks the CDN headers read into Geo
+#![cfg(feature = "axum-08")]
+use axum::http::{HeaderMap, HeaderValue, Request};
+use std::net::IpAddr;
+use yew_extra::geo::Geo;
+use yew_extra::{clear_request_parts, geo, provide_request_parts, request_scope, RequestContext};
+
+fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in pairs {
+        headers.append(*name, HeaderValue::from_str(value).unwrap());
+    }
+    headers
+}
+
+fn ip(ip: &str) -> IpAddr {
+    ip.parse().unwrap()
+}
+
+#[test]
+fn test_cloudflare() {
+    let geo = Geo::from_headers(&headers(&[
+        ("cf-ipcountry", "de"),
+        ("cf-connecting-ip", "2001:db8::1"),
+        ("x-forwarded-for", "203.0.113.9, 10.0.0.1"),
+    ]));
+    assert_eq!(geo.country.as_deref(), Some("DE"));
+    assert!(geo.in_countries(&["AT", "DE"]));
+    // The CDN's own header wins over the chain
+    assert_eq!(geo.client_ip, Some(ip("2001:db8::1")));
+    assert_eq!(geo.forwarded_for, [ip("203.0.113.9"), ip("10.0.0.1")]);
+
+    let unknown = Geo::from_headers(&headers(&[("cf-ipcountry", "XX")]));
+    assert_eq!(unknown.country, None);
+    assert!(!unknown.in_countries(&["XX"]));
+}
+
+#[test]
+fn test_other_cdns() {
+    let vercel = Geo::from_headers(&headers(&[
+        ("x-vercel-ip-country", "US"),
+        ("x-vercel-ip-country-region", "CA"),
+        ("x-vercel-ip-city", "San%20Francisco"),
+    ]));
+    assert_eq!(vercel.region.as_deref(), Some("CA"));
+    assert_eq!(vercel.city.as_deref(), Some("San Francisco"));
+
+    let fastly = Geo::from_headers(&headers(&[
+        ("fastly-geo-country-code", "JP"),
+        ("fastly-geo-city", "tokyo"),
+        ("fastly-client-ip", "198.51.100.7"),
+    ]));
+    assert_eq!(fastly.country.as_deref(), Some("JP"));
+    assert_eq!(fastly.client_ip, Some(ip("198.51.100.7")));
+
+    let cloudfront = Geo::from_headers(&headers(&[("cloudfront-viewer-country", "BR")]));
+    assert_eq!(cloudfront.country.as_deref(), Some("BR"));
+}
+
+#[test]
+fn test_forwarded_for_chains() {
+    let geo = Geo::from_headers(&headers(&[
+        ("x-forwarded-for", "\"[2001:db8::2]:4711\", 192.0.2.1:8080"),
+        ("x-forwarded-for", "unknown, 10.0.0.2"),
+    ]));
+    assert_eq!(
+        geo.forwarded_for,
+        [ip("2001:db8::2"), ip("192.0.2.1"), ip("10.0.0.2")]
+    );
+    assert_eq!(geo.client_ip, Some(ip("2001:db8::2")));
+    assert_eq!(Geo::from_headers(&HeaderMap::new()), Geo::default());
+}
+
+#[tokio::test]
+async fn test_request_being_handled() {
+    request_scope(async {
+        let (parts, _) = Request::get("/api/prices")
+            .header("cf-ipcountry", "FR")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let context = RequestContext::new(parts.clone());
+        provide_request_parts(parts).await;
+        let geo = geo().await.unwrap();
+        clear_request_parts().await;
+
+        assert_eq!(geo.country.as_deref(), Some("FR"));
+        assert_eq!(context.geo(), geo);
+    })
+    .await;
+}
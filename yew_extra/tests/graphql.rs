@@ -0,0 +1,184 @@
+// Checks the GraphQL schema built from registered endpoints and the calls of its fields
+#![cfg(all(feature = "graphql", feature = "axum-08"))]
+
+use axum::body::Body;
+use axum::http::{header, Request, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::future::Future;
+use std::pin::Pin;
+use tower::ServiceExt;
+use yew_extra::graphql::{build_schema, router, schema_of, GraphqlEndpoint};
+use yew_extra::schema::JsonSchema;
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(crate = "yew_extra::schema::schemars")]
+struct User {
+    id: u32,
+    name: String,
+    email: Option<String>,
+    manager: Option<Box<User>>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(crate = "yew_extra::schema::schemars")]
+struct ListUsersParams {
+    role: Option<String>,
+    limit: u32,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(crate = "yew_extra::schema::schemars")]
+struct RenameUserParams {
+    id: u32,
+    name: String,
+}
+
+type Handler = Pin<Box<dyn Future<Output = Response<Body>> + Send>>;
+
+fn json_response(status: StatusCode, body: Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn list_users_handler_wrapper(req: Request<Body>) -> Handler {
+    Box::pin(async move {
+        let query = req.uri().query().unwrap_or_default().to_string();
+        let caller = req
+            .headers()
+            .get("x-user")
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let boss = json!({ "id": 1, "name": "ada", "email": null, "manager": null });
+        json_response(
+            StatusCode::OK,
+            json!([{ "id": 2, "name": format!("{} {}", caller, query), "manager": boss }]),
+        )
+    })
+}
+
+fn rename_user_handler_wrapper(req: Request<Body>) -> Handler {
+    Box::pin(async move {
+        let body = axum::body::to_bytes(req.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let params: RenameUserParams = serde_json::from_slice(&body).unwrap();
+        if params.name.is_empty() {
+            return json_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                json!({ "error": "Empty name" }),
+            );
+        }
+        json_response(
+            StatusCode::OK,
+            json!({ "id": params.id, "name": params.name }),
+        )
+    })
+}
+
+fn version_handler_wrapper(_req: Request<Body>) -> Handler {
+    Box::pin(async { json_response(StatusCode::OK, json!([1, 2])) })
+}
+
+inventory::submit! {
+    GraphqlEndpoint::new(
+        "list_users",
+        "/api/users",
+        "GET",
+        Some(schema_of::<ListUsersParams>),
+        schema_of::<Vec<User>>,
+        list_users_handler_wrapper,
+    )
+}
+
+inventory::submit! {
+    GraphqlEndpoint::new(
+        "rename_user",
+        "/api/users/rename",
+        "POST",
+        Some(schema_of::<RenameUserParams>),
+        schema_of::<User>,
+        rename_user_handler_wrapper,
+    )
+}
+
+inventory::submit! {
+    GraphqlEndpoint::new(
+        "get_version",
+        "/api/version",
+        "GET",
+        None,
+        schema_of::<(u32, u32)>,
+        version_handler_wrapper,
+    )
+}
+
+async fn post_graphql(query: &str) -> Value {
+    let response = router::<()>("/graphql")
+        .oneshot(
+            Request::post("/graphql")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("x-user", "grace")
+                .body(Body::from(json!({ "query": query }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[test]
+fn test_schema_has_a_field_per_endpoint() {
+    let sdl = build_schema().unwrap().sdl();
+
+    assert!(sdl.contains("listUsers(limit: Int!, role: String): [User!]!"));
+    assert!(sdl.contains("getVersion: JSON!"));
+    assert!(sdl.contains("renameUser(id: Int!, name: String!): User!"));
+    assert!(sdl.contains("type Mutation"));
+    // Optional fields are nullable, recursive types refer to themselves
+    assert!(sdl.contains("\temail: String\n"));
+    assert!(sdl.contains("\tmanager: User\n"));
+    assert!(sdl.contains("scalar JSON"));
+}
+
+#[tokio::test]
+async fn test_queries_call_the_endpoints() {
+    let response = post_graphql(
+        r#"{ listUsers(limit: 5, role: "admin") { id name manager { name email } } getVersion }"#,
+    )
+    .await;
+
+    assert_eq!(response.get("errors"), None);
+    assert_eq!(
+        response["data"],
+        json!({
+            "listUsers": [{
+                "id": 2,
+                "name": "grace limit=5&role=admin",
+                "manager": { "name": "ada", "email": null },
+            }],
+            "getVersion": [1, 2],
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_failed_mutations_report_the_status() {
+    let response = post_graphql(
+        r#"mutation { renamed: renameUser(id: 3, name: "lin") { name } failed: renameUser(id: 3, name: "") { name } }"#,
+    )
+    .await;
+
+    assert_eq!(response["data"]["renamed"], json!({ "name": "lin" }));
+    let error = &response["errors"][0];
+    assert_eq!(error["message"], "Empty name");
+    assert_eq!(error["extensions"]["status"], 422);
+}
@@ -0,0 +1,71 @@
+// Checks the probes registered by health_routes!
+#![cfg(feature = "axum-08")]
+use axum::body::to_bytes;
+use axum::http::StatusCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tower::ServiceExt;
+use yew_extra::health::{register_check, unregister_check, HealthReport};
+use yew_extra::route_registry::registered_routes;
+use yew_extra::test::{test_router, TestRequest};
+
+yew_extra::health_routes!();
+
+async fn probe(path: &str) -> (StatusCode, HealthReport) {
+    let response = test_router()
+        .oneshot(TestRequest::get(path).into_request())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[test]
+fn test_probes_are_registered() {
+    let paths: Vec<&str> = registered_routes().iter().map(|route| route.path).collect();
+    assert_eq!(paths, ["/healthz", "/readyz"]);
+}
+
+#[tokio::test]
+async fn test_readiness_follows_the_checks() {
+    let (status, report) = probe("/healthz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(report.is_ok());
+
+    let warm = Arc::new(AtomicBool::new(false));
+    let cache_warm = warm.clone();
+    register_check("cache", move || {
+        let warm = cache_warm.load(Ordering::SeqCst);
+        async move {
+            if warm {
+                Ok(())
+            } else {
+                Err("cache is cold".to_string())
+            }
+        }
+    });
+    register_check("database", || async { Ok(()) });
+
+    let (status, report) = probe("/readyz").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(report.status, "unavailable");
+    assert_eq!(report.checks.len(), 2);
+    assert_eq!(report.checks[0].error.as_deref(), Some("cache is cold"));
+    assert!(report.checks[1].ok);
+
+    warm.store(true, Ordering::SeqCst);
+    let (status, report) = probe("/readyz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(report.is_ok());
+
+    // Liveness doesn't depend on the checks
+    register_check("database", || async {
+        Err("connection refused".to_string())
+    });
+    assert_eq!(probe("/readyz").await.0, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(probe("/healthz").await.0, StatusCode::OK);
+
+    unregister_check("database");
+    assert_eq!(probe("/readyz").await.0, StatusCode::OK);
+}
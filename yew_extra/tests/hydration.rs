@@ -0,0 +1,58 @@
+// Checks the snapshot of hook states recorded while rendering on the server
+use yew::prelude::*;
+use yew::ServerRenderer;
+use yew_extra::hydration::{fingerprint, use_hydration_check, HydrationSnapshot, SNAPSHOT_ID};
+
+#[derive(Properties, PartialEq)]
+struct ItemProps {
+    name: &'static str,
+}
+
+#[function_component(Item)]
+fn item(props: &ItemProps) -> Html {
+    let name = props.name;
+    use_hydration_check("use_item", || fingerprint(name));
+    html! { <li>{ name }</li> }
+}
+
+#[derive(Properties, PartialEq)]
+struct RootProps {
+    snapshot: HydrationSnapshot,
+}
+
+#[function_component(Root)]
+fn root(props: &RootProps) -> Html {
+    html! {
+        <ContextProvider<HydrationSnapshot> context={props.snapshot.clone()}>
+            <ul>
+                <Item name="first" />
+                <Item name="second" />
+            </ul>
+        </ContextProvider<HydrationSnapshot>>
+    }
+}
+
+#[tokio::test]
+async fn test_server_render_records_hook_states() {
+    let snapshot = HydrationSnapshot::new();
+    let context = snapshot.clone();
+    let body = ServerRenderer::<Root>::with_props(move || RootProps { snapshot: context })
+        .hydratable(false)
+        .render()
+        .await;
+    assert!(body.contains("<li>first</li>"));
+
+    let states = snapshot.states();
+    assert_eq!(
+        states["use_item"],
+        vec![fingerprint("first"), fingerprint("second")]
+    );
+    assert_ne!(states["use_item"][0], states["use_item"][1]);
+
+    let script = snapshot.script_tag();
+    assert!(script.starts_with(&format!(
+        r#"<script type="application/json" id="{}">"#,
+        SNAPSHOT_ID
+    )));
+    assert!(script.contains(&states["use_item"][1]));
+}
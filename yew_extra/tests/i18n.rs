@@ -0,0 +1,119 @@
+// Checks the localized error messages
+#![cfg(feature = "axum-08")]
+use axum::body::to_bytes;
+use axum::http::{Request, StatusCode};
+use axum::response::IntoResponse;
+use yew_extra::i18n::{
+    add_translations, localize_error, parse_accept_language, set_client_languages, LocalizedError,
+};
+use yew_extra::{clear_request_parts, provide_request_parts, request_scope};
+
+fn register() {
+    add_translations(
+        "en",
+        [
+            (
+                "order.too_large",
+                "At most {max} items per order, got {count}",
+            ),
+            ("order.closed", "The shop is closed"),
+        ],
+    );
+    add_translations(
+        "de",
+        [(
+            "order.too_large",
+            "Höchstens {max} Artikel pro Bestellung, nicht {count}",
+        )],
+    );
+}
+
+#[test]
+fn test_accept_language_order() {
+    assert_eq!(
+        parse_accept_language("fr;q=0.5, de-CH, en;q=0.8, *;q=0.1, it;q=0"),
+        ["de-CH", "en", "fr"]
+    );
+    assert!(parse_accept_language("").is_empty());
+}
+
+#[test]
+fn test_messages_fall_back_to_the_default_language() {
+    register();
+    let error = LocalizedError::new("order.too_large")
+        .arg("max", 10)
+        .arg("count", 12);
+
+    assert_eq!(
+        error.message(&["de-AT".to_string()]),
+        "Höchstens 10 Artikel pro Bestellung, nicht 12"
+    );
+    assert_eq!(
+        error.message(&["fr".to_string()]),
+        "At most 10 items per order, got 12"
+    );
+    assert_eq!(
+        LocalizedError::new("order.closed").message(&["de".to_string()]),
+        "The shop is closed"
+    );
+    assert_eq!(
+        LocalizedError::new("unknown.key").to_string(),
+        "unknown.key"
+    );
+}
+
+#[tokio::test]
+async fn test_response_follows_accept_language() {
+    request_scope(async {
+        register();
+        let (parts, _body) = Request::post("/api/orders")
+            .header("accept-language", "de-DE,de;q=0.9,en;q=0.8")
+            .body(())
+            .unwrap()
+            .into_parts();
+        provide_request_parts(parts).await;
+        let response = LocalizedError::new("order.too_large")
+            .status(422)
+            .arg("max", 10)
+            .arg("count", 12)
+            .into_response();
+        clear_request_parts().await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "error": "Höchstens 10 Artikel pro Bestellung, nicht 12",
+                "key": "order.too_large",
+                "args": { "max": "10", "count": "12" },
+            })
+        );
+    })
+    .await;
+}
+
+#[test]
+fn test_client_renders_the_key_in_its_languages() {
+    register();
+    let body = serde_json::json!({
+        "error": "At most 10 items per order, got 12",
+        "key": "order.too_large",
+        "args": { "max": 10, "count": "12" },
+    });
+
+    set_client_languages(vec!["de".to_string()]);
+    assert_eq!(
+        localize_error(&body).as_deref(),
+        Some("Höchstens 10 Artikel pro Bestellung, nicht 12")
+    );
+
+    // The server's message is kept without a translation in the client's languages
+    set_client_languages(vec!["fr".to_string()]);
+    assert_eq!(localize_error(&body), None);
+    assert_eq!(
+        localize_error(serde_json::json!({ "error": "Plain" })),
+        None
+    );
+}
@@ -0,0 +1,43 @@
+// Checks that streamed list responses produce the same JSON as axum::Json
+#![cfg(feature = "axum-08")]
+use axum::body::to_bytes;
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use yew_extra::json_stream::StreamingJson;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Row {
+    id: u32,
+    name: String,
+}
+
+async fn body(response: impl IntoResponse) -> String {
+    let body = to_bytes(response.into_response().into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_large_list_matches_json() {
+    let rows: Vec<Row> = (0..5_000)
+        .map(|id| Row {
+            id,
+            name: format!("Row {}", id),
+        })
+        .collect();
+
+    let streamed = body(StreamingJson::new(rows.clone())).await;
+    let buffered = body(axum::Json(rows.clone())).await;
+
+    assert_eq!(streamed, buffered);
+    assert_eq!(serde_json::from_str::<Vec<Row>>(&streamed).unwrap(), rows);
+}
+
+#[tokio::test]
+async fn test_empty_list() {
+    let response = StreamingJson::<Row>::new(Vec::new()).into_response();
+
+    assert_eq!(response.headers()["content-type"], "application/json");
+    assert_eq!(body(response).await, "[]");
+}
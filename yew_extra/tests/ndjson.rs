@@ -0,0 +1,88 @@
+// Checks that streamed items are written one JSON document per line and read back from chunks
+#![cfg(feature = "axum-08")]
+use axum::body::to_bytes;
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use yew_extra::ndjson::{LineDecoder, NdJson, StreamItems, CONTENT_TYPE};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Row {
+    id: u32,
+    name: String,
+}
+
+fn row(id: u32) -> Row {
+    Row {
+        id,
+        name: format!("Row {}", id),
+    }
+}
+
+#[tokio::test]
+async fn test_items_are_written_one_per_line() {
+    let items = futures_util::stream::iter((0..3).map(row));
+    let response = NdJson::new(items).into_response();
+
+    assert_eq!(response.headers()["content-type"], CONTENT_TYPE);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(
+        String::from_utf8(body.to_vec()).unwrap(),
+        "{\"id\":0,\"name\":\"Row 0\"}\n{\"id\":1,\"name\":\"Row 1\"}\n{\"id\":2,\"name\":\"Row 2\"}\n"
+    );
+}
+
+#[tokio::test]
+async fn test_empty_stream_has_an_empty_body() {
+    let response = NdJson::new(futures_util::stream::empty::<Row>()).into_response();
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(body.is_empty());
+}
+
+#[test]
+fn test_decoder_waits_for_the_end_of_a_line() {
+    let mut decoder = LineDecoder::new();
+
+    let items: Vec<Row> = decoder
+        .push(b"{\"id\":0,\"name\":\"Row 0\"}\n{\"id\":1,")
+        .unwrap();
+    assert_eq!(items, vec![row(0)]);
+
+    let items: Vec<Row> = decoder.push(b"\"name\":\"Row 1\"}\n").unwrap();
+    assert_eq!(items, vec![row(1)]);
+
+    let items: Vec<Row> = decoder.push(b"{\"id\":2,\"name\":\"Row 2\"}").unwrap();
+    assert!(items.is_empty());
+    assert_eq!(decoder.finish::<Row>().unwrap(), vec![row(2)]);
+}
+
+#[test]
+fn test_decoder_reports_invalid_lines() {
+    let mut decoder = LineDecoder::new();
+
+    let error = decoder.push::<Row>(b"{\"id\":0}\n").unwrap_err();
+    assert!(error.starts_with("Failed to parse streamed item"));
+}
+
+#[test]
+fn test_stream_items_share_their_buffer() {
+    let row = |id| Row {
+        id,
+        name: format!("row {}", id),
+    };
+    let mut items = StreamItems::default();
+    assert!(items.is_empty());
+    items.extend([row(1)]);
+    let first = items.clone();
+    items.extend([row(2), row(3)]);
+    assert_eq!(first.len(), 1);
+    assert_eq!(items.to_vec(), [row(1), row(2), row(3)]);
+
+    // Extending an older snapshot leaves the later one alone
+    let mut other = first.clone();
+    other.extend([row(4)]);
+    assert_eq!(other.to_vec(), [row(1), row(4)]);
+    assert_eq!(items.len(), 3);
+    assert_eq!(items.borrow()[2], row(3));
+    assert_ne!(first, other);
+}
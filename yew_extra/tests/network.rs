@@ -0,0 +1,9 @@
+// Checks how failed requests are reported depending on the connection
+use yew_extra::network::{fetch_error, is_online};
+
+#[test]
+fn test_fetch_errors_outside_the_browser_are_reported_as_is() {
+    assert!(is_online());
+    let error = fetch_error("TypeError: NetworkError");
+    assert_eq!(error, "Failed to fetch data: TypeError: NetworkError");
+}
@@ -0,0 +1,104 @@
+// Checks the replay protection of endpoints declared with `nonce`
+#![cfg(feature = "axum-08")]
+use axum::http::{Request, StatusCode};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use yew_extra::nonce::{
+    check_nonce, clear_nonce_store, set_nonce_store, InsertFuture, MemoryNonceStore, NonceStore,
+    DEFAULT_MAX_AGE, NONCE_HEADER, TIMESTAMP_HEADER,
+};
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn request(nonce: &str, timestamp: u64) -> Request<()> {
+    Request::post("/api/transfers")
+        .header(NONCE_HEADER, nonce)
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .body(())
+        .unwrap()
+}
+
+async fn status(request: Request<()>) -> Option<StatusCode> {
+    check_nonce(&request, DEFAULT_MAX_AGE)
+        .await
+        .err()
+        .map(|response| response.status())
+}
+
+#[tokio::test]
+async fn test_replays_are_rejected() {
+    assert_eq!(status(request("a1", now())).await, None);
+    assert_eq!(
+        status(request("a1", now())).await,
+        Some(StatusCode::CONFLICT)
+    );
+    assert_eq!(status(request("a2", now())).await, None);
+
+    // The same nonce on another endpoint is another request
+    let other = Request::put("/api/transfers")
+        .header(NONCE_HEADER, "a1")
+        .header(TIMESTAMP_HEADER, now().to_string())
+        .body(())
+        .unwrap();
+    assert_eq!(status(other).await, None);
+
+    // Servers with several instances keep them elsewhere
+    let kept: Arc<Mutex<Vec<(String, Duration)>>> = Arc::default();
+    let store = kept.clone();
+    set_nonce_store(move |key: String, ttl: Duration| -> InsertFuture {
+        store.lock().unwrap().push((key, ttl));
+        Box::pin(async { false })
+    });
+    let rejected = status(request("c1", now())).await;
+    clear_nonce_store();
+
+    assert_eq!(rejected, Some(StatusCode::CONFLICT));
+    assert_eq!(
+        kept.lock().unwrap().as_slice(),
+        [("POST /api/transfers c1".to_string(), DEFAULT_MAX_AGE * 2)]
+    );
+}
+
+#[tokio::test]
+async fn test_stale_and_incomplete_requests_are_rejected() {
+    let stale = now() - DEFAULT_MAX_AGE.as_secs() - 60;
+    assert_eq!(
+        status(request("b1", stale)).await,
+        Some(StatusCode::BAD_REQUEST)
+    );
+    let ahead = now() + DEFAULT_MAX_AGE.as_secs() + 60;
+    assert_eq!(
+        status(request("b2", ahead)).await,
+        Some(StatusCode::BAD_REQUEST)
+    );
+
+    let unsigned = Request::post("/api/transfers")
+        .header(NONCE_HEADER, "b3")
+        .body(())
+        .unwrap();
+    assert_eq!(status(unsigned).await, Some(StatusCode::BAD_REQUEST));
+}
+
+#[tokio::test]
+async fn test_memory_store_forgets_expired_nonces() {
+    let store = MemoryNonceStore::default();
+    assert!(
+        store
+            .insert("kept".to_string(), Duration::from_secs(60))
+            .await
+    );
+    assert!(store.insert("expired".to_string(), Duration::ZERO).await);
+
+    // Expired nonces are dropped by the next insert, the others are still kept
+    assert!(store.insert("expired".to_string(), Duration::ZERO).await);
+    assert!(
+        !store
+            .insert("kept".to_string(), Duration::from_secs(60))
+            .await
+    );
+}
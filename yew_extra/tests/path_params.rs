@@ -0,0 +1,162 @@
+// Checks the path parameters filled in by the clients and read back by the server
+#![cfg(feature = "axum-08")]
+use axum::body::{to_bytes, Body};
+use axum::http::{Method, Request, Response, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use serde::Deserialize;
+use std::future::Future;
+use std::pin::Pin;
+use tower::ServiceExt;
+use yew_extra::path_params::{extract_path, fill_path, match_path, router_path, segment};
+use yew_extra::route_registry::{find_route, matched_route, routes, RouteInfo};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct FileParams {
+    owner: u32,
+    path: String,
+}
+
+const TEMPLATE: &str = "/api/files/{owner}/{*path}";
+
+/// Answers with the parameters the wrapper of `TEMPLATE` reads
+async fn wrapper(request: Request<Body>) -> axum::response::Response {
+    let (mut parts, _body) = request.into_parts();
+    match extract_path::<FileParams>(&mut parts, TEMPLATE).await {
+        Ok(params) => format!("{} {}", params.owner, params.path).into_response(),
+        Err(response) => response,
+    }
+}
+
+fn file_wrapper(request: Request<Body>) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+    Box::pin(async move {
+        let matched = matched_route(&request).map(|route| route.path);
+        let response = wrapper(request).await;
+        let text = body_text(response.into_body()).await;
+        format!("{} via {:?}", text, matched).into_response()
+    })
+}
+
+// Registered in the syntax of Axum 0.7 while the router is on 0.8
+inventory::submit! {
+    RouteInfo::new("/api/files/:owner/*path", Method::GET, file_wrapper)
+}
+
+async fn body_text(body: Body) -> String {
+    String::from_utf8(to_bytes(body, usize::MAX).await.unwrap().to_vec()).unwrap()
+}
+
+#[test]
+fn test_clients_fill_in_the_path() {
+    assert_eq!(segment("a b"), "a b");
+    assert_eq!(segment(&42), "42");
+    assert_eq!(segment(&true), "true");
+
+    assert_eq!(
+        fill_path("/api/users/{id}/posts", &[("id", segment(&7))]),
+        "/api/users/7/posts"
+    );
+    assert_eq!(
+        fill_path("/api/users/:name", &[("name", segment("Ada/Lovelace?"))]),
+        "/api/users/Ada%2FLovelace%3F"
+    );
+    assert_eq!(
+        fill_path(
+            TEMPLATE,
+            &[("owner", segment(&1)), ("path", segment("a b/c.txt"))]
+        ),
+        "/api/files/1/a%20b/c.txt"
+    );
+}
+
+#[test]
+fn test_paths_are_matched() {
+    assert_eq!(
+        match_path("/api/users/{id}", "/api/users/42"),
+        Some(vec![("id".to_string(), "42".to_string())])
+    );
+    // A prefix the router was nested under
+    assert_eq!(
+        match_path("/api/users/:id", "/v1/api/users/Ada%20L"),
+        Some(vec![("id".to_string(), "Ada L".to_string())])
+    );
+    assert_eq!(
+        match_path(TEMPLATE, "/api/files/1/a/b.txt"),
+        Some(vec![
+            ("owner".to_string(), "1".to_string()),
+            ("path".to_string(), "a/b.txt".to_string())
+        ])
+    );
+    assert_eq!(match_path("/api/users/{id}", "/api/users"), None);
+    assert_eq!(match_path("/api/users/{id}", "/api/users/42/posts"), None);
+    assert_eq!(match_path(TEMPLATE, "/api/files/1"), None);
+}
+
+#[tokio::test]
+async fn test_the_router_provides_the_params() {
+    let router = Router::new().route(TEMPLATE, get(wrapper));
+    let response = router
+        .oneshot(
+            Request::get("/api/files/3/notes/a%20b.md")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_text(response.into_body()).await, "3 notes/a b.md");
+}
+
+#[tokio::test]
+async fn test_requests_without_a_router_are_matched() {
+    let response = wrapper(
+        Request::get("/api/files/3/notes.md")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_text(response.into_body()).await, "3 notes.md");
+
+    // Parameters that don't parse
+    let response = wrapper(
+        Request::get("/api/files/me/notes.md")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = wrapper(Request::get("/api/other").body(Body::empty()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn test_paths_are_translated_for_the_router() {
+    assert_eq!(router_path("/api/users/:id"), "/api/users/{id}");
+    assert_eq!(router_path("/api/files/:owner/*path"), TEMPLATE);
+    assert_eq!(router_path(TEMPLATE), TEMPLATE);
+    assert_eq!(router_path("/api/users"), "/api/users");
+}
+
+#[tokio::test]
+async fn test_registered_paths_of_the_other_version_are_routed() {
+    let response = routes::<()>()
+        .oneshot(
+            Request::get("/api/files/3/notes.md")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        body_text(response.into_body()).await,
+        r#"3 notes.md via Some("/api/files/:owner/*path")"#
+    );
+
+    // Either syntax finds the route
+    assert!(find_route(&Method::GET, TEMPLATE).is_some());
+    assert!(find_route(&Method::GET, "/api/files/:owner/*path").is_some());
+}
@@ -0,0 +1,19 @@
+// Checks polling stays out of server renders
+use yew::prelude::*;
+use yew::LocalServerRenderer;
+use yew_extra::poll::use_poll;
+
+#[function_component(Status)]
+fn status() -> Html {
+    use_poll(10, Callback::from(|()| panic!("Server renders don't poll")));
+    html! { <p>{ "Running" }</p> }
+}
+
+#[tokio::test]
+async fn test_server_renders_dont_poll() {
+    let body = LocalServerRenderer::<Status>::new()
+        .hydratable(false)
+        .render()
+        .await;
+    assert_eq!(body, "<p>Running</p>");
+}
@@ -0,0 +1,59 @@
+// Checks query strings with repeated keys, written and read in order
+use serde::{Deserialize, Serialize};
+use yew_extra::query::{from_query_str, to_query_string};
+use yew_extra::test::TestRequest;
+use yew_extra::{clear_request_parts, extract_query, provide_request_parts, request_scope};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Filter {
+    q: String,
+    tag: Vec<String>,
+    ids: Vec<u32>,
+    page: Option<u32>,
+}
+
+#[test]
+fn test_sequences_are_repeated_keys() {
+    let filter = Filter {
+        q: "red boots".to_string(),
+        tag: vec!["b".to_string(), "a&b".to_string()],
+        ids: vec![3, 1, 2],
+        page: None,
+    };
+    let query = to_query_string(&filter).unwrap();
+    assert_eq!(query, "q=red+boots&tag=b&tag=a%26b&ids=3&ids=1&ids=2");
+    assert_eq!(from_query_str::<Filter>(&query).unwrap(), filter);
+}
+
+#[test]
+fn test_single_and_interleaved_keys() {
+    let filter: Filter = from_query_str("tag=x&q=y&ids=7&tag=w&page=2").unwrap();
+    assert_eq!(filter.tag, ["x", "w"]);
+    assert_eq!(filter.ids, [7]);
+    assert_eq!(filter.page, Some(2));
+
+    assert!(from_query_str::<Filter>("q=y&tag=x&ids=seven").is_err());
+}
+
+#[tokio::test]
+async fn test_extract_query_reads_repeated_keys() {
+    request_scope(async {
+        let (parts, _) = TestRequest::get("/api/items")
+            .query(&[
+                ("q", "boots"),
+                ("tag", "sale"),
+                ("tag", "red"),
+                ("ids", "1"),
+            ])
+            .into_request()
+            .into_parts();
+        provide_request_parts(parts).await;
+        let filter = extract_query::<Filter>().await;
+        clear_request_parts().await;
+
+        let filter = filter.unwrap();
+        assert_eq!(filter.tag, ["sale", "red"]);
+        assert_eq!(filter.ids, [1]);
+    })
+    .await;
+}
@@ -0,0 +1,68 @@
+// Checks that the record middleware captures request/response pairs
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::{get, post};
+use axum::Router;
+use tower::ServiceExt;
+use yew_extra::recording::{record, Cassette, Recorder};
+
+fn app(recorder: &Recorder) -> Router {
+    Router::new()
+        .route("/api/users", get(|| async { r#"[{"name":"Ann"}]"# }))
+        .route(
+            "/api/users/create",
+            post(|body: String| async move { (StatusCode::CREATED, body) }),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            recorder.clone(),
+            record,
+        ))
+}
+
+#[tokio::test]
+async fn test_record_and_find_interactions() {
+    let recorder = Recorder::new();
+
+    let response = app(&recorder)
+        .oneshot(
+            Request::get("/api/users?role=admin")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    app(&recorder)
+        .oneshot(
+            Request::post("/api/users/create")
+                .body(Body::from(r#"{"name":"Bob","admin":false}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // The cassette survives a JSON round trip
+    let cassette = Cassette::from_json(&recorder.cassette().to_json()).unwrap();
+    assert_eq!(cassette.interactions.len(), 2);
+
+    let list = cassette.find("GET", "/api/users?role=admin", None).unwrap();
+    assert_eq!(list.response.status, 200);
+    assert_eq!(list.response.body, r#"[{"name":"Ann"}]"#);
+
+    // JSON bodies match regardless of key order and whitespace
+    let create = cassette
+        .find(
+            "POST",
+            "/api/users/create",
+            Some(r#"{ "admin": false, "name": "Bob" }"#),
+        )
+        .unwrap();
+    assert_eq!(create.response.status, 201);
+
+    assert!(cassette.find("GET", "/api/users", None).is_none());
+    assert!(cassette
+        .find("POST", "/api/users/create", Some(r#"{"name":"Eve"}"#))
+        .is_none());
+}
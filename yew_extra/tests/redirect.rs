@@ -0,0 +1,101 @@
+// Checks the redirects server functions send with redirect()
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::header::{ACCEPT, LOCATION};
+use axum::http::{Method, Request, Response, StatusCode};
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew_extra::redirect::{
+    clear_redirect_handler, follow, follow_header, set_redirect_handler, REDIRECT_HEADER,
+};
+use yew_extra::{
+    apply_response_headers, provide_request_parts, redirect, request_scope, ExtractError,
+};
+
+/// Runs a server function calling `redirect(location)` for a request, and returns its response
+async fn redirected(method: Method, accept: Option<&str>, location: &str) -> Response<Body> {
+    let mut request = Request::builder().method(method).uri("/api/logout");
+    if let Some(accept) = accept {
+        request = request.header(ACCEPT, accept);
+    }
+    let (parts, _body) = request.body(()).unwrap().into_parts();
+    request_scope(async move {
+        provide_request_parts(parts).await;
+        redirect(location).await.unwrap();
+        apply_response_headers(Response::new(Body::from("null"))).await
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_browsers_get_a_redirect() {
+    let accept = Some("text/html,application/xhtml+xml,*/*;q=0.8");
+    let response = redirected(Method::GET, accept, "/login").await;
+    assert_eq!(response.status(), StatusCode::FOUND);
+    assert_eq!(response.headers()[LOCATION], "/login");
+
+    // Forms posted without JavaScript load the page with a GET
+    let response = redirected(Method::POST, accept, "/login").await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(response.headers()[LOCATION], "/login");
+    assert!(response.headers().get(REDIRECT_HEADER).is_none());
+}
+
+#[tokio::test]
+async fn test_clients_get_the_redirect_header() {
+    for accept in [None, Some("application/json")] {
+        let response = redirected(Method::POST, accept, "/login?next=%2F").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[REDIRECT_HEADER], "/login?next=%2F");
+        assert!(response.headers().get(LOCATION).is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_redirects() {
+    let result = request_scope(async {
+        let (parts, _body) = Request::get("/api/logout").body(()).unwrap().into_parts();
+        provide_request_parts(parts).await;
+        redirect("/login\n").await
+    })
+    .await;
+    assert!(matches!(result, Err(ExtractError::ExtractionFailed(_))));
+
+    assert!(matches!(
+        redirect("/login").await,
+        Err(ExtractError::MissingParts(_))
+    ));
+}
+
+#[test]
+fn test_follow_uses_the_handler() {
+    let followed = Rc::new(RefCell::new(Vec::new()));
+    let recorded = followed.clone();
+    set_redirect_handler(move |location| recorded.borrow_mut().push(location.to_string()));
+    follow("/login");
+    clear_redirect_handler();
+    // Without a handler and the client feature, nothing happens
+    follow("/elsewhere");
+    assert_eq!(*followed.borrow(), ["/login"]);
+}
+
+#[tokio::test]
+async fn test_clients_follow_the_redirect_of_a_response() {
+    let followed = Rc::new(RefCell::new(Vec::new()));
+    let recorded = followed.clone();
+    set_redirect_handler(move |location| recorded.borrow_mut().push(location.to_string()));
+
+    // How the generated clients and hooks read the headers of their response
+    let read = |response: &Response<Body>| {
+        follow_header(|name| {
+            let value = response.headers().get(name)?;
+            value.to_str().ok().map(str::to_string)
+        })
+    };
+    let response = redirected(Method::POST, None, "/login").await;
+    assert!(read(&response));
+    assert!(!read(&Response::new(Body::from("null"))));
+    clear_redirect_handler();
+
+    assert_eq!(*followed.borrow(), ["/login"]);
+}
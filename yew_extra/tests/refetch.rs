@@ -0,0 +1,32 @@
+// Checks the refetches components ask their hooks for
+use std::cell::RefCell;
+use yew::prelude::*;
+use yew::LocalServerRenderer;
+use yew_extra::hooks::{use_refetch, Refetch};
+
+thread_local! {
+    static RENDERED: RefCell<Option<Refetch>> = const { RefCell::new(None) };
+}
+
+#[function_component(Status)]
+fn status() -> Html {
+    let refetch = use_refetch();
+    let requests = refetch.requests();
+    RENDERED.with(|rendered| *rendered.borrow_mut() = Some(refetch));
+    html! { <p>{ format!("{} refetches", requests) }</p> }
+}
+
+#[tokio::test]
+async fn test_refetches_are_taken_once() {
+    let body = LocalServerRenderer::<Status>::new()
+        .hydratable(false)
+        .render()
+        .await;
+    assert_eq!(body, "<p>0 refetches</p>");
+
+    let refetch = RENDERED.with(|rendered| rendered.borrow_mut().take()).unwrap();
+    assert!(!refetch.take_pending());
+    refetch.callback().emit(());
+    assert!(refetch.take_pending());
+    assert!(!refetch.take_pending());
+}
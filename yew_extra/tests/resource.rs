@@ -0,0 +1,78 @@
+// Checks resources read, refetch and notify the shared cache outside components
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+use yew_extra::cache::{self, CacheConfig, CacheScope};
+use yew_extra::resource::Resource;
+
+fn counting(key: &str, calls: Rc<Cell<u32>>) -> Resource<Vec<String>> {
+    Resource::new(key, move || {
+        let calls = calls.clone();
+        async move {
+            calls.set(calls.get() + 1);
+            Ok(vec![format!("fetch {}", calls.get())])
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_refetch_fills_the_cache_and_notifies() {
+    let calls = Rc::new(Cell::new(0));
+    let resource = counting("GET /api/resource/todos", calls.clone());
+    assert_eq!(resource.get(), None);
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let subscription = resource.subscribe({
+        let seen = seen.clone();
+        move |value| seen.borrow_mut().push(value)
+    });
+
+    assert_eq!(resource.refetch().await.unwrap(), ["fetch 1"]);
+    assert_eq!(resource.get().unwrap(), ["fetch 1"]);
+    assert_eq!(
+        cache::get::<Vec<String>>("GET /api/resource/todos")
+            .unwrap()
+            .value,
+        ["fetch 1"]
+    );
+    assert_eq!(*seen.borrow(), [Some(vec!["fetch 1".to_string()])]);
+
+    // Updates made elsewhere reach the subscriber too
+    cache::update::<Vec<String>>("GET /api/resource/todos", |todos| {
+        todos.push("patched".to_string())
+    });
+    assert_eq!(seen.borrow().len(), 2);
+
+    drop(subscription);
+    resource.refetch().await.unwrap();
+    assert_eq!(seen.borrow().len(), 2);
+    assert_eq!(calls.get(), 2);
+}
+
+#[tokio::test]
+async fn test_load_uses_fresh_values() {
+    let scope = CacheScope::new(CacheConfig::new().stale_time(Duration::from_secs(60)));
+    let calls = Rc::new(Cell::new(0));
+    let resource = counting("GET /api/resource/fresh", calls.clone()).in_scope(scope.clone());
+
+    assert_eq!(resource.load().await.unwrap(), ["fetch 1"]);
+    assert_eq!(resource.load().await.unwrap(), ["fetch 1"]);
+    assert_eq!(calls.get(), 1);
+    assert!(cache::get::<Vec<String>>("GET /api/resource/fresh").is_none());
+
+    // Stale values are fetched again
+    scope.configure(CacheConfig::new());
+    assert_eq!(resource.load().await.unwrap(), ["fetch 2"]);
+}
+
+#[tokio::test]
+async fn test_failed_fetches_keep_the_cached_value() {
+    cache::insert("GET /api/resource/failing", 7u32);
+    let resource: Resource<u32> = Resource::new("GET /api/resource/failing", || async {
+        Err("offline".to_string())
+    });
+
+    assert_eq!(resource.refetch().await, Err("offline".to_string()));
+    assert_eq!(resource.get(), Some(7));
+    assert_eq!(resource.clone(), resource);
+}
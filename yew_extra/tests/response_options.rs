@@ -0,0 +1,93 @@
+// Checks the status, headers and cookies server functions set through response_options()
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE, SET_COOKIE};
+use axum::http::{HeaderValue, Request, Response, StatusCode};
+use yew_extra::{
+    append_response_header, apply_response_headers, provide_request_parts, request_scope,
+    response_options, run_blocking, ExtractError, ResponseOptions,
+};
+
+fn json_response() -> Response<Body> {
+    Response::builder()
+        .header(CONTENT_TYPE, "application/json")
+        .header(CACHE_CONTROL, "max-age=60")
+        .body(Body::from("{}"))
+        .unwrap()
+}
+
+#[test]
+fn test_options_apply_once() {
+    let options = ResponseOptions::default();
+    options.set_status(StatusCode::CREATED);
+    options.insert_header(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    options.append_header(CACHE_CONTROL, HeaderValue::from_static("private"));
+    options.add_cookie("session=abc; HttpOnly").unwrap();
+    assert!(options.add_cookie("bad\nvalue").is_err());
+    assert_eq!(options.status(), Some(StatusCode::CREATED));
+    assert_eq!(options.headers().get_all(CACHE_CONTROL).iter().count(), 2);
+
+    let response = options.apply(json_response());
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+    // Inserted headers replace those of the response, appended ones add to them
+    let cache_control: Vec<_> = response.headers().get_all(CACHE_CONTROL).iter().collect();
+    assert_eq!(cache_control, ["no-store", "private"]);
+    assert_eq!(response.headers()[SET_COOKIE], "session=abc; HttpOnly");
+
+    // The options were used up
+    assert_eq!(options.status(), None);
+    let response = options.apply(json_response());
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CACHE_CONTROL], "max-age=60");
+}
+
+#[test]
+fn test_insert_replaces_appended_values() {
+    let options = ResponseOptions::default();
+    options.append_header(CACHE_CONTROL, HeaderValue::from_static("private"));
+    options.insert_header(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    let headers = options.headers();
+    let cache_control: Vec<_> = headers.get_all(CACHE_CONTROL).iter().collect();
+    assert_eq!(cache_control, ["no-store"]);
+}
+
+#[tokio::test]
+async fn test_server_functions_set_the_response() {
+    let response = request_scope(async {
+        let (parts, _body) = Request::post("/api/session").body(()).unwrap().into_parts();
+        provide_request_parts(parts).await;
+
+        // What a server function does, on a blocking thread too
+        let options = response_options().await.unwrap();
+        options.set_status(StatusCode::CREATED);
+        run_blocking(async {
+            response_options()
+                .await
+                .unwrap()
+                .add_cookie("session=abc")
+                .unwrap();
+        })
+        .await;
+        append_response_header(CACHE_CONTROL, HeaderValue::from_static("no-store"))
+            .await
+            .unwrap();
+
+        // What the generated handler does with the response
+        apply_response_headers(json_response()).await
+    })
+    .await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[SET_COOKIE], "session=abc");
+    let cache_control: Vec<_> = response.headers().get_all(CACHE_CONTROL).iter().collect();
+    assert_eq!(cache_control, ["max-age=60", "no-store"]);
+}
+
+#[tokio::test]
+async fn test_outside_a_request() {
+    assert!(matches!(
+        response_options().await,
+        Err(ExtractError::MissingParts(_))
+    ));
+}
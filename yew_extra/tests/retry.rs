@@ -0,0 +1,24 @@
+// Checks which failures the generated clients retry and how long they wait
+use yew_extra::retry::{is_retryable_status, retry_delay_ms, MAX_RETRY_DELAY_MS, RETRY_DELAY_MS};
+
+#[test]
+fn test_temporary_failures_are_retried() {
+    for status in [408, 429, 500, 502, 503, 504] {
+        assert!(is_retryable_status(status, false), "{}", status);
+    }
+    for status in [200, 400, 401, 404, 422] {
+        assert!(!is_retryable_status(status, true), "{}", status);
+    }
+
+    // Another attempt with the same idempotency key is still running
+    assert!(is_retryable_status(409, true));
+    assert!(!is_retryable_status(409, false));
+}
+
+#[test]
+fn test_backoff_doubles_up_to_the_limit() {
+    assert_eq!(retry_delay_ms(1), RETRY_DELAY_MS);
+    assert_eq!(retry_delay_ms(2), RETRY_DELAY_MS * 2);
+    assert_eq!(retry_delay_ms(3), RETRY_DELAY_MS * 4);
+    assert_eq!(retry_delay_ms(40), MAX_RETRY_DELAY_MS);
+}
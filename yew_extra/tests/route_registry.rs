@@ -0,0 +1,256 @@
+// Checks that test_router mounts every registered route
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::{header, Method, Request, Response, StatusCode};
+use std::future::Future;
+use std::pin::Pin;
+use tower::ServiceExt;
+use yew_extra::route_registry::{
+    api_fallback, find_route, into_lambda_service, matched_route, normalize_paths,
+    registered_routes, routes, Auth, PathOptions, RouteInfo, RouteMetadata, TrailingSlash,
+};
+use yew_extra::test::{call_handler, test_router, TestRequest};
+
+fn list_todos_handler_wrapper(
+    _req: Request<Body>,
+) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+    Box::pin(async { Response::new(Body::from(r#"["write tests"]"#)) })
+}
+
+fn create_todo_handler_wrapper(
+    req: Request<Body>,
+) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+    Box::pin(async move {
+        let body = axum::body::to_bytes(req.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        Response::builder()
+            .status(StatusCode::CREATED)
+            .body(Body::from(body))
+            .unwrap()
+    })
+}
+
+inventory::submit! {
+    RouteInfo::new("/api/todos", Method::GET, list_todos_handler_wrapper).metadata(
+        RouteMetadata::new()
+            .auth(Auth::Public)
+            .tags(&["public", "todos"])
+            .owner("web"),
+    )
+}
+
+inventory::submit! {
+    RouteInfo::new("/api/todos", Method::POST, create_todo_handler_wrapper)
+        .metadata(RouteMetadata::new().auth(Auth::Required))
+}
+
+#[test]
+fn test_registered_routes_are_sorted() {
+    let routes: Vec<(&str, &str)> = registered_routes()
+        .iter()
+        .map(|route| (route.path, route.method.as_str()))
+        .collect();
+    assert_eq!(routes, vec![("/api/todos", "GET"), ("/api/todos", "POST")]);
+}
+
+#[tokio::test]
+async fn test_router_dispatches_by_method() {
+    let list = call_handler::<Vec<String>, _, _>(
+        |req| async { test_router().oneshot(req).await.unwrap() },
+        TestRequest::get("/api/todos"),
+    )
+    .await;
+    assert_eq!(list.data.unwrap(), vec!["write tests".to_string()]);
+
+    let created = call_handler::<String, _, _>(
+        |req| async { test_router().oneshot(req).await.unwrap() },
+        TestRequest::post("/api/todos").json(&"ship it"),
+    )
+    .await;
+    assert_eq!(created.status, StatusCode::CREATED);
+    assert_eq!(created.data.unwrap(), "ship it");
+
+    let response = test_router()
+        .oneshot(TestRequest::delete("/api/todos").into_request())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[test]
+fn test_metadata_is_found_by_method_and_path() {
+    let list = find_route(&Method::GET, "/api/todos").unwrap();
+    assert!(list.metadata.has_tag("public"));
+    assert_eq!(list.metadata.owner, Some("web"));
+    let head = find_route(&Method::HEAD, "/api/todos").unwrap();
+    assert_eq!(head.metadata, list.metadata);
+    let create = find_route(&Method::POST, "/api/todos").unwrap();
+    assert_eq!(create.metadata.auth, Some(Auth::Required));
+    assert!(create.metadata.tags.is_empty());
+    assert!(find_route(&Method::DELETE, "/api/todos").is_none());
+    assert!(find_route(&Method::GET, "/api/nope").is_none());
+}
+
+#[tokio::test]
+async fn test_middleware_branches_on_the_matched_route() {
+    async fn require_auth(req: Request<Body>, next: axum::middleware::Next) -> Response<Body> {
+        let public =
+            matched_route(&req).is_some_and(|route| route.metadata.auth == Some(Auth::Public));
+        if !public && !req.headers().contains_key(header::AUTHORIZATION) {
+            return Response::builder()
+                .status(StatusCode::UNAUTHORIZED)
+                .body(Body::empty())
+                .unwrap();
+        }
+        next.run(req).await
+    }
+    let app: axum::Router = routes().layer(axum::middleware::from_fn(require_auth));
+
+    let list = app
+        .clone()
+        .oneshot(TestRequest::get("/api/todos").into_request())
+        .await
+        .unwrap();
+    assert_eq!(list.status(), StatusCode::OK);
+    let create = app
+        .clone()
+        .oneshot(
+            TestRequest::post("/api/todos")
+                .json(&"ship it")
+                .into_request(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(create.status(), StatusCode::UNAUTHORIZED);
+    let signed_in = app
+        .oneshot(
+            TestRequest::post("/api/todos")
+                .header("authorization", "Bearer token")
+                .json(&"ship it")
+                .into_request(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(signed_in.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_other_methods_get_405_instead_of_the_fallback() {
+    let app: axum::Router = routes().fallback(|| async { "<html>app</html>" });
+    let response = app
+        .oneshot(TestRequest::delete("/api/todos").into_request())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[header::ALLOW], "GET, HEAD, POST");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        body["error"],
+        "Method DELETE not allowed, expected GET, HEAD, POST"
+    );
+}
+
+#[tokio::test]
+async fn test_unknown_api_paths_get_a_json_404() {
+    let app: axum::Router = axum::Router::new()
+        .merge(routes())
+        .merge(api_fallback("/api/"))
+        .fallback(|| async { "<html>app</html>" });
+
+    let response = app
+        .clone()
+        .oneshot(TestRequest::get("/api/nope").into_request())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"], "No endpoint for GET /api/nope");
+
+    // Registered routes and pages outside the prefix are unaffected
+    let response = app
+        .clone()
+        .oneshot(TestRequest::get("/api/todos").into_request())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = app
+        .oneshot(TestRequest::get("/about").into_request())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_paths_are_normalized_before_routing() {
+    let strict = normalize_paths(routes::<()>(), PathOptions::default());
+    let response = strict
+        .oneshot(TestRequest::get("/api/todos/").into_request())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let merged = normalize_paths(
+        routes::<()>(),
+        PathOptions::default()
+            .trailing_slash(TrailingSlash::Merge)
+            .merge_slashes(true),
+    );
+    for path in ["/api/todos/", "//api//todos", "/api/todos///"] {
+        let response = merged
+            .clone()
+            .oneshot(TestRequest::get(path).into_request())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", path);
+    }
+
+    let redirected = normalize_paths(
+        routes::<()>(),
+        PathOptions::default().trailing_slash(TrailingSlash::Redirect),
+    );
+    let response = redirected
+        .clone()
+        .oneshot(TestRequest::get("/api/todos/?page=2").into_request())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(response.headers()[header::LOCATION], "/api/todos?page=2");
+
+    // Never a protocol-relative URL pointing to another host
+    let response = redirected
+        .oneshot(TestRequest::get("//evil.example/").into_request())
+        .await
+        .unwrap();
+    assert_eq!(response.headers()[header::LOCATION], "/evil.example");
+}
+
+#[tokio::test]
+async fn test_lambda_service_accepts_other_bodies_in_a_scope_of_its_own() {
+    yew_extra::request_scope(async {
+        // The invocation mustn't see or replace the parts of the surrounding scope
+        let (outer, _body) = Request::get("/outer").body(()).unwrap().into_parts();
+        yew_extra::provide_request_parts(outer).await;
+
+        let request = Request::post("/api/todos")
+            .body(r#""from lambda""#.to_string())
+            .unwrap();
+        let response = into_lambda_service().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, r#""from lambda""#);
+
+        assert_eq!(yew_extra::extract::<Method>().await.unwrap(), Method::GET);
+    })
+    .await;
+}
@@ -0,0 +1,114 @@
+// Checks that other server frameworks can run the registered routes through the traits
+#![cfg(feature = "axum-08")]
+use axum::body::{Body, Bytes};
+use axum::http::{Method, Request, Response, StatusCode};
+use std::future::Future;
+use std::pin::Pin;
+use yew_extra::route_registry::{routes, RouteInfo};
+use yew_extra::server::{build_request, call, handle, ServerRequest, ServerResponse};
+
+/// The request type of a made-up framework
+struct CustomRequest {
+    method: &'static str,
+    uri: &'static str,
+    headers: Vec<(&'static str, &'static str)>,
+    body: &'static str,
+}
+
+impl ServerRequest for CustomRequest {
+    fn into_request(self) -> Result<Request<Body>, String> {
+        build_request(self.method, self.uri, self.headers, self.body)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct CustomResponse {
+    status: u16,
+    content_type: Option<Vec<u8>>,
+    body: Bytes,
+}
+
+impl ServerResponse for CustomResponse {
+    fn from_parts(status: u16, headers: Vec<(String, Vec<u8>)>, body: Bytes) -> Self {
+        let content_type = headers
+            .into_iter()
+            .find(|(name, _)| name == "content-type")
+            .map(|(_, value)| value);
+        CustomResponse {
+            status,
+            content_type,
+            body,
+        }
+    }
+}
+
+fn rename_handler_wrapper(
+    req: Request<Body>,
+) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+    Box::pin(async move {
+        let tenant = req.headers()["x-tenant"].to_str().unwrap().to_string();
+        let body = axum::body::to_bytes(req.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        Response::builder()
+            .status(StatusCode::ACCEPTED)
+            .header("content-type", "text/plain")
+            .body(Body::from(format!(
+                "{} renamed to {}",
+                tenant,
+                String::from_utf8_lossy(&body)
+            )))
+            .unwrap()
+    })
+}
+
+inventory::submit! {
+    RouteInfo::new("/api/rename", Method::PUT, rename_handler_wrapper)
+}
+
+fn rename_request() -> CustomRequest {
+    CustomRequest {
+        method: "PUT",
+        uri: "/api/rename",
+        headers: vec![("x-tenant", "acme")],
+        body: "globex",
+    }
+}
+
+#[tokio::test]
+async fn test_custom_backend_routes_requests() {
+    let response: CustomResponse = handle(routes(), rename_request()).await;
+    assert_eq!(response.status, 202);
+    assert_eq!(response.content_type, Some(b"text/plain".to_vec()));
+    assert_eq!(response.body, "acme renamed to globex");
+
+    let missing = CustomRequest {
+        uri: "/api/missing",
+        ..rename_request()
+    };
+    let response: CustomResponse = handle(routes(), missing).await;
+    assert_eq!(response.status, 404);
+}
+
+#[tokio::test]
+async fn test_custom_backend_calls_single_wrappers() {
+    let response: CustomResponse = call(rename_handler_wrapper, rename_request()).await;
+    assert_eq!(response.body, "acme renamed to globex");
+
+    let invalid = CustomRequest {
+        method: "NOT A METHOD",
+        ..rename_request()
+    };
+    let response: CustomResponse = call(rename_handler_wrapper, invalid).await;
+    assert_eq!(response.status, 400);
+}
+
+#[tokio::test]
+async fn test_axum_types_are_the_default_backend() {
+    let request = Request::put("/api/rename")
+        .header("x-tenant", "acme")
+        .body(Body::from("initech"))
+        .unwrap();
+    let response: Response<Body> = handle(routes(), request).await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+}
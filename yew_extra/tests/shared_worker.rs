@@ -0,0 +1,29 @@
+// Checks the route serving the shared worker script
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use tower::ServiceExt;
+use yew_extra::shared_worker::{shared_worker_routes, SHARED_WORKER_JS};
+
+#[tokio::test]
+async fn test_worker_script_is_served() {
+    let response = shared_worker_routes::<()>()
+        .oneshot(
+            Request::get("/shared-worker.js")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/javascript"
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body, SHARED_WORKER_JS.as_bytes());
+    assert!(SHARED_WORKER_JS.contains("onconnect"));
+}
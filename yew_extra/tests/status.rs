@@ -0,0 +1,21 @@
+// Checks the responses of server functions returning a status along with their data
+#![cfg(feature = "axum-08")]
+use axum::body::to_bytes;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use yew_extra::WithStatus;
+
+#[tokio::test]
+async fn test_status_and_json_body() {
+    let response = WithStatus::created(vec!["a", "b"]).into_response();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[..], br#"["a","b"]"#);
+}
+
+#[test]
+fn test_invalid_status_is_a_server_error() {
+    let (status, _) = WithStatus::new(1000, ()).into_parts();
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,33 @@
+// Checks the service worker manifest built from registered routes
+use yew_extra::sw::{precache_manifest, BackgroundSyncRoute, CacheStrategy, CacheableRoute};
+
+inventory::submit! {
+    CacheableRoute::new("/api/todos", CacheStrategy::StaleWhileRevalidate, true)
+}
+
+inventory::submit! {
+    CacheableRoute::new("/api/todo", CacheStrategy::NetworkFirst, false)
+}
+
+inventory::submit! {
+    BackgroundSyncRoute::new("/api/todos", "POST")
+}
+
+#[test]
+fn test_manifest_lists_registered_routes() {
+    let manifest = precache_manifest();
+    let json = serde_json::to_value(&manifest).unwrap();
+
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "routes": [
+                { "path": "/api/todo", "strategy": "network-first", "precache": false },
+                { "path": "/api/todos", "strategy": "stale-while-revalidate", "precache": true },
+            ],
+            "background_sync": [
+                { "path": "/api/todos", "method": "POST" },
+            ]
+        })
+    );
+}
@@ -0,0 +1,99 @@
+// Checks call_handler against a handler shaped like the generated wrappers
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::{HeaderMap, Request, Response, StatusCode};
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use yew_extra::test::{call_handler, TestRequest};
+use yew_extra::{clear_request_parts, extract, provide_request_parts};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Greeting {
+    message: String,
+}
+
+#[derive(Serialize)]
+struct GreetParams {
+    name: String,
+}
+
+// What the function body sees through extract()
+async fn greet(name: String) -> Result<Greeting, String> {
+    let headers: HeaderMap = extract().await.map_err(|e| e.to_string())?;
+    let cookies = headers
+        .get("cookie")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    if !cookies.contains("session=abc") {
+        return Err("Not signed in".to_string());
+    }
+    Ok(Greeting {
+        message: format!("Hello, {}", name),
+    })
+}
+
+async fn greet_handler_wrapper(req: Request<Body>) -> Response<Body> {
+    let (parts, _body) = req.into_parts();
+    let name = parts
+        .uri
+        .query()
+        .and_then(|query| query.strip_prefix("name="))
+        .unwrap_or_default()
+        .to_string();
+
+    provide_request_parts(parts).await;
+    let result = greet(name).await;
+    clear_request_parts().await;
+
+    match result {
+        Ok(greeting) => axum::Json(greeting).into_response(),
+        Err(e) => (StatusCode::UNAUTHORIZED, e).into_response(),
+    }
+}
+
+#[tokio::test]
+async fn test_call_handler_decodes_response() {
+    let response = call_handler::<Greeting, _, _>(
+        greet_handler_wrapper,
+        TestRequest::get("/api/greet")
+            .query(&GreetParams {
+                name: "Ann".to_string(),
+            })
+            .cookie("theme", "dark")
+            .cookie("session", "abc"),
+    )
+    .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        response.data.unwrap(),
+        Greeting {
+            message: "Hello, Ann".to_string()
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_call_handler_reports_error_body() {
+    let response =
+        call_handler::<Greeting, _, _>(greet_handler_wrapper, TestRequest::get("/api/greet")).await;
+
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(response.data.unwrap_err(), "Not signed in");
+}
+
+#[test]
+fn test_request_building() {
+    let request = TestRequest::post("/api/items?draft=true")
+        .header("x-request-id", "42")
+        .json(&GreetParams {
+            name: "Bob".to_string(),
+        })
+        .into_request();
+
+    assert_eq!(request.method(), "POST");
+    assert_eq!(request.uri(), "/api/items?draft=true");
+    assert_eq!(request.headers()["x-request-id"], "42");
+    assert_eq!(request.headers()["content-type"], "application/json");
+}
@@ -0,0 +1,53 @@
+// Checks the deadline applied to endpoints declared with `server_timeout`
+#![cfg(feature = "axum-08")]
+use axum::body::{to_bytes, Body};
+use axum::http::{Response, StatusCode};
+use std::time::Duration;
+use yew_extra::timeout::{with_timeout, TimeoutError};
+use yew_extra::{clear_request_parts, provide_request_parts, remaining_time, request_scope};
+
+#[tokio::test]
+async fn test_fast_handler_keeps_its_response() {
+    let response = with_timeout(Duration::from_secs(5), async {
+        Response::new(Body::from("done"))
+    })
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_stuck_handler_times_out() {
+    let response = with_timeout(Duration::from_millis(20), async {
+        std::future::pending::<()>().await;
+        Response::new(Body::from("never"))
+    })
+    .await;
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let error: TimeoutError = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error, TimeoutError::new(20));
+    assert_eq!(error.error, "timeout");
+}
+
+#[tokio::test]
+async fn test_handler_sees_the_time_left() {
+    request_scope(async {
+        let (parts, _) = axum::http::Request::new(()).into_parts();
+        provide_request_parts(parts).await;
+        assert_eq!(remaining_time().await, None);
+
+        let response = with_timeout(Duration::from_secs(5), async {
+            let left = remaining_time().await.unwrap();
+            assert!(left > Duration::from_secs(4) && left <= Duration::from_secs(5));
+            Response::new(Body::from("done"))
+        })
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        clear_request_parts().await;
+        assert_eq!(remaining_time().await, None);
+    })
+    .await;
+}
@@ -0,0 +1,93 @@
+// Checks that the transaction of a request is committed or rolled back with its response
+#![cfg(all(feature = "sqlx", feature = "axum-08"))]
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use sqlx::sqlite::{Sqlite, SqlitePool, SqlitePoolOptions};
+use tower::ServiceExt;
+use yew_extra::db::provide_transactions;
+use yew_extra::{clear_request_parts, provide_request_parts, request_scope, tx};
+
+async fn insert(name: &str) -> Result<(), sqlx::Error> {
+    let mut tx = tx::<Sqlite>().await?;
+    sqlx::query("INSERT INTO items (name) VALUES (?)")
+        .bind(name)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+async fn handler(request: Request) -> StatusCode {
+    request_scope(async move {
+        let fail = request.uri().query() == Some("fail");
+        let (parts, _) = request.into_parts();
+        provide_request_parts(parts).await;
+        let result = async {
+            insert("first").await?;
+            insert("second").await?;
+            if fail {
+                return Err(sqlx::Error::RowNotFound);
+            }
+            Ok(())
+        }
+        .await;
+        clear_request_parts().await;
+        match result {
+            Ok(()) => StatusCode::CREATED,
+            Err(_) => StatusCode::NOT_FOUND,
+        }
+    })
+    .await
+}
+
+async fn pool() -> SqlitePool {
+    // One connection, each in-memory connection has a database of its own
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE items (name TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool
+}
+
+async fn count(pool: &SqlitePool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM items")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn call(app: &Router, uri: &str) -> StatusCode {
+    let request = Request::post(uri).body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_success_commits_and_failure_rolls_back() {
+    let pool = pool().await;
+    let app = provide_transactions(Router::new().route("/items", post(handler)), pool.clone());
+
+    assert_eq!(call(&app, "/items").await, StatusCode::CREATED);
+    assert_eq!(count(&pool).await, 2);
+
+    assert_eq!(call(&app, "/items?fail").await, StatusCode::NOT_FOUND);
+    assert_eq!(count(&pool).await, 2);
+}
+
+#[tokio::test]
+async fn test_without_provide_transactions() {
+    request_scope(async {
+        let (parts, _) = Request::new(()).into_parts();
+        provide_request_parts(parts).await;
+        let result = tx::<Sqlite>().await;
+        clear_request_parts().await;
+        assert!(matches!(result, Err(sqlx::Error::Configuration(_))));
+    })
+    .await;
+}
@@ -0,0 +1,83 @@
+// Checks the server side of endpoints declared with `transform`
+#![cfg(feature = "axum-08")]
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use yew_extra::transform::{decode_request, encode_response, response_text, PayloadTransform};
+
+/// Prefixes bodies with a tag, with keys of its own for each direction
+struct Tagged;
+
+impl PayloadTransform for Tagged {
+    fn encode_response(body: Vec<u8>) -> Result<Vec<u8>, String> {
+        Ok([b"res:".as_slice(), &body].concat())
+    }
+
+    fn decode_response(body: Vec<u8>) -> Result<Vec<u8>, String> {
+        body.strip_prefix(b"res:")
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| "Missing tag".to_string())
+    }
+
+    fn decode_request(body: Vec<u8>) -> Result<Vec<u8>, String> {
+        body.strip_prefix(b"req:")
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| "Missing tag".to_string())
+    }
+}
+
+async fn body_text(body: Body) -> String {
+    String::from_utf8(to_bytes(body, usize::MAX).await.unwrap().to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_request_bodies_are_decoded() {
+    let request = Request::post("/api/notes")
+        .header("content-length", "13")
+        .body(Body::from(r#"req:{"id":1}"#))
+        .unwrap();
+    let request = decode_request::<Tagged>(request, None).await.unwrap();
+    assert!(request.headers().get("content-length").is_none());
+    assert_eq!(body_text(request.into_body()).await, r#"{"id":1}"#);
+
+    // Reads have no body to decode
+    let request = Request::get("/api/notes").body(Body::empty()).unwrap();
+    assert!(decode_request::<Tagged>(request, None).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_undecodable_and_oversized_requests_are_rejected() {
+    let request = Request::post("/api/notes").body(Body::from("{}")).unwrap();
+    let response = decode_request::<Tagged>(request, None).await.unwrap_err();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let request = Request::post("/api/notes")
+        .body(Body::from("req:0123456789"))
+        .unwrap();
+    let response = decode_request::<Tagged>(request, Some(8))
+        .await
+        .unwrap_err();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_successful_responses_are_encoded() {
+    let response = encode_response::<Tagged>(Json(vec![1, 2]).into_response()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, "res:[1,2]");
+
+    // What the generated clients parse
+    assert_eq!(response_text::<Tagged>(body.to_vec()).unwrap(), "[1,2]");
+    assert!(response_text::<Tagged>(b"[1,2]".to_vec()).is_err());
+}
+
+#[tokio::test]
+async fn test_errors_are_left_alone() {
+    let error = (StatusCode::NOT_FOUND, Json("missing")).into_response();
+    let response = encode_response::<Tagged>(error).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(body_text(response.into_body()).await, r#""missing""#);
+}
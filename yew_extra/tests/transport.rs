@@ -0,0 +1,54 @@
+// Checks that requests received over Tauri IPC run through the app's router
+#![cfg(feature = "axum-08")]
+use axum::http::HeaderMap;
+use axum::routing::{get, post};
+use axum::Router;
+use yew_extra::transport::{dispatch, IpcRequest};
+
+fn router() -> Router {
+    Router::new()
+        .route(
+            "/api/echo",
+            post(|headers: HeaderMap, body: String| async move {
+                let tenant = headers
+                    .get("x-tenant")
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or("none")
+                    .to_string();
+                format!("{}:{}", tenant, body)
+            }),
+        )
+        .route("/api/ping", get(|| async { "pong" }))
+}
+
+fn request(method: &str, uri: &str, body: Option<&str>) -> IpcRequest {
+    IpcRequest {
+        method: method.to_string(),
+        uri: uri.to_string(),
+        headers: vec![("x-tenant".to_string(), "acme".to_string())],
+        body: body.map(str::to_string),
+    }
+}
+
+#[tokio::test]
+async fn test_dispatch_runs_the_router() {
+    let response = dispatch(router(), request("POST", "/api/echo", Some("hello"))).await;
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, "acme:hello");
+    assert!(response
+        .headers
+        .iter()
+        .any(|(name, value)| name == "content-type" && value.starts_with("text/plain")));
+
+    let response = dispatch(router(), request("GET", "/api/ping?verbose=1", None)).await;
+    assert_eq!(response.body, "pong");
+}
+
+#[tokio::test]
+async fn test_dispatch_reports_unknown_routes_and_bad_requests() {
+    let response = dispatch(router(), request("GET", "/api/missing", None)).await;
+    assert_eq!(response.status, 404);
+
+    let response = dispatch(router(), request("NOT A METHOD", "/api/ping", None)).await;
+    assert_eq!(response.status, 400);
+}
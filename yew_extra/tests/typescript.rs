@@ -0,0 +1,100 @@
+// Checks the TypeScript client generated for registered endpoints
+#![cfg(feature = "schema")]
+
+use serde::{Deserialize, Serialize};
+use yew_extra::schema::JsonSchema;
+use yew_extra::typescript::{generate_client, registered_endpoints, schema_of, TsEndpoint};
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(crate = "yew_extra::schema::schemars")]
+#[allow(dead_code)]
+enum Role {
+    Admin,
+    Member,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(crate = "yew_extra::schema::schemars")]
+#[allow(dead_code)]
+struct User {
+    id: u32,
+    name: String,
+    email: Option<String>,
+    role: Role,
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(crate = "yew_extra::schema::schemars")]
+#[allow(dead_code)]
+struct ListUsersParams {
+    role: Option<Role>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[schemars(crate = "yew_extra::schema::schemars")]
+#[allow(dead_code)]
+struct RenameUserParams {
+    id: u32,
+    name: String,
+}
+
+inventory::submit! {
+    TsEndpoint::new(
+        "list_users",
+        "/api/users",
+        "GET",
+        Some(schema_of::<ListUsersParams>),
+        schema_of::<Vec<User>>,
+    )
+}
+
+inventory::submit! {
+    TsEndpoint::new(
+        "rename_user",
+        "/api/users/rename",
+        "POST",
+        Some(schema_of::<RenameUserParams>),
+        schema_of::<User>,
+    )
+}
+
+inventory::submit! {
+    TsEndpoint::new("get_version", "/api/version", "GET", None, schema_of::<(u32, u32)>)
+}
+
+#[test]
+fn test_endpoints_are_sorted_by_path() {
+    let paths: Vec<&str> = registered_endpoints()
+        .iter()
+        .map(|endpoint| endpoint.path)
+        .collect();
+    assert_eq!(paths, ["/api/users", "/api/users/rename", "/api/version"]);
+}
+
+#[test]
+fn test_client_declares_the_types() {
+    let client = generate_client();
+
+    assert!(client.contains(r#"export type Role = "Admin" | "Member";"#));
+    assert!(client.contains(
+        "export type User = { email?: string | null; id: number; name: string; role: Role; tags: string[] };"
+    ));
+    assert!(client.contains("export type ListUsersParams = { role?: Role | null };"));
+    assert!(client.contains("export class ApiError extends Error"));
+}
+
+#[test]
+fn test_client_has_a_function_per_endpoint() {
+    let client = generate_client();
+
+    assert!(client.contains(
+        "export function listUsers(params: ListUsersParams, init?: RequestInit): Promise<User[]> {\n  return request<User[]>(\"GET\", \"/api/users\", params, init);"
+    ));
+    assert!(client.contains(
+        "export function renameUser(params: RenameUserParams, init?: RequestInit): Promise<User> {"
+    ));
+    assert!(client.contains(
+        "export function getVersion(init?: RequestInit): Promise<[number, number]> {\n  return request<[number, number]>(\"GET\", \"/api/version\", undefined, init);"
+    ));
+}
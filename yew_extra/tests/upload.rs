@@ -0,0 +1,61 @@
+// Exercises the server side of the chunked upload protocol
+#![cfg(feature = "axum-08")]
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use yew_extra::upload::{handle_append, handle_create, handle_offset, take_upload, UploadStatus};
+
+async fn status_of(response: axum::http::Response<Body>) -> UploadStatus {
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+fn append(upload_id: &str, offset: u64, chunk: &'static [u8]) -> Request<Body> {
+    Request::patch(format!(
+        "/upload/append?upload_id={}&offset={}",
+        upload_id, offset
+    ))
+    .body(Body::from(chunk))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_chunked_upload_round_trip() {
+    let create = Request::post("/upload/create")
+        .body(Body::from(
+            r#"{"file_name":"hello.txt","size":11,"content_type":"text/plain"}"#,
+        ))
+        .unwrap();
+    let response = handle_create(create).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let status = status_of(response).await;
+    assert_eq!(status.offset, 0);
+
+    let upload_id = status.upload_id;
+
+    // Incomplete uploads can't be taken yet
+    let response = handle_append(append(&upload_id, 0, b"hello ")).await;
+    assert_eq!(status_of(response).await.offset, 6);
+    assert!(take_upload(&upload_id).await.is_none());
+
+    // A chunk at the wrong offset is rejected so the client can resync
+    let response = handle_append(append(&upload_id, 2, b"world")).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let offset = Request::get(format!("/upload/offset?upload_id={}", upload_id))
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(status_of(handle_offset(offset).await).await.offset, 6);
+
+    let response = handle_append(append(&upload_id, 6, b"world")).await;
+    assert_eq!(status_of(response).await.offset, 11);
+
+    let file = take_upload(&upload_id).await.unwrap();
+    assert_eq!(file.file_name(), "hello.txt");
+    assert_eq!(file.content_type(), Some("text/plain"));
+    assert_eq!(file.bytes().await.unwrap(), b"hello world");
+
+    // The temporary file is removed once the upload is dropped
+    let path = file.path().to_path_buf();
+    drop(file);
+    assert!(!path.exists());
+}
@@ -0,0 +1,37 @@
+// Checks that upload sessions beyond the configured limits are refused. The limits are
+// global, so this runs apart from the other upload tests.
+#![cfg(feature = "axum-08")]
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use yew_extra::upload::{handle_create, set_upload_limits, UploadLimits};
+
+async fn create(size: u64) -> StatusCode {
+    let body = format!(
+        r#"{{"file_name":"data.bin","size":{},"content_type":null}}"#,
+        size
+    );
+    let request = Request::post("/upload/create")
+        .body(Body::from(body))
+        .unwrap();
+    handle_create(request).await.status()
+}
+
+#[tokio::test]
+async fn test_sessions_beyond_the_limits_are_refused() {
+    // Declaring a huge file is refused before anything is written
+    assert_eq!(create(u64::MAX).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    set_upload_limits(UploadLimits {
+        max_file_size: 100,
+        max_sessions: 2,
+        max_total_size: 150,
+    });
+    assert_eq!(create(101).await, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(create(100).await, StatusCode::CREATED);
+    // The open sessions may only declare 150 bytes together
+    assert_eq!(create(60).await, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(create(50).await, StatusCode::CREATED);
+    assert_eq!(create(0).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    set_upload_limits(UploadLimits::default());
+}
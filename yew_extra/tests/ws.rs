@@ -0,0 +1,137 @@
+// Checks that WebSocket endpoints exchange typed messages and reject plain requests
+#![cfg(all(feature = "ws", feature = "axum-08"))]
+
+use axum::body::Body;
+use axum::http::{HeaderMap, Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+use tower::ServiceExt;
+use yew_extra::ws::{handle, websocket_url, Inbound};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ChatIn {
+    text: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ChatOut {
+    from: String,
+    text: String,
+}
+
+async fn chat(
+    inbound: Inbound<ChatIn>,
+) -> Result<impl Stream<Item = ChatOut>, (StatusCode, &'static str)> {
+    let headers = yew_extra::extract::<HeaderMap>().await.unwrap();
+    let Some(user) = headers.get("x-user").and_then(|user| user.to_str().ok()) else {
+        return Err((StatusCode::UNAUTHORIZED, "Log in to chat"));
+    };
+    let user = user.to_string();
+    Ok(inbound.map(move |message| ChatOut {
+        from: user.clone(),
+        text: message.text.to_uppercase(),
+    }))
+}
+
+fn app() -> Router {
+    Router::new().route(
+        "/ws/chat",
+        get(|req: Request<Body>| yew_extra::request_scope(handle(req, chat))),
+    )
+}
+
+async fn serve() -> String {
+    let app = app();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("ws://{}/ws/chat", address)
+}
+
+#[tokio::test]
+async fn test_messages_are_answered_as_typed_json() {
+    let mut request = serve().await.into_client_request().unwrap();
+    request
+        .headers_mut()
+        .insert("x-user", "ada".parse().unwrap());
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+
+    socket
+        .send(Message::text("{\"text\":\"hello\"}"))
+        .await
+        .unwrap();
+    // Messages that aren't a `ChatIn` are dropped
+    socket.send(Message::text("not json")).await.unwrap();
+    socket
+        .send(Message::text("{\"text\":\"bye\"}"))
+        .await
+        .unwrap();
+
+    let mut received = Vec::new();
+    while received.len() < 2 {
+        match socket.next().await.unwrap().unwrap() {
+            Message::Text(text) => received.push(serde_json::from_str::<ChatOut>(&text).unwrap()),
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+    assert_eq!(
+        received,
+        vec![
+            ChatOut {
+                from: "ada".to_string(),
+                text: "HELLO".to_string()
+            },
+            ChatOut {
+                from: "ada".to_string(),
+                text: "BYE".to_string()
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_error_answers_the_upgrade_request() {
+    let request = serve().await.into_client_request().unwrap();
+
+    let error = tokio_tungstenite::connect_async(request).await.unwrap_err();
+    let tokio_tungstenite::tungstenite::Error::Http(response) = error else {
+        panic!("expected an HTTP error, got {:?}", error);
+    };
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_plain_requests_are_rejected() {
+    let request = Request::builder()
+        .uri("/ws/chat")
+        .header("x-user", "ada")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn test_websocket_url_follows_the_api_base() {
+    assert_eq!(
+        websocket_url("https://api.example.com/v1", "/ws/chat", "http://localhost"),
+        "wss://api.example.com/v1/ws/chat"
+    );
+    assert_eq!(
+        websocket_url("http://localhost:3000/", "/ws/chat", "http://localhost"),
+        "ws://localhost:3000/ws/chat"
+    );
+    assert_eq!(
+        websocket_url("", "/ws/chat", "https://example.com"),
+        "wss://example.com/ws/chat"
+    );
+    assert_eq!(
+        websocket_url("/app", "/ws/chat", "http://localhost:8080"),
+        "ws://localhost:8080/app/ws/chat"
+    );
+}
@@ -4,16 +4,361 @@ use syn::{
     parse::Parse, parse::ParseStream, parse_macro_input, FnArg, ItemFn, Pat, ReturnType, Type,
 };
 
+/// Fetch options accepted as macro arguments, with the values each one allows
+const FETCH_OPTIONS: &[(&str, &str, &[&str])] = &[
+    (
+        "credentials",
+        "RequestCredentials",
+        &["omit", "same-origin", "include"],
+    ),
+    (
+        "cache",
+        "RequestCache",
+        &[
+            "default",
+            "no-store",
+            "reload",
+            "no-cache",
+            "force-cache",
+            "only-if-cached",
+        ],
+    ),
+    (
+        "mode",
+        "RequestMode",
+        &["same-origin", "no-cors", "cors", "navigate"],
+    ),
+    (
+        "redirect",
+        "RequestRedirect",
+        &["follow", "error", "manual"],
+    ),
+];
+
+/// Fixture used instead of the server when the app is built with the `mock` feature
+#[derive(Clone)]
+struct MockOptions {
+    fixture: syn::Path,
+    latency_ms: Option<u32>,
+}
+
+/// Attributes of the server function that are copied onto the generated items
+struct ForwardedAttrs {
+    /// Doc comments, shown on every item users call
+    docs: Vec<syn::Attribute>,
+    /// Lint attributes and `#[deprecated]`
+    lints: Vec<syn::Attribute>,
+    /// Everything else, e.g. `#[tracing::instrument]`, which only applies to the server handler
+    server: Vec<syn::Attribute>,
+}
+
+impl ForwardedAttrs {
+    fn new(attrs: &[syn::Attribute]) -> Self {
+        let mut forwarded = ForwardedAttrs {
+            docs: Vec::new(),
+            lints: Vec::new(),
+            server: Vec::new(),
+        };
+        for attr in attrs {
+            let path = attr.path();
+            if path.is_ident("doc") {
+                forwarded.docs.push(attr.clone());
+            } else if path.is_ident("deprecated")
+                || PARAM_LINT_ATTRS.iter().any(|name| path.is_ident(name))
+            {
+                forwarded.lints.push(attr.clone());
+            } else if !path.is_ident("cfg") {
+                forwarded.server.push(attr.clone());
+            }
+        }
+        forwarded
+    }
+
+    fn is_deprecated(&self) -> bool {
+        self.lints
+            .iter()
+            .any(|attr| attr.path().is_ident("deprecated"))
+    }
+
+    /// Attributes for the client functions and hooks
+    fn client(&self) -> proc_macro2::TokenStream {
+        let docs = &self.docs;
+        let lints = &self.lints;
+        // The generated variants call each other, which shouldn't warn
+        let allow_deprecated = if self.is_deprecated() {
+            quote! { #[allow(deprecated)] }
+        } else {
+            quote! {}
+        };
+        quote! {
+            #(#docs)*
+            #(#lints)*
+            #allow_deprecated
+        }
+    }
+
+    /// Attributes for the server handler, which runs the function body
+    fn handler(&self) -> proc_macro2::TokenStream {
+        let docs = &self.docs;
+        let lints = self
+            .lints
+            .iter()
+            .filter(|attr| !attr.path().is_ident("deprecated"));
+        let server = &self.server;
+        quote! {
+            #(#docs)*
+            #(#lints)*
+            #(#server)*
+        }
+    }
+
+    /// Attributes for the `Params` struct
+    fn params(&self, fn_name: &syn::Ident) -> proc_macro2::TokenStream {
+        if self.docs.is_empty() {
+            return quote! {};
+        }
+        let docs = &self.docs;
+        let heading = format!(" Parameters of [`{}`]", fn_name);
+        quote! {
+            #[doc = #heading]
+            #[doc = ""]
+            #(#docs)*
+        }
+    }
+}
+
+/// A concrete instantiation of a generic server function, declared with
+/// `instance(name = "users", T = "User", path = "/api/users/count")`
+#[derive(Clone)]
+struct Instance {
+    /// Suffix of the generated function, e.g. `count_users`
+    name: syn::Ident,
+    /// Generic parameters and the types they are replaced with
+    types: Vec<(syn::Ident, Type)>,
+    /// Path of the endpoint, `<path>/<name>` by default
+    path: Option<String>,
+}
+
+impl Parse for Instance {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut name = None;
+        let mut types = Vec::new();
+        let mut path = None;
+
+        while !input.is_empty() {
+            let ident: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            let value: syn::LitStr = input.parse()?;
+
+            if ident == "name" {
+                name = Some(syn::Ident::new(&value.value(), value.span()));
+            } else if ident == "path" {
+                path = Some(value.value());
+            } else {
+                types.push((ident, value.parse::<Type>()?));
+            }
+
+            if input.peek(syn::Token![,]) {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        let name = name.ok_or_else(|| {
+            syn::Error::new(
+                input.span(),
+                "Missing 'name' in instance(...), e.g. instance(name = \"users\", T = \"User\")",
+            )
+        })?;
+
+        Ok(Instance { name, types, path })
+    }
+}
+
+/// Retirement of an endpoint declared with `deprecated(...)`
+#[derive(Clone, Default)]
+struct DeprecatedArgs {
+    /// `YYYY-MM-DD` date the endpoint was deprecated
+    since: Option<String>,
+    /// `YYYY-MM-DD` date the endpoint stops answering
+    sunset: Option<String>,
+    /// Path or URL of the endpoint to use instead
+    replacement: Option<String>,
+}
+
+impl Parse for DeprecatedArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = DeprecatedArgs::default();
+
+        while !input.is_empty() {
+            let ident: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            let value: syn::LitStr = input.parse()?;
+
+            if ident == "since" || ident == "sunset" {
+                if !is_iso_date(&value.value()) {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        format!(
+                            "Invalid {} date '{}'. Expected YYYY-MM-DD, e.g. \"2025-12-01\"",
+                            ident,
+                            value.value()
+                        ),
+                    ));
+                }
+                if ident == "since" {
+                    args.since = Some(value.value());
+                } else {
+                    args.sunset = Some(value.value());
+                }
+            } else if ident == "replacement" {
+                args.replacement = Some(value.value());
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!(
+                        "Unknown deprecated argument '{}'. Expected one of: since, sunset, replacement",
+                        ident
+                    ),
+                ));
+            }
+
+            if input.peek(syn::Token![,]) {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+/// Whether `value` is a `YYYY-MM-DD` date
+fn is_iso_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+    let digits =
+        |part: &str, len: usize| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+    digits(year, 4)
+        && digits(month, 2)
+        && digits(day, 2)
+        && (1..=12).contains(&month.parse::<u32>().unwrap_or(0))
+        && (1..=31).contains(&day.parse::<u32>().unwrap_or(0))
+}
+
 // Define a custom parser for the macro arguments
+#[derive(Clone)]
 struct MacroArgs {
     path: String,
     method: String,
+    /// Whether `method` was given, rather than defaulting to POST
+    method_declared: bool,
+    form: bool,
+    upload: bool,
+    sw_cache: Option<String>,
+    background_sync: bool,
+    schema_check: bool,
+    /// Register the endpoint for yew_extra's TypeScript client
+    typescript: bool,
+    graphql: bool,
+    mock: Option<MockOptions>,
+    debug_expand: bool,
+    coalesce: bool,
+    /// Run the body on the blocking thread pool
+    blocking: bool,
+    /// Deadline for the server function in milliseconds
+    server_timeout: Option<u64>,
+    /// Serialize the returned list while sending it
+    stream_json: bool,
+    /// Keep the request body for `extract_body()`
+    raw_body: bool,
+    /// Size limit of the kept body in bytes, yew_extra's default if unset
+    body_limit: Option<usize>,
+    /// Reject requests with a reused or stale nonce
+    nonce: bool,
+    /// How old the timestamp of a request may be in milliseconds, yew_extra's default if unset
+    nonce_max_age: Option<u64>,
+    /// Parse responses with the browser's `JSON.parse`
+    fast_json: bool,
+    /// Keep the hook's data in an `Rc`
+    shared_data: bool,
+    /// Number of times the client retries a request that failed temporarily
+    retry: Option<u32>,
+    /// Send an `Idempotency-Key` with every mutation
+    idempotent: bool,
+    /// Function providing data the hook shows until the first response arrives
+    placeholder_data: Option<syn::Path>,
+    /// Function turning the hook's parameters into the key deciding when it refetches
+    key: Option<syn::Path>,
+    /// Function patching the cache before a mutation's response arrives
+    optimistic: Option<syn::Path>,
+    /// Name of the generated parameter struct, `<Function>Params` by default
+    params: Option<syn::Ident>,
+    instances: Vec<Instance>,
+    /// Fetch options as (option name, value) pairs, e.g. ("credentials", "include")
+    fetch_options: Vec<(String, String)>,
+    /// Origin of the service the endpoint is handled by, instead of this app
+    base: Option<String>,
+    /// Who may call the endpoint: "required", "optional" or "public"
+    auth: Option<String>,
+    tags: Vec<String>,
+    /// Team owning the endpoint
+    owner: Option<String>,
+    /// Runtime flag the endpoint only answers while it's on
+    feature_flag: Option<String>,
+    /// Media type of the successful responses, sent by the client as `Accept`
+    content_type: Option<String>,
+    /// `PayloadTransform` the request and response bodies go through
+    transform: Option<syn::Path>,
+    deprecated: Option<DeprecatedArgs>,
+    /// Type of the app state the handler takes as `State<T>`
+    state: Option<syn::Type>,
+    /// Milliseconds between the refetches of the hook
+    poll_interval: Option<u64>,
 }
 
 impl Parse for MacroArgs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut path = None;
         let mut method = None;
+        let mut form = false;
+        let mut upload = false;
+        let mut sw_cache = None;
+        let mut background_sync = false;
+        let mut schema_check = false;
+        let mut typescript = false;
+        let mut graphql = false;
+        let mut mock_fixture = None;
+        let mut mock_latency = None;
+        let mut debug_expand = false;
+        let mut coalesce = false;
+        let mut blocking = false;
+        let mut server_timeout = None;
+        let mut stream_json = false;
+        let mut raw_body = false;
+        let mut body_limit = None;
+        let mut nonce = false;
+        let mut nonce_max_age = None;
+        let mut fast_json = false;
+        let mut shared_data = false;
+        let mut retry = None;
+        let mut idempotent = false;
+        let mut placeholder_data = None;
+        let mut key = None;
+        let mut optimistic = None;
+        let mut params = None;
+        let mut instances = Vec::new();
+        let mut fetch_options = Vec::new();
+        let mut base = None;
+        let mut auth = None;
+        let mut tags = Vec::new();
+        let mut owner = None;
+        let mut feature_flag = None;
+        let mut content_type = None;
+        let mut transform = None;
+        let mut deprecated = None;
+        let mut state = None;
+        let mut poll_interval = None;
 
         // Parse arguments in any order
         loop {
@@ -23,6 +368,34 @@ impl Parse for MacroArgs {
 
             // Parse the identifier (either "path" or "method")
             let ident: syn::Ident = input.parse()?;
+
+            if ident == "instance" {
+                let content;
+                syn::parenthesized!(content in input);
+                instances.push(content.parse::<Instance>()?);
+                if input.peek(syn::Token![,]) {
+                    input.parse::<syn::Token![,]>()?;
+                    continue;
+                }
+                break;
+            }
+
+            // `deprecated` alone, or with the dates and replacement in parentheses
+            if ident == "deprecated" {
+                deprecated = Some(if input.peek(syn::token::Paren) {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    content.parse::<DeprecatedArgs>()?
+                } else {
+                    DeprecatedArgs::default()
+                });
+                if input.peek(syn::Token![,]) {
+                    input.parse::<syn::Token![,]>()?;
+                    continue;
+                }
+                break;
+            }
+
             input.parse::<syn::Token![=]>()?;
 
             if ident == "path" {
@@ -40,10 +413,212 @@ impl Parse for MacroArgs {
                     ));
                 }
                 method = Some(method_value);
+            } else if ident == "form" {
+                let form_lit: syn::LitBool = input.parse()?;
+                form = form_lit.value;
+            } else if ident == "upload" {
+                let upload_lit: syn::LitBool = input.parse()?;
+                upload = upload_lit.value;
+            } else if ident == "sw_cache" {
+                let strategy_lit: syn::LitStr = input.parse()?;
+                let strategy = strategy_lit.value();
+
+                // Validate the caching strategy
+                if !["stale-while-revalidate", "cache-first", "network-first"]
+                    .contains(&strategy.as_str())
+                {
+                    return Err(syn::Error::new(
+                        strategy_lit.span(),
+                        "Invalid cache strategy. Must be one of: stale-while-revalidate, cache-first, network-first",
+                    ));
+                }
+                sw_cache = Some(strategy);
+            } else if ident == "background_sync" {
+                let sync_lit: syn::LitBool = input.parse()?;
+                background_sync = sync_lit.value;
+            } else if ident == "schema_check" {
+                let check_lit: syn::LitBool = input.parse()?;
+                schema_check = check_lit.value;
+            } else if ident == "typescript" {
+                let typescript_lit: syn::LitBool = input.parse()?;
+                typescript = typescript_lit.value;
+            } else if ident == "graphql" {
+                let graphql_lit: syn::LitBool = input.parse()?;
+                graphql = graphql_lit.value;
+            } else if ident == "mock" {
+                let fixture_lit: syn::LitStr = input.parse()?;
+                mock_fixture = Some(fixture_lit.parse::<syn::Path>()?);
+            } else if ident == "mock_latency" {
+                let latency_lit: syn::LitInt = input.parse()?;
+                mock_latency = Some((latency_lit.base10_parse::<u32>()?, latency_lit.span()));
+            } else if ident == "debug_expand" {
+                let expand_lit: syn::LitBool = input.parse()?;
+                debug_expand = expand_lit.value;
+            } else if ident == "coalesce" {
+                let coalesce_lit: syn::LitBool = input.parse()?;
+                coalesce = coalesce_lit.value;
+            } else if ident == "blocking" {
+                let blocking_lit: syn::LitBool = input.parse()?;
+                blocking = blocking_lit.value;
+            } else if ident == "server_timeout" {
+                let timeout_lit: syn::LitStr = input.parse()?;
+                server_timeout = Some(parse_duration_ms(&timeout_lit)?);
+            } else if ident == "stream_json" {
+                let stream_lit: syn::LitBool = input.parse()?;
+                stream_json = stream_lit.value;
+            } else if ident == "raw_body" {
+                // Either `true` or the size limit, e.g. "10MB"
+                if input.peek(syn::LitStr) {
+                    let limit_lit: syn::LitStr = input.parse()?;
+                    body_limit = Some(parse_size_bytes(&limit_lit)?);
+                    raw_body = true;
+                } else {
+                    let raw_lit: syn::LitBool = input.parse()?;
+                    raw_body = raw_lit.value;
+                }
+            } else if ident == "nonce" {
+                // Either `true` or the allowed age, e.g. "2m"
+                if input.peek(syn::LitStr) {
+                    let age_lit: syn::LitStr = input.parse()?;
+                    nonce_max_age = Some(parse_duration_ms(&age_lit)?);
+                    nonce = true;
+                } else {
+                    let nonce_lit: syn::LitBool = input.parse()?;
+                    nonce = nonce_lit.value;
+                }
+            } else if ident == "fast_json" {
+                let fast_lit: syn::LitBool = input.parse()?;
+                fast_json = fast_lit.value;
+            } else if ident == "shared_data" {
+                let shared_lit: syn::LitBool = input.parse()?;
+                shared_data = shared_lit.value;
+            } else if ident == "retry" {
+                let retry_lit: syn::LitInt = input.parse()?;
+                retry = Some(retry_lit.base10_parse::<u32>()?);
+            } else if ident == "idempotent" {
+                let idempotent_lit: syn::LitBool = input.parse()?;
+                idempotent = idempotent_lit.value;
+            } else if ident == "placeholder_data" {
+                let placeholder_lit: syn::LitStr = input.parse()?;
+                placeholder_data = Some(placeholder_lit.parse::<syn::Path>()?);
+            } else if ident == "key" {
+                let key_lit: syn::LitStr = input.parse()?;
+                key = Some(key_lit.parse::<syn::Path>()?);
+            } else if ident == "optimistic" {
+                let optimistic_lit: syn::LitStr = input.parse()?;
+                optimistic = Some(optimistic_lit.parse::<syn::Path>()?);
+            } else if ident == "base" {
+                let base_lit: syn::LitStr = input.parse()?;
+                let value = base_lit.value();
+                if !value.starts_with("http://") && !value.starts_with("https://") {
+                    return Err(syn::Error::new(
+                        base_lit.span(),
+                        "The 'base' option must be an absolute URL, e.g. \"https://files.example.com\"",
+                    ));
+                }
+                base = Some(value.trim_end_matches('/').to_string());
+            } else if ident == "auth" {
+                let auth_lit: syn::LitStr = input.parse()?;
+                let value = auth_lit.value();
+                if !["required", "optional", "public"].contains(&value.as_str()) {
+                    return Err(syn::Error::new(
+                        auth_lit.span(),
+                        "Invalid auth value. Must be one of: required, optional, public",
+                    ));
+                }
+                auth = Some(value);
+            } else if ident == "tags" {
+                // Either one tag or a list, e.g. ["public", "billing"]
+                if input.peek(syn::token::Bracket) {
+                    let content;
+                    syn::bracketed!(content in input);
+                    let list = content.parse_terminated(
+                        |input: ParseStream| input.parse::<syn::LitStr>(),
+                        syn::Token![,],
+                    )?;
+                    tags.extend(list.iter().map(|tag| tag.value()));
+                } else {
+                    let tag_lit: syn::LitStr = input.parse()?;
+                    tags.push(tag_lit.value());
+                }
+            } else if ident == "owner" {
+                let owner_lit: syn::LitStr = input.parse()?;
+                owner = Some(owner_lit.value());
+            } else if ident == "feature_flag" {
+                let flag_lit: syn::LitStr = input.parse()?;
+                feature_flag = Some(flag_lit.value());
+            } else if ident == "content_type" {
+                let content_type_lit: syn::LitStr = input.parse()?;
+                let value = content_type_lit.value();
+                // Anything else would panic as a header value
+                let valid = value.contains('/')
+                    && value
+                        .bytes()
+                        .all(|byte| byte == b' ' || byte.is_ascii_graphic());
+                if !valid {
+                    return Err(syn::Error::new(
+                        content_type_lit.span(),
+                        format!(
+                            "Invalid content_type '{}'. Expected a media type such as \"application/vnd.myapp+json\"",
+                            value
+                        ),
+                    ));
+                }
+                content_type = Some(value);
+            } else if ident == "transform" {
+                let transform_lit: syn::LitStr = input.parse()?;
+                transform = Some(transform_lit.parse::<syn::Path>()?);
+            } else if ident == "state" {
+                // The type itself, or its name in a string like the other options
+                state = Some(if input.peek(syn::LitStr) {
+                    input.parse::<syn::LitStr>()?.parse::<syn::Type>()?
+                } else {
+                    input.parse::<syn::Type>()?
+                });
+            } else if ident == "poll_interval" {
+                // Milliseconds, or a duration like the other options, e.g. "5s"
+                poll_interval = Some(if input.peek(syn::LitStr) {
+                    parse_duration_ms(&input.parse::<syn::LitStr>()?)?
+                } else {
+                    let interval_lit: syn::LitInt = input.parse()?;
+                    match interval_lit.base10_parse::<u64>()? {
+                        0 => {
+                            return Err(syn::Error::new(
+                                interval_lit.span(),
+                                "The poll interval must be at least 1 millisecond",
+                            ))
+                        }
+                        interval => interval,
+                    }
+                });
+            } else if ident == "params" {
+                let params_lit: syn::LitStr = input.parse()?;
+                params = Some(params_lit.parse::<syn::Ident>()?);
+            } else if let Some((name, _, allowed)) =
+                FETCH_OPTIONS.iter().find(|(name, _, _)| ident == name)
+            {
+                let option_lit: syn::LitStr = input.parse()?;
+                let value = option_lit.value();
+
+                // Validate the value against the Fetch API
+                if !allowed.contains(&value.as_str()) {
+                    return Err(syn::Error::new(
+                        option_lit.span(),
+                        format!(
+                            "Invalid {} value. Must be one of: {}",
+                            name,
+                            allowed.join(", ")
+                        ),
+                    ));
+                }
+                fetch_options.push((name.to_string(), value));
             } else {
                 return Err(syn::Error::new(
                     ident.span(),
-                    format!("Unknown argument '{}'. Expected 'path' or 'method'", ident),
+                    format!(
+                        "Unknown argument '{}'. Expected one of: path, method, form, upload, sw_cache, background_sync, schema_check, typescript, graphql, mock, mock_latency, debug_expand, coalesce, blocking, server_timeout, stream_json, raw_body, nonce, fast_json, shared_data, retry, idempotent, placeholder_data, key, optimistic, params, instance, base, auth, tags, owner, feature_flag, content_type, transform, deprecated, state, poll_interval, credentials, cache, mode, redirect",
+                        ident
+                    ),
                 ));
             }
 
@@ -60,292 +635,4735 @@ impl Parse for MacroArgs {
             path.ok_or_else(|| syn::Error::new(input.span(), "Missing required argument 'path'"))?;
 
         // Method defaults to POST if not specified
+        let method_declared = method.is_some();
         let method = method.unwrap_or_else(|| "POST".to_string());
 
-        Ok(MacroArgs { path, method })
-    }
-}
+        // Forms submit mutations, so they make no sense for GET endpoints
+        if form && method == "GET" {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'form' option is only supported for mutation endpoints (POST, PUT, DELETE, PATCH)",
+            ));
+        }
 
-/// A procedural macro that generates both server-side API endpoint and client-side Yew hook
-///
+        // Uploads are completed with a POST carrying the upload ID
+        if upload && method != "POST" {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'upload' option is only supported for POST endpoints",
+            ));
+        }
 
-/// This will generate:
-/// - A server-side handler function for use with Axum
-/// - A client-side Yew hook (use_users) that fetches data from the endpoint
-#[proc_macro_attribute]
-pub fn yewserverhook(args: TokenStream, input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as ItemFn);
+        if upload && form {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'form' and 'upload' options can't be combined",
+            ));
+        }
 
-    // Parse the path and method arguments
-    let args = parse_macro_input!(args as MacroArgs);
-    let path = args.path;
-    let method = args.method;
+        // Reads change nothing worth replaying
+        if nonce && method == "GET" {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'nonce' option is only supported for mutation endpoints (POST, PUT, DELETE, PATCH)",
+            ));
+        }
 
-    // Extract function details
-    let fn_name = &input.sig.ident;
-    let fn_vis = &input.vis;
-    let fn_block = &input.block;
-    let fn_inputs = &input.sig.inputs;
-    let fn_output = &input.sig.output;
+        // Every attempt or delayed replay of the same request carries the same nonce, which
+        // the server rejects
+        if nonce && (retry.is_some() || background_sync) {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'nonce' option can't be combined with 'retry' or 'background_sync', the server would reject the repeated request as a replay",
+            ));
+        }
 
-    // Determine if function has parameters (excluding self)
-    let has_params = !fn_inputs.is_empty();
+        if nonce && upload {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'nonce' and 'upload' options can't be combined",
+            ));
+        }
 
-    // Extract return type and error type
-    let (return_type, error_type) = extract_return_type(fn_output);
-    let error_type = error_type.unwrap_or_else(|| quote! { () });
+        // Only reads can be answered from the service worker cache
+        if sw_cache.is_some() && method != "GET" {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'sw_cache' option is only supported for GET endpoints",
+            ));
+        }
 
-    // Generate hook name from function name (e.g., get_users -> use_users)
-    let hook_name = format!("use_{}", fn_name.to_string());
-    let hook_ident = syn::Ident::new(&hook_name, fn_name.span());
+        if stream_json && upload {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'stream_json' and 'upload' options can't be combined",
+            ));
+        }
 
-    // Generate parameter struct if needed
-    let param_struct = if has_params {
-        generate_param_struct(fn_name, fn_inputs)
-    } else {
-        quote! {}
-    };
+        // Coalescing buffers the response to share it, which defeats streaming it
+        if coalesce && stream_json {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'coalesce' and 'stream_json' options can't be combined",
+            ));
+        }
 
-    // Generate the server handler
-    let server_handler = generate_server_handler(
-        fn_name,
-        fn_vis,
-        fn_block,
-        fn_inputs,
-        fn_output,
-        has_params,
-        &return_type,
-        &error_type,
-        &path,
-        &method,
-    );
+        if server_timeout.is_some() && upload {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'server_timeout' and 'upload' options can't be combined",
+            ));
+        }
 
-    // Generate the client hook
-    let client_hook = generate_client_hook(
-        &hook_ident,
-        fn_vis,
-        &path,
-        &return_type,
-        has_params,
-        fn_name,
-        fn_inputs,
-        &method,
-    );
+        if feature_flag.is_some() && upload {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'feature_flag' and 'upload' options can't be combined",
+            ));
+        }
 
-    // Generate the direct callable function for client
-    let client_function = generate_client_function(
-        fn_name,
-        fn_vis,
-        &path,
-        &return_type,
-        has_params,
-        fn_inputs,
-        &method,
-    );
+        // Uploads read their chunks themselves
+        if raw_body && upload {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'raw_body' and 'upload' options can't be combined",
+            ));
+        }
 
-    // Don't generate additional wrapper - the hook_ident is already what we want
-    let hook_wrapper = quote! {};
+        if blocking && upload {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'blocking' and 'upload' options can't be combined",
+            ));
+        }
 
-    let expanded = quote! {
+        // Upload endpoints don't get a hook
+        if shared_data && upload {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'shared_data' and 'upload' options can't be combined",
+            ));
+        }
 
-        #[cfg(feature = "ssr")]
-        #input
+        // Only reads are safe to repeat on their own
+        if poll_interval.is_some() && method != "GET" {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'poll_interval' option is only supported for GET endpoints",
+            ));
+        }
 
-        #param_struct
+        // Mutations must run once per request
+        if coalesce && method != "GET" {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'coalesce' option is only supported for GET endpoints",
+            ));
+        }
 
-        #server_handler
+        // Reads are safe to repeat, only mutations need a key
+        if idempotent && method == "GET" {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'idempotent' option is only supported for mutation endpoints (POST, PUT, DELETE, PATCH)",
+            ));
+        }
 
-        #client_hook
+        // A retried mutation must not be applied twice
+        if retry.is_some() && method != "GET" && !idempotent && !background_sync {
+            return Err(syn::Error::new(
+                input.span(),
+                "Retrying a mutation could apply it twice. Add 'idempotent = true' so every attempt carries the same Idempotency-Key",
+            ));
+        }
 
-        #[cfg(not(feature = "ssr"))]
-        #client_function
+        // Only mutations change what the cached reads return
+        if optimistic.is_some() && method == "GET" {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'optimistic' option is only supported for mutation endpoints (POST, PUT, DELETE, PATCH)",
+            ));
+        }
 
-        #hook_wrapper
-    };
+        if optimistic.is_some() && upload {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'optimistic' and 'upload' options can't be combined",
+            ));
+        }
 
-    TokenStream::from(expanded)
-}
+        // Only reads get a hook with data to show before the response
+        if placeholder_data.is_some() && method != "GET" {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'placeholder_data' option is only supported for GET endpoints",
+            ));
+        }
 
-fn extract_return_type(
-    output: &ReturnType,
-) -> (proc_macro2::TokenStream, Option<proc_macro2::TokenStream>) {
-    match output {
-        ReturnType::Default => (quote! { () }, None),
-        ReturnType::Type(_, ty) => {
-            // Extract the inner type from Result<T, E>
-            if let Type::Path(type_path) = &**ty {
-                if let Some(segment) = type_path.path.segments.last() {
-                    if segment.ident == "Result" {
-                        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                            if let (
-                                Some(syn::GenericArgument::Type(ok_type)),
-                                Some(syn::GenericArgument::Type(err_type)),
-                            ) = (args.args.first(), args.args.iter().nth(1))
-                            {
-                                return (quote! { #ok_type }, Some(quote! { #err_type }));
-                            }
-                        }
-                    }
-                }
-            }
-            (quote! { #ty }, None)
+        // The key decides when the hook of a read refetches
+        if key.is_some() && method != "GET" {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'key' option is only supported for GET endpoints",
+            ));
         }
-    }
-}
 
-fn generate_param_struct(
-    fn_name: &syn::Ident,
-    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
-) -> proc_macro2::TokenStream {
-    let struct_name = syn::Ident::new(
-        &format!("{}Params", to_pascal_case(&fn_name.to_string())),
-        fn_name.span(),
-    );
+        if upload && (retry.is_some() || idempotent) {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'retry' and 'idempotent' options can't be combined with 'upload', which retries its chunks itself",
+            ));
+        }
 
-    let mut fields = Vec::new();
+        // Reads aren't queued, they are served from the cache instead
+        if background_sync && method == "GET" {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'background_sync' option is only supported for mutation endpoints (POST, PUT, DELETE, PATCH)",
+            ));
+        }
 
-    for input in inputs {
-        if let FnArg::Typed(pat_type) = input {
-            if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                let field_name = &pat_ident.ident;
-                let field_type = &pat_type.ty;
-                fields.push(quote! {
-                    pub #field_name: #field_type
-                });
-            }
+        if background_sync && upload {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'background_sync' and 'upload' options can't be combined",
+            ));
         }
-    }
 
-    quote! {
-        #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
-        pub struct #struct_name {
-            #(#fields),*
+        // The chunked upload protocol isn't a single JSON request a fetch wrapper could send
+        if upload && typescript {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'typescript' and 'upload' options can't be combined",
+            ));
+        }
+
+        // The GraphQL facade answers with the parsed JSON of a single response, and sends
+        // the headers of the GraphQL request to every endpoint it calls
+        if graphql {
+            let incompatible = [
+                ("upload", upload),
+                ("stream_json", stream_json),
+                ("nonce", nonce),
+                ("transform", transform.is_some()),
+            ];
+            if let Some((name, _)) = incompatible.iter().find(|(_, set)| *set) {
+                return Err(syn::Error::new(
+                    input.span(),
+                    format!("The 'graphql' and '{}' options can't be combined", name),
+                ));
+            }
+        }
+
+        if content_type.is_some() && upload {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'content_type' and 'upload' options can't be combined",
+            ));
+        }
+
+        if deprecated.is_some() && upload {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'deprecated' and 'upload' options can't be combined",
+            ));
+        }
+
+        // The GraphQL facade and the upload protocol call the stateless wrapper
+        if state.is_some() && (upload || graphql) {
+            let option = if upload { "upload" } else { "graphql" };
+            return Err(syn::Error::new(
+                input.span(),
+                format!("The 'state' and '{}' options can't be combined", option),
+            ));
+        }
+
+        // Only the Rust clients fill in the path, uploads send their chunks to fixed paths
+        if !path_parameter_names(&path).is_empty() {
+            let incompatible = [
+                ("upload", upload),
+                ("typescript", typescript),
+                ("graphql", graphql),
+            ];
+            if let Some((name, _)) = incompatible.iter().find(|(_, set)| *set) {
+                return Err(syn::Error::new(
+                    input.span(),
+                    format!(
+                        "Path parameters can't be combined with the '{}' option",
+                        name
+                    ),
+                ));
+            }
+        }
+
+        // Transformed bodies are read as a whole, and only the Rust clients know the transform
+        if transform.is_some() {
+            let incompatible = [
+                ("upload", upload),
+                ("stream_json", stream_json),
+                ("typescript", typescript),
+            ];
+            if let Some((name, _)) = incompatible.iter().find(|(_, set)| *set) {
+                return Err(syn::Error::new(
+                    input.span(),
+                    format!("The 'transform' and '{}' options can't be combined", name),
+                ));
+            }
+        }
+
+        // Most upload requests are sent by yew_extra, which doesn't know about these options
+        if upload && !fetch_options.is_empty() {
+            return Err(syn::Error::new(
+                input.span(),
+                "Fetch options (credentials, cache, mode, redirect) can't be combined with 'upload'",
+            ));
+        }
+
+        // Endpoints of another service aren't routed, cached or described by this app
+        if base.is_some() {
+            let local_only = [
+                ("upload", upload),
+                ("sw_cache", sw_cache.is_some()),
+                ("background_sync", background_sync),
+                ("typescript", typescript),
+                ("graphql", graphql),
+            ];
+            if let Some((name, _)) = local_only.iter().find(|(_, set)| *set) {
+                return Err(syn::Error::new(
+                    input.span(),
+                    format!("The 'base' and '{}' options can't be combined", name),
+                ));
+            }
+        }
+
+        // Every instance would get the same struct name
+        if params.is_some() && !instances.is_empty() {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'params' option can't be combined with 'instance', each instance names its own struct",
+            ));
+        }
+
+        // Edge runtimes like Cloudflare Workers have no tokio worker threads, filesystem or
+        // link-time route registry
+        if cfg!(feature = "workers") {
+            let unsupported = [
+                ("upload", upload),
+                ("sw_cache", sw_cache.is_some()),
+                ("background_sync", background_sync),
+                ("typescript", typescript),
+                ("graphql", graphql),
+                ("idempotent", idempotent),
+                ("coalesce", coalesce),
+                ("blocking", blocking),
+                ("server_timeout", server_timeout.is_some()),
+                ("stream_json", stream_json),
+                ("raw_body", raw_body),
+                ("nonce", nonce),
+            ];
+            if let Some((name, _)) = unsupported.iter().find(|(_, set)| *set) {
+                return Err(syn::Error::new(
+                    input.span(),
+                    format!(
+                        "The '{}' option isn't supported with the `workers` feature",
+                        name
+                    ),
+                ));
+            }
+        }
+
+        let mock = match (mock_fixture, mock_latency) {
+            (Some(fixture), latency) => Some(MockOptions {
+                fixture,
+                latency_ms: latency.map(|(ms, _)| ms),
+            }),
+            (None, Some((_, span))) => {
+                return Err(syn::Error::new(
+                    span,
+                    "The 'mock_latency' option requires a 'mock' fixture",
+                ));
+            }
+            (None, None) => None,
+        };
+
+        // The upload client sends a browser File through several requests, which a fixture can't stand in for
+        if upload && mock.is_some() {
+            return Err(syn::Error::new(
+                input.span(),
+                "The 'mock' and 'upload' options can't be combined",
+            ));
+        }
+
+        Ok(MacroArgs {
+            path,
+            method,
+            method_declared,
+            form,
+            upload,
+            sw_cache,
+            background_sync,
+            schema_check,
+            typescript,
+            graphql,
+            mock,
+            debug_expand,
+            coalesce,
+            blocking,
+            server_timeout,
+            stream_json,
+            raw_body,
+            body_limit,
+            nonce,
+            nonce_max_age,
+            fast_json,
+            shared_data,
+            retry,
+            idempotent,
+            placeholder_data,
+            key,
+            optimistic,
+            params,
+            instances,
+            fetch_options,
+            base,
+            auth,
+            tags,
+            owner,
+            feature_flag,
+            content_type,
+            transform,
+            deprecated,
+            state,
+            poll_interval,
+        })
+    }
+}
+
+/// Sets the `RouteMetadata` of the registered route from the `auth`, `tags` and `owner`
+/// options, appended to `RouteInfo::new(..)`
+fn generate_route_metadata(args: &MacroArgs) -> proc_macro2::TokenStream {
+    if args.auth.is_none()
+        && args.tags.is_empty()
+        && args.owner.is_none()
+        && args.deprecated.is_none()
+    {
+        return quote! {};
+    }
+    let auth = args.auth.as_deref().map(|auth| {
+        let variant = match auth {
+            "required" => quote! { Required },
+            "optional" => quote! { Optional },
+            _ => quote! { Public },
+        };
+        quote! { .auth(crate::route_registry::Auth::#variant) }
+    });
+    let tags = &args.tags;
+    let tags = (!tags.is_empty()).then(|| quote! { .tags(&[#(#tags),*]) });
+    let owner = args.owner.as_ref().map(|owner| quote! { .owner(#owner) });
+    let deprecation = args.deprecated.as_ref().map(|deprecated| {
+        let deprecation = generate_deprecation(deprecated);
+        quote! { .deprecation(#deprecation) }
+    });
+    quote! {
+        .metadata(crate::route_registry::RouteMetadata::new() #auth #tags #owner #deprecation)
+    }
+}
+
+/// The `yew_extra::deprecation::Deprecation` of an endpoint declared with `deprecated`
+fn generate_deprecation(deprecated: &DeprecatedArgs) -> proc_macro2::TokenStream {
+    let since = deprecated
+        .since
+        .as_ref()
+        .map(|since| quote! { .since(#since) });
+    let sunset = deprecated
+        .sunset
+        .as_ref()
+        .map(|sunset| quote! { .sunset(#sunset) });
+    let replacement = deprecated
+        .replacement
+        .as_ref()
+        .map(|replacement| quote! { .replacement(#replacement) });
+    quote! { ::yew_extra::deprecation::Deprecation::new() #since #sunset #replacement }
+}
+
+/// Parses a duration such as `"500ms"`, `"10s"` or `"2m"` into milliseconds
+fn parse_duration_ms(lit: &syn::LitStr) -> syn::Result<u64> {
+    let value = lit.value();
+    let value = value.trim();
+    let (number, unit) = value
+        .find(|c: char| !c.is_ascii_digit())
+        .map(|index| value.split_at(index))
+        .unwrap_or((value, ""));
+    let multiplier = match unit.trim() {
+        "ms" => Some(1),
+        "s" => Some(1_000),
+        "m" => Some(60_000),
+        _ => None,
+    };
+    match (number.parse::<u64>(), multiplier) {
+        (Ok(number), Some(multiplier)) if number > 0 => Ok(number * multiplier),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            "Invalid duration. Expected a positive number with a unit, e.g. \"500ms\", \"10s\" or \"2m\"",
+        )),
+    }
+}
+
+/// Parses a size such as `"512KB"`, `"10MB"` or `"1GB"` into bytes
+fn parse_size_bytes(lit: &syn::LitStr) -> syn::Result<usize> {
+    let value = lit.value();
+    let value = value.trim();
+    let (number, unit) = value
+        .find(|c: char| !c.is_ascii_digit())
+        .map(|index| value.split_at(index))
+        .unwrap_or((value, ""));
+    let multiplier = match unit.trim().to_uppercase().as_str() {
+        "B" => Some(1),
+        "KB" => Some(1024),
+        "MB" => Some(1024 * 1024),
+        "GB" => Some(1024 * 1024 * 1024),
+        _ => None,
+    };
+    match (number.parse::<usize>(), multiplier) {
+        (Ok(number), Some(multiplier)) if number > 0 => {
+            number.checked_mul(multiplier).ok_or_else(|| {
+                syn::Error::new(lit.span(), "The size doesn't fit in a usize")
+            })
+        }
+        _ => Err(syn::Error::new(
+            lit.span(),
+            "Invalid size. Expected a positive number with a unit, e.g. \"512KB\", \"10MB\" or \"1GB\"",
+        )),
+    }
+}
+
+/// A procedural macro that generates both server-side API endpoint and client-side Yew hook
+///
+/// This will generate:
+/// - A server-side handler function for use with Axum
+/// - A client-side Yew hook (use_users) that fetches data from the endpoint
+#[proc_macro_attribute]
+pub fn yewserverhook(args: TokenStream, input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ItemFn);
+
+    // Parse the path and method arguments
+    let args = parse_macro_input!(args as MacroArgs);
+
+    // Generic functions become one endpoint per declared instance
+    if !args.instances.is_empty() {
+        return expand_instances(args, input)
+            .unwrap_or_else(|e| e.to_compile_error())
+            .into();
+    }
+
+    expand_endpoint(args, input).into()
+}
+
+/// Options a group can set for the endpoints it contains, besides `prefix`
+const GROUP_OPTIONS: &[&str] = &[
+    "method",
+    "schema_check",
+    "typescript",
+    "graphql",
+    "base",
+    "fast_json",
+    "shared_data",
+    "credentials",
+    "cache",
+    "mode",
+    "redirect",
+    "auth",
+    "tags",
+    "owner",
+    "feature_flag",
+    "content_type",
+    "transform",
+    "deprecated",
+    "state",
+];
+
+/// Applies shared settings to every `#[yewserverhook]` function in a module
+///
+/// `prefix` is prepended to the path of each endpoint, and the other options become the
+/// defaults for the endpoints that don't set them themselves:
+///
+/// ```ignore
+/// #[yewserverhook_group(prefix = "/api/admin", method = "GET", credentials = "include")]
+/// pub mod admin {
+///     #[yewserverhook(path = "/users")] // GET /api/admin/users
+///     pub async fn list_users() -> Result<Vec<User>, AppError> { ... }
+///
+///     #[yewserverhook(path = "/users", method = "DELETE")] // DELETE /api/admin/users
+///     pub async fn delete_user(id: u32) -> Result<(), AppError> { ... }
+/// }
+/// ```
+///
+/// Nested groups add their prefix to the one of the enclosing group.
+#[proc_macro_attribute]
+pub fn yewserverhook_group(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args with syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated);
+    let mut module = parse_macro_input!(input as syn::ItemMod);
+
+    expand_group(args.into_iter().collect(), &mut module)
+        .map(|()| quote! { #module })
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn expand_group(args: Vec<syn::Meta>, module: &mut syn::ItemMod) -> syn::Result<()> {
+    let mut prefix = None;
+    let mut defaults = Vec::new();
+    for meta in args {
+        let name = meta
+            .path()
+            .get_ident()
+            .map(|ident| ident.to_string())
+            .unwrap_or_default();
+        if name == "prefix" {
+            prefix = Some(group_string_value(&meta)?);
+        } else if GROUP_OPTIONS.contains(&name.as_str()) {
+            defaults.push(meta);
+        } else {
+            return Err(syn::Error::new_spanned(
+                meta.path(),
+                format!(
+                    "Unknown group argument '{}'. Expected one of: prefix, {}",
+                    name,
+                    GROUP_OPTIONS.join(", ")
+                ),
+            ));
+        }
+    }
+
+    let Some((_, items)) = &mut module.content else {
+        return Err(syn::Error::new_spanned(
+            &module.ident,
+            "#[yewserverhook_group] requires an inline module, e.g. `mod admin { ... }`",
+        ));
+    };
+    apply_group(items, prefix.as_ref(), &defaults)
+}
+
+/// Rewrites the endpoint and nested group attributes of `items` with the group's settings
+fn apply_group(
+    items: &mut [syn::Item],
+    prefix: Option<&syn::LitStr>,
+    defaults: &[syn::Meta],
+) -> syn::Result<()> {
+    for item in items {
+        match item {
+            syn::Item::Fn(item_fn) => {
+                for attr in &mut item_fn.attrs {
+                    if attr_is(attr, "yewserverhook") {
+                        merge_group_args(attr, "path", prefix, defaults)?;
+                    }
+                }
+            }
+            syn::Item::Mod(nested) => {
+                // A nested group applies its own settings once it is expanded
+                if let Some(attr) = nested
+                    .attrs
+                    .iter_mut()
+                    .find(|attr| attr_is(attr, "yewserverhook_group"))
+                {
+                    merge_group_args(attr, "prefix", prefix, defaults)?;
+                } else if let Some((_, items)) = &mut nested.content {
+                    apply_group(items, prefix, defaults)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Whether `attr` is the given macro, used directly or through its crate path
+fn attr_is(attr: &syn::Attribute, name: &str) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == name)
+}
+
+/// Prepends `prefix` to the `key` argument of `attr` and adds the defaults it doesn't override
+fn merge_group_args(
+    attr: &mut syn::Attribute,
+    key: &str,
+    prefix: Option<&syn::LitStr>,
+    defaults: &[syn::Meta],
+) -> syn::Result<()> {
+    let mut args: Vec<syn::Meta> = match &attr.meta {
+        syn::Meta::List(list) => list
+            .parse_args_with(
+                syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+            )?
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    if let Some(prefix) = prefix {
+        let key_ident = syn::Ident::new(key, proc_macro2::Span::call_site());
+        let mut found = false;
+        for arg in &mut args {
+            if arg.path().is_ident(key) {
+                let value = group_string_value(arg)?;
+                let joined =
+                    syn::LitStr::new(&join_paths(&prefix.value(), &value.value()), value.span());
+                *arg = syn::parse_quote! { #key_ident = #joined };
+                found = true;
+            }
+        }
+        // A nested group without a prefix of its own still gets the outer one
+        if !found && key == "prefix" {
+            args.push(syn::parse_quote! { #key_ident = #prefix });
+        }
+    }
+
+    for default in defaults {
+        if !args.iter().any(|arg| arg.path() == default.path()) {
+            args.push(default.clone());
+        }
+    }
+
+    let path = attr.path().clone();
+    *attr = syn::parse_quote! { #[#path(#(#args),*)] };
+    Ok(())
+}
+
+/// Returns the string literal of a `name = "value"` argument
+fn group_string_value(meta: &syn::Meta) -> syn::Result<syn::LitStr> {
+    if let syn::Meta::NameValue(syn::MetaNameValue {
+        value:
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }),
+        ..
+    }) = meta
+    {
+        return Ok(lit.clone());
+    }
+    Err(syn::Error::new_spanned(
+        meta,
+        "Expected a string value, e.g. `prefix = \"/api/admin\"`",
+    ))
+}
+
+/// Joins a group prefix and an endpoint path with a single slash
+fn join_paths(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    let path = path.trim_start_matches('/');
+    if path.is_empty() {
+        return prefix.to_string();
+    }
+    format!("{}/{}", prefix, path)
+}
+
+/// A procedural macro that generates a WebSocket endpoint exchanging typed messages
+///
+/// The function receives the messages of the client as an `Inbound<I>` stream and returns
+/// the stream of messages to send back, both serialized as JSON:
+///
+/// ```ignore
+/// #[yewserverws(path = "/ws/chat")]
+/// pub async fn chat(inbound: Inbound<ChatIn>) -> Result<impl Stream<Item = ChatOut>, AppError> {
+///     Ok(inbound.map(|message| ChatOut { text: message.text }))
+/// }
+/// ```
+///
+/// This will generate:
+/// - A GET route upgrading the request to a WebSocket (needs yew_extra's `ws` feature)
+/// - A client-side Yew hook (use_chat_ws) with the received messages and a `send`
+///   callback, which reconnects when the connection is lost
+#[proc_macro_attribute]
+pub fn yewserverws(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args with syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated);
+    let input = parse_macro_input!(input as ItemFn);
+
+    expand_ws(args.into_iter().collect(), input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn expand_ws(args: Vec<syn::Meta>, input: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let mut path = None;
+    let mut debug_expand = expand_requested_by_env(&input.sig.ident);
+    for meta in &args {
+        let value = match meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value: syn::Expr::Lit(syn::ExprLit { lit, .. }),
+                ..
+            }) => Some(lit),
+            _ => None,
+        };
+        let name = meta
+            .path()
+            .get_ident()
+            .map(|ident| ident.to_string())
+            .unwrap_or_default();
+        match (name.as_str(), value) {
+            ("path", Some(syn::Lit::Str(lit))) => path = Some(lit.value()),
+            ("path", _) => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "Expected a string value, e.g. `path = \"/ws/chat\"`",
+                ))
+            }
+            ("debug_expand", Some(syn::Lit::Bool(lit))) => debug_expand |= lit.value,
+            ("debug_expand", _) => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "Expected a boolean value, e.g. `debug_expand = true`",
+                ))
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    meta.path(),
+                    format!(
+                        "Unknown argument '{}'. Expected one of: path, debug_expand",
+                        name
+                    ),
+                ))
+            }
+        }
+    }
+    let path = path.ok_or_else(|| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "Missing required argument 'path'",
+        )
+    })?;
+
+    let fn_name = &input.sig.ident;
+    if input.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            input.sig.fn_token,
+            format!(
+                "Server functions must be async, declare it as `async fn {}`",
+                fn_name
+            ),
+        ));
+    }
+
+    // The only parameter is the stream of messages from the client
+    let inbound_type = match input.sig.inputs.iter().collect::<Vec<_>>().as_slice() {
+        [FnArg::Typed(pat_type)] => inbound_message_type(&pat_type.ty),
+        _ => None,
+    };
+    let Some(inbound_type) = inbound_type else {
+        return Err(syn::Error::new_spanned(
+            &input.sig.inputs,
+            "WebSocket functions take the messages of the client as their only parameter, e.g. `inbound: Inbound<ChatIn>`",
+        ));
+    };
+    let (return_type, error_type) = extract_return_type(&input.sig.output);
+    let outbound_type = match error_type {
+        Some(_) => stream_item_type(&return_type),
+        None => None,
+    };
+    let Some(outbound_type) = outbound_type else {
+        return Err(syn::Error::new_spanned(
+            &input.sig.output,
+            "WebSocket functions must return the messages to send as `Result<impl Stream<Item = T>, E>`",
+        ));
+    };
+
+    let vis = &input.vis;
+    let client_attrs = ForwardedAttrs::new(&input.attrs).client();
+    let wrapper_fn_name = syn::Ident::new(&format!("{}_ws_wrapper", fn_name), fn_name.span());
+    let hook_name = syn::Ident::new(&format!("use_{}_ws", fn_name), fn_name.span());
+    let host_url = quote! { ::yew_extra::base_path::api_base(::std::option_env!("YEW_API_BASE")) };
+
+    let expanded = quote! {
+        #[cfg(feature = "ssr")]
+        #input
+
+        #[cfg(all(feature = "ssr", not(test)))]
+        #vis fn #wrapper_fn_name(
+            req: ::axum::http::Request<::axum::body::Body>
+        ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ::axum::http::Response<::axum::body::Body>> + Send>> {
+            Box::pin(::yew_extra::request_scope(::yew_extra::ws::handle(req, #fn_name)))
+        }
+
+        #[cfg(all(feature = "ssr", not(test)))]
+        ::inventory::submit! {
+            crate::route_registry::RouteInfo::new(
+                #path,
+                ::axum::http::Method::GET,
+                #wrapper_fn_name
+            )
+        }
+
+        // Only the browser connects, the server renders the hook as connecting
+        #[yew::hook]
+        #client_attrs
+        #vis fn #hook_name() -> ::yew_extra::ws::WsHook<#inbound_type, #outbound_type> {
+            ::yew_extra::ws::use_ws(#host_url, #path)
+        }
+    };
+
+    let expanded = apply_cfg_attrs(&input.attrs, expanded);
+    Ok(if debug_expand {
+        write_expansion("yewserverws", fn_name, &path, "GET", expanded)
+    } else {
+        expanded
+    })
+}
+
+/// The `I` of an `Inbound<I>` parameter
+fn inbound_message_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Inbound" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.iter().collect::<Vec<_>>().as_slice() {
+        [syn::GenericArgument::Type(message_type)] => Some(message_type),
+        _ => None,
+    }
+}
+
+/// Expands a generic server function into a concrete endpoint per `instance(...)`.
+///
+/// Each instance gets a copy of the function with the generic parameters replaced by the
+/// instance's types, named `<function>_<instance name>`. The generic function itself is kept
+/// for server code calling it directly.
+fn expand_instances(args: MacroArgs, input: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let generics = &input.sig.generics;
+    let mut type_params = Vec::new();
+    for param in &generics.params {
+        match param {
+            syn::GenericParam::Type(type_param) => type_params.push(&type_param.ident),
+            syn::GenericParam::Lifetime(_) => {}
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "Only type parameters can be instantiated, const parameters aren't supported",
+                ));
+            }
+        }
+    }
+
+    if type_params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.sig.ident,
+            "The 'instance' option requires a generic function, e.g. `async fn count<T: Record>()`",
+        ));
+    }
+
+    // Inline bounds move to the where clause so they are still checked for each instance
+    let mut predicates: Vec<syn::WherePredicate> = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Type(type_param) if !type_param.bounds.is_empty() => {
+                let ident = &type_param.ident;
+                let bounds = &type_param.bounds;
+                Some(syn::parse_quote! { #ident: #bounds })
+            }
+            _ => None,
+        })
+        .collect();
+    if let Some(where_clause) = &generics.where_clause {
+        predicates.extend(where_clause.predicates.iter().cloned());
+    }
+
+    let mut expanded = proc_macro2::TokenStream::new();
+    let mut paths: Vec<String> = Vec::new();
+
+    for instance in &args.instances {
+        for (ident, _) in &instance.types {
+            if !type_params.contains(&ident) {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "`{}` isn't a type parameter of `{}`",
+                        ident, input.sig.ident
+                    ),
+                ));
+            }
+        }
+        for param in &type_params {
+            if !instance.types.iter().any(|(ident, _)| ident == *param) {
+                return Err(syn::Error::new_spanned(
+                    &instance.name,
+                    format!(
+                        "Instance `{}` doesn't give a type for `{}`, add `{} = \"...\"`",
+                        instance.name, param, param
+                    ),
+                ));
+            }
+        }
+
+        let path = instance
+            .path
+            .clone()
+            .unwrap_or_else(|| format!("{}/{}", args.path.trim_end_matches('/'), instance.name));
+        if paths.contains(&path) {
+            return Err(syn::Error::new_spanned(
+                &instance.name,
+                format!("Another instance already uses the path {}", path),
+            ));
+        }
+        paths.push(path.clone());
+
+        // Replace the type parameters in the whole function, body included
+        let mut concrete = input.clone();
+        concrete.sig.generics = syn::Generics::default();
+        concrete.sig.generics.params = generics
+            .params
+            .iter()
+            .filter(|param| matches!(param, syn::GenericParam::Lifetime(_)))
+            .cloned()
+            .collect();
+        if !predicates.is_empty() {
+            concrete.sig.generics.where_clause = Some(syn::parse_quote! { where #(#predicates),* });
+        }
+        let tokens = substitute_types(quote! { #concrete }, &instance.types);
+        let mut concrete: ItemFn = syn::parse2(tokens)?;
+        concrete.sig.ident = syn::Ident::new(
+            &format!("{}_{}", input.sig.ident, instance.name),
+            input.sig.ident.span(),
+        );
+
+        let mut instance_args = args.clone();
+        instance_args.path = path;
+        instance_args.instances = Vec::new();
+        expanded.extend(expand_endpoint(instance_args, concrete));
+    }
+
+    let generic_fn = strip_validate_attrs(&input);
+    let generic_fn = match &args.state {
+        Some(state_type) => with_state_arg(generic_fn, state_type),
+        None => generic_fn,
+    };
+    Ok(quote! {
+        #[cfg(feature = "ssr")]
+        #[allow(dead_code)]
+        #generic_fn
+
+        #expanded
+    })
+}
+
+/// Replaces every occurrence of the given identifiers with their types
+fn substitute_types(
+    tokens: proc_macro2::TokenStream,
+    types: &[(syn::Ident, Type)],
+) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|tree| match tree {
+            proc_macro2::TokenTree::Ident(ident) => {
+                match types.iter().find(|(param, _)| *param == ident) {
+                    Some((_, ty)) => quote! { #ty },
+                    None => quote! { #ident },
+                }
+            }
+            proc_macro2::TokenTree::Group(group) => {
+                let mut substituted = proc_macro2::Group::new(
+                    group.delimiter(),
+                    substitute_types(group.stream(), types),
+                );
+                substituted.set_span(group.span());
+                quote! { #substituted }
+            }
+            other => quote! { #other },
+        })
+        .collect()
+}
+
+fn expand_endpoint(args: MacroArgs, input: ItemFn) -> proc_macro2::TokenStream {
+    let route_metadata = generate_route_metadata(&args);
+    let deprecation = args.deprecated.as_ref().map(generate_deprecation);
+    let path = args.path;
+    let method = args.method;
+    let form = args.form;
+    let upload = args.upload;
+    let sw_cache = args.sw_cache;
+    let background_sync = args.background_sync;
+    let schema_check = args.schema_check;
+    let fast_json = args.fast_json;
+    let mock = args.mock;
+    let debug_expand = args.debug_expand || expand_requested_by_env(&input.sig.ident);
+    let fetch_options = generate_fetch_options(&args.fetch_options);
+    // Clients of a custom media type ask for it
+    let fetch_options = match &args.content_type {
+        Some(content_type) => quote! { #fetch_options .header("Accept", #content_type) },
+        None => fetch_options,
+    };
+
+    // Extract function details
+    let fn_name = &input.sig.ident;
+    let fn_vis = &input.vis;
+    let forwarded_attrs = ForwardedAttrs::new(&input.attrs);
+    let params_ident = args.params.unwrap_or_else(|| {
+        syn::Ident::new(
+            &format!("{}Params", to_pascal_case(&fn_name.to_string())),
+            fn_name.span(),
+        )
+    });
+    let fn_block = &input.block;
+    let fn_output = &input.sig.output;
+
+    // Reject signatures the generated code can't support, pointing at the offending tokens
+    if let Err(e) = check_signature(&input.sig) {
+        return e.to_compile_error();
+    }
+
+    // A `RequestContext` parameter is filled in by the server handler, not sent by the client
+    let (context_param, endpoint_inputs) = match split_context_param(&input.sig.inputs) {
+        Ok(split) => split,
+        Err(e) => return e.to_compile_error(),
+    };
+    if let (Some(context_param), true) = (&context_param, upload) {
+        return syn::Error::new_spanned(
+            &context_param.ty,
+            "Upload endpoints can't take a `RequestContext`, use `extract()` instead",
+        )
+        .to_compile_error();
+    }
+
+    // Give every parameter a plain name for the generated code, the server handler
+    // rebinds it to the declared pattern before running the body
+    let (normalized_inputs, param_bindings) = normalize_params(&endpoint_inputs);
+    let fn_inputs = &normalized_inputs;
+
+    // Parameters named in the path are sent in the URL, the others in the query or body
+    let path_params: Vec<syn::Ident> = match path_parameter_names(&path)
+        .into_iter()
+        .map(|name| {
+            fn_inputs
+                .iter()
+                .find_map(|input| match input {
+                    FnArg::Typed(pat_type) => match &*pat_type.pat {
+                        Pat::Ident(pat_ident) if pat_ident.ident == name => {
+                            Some(pat_ident.ident.clone())
+                        }
+                        _ => None,
+                    },
+                    FnArg::Receiver(_) => None,
+                })
+                .ok_or_else(|| {
+                    syn::Error::new(
+                        fn_name.span(),
+                        format!(
+                            "The path parameter '{}' doesn't match a parameter of `{}`",
+                            name, fn_name
+                        ),
+                    )
+                })
+        })
+        .collect()
+    {
+        Ok(path_params) => path_params,
+        Err(e) => return e.to_compile_error(),
+    };
+    let (path_inputs, body_inputs): (Vec<&FnArg>, Vec<&FnArg>) =
+        fn_inputs.iter().partition(|input| match input {
+            FnArg::Typed(pat_type) => matches!(
+                &*pat_type.pat,
+                Pat::Ident(pat_ident) if path_params.contains(&pat_ident.ident)
+            ),
+            FnArg::Receiver(_) => false,
+        });
+    let path_inputs: syn::punctuated::Punctuated<FnArg, syn::token::Comma> =
+        path_inputs.into_iter().cloned().collect();
+    let body_inputs: syn::punctuated::Punctuated<FnArg, syn::token::Comma> =
+        body_inputs.into_iter().cloned().collect();
+
+    // Determine if function has parameters sent in the query or body (excluding self)
+    let has_params = !body_inputs.is_empty();
+
+    // Collect #[validate(...)] rules declared on the parameters
+    let validations = match parse_validations(&body_inputs) {
+        Ok(validations) => validations,
+        Err(e) => return e.to_compile_error(),
+    };
+    if let Some(FnArg::Typed(pat_type)) = path_inputs.iter().find(|input| match input {
+        FnArg::Typed(pat_type) => pat_type.attrs.iter().any(is_validate_attr),
+        FnArg::Receiver(_) => false,
+    }) {
+        return syn::Error::new_spanned(
+            &pat_type.pat,
+            "Path parameters can't be validated, check them in the function body",
+        )
+        .to_compile_error();
+    }
+    let has_validation = !validations.is_empty();
+
+    // Extract return type and error type
+    let (return_type, error_type) = extract_return_type(fn_output);
+    let error_type = error_type.unwrap_or_else(|| quote! { () });
+
+    // `WithStatus<T>` and `(StatusCode, T)` send `T`, which is what the client decodes
+    let (return_type, status_type) = match status_data_type(&return_type) {
+        Some(data_type) => (data_type, Some(return_type)),
+        None => (return_type, None),
+    };
+    if status_type.is_some() && (args.stream_json || upload) {
+        let option = if upload { "upload" } else { "stream_json" };
+        return syn::Error::new_spanned(
+            fn_output,
+            format!(
+                "The '{}' option can't be combined with a `WithStatus<T>` or `(StatusCode, T)` return",
+                option
+            ),
+        )
+        .to_compile_error();
+    }
+
+    // Requests go to the other service of external endpoints, otherwise to the API base the
+    // client was built with or this app under its base path
+    let host_url = match &args.base {
+        Some(base) => quote! { #base.to_string() },
+        None => quote! {
+            ::yew_extra::base_path::api_base(::std::option_env!("YEW_API_BASE"))
+        },
+    };
+
+    // Streams are answered line by line as they yield their items
+    let stream_item = stream_item_type(&return_type);
+    if stream_item.is_none()
+        && matches!(
+            syn::parse2::<Type>(return_type.clone()),
+            Ok(Type::ImplTrait(_))
+        )
+    {
+        return syn::Error::new_spanned(
+            fn_output,
+            "The only `impl Trait` server functions can return is `impl Stream<Item = T>`, use a concrete type such as `Vec<T>` otherwise",
+        )
+        .to_compile_error();
+    }
+    if let Some(item_type) = stream_item {
+        let conflicting = [
+            ("upload", upload),
+            ("form", form),
+            ("stream_json", args.stream_json),
+            ("coalesce", args.coalesce),
+            ("blocking", args.blocking),
+            ("state", args.state.is_some()),
+        ];
+        if let Some((option, _)) = conflicting.iter().find(|(_, set)| *set) {
+            return syn::Error::new_spanned(
+                fn_output,
+                format!(
+                    "The '{}' option can't be combined with an `impl Stream` return",
+                    option
+                ),
+            )
+            .to_compile_error();
+        }
+        let expanded = generate_stream_endpoint(
+            &input,
+            &params_ident,
+            &path,
+            &method,
+            &item_type,
+            &body_inputs,
+            &path_inputs,
+            &param_bindings,
+            context_param.as_ref(),
+            &validations,
+            &route_metadata,
+            &fetch_options,
+            &host_url,
+        );
+        let expanded = apply_cfg_attrs(&input.attrs, expanded);
+        return if debug_expand {
+            write_expansion("yewserverhook", fn_name, &path, &method, expanded)
+        } else {
+            expanded
+        };
+    }
+
+    // Only lists are written out incrementally
+    if args.stream_json && !is_vec_type(&return_type) {
+        return syn::Error::new_spanned(
+            fn_output,
+            "The 'stream_json' option requires the function to return `Result<Vec<T>, E>`",
+        )
+        .to_compile_error();
+    }
+
+    // Upload endpoints use their own chunked protocol instead of a single request
+    if upload {
+        let expanded = generate_upload_endpoint(
+            &input,
+            &params_ident,
+            &path,
+            &return_type,
+            schema_check,
+            fast_json,
+            &route_metadata,
+        )
+        .unwrap_or_else(|e| e.to_compile_error());
+        let expanded = apply_cfg_attrs(&input.attrs, expanded);
+        return if debug_expand {
+            write_expansion("yewserverhook", fn_name, &path, &method, expanded)
+        } else {
+            expanded
+        };
+    }
+
+    if form && fn_inputs.is_empty() {
+        return syn::Error::new(
+            fn_name.span(),
+            "The 'form' option requires the function to take at least one parameter",
+        )
+        .to_compile_error();
+    }
+
+    // Generate hook name from function name (e.g., get_users -> use_users)
+    let hook_name = format!("use_{}", fn_name);
+    let hook_ident = syn::Ident::new(&hook_name, fn_name.span());
+
+    // Generate parameter struct if needed
+    let param_struct = if has_params {
+        generate_param_struct(
+            fn_name,
+            &params_ident,
+            &body_inputs,
+            method == "GET",
+            args.typescript || args.graphql,
+            args.key.is_none(),
+            &forwarded_attrs,
+        )
+    } else {
+        quote! {}
+    };
+
+    // Generate the validate() method shared by the server wrapper and the client
+    let validate_impl = if has_validation {
+        generate_validate_impl(&params_ident, &validations)
+    } else {
+        quote! {}
+    };
+
+    // The validate attributes are only meaningful to this macro, so strip them from the emitted function
+    let emitted_fn = strip_validate_attrs(&input);
+    let emitted_fn = match &args.state {
+        Some(state_type) => with_state_arg(emitted_fn, state_type),
+        None => emitted_fn,
+    };
+
+    // Generate the server handler
+    let server_handler = generate_server_handler(
+        fn_name,
+        &params_ident,
+        fn_vis,
+        fn_block,
+        &body_inputs,
+        &path_inputs,
+        fn_output,
+        has_params,
+        &return_type,
+        status_type.as_ref(),
+        &error_type,
+        &path,
+        &method,
+        has_validation,
+        args.coalesce,
+        args.blocking,
+        args.server_timeout,
+        args.stream_json,
+        args.raw_body.then(|| match args.body_limit {
+            Some(limit) => quote! { #limit },
+            None => quote! { ::yew_extra::DEFAULT_BODY_LIMIT },
+        }),
+        args.nonce.then(|| match args.nonce_max_age {
+            Some(max_age) => quote! { ::std::time::Duration::from_millis(#max_age) },
+            None => quote! { ::yew_extra::nonce::DEFAULT_MAX_AGE },
+        }),
+        context_param.as_ref(),
+        &param_bindings,
+        args.base.is_some(),
+        &route_metadata,
+        args.feature_flag.as_deref(),
+        args.content_type.as_deref(),
+        args.transform.as_ref(),
+        deprecation.as_ref(),
+        args.state.as_ref(),
+        &forwarded_attrs,
+    );
+
+    // Mutations fetching on mount are sent by every render of their component. Endpoints
+    // relying on the default method keep the hook as it was, declared mutations are pointed
+    // to the triggered hook.
+    let mount_deprecation = if method != "GET" && args.method_declared {
+        let note = format!(
+            "`{}` sends the {} request every time the component mounts, use `use_{}_mutation` to send it on demand",
+            hook_ident, method, fn_name
+        );
+        Some(quote! { #[deprecated(note = #note)] })
+    } else {
+        None
+    };
+
+    // Generate the client hook
+    let client_hook = generate_client_hook(
+        &hook_ident,
+        fn_vis,
+        &path,
+        &return_type,
+        has_params,
+        &params_ident,
+        fn_inputs,
+        &path_params,
+        &method,
+        has_validation,
+        schema_check,
+        fast_json,
+        args.transform.as_ref(),
+        deprecation.as_ref(),
+        args.shared_data,
+        args.retry,
+        args.idempotent || background_sync,
+        args.nonce,
+        args.placeholder_data.as_ref(),
+        args.key.as_ref(),
+        args.poll_interval,
+        mock.as_ref(),
+        &fetch_options,
+        &host_url,
+        mount_deprecation.as_ref(),
+        &forwarded_attrs,
+    );
+
+    // Generate the direct callable function for client
+    let client_function = generate_client_function(
+        fn_name,
+        &params_ident,
+        fn_vis,
+        &path,
+        &return_type,
+        has_params,
+        fn_inputs,
+        &path_params,
+        &method,
+        has_validation,
+        background_sync,
+        args.idempotent,
+        args.nonce,
+        args.retry,
+        args.optimistic.as_ref(),
+        schema_check,
+        fast_json,
+        args.transform.as_ref(),
+        deprecation.as_ref(),
+        mock.as_ref(),
+        &fetch_options,
+        &host_url,
+        &forwarded_attrs,
+    );
+
+    // Let apps fill the hook's cache before it mounts, e.g. right after hydration
+    let prefetch_function = if method == "GET" {
+        generate_prefetch_function(
+            fn_name,
+            &params_ident,
+            fn_vis,
+            &path,
+            &return_type,
+            has_params,
+            fn_inputs,
+            &path_params,
+            args.shared_data,
+            &host_url,
+            &forwarded_attrs,
+        )
+    } else {
+        quote! {}
+    };
+
+    // Mutations also get a hook that only sends them when triggered
+    let mutation_hook = if method != "GET" && !upload {
+        generate_mutation_hook(
+            fn_name,
+            &params_ident,
+            fn_vis,
+            &return_type,
+            has_params,
+            fn_inputs,
+            &path_params,
+            &forwarded_attrs,
+        )
+    } else {
+        quote! {}
+    };
+
+    // Generate the form component if requested
+    let form_component = if form {
+        match generate_form_component(
+            fn_name,
+            &params_ident,
+            fn_vis,
+            &return_type,
+            has_params,
+            fn_inputs,
+            &path_params,
+        ) {
+            Ok(tokens) => tokens,
+            Err(e) => return e.to_compile_error(),
+        }
+    } else {
+        quote! {}
+    };
+
+    // List the endpoint in the service worker manifest if requested
+    let sw_registration = match &sw_cache {
+        Some(strategy) => {
+            generate_sw_registration(&path, strategy, has_params || !path_params.is_empty())
+        }
+        None => quote! {},
+    };
+
+    // Let the service worker queue the mutation while offline if requested
+    let sync_registration = if background_sync {
+        generate_sync_registration(&path, &method)
+    } else {
+        quote! {}
+    };
+
+    // List the endpoint in the TypeScript client if requested
+    let ts_registration = if args.typescript {
+        let params_schema = has_params.then_some(&params_ident);
+        generate_ts_registration(fn_name, &path, &method, params_schema, &return_type)
+    } else {
+        quote! {}
+    };
+
+    // Expose the endpoint through the GraphQL facade if requested
+    let graphql_registration = if args.graphql {
+        let params_schema = has_params.then_some(&params_ident);
+        generate_graphql_registration(fn_name, &path, &method, params_schema, &return_type)
+    } else {
+        quote! {}
+    };
+
+    // Don't generate additional wrapper - the hook_ident is already what we want
+    let hook_wrapper = quote! {};
+
+    let expanded = quote! {
+
+        #[cfg(feature = "ssr")]
+        #emitted_fn
+
+        #param_struct
+
+        #validate_impl
+
+        #server_handler
+
+        #client_hook
+
+        #[cfg(not(feature = "ssr"))]
+        #client_function
+
+        #prefetch_function
+
+        #mutation_hook
+
+        #form_component
+
+        #sw_registration
+
+        #sync_registration
+
+        #ts_registration
+
+        #graphql_registration
+
+        #hook_wrapper
+    };
+
+    // Gate everything generated behind the function's own `#[cfg(...)]` attributes
+    let expanded = apply_cfg_attrs(&input.attrs, expanded);
+
+    if debug_expand {
+        return write_expansion("yewserverhook", fn_name, &path, &method, expanded);
+    }
+
+    expanded
+}
+
+/// Adds the `#[cfg(...)]` attributes of the server function to every generated item, so a
+/// feature-gated function doesn't leave a hook, client function or route behind
+fn apply_cfg_attrs(
+    attrs: &[syn::Attribute],
+    expanded: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let cfgs: Vec<&syn::Attribute> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .collect();
+    if cfgs.is_empty() {
+        return expanded;
+    }
+
+    let file = match syn::parse2::<syn::File>(expanded.clone()) {
+        Ok(file) => file,
+        // Compile errors aren't items, leave them as they are
+        Err(_) => return expanded,
+    };
+    let items = file.items.into_iter().map(|item| {
+        quote! {
+            #(#cfgs)*
+            #item
+        }
+    });
+    quote! { #(#items)* }
+}
+
+/// Environment variable listing the functions whose expansion should be written out,
+/// as a comma-separated list of names or `1` for all of them
+const EXPAND_ENV_VAR: &str = "YEW_SERVER_HOOK_EXPAND";
+
+/// Environment variable that makes the build print where each expansion was written, which
+/// it otherwise doesn't, so `debug_expand` stays quiet in every build it is left on
+const EXPAND_LOG_ENV_VAR: &str = "YEW_SERVER_HOOK_EXPAND_LOG";
+
+fn expand_requested_by_env(fn_name: &syn::Ident) -> bool {
+    let Ok(value) = std::env::var(EXPAND_ENV_VAR) else {
+        return false;
+    };
+    let fn_name = fn_name.to_string();
+    value
+        .split(',')
+        .map(str::trim)
+        .any(|entry| entry == "1" || entry == "*" || entry == fn_name)
+}
+
+/// Writes the formatted expansion of an endpoint to
+/// `target/yew_server_hook/<crate>/<function>.rs` and notes the location on stderr.
+///
+/// The generated code is returned unchanged, or with a compile error if the file can't be written.
+fn write_expansion(
+    macro_name: &str,
+    fn_name: &syn::Ident,
+    path: &str,
+    method: &str,
+    expanded: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let formatted = match syn::parse2::<syn::File>(expanded.clone()) {
+        Ok(file) => prettyplease::unparse(&file),
+        // Fall back to the raw tokens, e.g. when the expansion is a compile error
+        Err(_) => expanded.to_string(),
+    };
+    let contents = format!(
+        "// Expansion of #[{}] on `{}` ({} {})\n\n{}",
+        macro_name, fn_name, method, path, formatted
+    );
+
+    let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+        .map(std::path::PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("CARGO_MANIFEST_DIR")
+                .map(|dir| std::path::PathBuf::from(dir).join("target"))
+        })
+        .unwrap_or_else(|| std::path::PathBuf::from("target"));
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "crate".to_string());
+    let dir = target_dir.join("yew_server_hook").join(crate_name);
+    let file = dir.join(format!("{}.rs", fn_name));
+
+    match std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&file, contents)) {
+        Ok(()) => {
+            if std::env::var_os(EXPAND_LOG_ENV_VAR).is_some() {
+                eprintln!(
+                    "note: expansion of #[{}] on `{}` written to {}",
+                    macro_name,
+                    fn_name,
+                    file.display()
+                );
+            }
+            expanded
+        }
+        Err(e) => {
+            let error = syn::Error::new(
+                fn_name.span(),
+                format!(
+                    "Failed to write the expansion of `{}` to {}: {}",
+                    fn_name,
+                    file.display(),
+                    e
+                ),
+            )
+            .to_compile_error();
+            quote! {
+                #expanded
+                #error
+            }
+        }
+    }
+}
+
+/// Registers a GET endpoint in yew_extra's service worker manifest.
+///
+/// Endpoints without parameters always hit the same URL, so the service worker precaches them
+fn generate_sw_registration(
+    path: &str,
+    strategy: &str,
+    has_params: bool,
+) -> proc_macro2::TokenStream {
+    let strategy_ident = match strategy {
+        "cache-first" => quote! { CacheFirst },
+        "network-first" => quote! { NetworkFirst },
+        _ => quote! { StaleWhileRevalidate },
+    };
+    let precache = !has_params;
+
+    quote! {
+        #[cfg(all(feature = "ssr", not(test)))]
+        ::inventory::submit! {
+            ::yew_extra::sw::CacheableRoute::new(
+                #path,
+                ::yew_extra::sw::CacheStrategy::#strategy_ident,
+                #precache
+            )
+        }
+    }
+}
+
+/// Registers a mutation endpoint that yew_extra's service worker queues while offline
+fn generate_sync_registration(path: &str, method: &str) -> proc_macro2::TokenStream {
+    quote! {
+        #[cfg(all(feature = "ssr", not(test)))]
+        ::inventory::submit! {
+            ::yew_extra::sw::BackgroundSyncRoute::new(#path, #method)
+        }
+    }
+}
+
+/// Registers the endpoint with yew_extra's TypeScript client generator
+fn generate_ts_registration(
+    fn_name: &syn::Ident,
+    path: &str,
+    method: &str,
+    params_ident: Option<&syn::Ident>,
+    return_type: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let name = fn_name.to_string();
+    let params_schema = match params_ident {
+        Some(params_ident) => quote! { Some(::yew_extra::typescript::schema_of::<#params_ident>) },
+        None => quote! { None },
+    };
+    quote! {
+        #[cfg(feature = "ssr")]
+        ::inventory::submit! {
+            ::yew_extra::typescript::TsEndpoint::new(
+                #name,
+                #path,
+                #method,
+                #params_schema,
+                ::yew_extra::typescript::schema_of::<#return_type>
+            )
+        }
+    }
+}
+
+/// Registers the endpoint with yew_extra's GraphQL facade, which calls it through the
+/// generated wrapper
+fn generate_graphql_registration(
+    fn_name: &syn::Ident,
+    path: &str,
+    method: &str,
+    params_ident: Option<&syn::Ident>,
+    return_type: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let name = fn_name.to_string();
+    let wrapper_fn_name = syn::Ident::new(&format!("{}_handler_wrapper", fn_name), fn_name.span());
+    let params_schema = match params_ident {
+        Some(params_ident) => quote! { Some(::yew_extra::graphql::schema_of::<#params_ident>) },
+        None => quote! { None },
+    };
+    quote! {
+        #[cfg(all(feature = "ssr", not(test)))]
+        ::inventory::submit! {
+            ::yew_extra::graphql::GraphqlEndpoint::new(
+                #name,
+                #path,
+                #method,
+                #params_schema,
+                ::yew_extra::graphql::schema_of::<#return_type>,
+                #wrapper_fn_name
+            )
+        }
+    }
+}
+
+/// Builder calls applying the endpoint's fetch options to a gloo_net request
+fn generate_fetch_options(options: &[(String, String)]) -> proc_macro2::TokenStream {
+    let calls = options.iter().map(|(name, value)| {
+        let (_, type_name, _) = FETCH_OPTIONS
+            .iter()
+            .find(|(option, _, _)| option == name)
+            .expect("fetch options are validated while parsing");
+        let method = syn::Ident::new(name, proc_macro2::Span::call_site());
+        let type_ident = syn::Ident::new(type_name, proc_macro2::Span::call_site());
+        // e.g. "same-origin" -> SameOrigin
+        let variant = syn::Ident::new(
+            &to_pascal_case(&value.replace('-', "_")),
+            proc_macro2::Span::call_site(),
+        );
+        quote! { .#method(::yew_extra::fetch::#type_ident::#variant) }
+    });
+
+    quote! { #(#calls)* }
+}
+
+/// Checks that the function can be turned into an endpoint, collecting an error for every
+/// unsupported part of the signature
+fn check_signature(sig: &syn::Signature) -> syn::Result<()> {
+    let fn_name = &sig.ident;
+    let mut errors: Vec<syn::Error> = Vec::new();
+
+    if sig.asyncness.is_none() {
+        errors.push(syn::Error::new_spanned(
+            sig.fn_token,
+            format!(
+                "Server functions must be async, declare it as `async fn {}`",
+                fn_name
+            ),
+        ));
+    }
+
+    // Lifetimes only appear in borrowed parameters, which are sent as owned values
+    if sig.generics.type_params().next().is_some() || sig.generics.const_params().next().is_some() {
+        errors.push(syn::Error::new_spanned(
+            &sig.generics,
+            format!(
+                "Server functions can't be generic, use concrete types or declare the endpoints to generate with `instance(name = \"...\", {} = \"Type\")`",
+                sig.generics
+                    .type_params()
+                    .next()
+                    .map(|param| param.ident.to_string())
+                    .unwrap_or_else(|| "T".to_string())
+            ),
+        ));
+    }
+
+    for input in &sig.inputs {
+        match input {
+            FnArg::Receiver(receiver) => errors.push(syn::Error::new_spanned(
+                receiver,
+                format!(
+                    "Server functions can't take `self`, move `{}` out of the impl block and pass what it needs as parameters",
+                    fn_name
+                ),
+            )),
+            FnArg::Typed(pat_type) => {
+                if let Some(impl_trait) = find_impl_trait(&pat_type.ty) {
+                    errors.push(syn::Error::new_spanned(
+                        impl_trait,
+                        "`impl Trait` parameters can't be deserialized from a request, use a concrete type such as `String` or `Vec<T>`",
+                    ));
+                }
+            }
+        }
+    }
+
+    match &sig.output {
+        ReturnType::Default => errors.push(syn::Error::new(
+            sig.paren_token.span.close(),
+            "Server functions must return `Result<T, E>`, add a return type such as `-> Result<T, AppError>`",
+        )),
+        ReturnType::Type(_, ty) => {
+            if extract_return_type(&sig.output).1.is_none() {
+                let is_result_alias = matches!(
+                    &**ty,
+                    Type::Path(type_path)
+                        if type_path.path.segments.last().is_some_and(|segment| segment.ident == "Result")
+                );
+                let message = if is_result_alias {
+                    "Server functions must return `Result<T, E>` with both types spelled out, aliases like `anyhow::Result<T>` aren't supported".to_string()
+                } else {
+                    format!(
+                        "Server functions must return `Result<T, E>`, e.g. `-> Result<{}, AppError>`",
+                        quote! { #ty }
+                    )
+                };
+                errors.push(syn::Error::new_spanned(ty, message));
+            }
+        }
+    }
+
+    match errors.into_iter().reduce(|mut combined, error| {
+        combined.combine(error);
+        combined
+    }) {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Finds an `impl Trait` type anywhere within a parameter type
+fn find_impl_trait(ty: &Type) -> Option<&syn::TypeImplTrait> {
+    match ty {
+        Type::ImplTrait(impl_trait) => Some(impl_trait),
+        Type::Reference(reference) => find_impl_trait(&reference.elem),
+        Type::Paren(paren) => find_impl_trait(&paren.elem),
+        Type::Group(group) => find_impl_trait(&group.elem),
+        Type::Slice(slice) => find_impl_trait(&slice.elem),
+        Type::Array(array) => find_impl_trait(&array.elem),
+        Type::Tuple(tuple) => tuple.elems.iter().find_map(find_impl_trait),
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .iter()
+            .find_map(|segment| match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) => {
+                    args.args.iter().find_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => find_impl_trait(ty),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            }),
+        _ => None,
+    }
+}
+
+fn extract_return_type(
+    output: &ReturnType,
+) -> (proc_macro2::TokenStream, Option<proc_macro2::TokenStream>) {
+    match output {
+        ReturnType::Default => (quote! { () }, None),
+        ReturnType::Type(_, ty) => {
+            // Extract the inner type from Result<T, E>
+            if let Type::Path(type_path) = &**ty {
+                if let Some(segment) = type_path.path.segments.last() {
+                    if segment.ident == "Result" {
+                        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                            if let (
+                                Some(syn::GenericArgument::Type(ok_type)),
+                                Some(syn::GenericArgument::Type(err_type)),
+                            ) = (args.args.first(), args.args.iter().nth(1))
+                            {
+                                return (quote! { #ok_type }, Some(quote! { #err_type }));
+                            }
+                        }
+                    }
+                }
+            }
+            (quote! { #ty }, None)
+        }
+    }
+}
+
+/// The `T` of a `WithStatus<T>` or `(StatusCode, T)` return type
+fn status_data_type(ty: &proc_macro2::TokenStream) -> Option<proc_macro2::TokenStream> {
+    let names = |ty: &Type, name: &str| match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == name),
+        _ => false,
+    };
+    match syn::parse2::<Type>(ty.clone()).ok()? {
+        Type::Tuple(tuple) if tuple.elems.len() == 2 && names(&tuple.elems[0], "StatusCode") => {
+            let data_type = &tuple.elems[1];
+            Some(quote! { #data_type })
+        }
+        Type::Path(type_path) => {
+            let segment = type_path.path.segments.last()?;
+            if segment.ident != "WithStatus" {
+                return None;
+            }
+            match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+                    syn::GenericArgument::Type(data_type) => Some(quote! { #data_type }),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether the tokens name a `Vec<T>`
+fn is_vec_type(ty: &proc_macro2::TokenStream) -> bool {
+    match syn::parse2::<Type>(ty.clone()) {
+        Ok(Type::Path(type_path)) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Vec"),
+        _ => false,
+    }
+}
+
+fn generate_param_struct(
+    fn_name: &syn::Ident,
+    params_ident: &syn::Ident,
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    query: bool,
+    derive_schema: bool,
+    compare: bool,
+    forwarded_attrs: &ForwardedAttrs,
+) -> proc_macro2::TokenStream {
+    let struct_attrs = forwarded_attrs.params(fn_name);
+    // Hooks without a key function compare the parameters to know when to refetch
+    let compare_derive = if compare {
+        quote! { #[derive(PartialEq)] }
+    } else {
+        quote! {}
+    };
+    // The TypeScript client and GraphQL schema are generated on the server, from the schema
+    // of the struct
+    let schema_derive = if derive_schema {
+        quote! {
+            #[cfg_attr(feature = "ssr", derive(::yew_extra::schema::JsonSchema))]
+            #[cfg_attr(feature = "ssr", schemars(crate = "::yew_extra::schema::schemars"))]
+        }
+    } else {
+        quote! {}
+    };
+    let struct_name = params_ident;
+
+    let mut fields = Vec::new();
+
+    for input in inputs {
+        if let FnArg::Typed(pat_type) = input {
+            if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                let field_name = &pat_ident.ident;
+                let field_type = &pat_type.ty;
+                // An empty Vec leaves no key in the query string, read it back as empty
+                let default = if query && is_vec_type(&quote! { #field_type }) {
+                    quote! { #[serde(default)] }
+                } else {
+                    quote! {}
+                };
+                fields.push(quote! {
+                    #default
+                    pub #field_name: #field_type
+                });
+            }
+        }
+    }
+
+    quote! {
+        #struct_attrs
+        #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+        #compare_derive
+        #schema_derive
+        pub struct #struct_name {
+            #(#fields),*
+        }
+    }
+}
+
+/// Validation rules declared on a single parameter
+struct FieldValidation {
+    field: syn::Ident,
+    rules: Vec<ValidationRule>,
+}
+
+enum ValidationRule {
+    Length {
+        min: Option<syn::LitInt>,
+        max: Option<syn::LitInt>,
+        equal: Option<syn::LitInt>,
+        message: Option<String>,
+    },
+    Range {
+        min: Option<f64>,
+        max: Option<f64>,
+        message: Option<String>,
+    },
+    Regex {
+        pattern: syn::LitStr,
+        message: Option<String>,
+    },
+}
+
+fn is_validate_attr(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("validate")
+}
+
+fn parse_number_lit(meta: &syn::meta::ParseNestedMeta) -> syn::Result<f64> {
+    let lit: syn::Lit = meta.value()?.parse()?;
+    match &lit {
+        syn::Lit::Int(int) => int.base10_parse::<f64>(),
+        syn::Lit::Float(float) => float.base10_parse::<f64>(),
+        _ => Err(syn::Error::new(lit.span(), "Expected a number")),
+    }
+}
+
+/// Parses a `regex` pattern at expansion time, so a typo fails the build instead of the first request
+fn check_regex(pattern: &syn::LitStr) -> syn::Result<()> {
+    regex_syntax::Parser::new()
+        .parse(&pattern.value())
+        .map(|_| ())
+        .map_err(|e| syn::Error::new(pattern.span(), format!("Invalid regex pattern: {}", e)))
+}
+
+fn parse_validations(
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+) -> syn::Result<Vec<FieldValidation>> {
+    let mut validations = Vec::new();
+
+    for input in inputs {
+        if let FnArg::Typed(pat_type) = input {
+            let mut rules = Vec::new();
+
+            for attr in pat_type.attrs.iter().filter(|attr| is_validate_attr(attr)) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("length") {
+                        let (mut min, mut max, mut equal, mut message) = (None, None, None, None);
+                        meta.parse_nested_meta(|inner| {
+                            if inner.path.is_ident("min") {
+                                min = Some(inner.value()?.parse()?);
+                            } else if inner.path.is_ident("max") {
+                                max = Some(inner.value()?.parse()?);
+                            } else if inner.path.is_ident("equal") {
+                                equal = Some(inner.value()?.parse()?);
+                            } else if inner.path.is_ident("message") {
+                                message = Some(inner.value()?.parse::<syn::LitStr>()?.value());
+                            } else {
+                                return Err(inner.error(
+                                    "Unknown length option. Expected 'min', 'max', 'equal' or 'message'",
+                                ));
+                            }
+                            Ok(())
+                        })?;
+                        if min.is_none() && max.is_none() && equal.is_none() {
+                            return Err(meta.error(
+                                "length() needs at least one of 'min', 'max' or 'equal'",
+                            ));
+                        }
+                        rules.push(ValidationRule::Length { min, max, equal, message });
+                    } else if meta.path.is_ident("range") {
+                        let (mut min, mut max, mut message) = (None, None, None);
+                        meta.parse_nested_meta(|inner| {
+                            if inner.path.is_ident("min") {
+                                min = Some(parse_number_lit(&inner)?);
+                            } else if inner.path.is_ident("max") {
+                                max = Some(parse_number_lit(&inner)?);
+                            } else if inner.path.is_ident("message") {
+                                message = Some(inner.value()?.parse::<syn::LitStr>()?.value());
+                            } else {
+                                return Err(inner.error(
+                                    "Unknown range option. Expected 'min', 'max' or 'message'",
+                                ));
+                            }
+                            Ok(())
+                        })?;
+                        if min.is_none() && max.is_none() {
+                            return Err(meta.error("range() needs at least one of 'min' or 'max'"));
+                        }
+                        rules.push(ValidationRule::Range { min, max, message });
+                    } else if meta.path.is_ident("regex") {
+                        // Accept both `regex = "..."` and `regex(pattern = "...", message = "...")`
+                        if meta.input.peek(syn::Token![=]) {
+                            let pattern: syn::LitStr = meta.value()?.parse()?;
+                            check_regex(&pattern)?;
+                            rules.push(ValidationRule::Regex { pattern, message: None });
+                        } else {
+                            let (mut pattern, mut message) = (None, None);
+                            meta.parse_nested_meta(|inner| {
+                                if inner.path.is_ident("pattern") {
+                                    pattern = Some(inner.value()?.parse::<syn::LitStr>()?);
+                                } else if inner.path.is_ident("message") {
+                                    message = Some(inner.value()?.parse::<syn::LitStr>()?.value());
+                                } else {
+                                    return Err(inner.error(
+                                        "Unknown regex option. Expected 'pattern' or 'message'",
+                                    ));
+                                }
+                                Ok(())
+                            })?;
+                            let pattern = pattern
+                                .ok_or_else(|| meta.error("Missing required regex option 'pattern'"))?;
+                            check_regex(&pattern)?;
+                            rules.push(ValidationRule::Regex { pattern, message });
+                        }
+                    } else {
+                        return Err(meta.error(
+                            "Unknown validation rule. Expected 'length', 'range' or 'regex'",
+                        ));
+                    }
+                    Ok(())
+                })?;
+            }
+
+            if !rules.is_empty() {
+                let Pat::Ident(pat_ident) = &*pat_type.pat else {
+                    return Err(syn::Error::new_spanned(
+                        &pat_type.pat,
+                        "Validation rules can only be declared on simple `name: Type` parameters",
+                    ));
+                };
+                validations.push(FieldValidation {
+                    field: pat_ident.ident.clone(),
+                    rules,
+                });
+            }
+        }
+    }
+
+    Ok(validations)
+}
+
+/// Lint attributes kept on the rebinding of a parameter, the only other attributes allowed
+/// on parameters are `cfg` and this macro's `validate`
+const PARAM_LINT_ATTRS: &[&str] = &["allow", "expect", "warn", "deny", "forbid"];
+
+/// Turns every parameter into a plain `name: Type` with only its `#[validate]` attributes,
+/// returning the `let` statements that rebind the names to the declared patterns.
+///
+/// Patterns are named after the type they destructure (`UserFilter { q, limit }` becomes
+/// `user_filter`), or after their position (`arg0`) when there is no such type.
+fn normalize_params(
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+) -> (
+    syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    Vec<proc_macro2::TokenStream>,
+) {
+    let mut normalized = syn::punctuated::Punctuated::new();
+    let mut bindings = Vec::new();
+    let mut names: Vec<String> = Vec::new();
+
+    for (index, input) in inputs.iter().enumerate() {
+        let FnArg::Typed(pat_type) = input else {
+            continue;
+        };
+
+        let mut pat = &*pat_type.pat;
+        while let Pat::Paren(paren) = pat {
+            pat = &paren.pat;
+        }
+
+        let ident = match pat {
+            Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+            _ => {
+                let base = pattern_type_name(pat)
+                    .map(|name| to_snake_case(&name))
+                    .unwrap_or_else(|| format!("arg{}", index));
+                let name = if names.contains(&base) || param_is_named(inputs, &base) {
+                    format!("{}_{}", base, index)
+                } else {
+                    base
+                };
+                syn::Ident::new(&name, proc_macro2::Span::call_site())
+            }
+        };
+        names.push(ident.to_string());
+
+        let lint_attrs: Vec<&syn::Attribute> = pat_type
+            .attrs
+            .iter()
+            .filter(|attr| {
+                PARAM_LINT_ATTRS
+                    .iter()
+                    .any(|name| attr.path().is_ident(name))
+            })
+            .collect();
+        // Borrowed parameters are sent as their owned type and borrowed again on the server
+        let owned = owned_param_type(&pat_type.ty);
+        if owned.is_some() {
+            let borrowed_ty = elide_lifetimes(&pat_type.ty);
+            bindings.push(match &*pat_type.ty {
+                Type::Reference(reference) if reference.mutability.is_some() => quote! {
+                    let mut #ident = #ident;
+                    let #ident: #borrowed_ty = &mut #ident;
+                },
+                Type::Reference(_) => quote! {
+                    let #ident: #borrowed_ty = &#ident;
+                },
+                _ => quote! {
+                    let #ident: #borrowed_ty = ::std::borrow::Cow::Owned(#ident);
+                },
+            });
+        }
+
+        let is_plain = matches!(
+            pat,
+            Pat::Ident(pat_ident)
+                if pat_ident.by_ref.is_none() && pat_ident.mutability.is_none() && pat_ident.subpat.is_none()
+        );
+        if !is_plain || !lint_attrs.is_empty() {
+            bindings.push(quote! {
+                #(#lint_attrs)*
+                let #pat = #ident;
+            });
+        }
+
+        let validate_attrs: Vec<syn::Attribute> = pat_type
+            .attrs
+            .iter()
+            .filter(|attr| is_validate_attr(attr))
+            .cloned()
+            .collect();
+        let ty = owned.unwrap_or_else(|| (*pat_type.ty).clone());
+        normalized.push(syn::parse_quote! { #(#validate_attrs)* #ident: #ty });
+    }
+
+    (normalized, bindings)
+}
+
+/// The owned type a borrowed parameter is sent as: `&str` and `Cow<str>` become `String`,
+/// `&[T]` and `Cow<[T]>` become `Vec<T>`, and `&T` and `Cow<T>` become `T`
+fn owned_param_type(ty: &Type) -> Option<Type> {
+    let borrowed = match ty {
+        Type::Reference(reference) => &*reference.elem,
+        Type::Path(type_path) => {
+            let segment = type_path.path.segments.last()?;
+            if segment.ident != "Cow" {
+                return None;
+            }
+            let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            args.args.iter().find_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })?
+        }
+        _ => return None,
+    };
+
+    // The owned type keeps the span of the parameter's type, for errors about it
+    Some(match borrowed {
+        Type::Path(type_path) if type_path.path.is_ident("str") => {
+            syn::parse_quote_spanned! { syn::spanned::Spanned::span(ty)=> String }
+        }
+        Type::Slice(slice) => {
+            let elem = &slice.elem;
+            syn::parse_quote_spanned! { syn::spanned::Spanned::span(ty)=> Vec<#elem> }
+        }
+        other => other.clone(),
+    })
+}
+
+/// Replaces named lifetimes other than `'static` with `'_`, so a borrowed parameter type can
+/// be written inside the generated handler
+fn elide_lifetimes(ty: &Type) -> proc_macro2::TokenStream {
+    fn elide(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let mut output = Vec::new();
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(tree) = tokens.next() {
+            match tree {
+                proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                    match tokens.peek() {
+                        Some(proc_macro2::TokenTree::Ident(ident)) if ident != "static" => {
+                            let span = ident.span();
+                            tokens.next();
+                            output.push(proc_macro2::TokenTree::Punct(punct));
+                            output.push(proc_macro2::TokenTree::Ident(proc_macro2::Ident::new(
+                                "_", span,
+                            )));
+                        }
+                        _ => output.push(proc_macro2::TokenTree::Punct(punct)),
+                    }
+                }
+                proc_macro2::TokenTree::Group(group) => {
+                    let mut elided =
+                        proc_macro2::Group::new(group.delimiter(), elide(group.stream()));
+                    elided.set_span(group.span());
+                    output.push(proc_macro2::TokenTree::Group(elided));
+                }
+                other => output.push(other),
+            }
+        }
+        output.into_iter().collect()
+    }
+
+    elide(quote! { #ty })
+}
+
+/// The name of the type a struct or tuple struct pattern destructures
+fn pattern_type_name(pat: &Pat) -> Option<String> {
+    let path = match pat {
+        Pat::Struct(pat_struct) => &pat_struct.path,
+        Pat::TupleStruct(pat_tuple_struct) => &pat_tuple_struct.path,
+        _ => return None,
+    };
+    path.segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+}
+
+fn param_is_named(
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    name: &str,
+) -> bool {
+    inputs.iter().any(|input| match input {
+        FnArg::Typed(pat_type) => {
+            matches!(&*pat_type.pat, Pat::Ident(pat_ident) if pat_ident.ident == name)
+        }
+        FnArg::Receiver(_) => false,
+    })
+}
+
+fn strip_validate_attrs(input: &ItemFn) -> ItemFn {
+    let mut input = input.clone();
+    for arg in input.sig.inputs.iter_mut() {
+        if let FnArg::Typed(pat_type) = arg {
+            pat_type.attrs.retain(|attr| !is_validate_attr(attr));
+        }
+    }
+    input
+}
+
+/// On the server, the function of an endpoint declared with `state` takes the app state
+/// first, and `state()` returns it in the body like in the handler
+fn with_state_arg(mut input: ItemFn, state_type: &syn::Type) -> ItemFn {
+    input
+        .sig
+        .inputs
+        .insert(0, syn::parse_quote! { app_state: #state_type });
+    input.block.stmts.insert(
+        0,
+        syn::parse_quote! {
+            #[allow(unused_variables)]
+            let state = move || ::std::clone::Clone::clone(&app_state);
+        },
+    );
+    input
+}
+
+/// Generates the server-side check that answers invalid parameters with 422 and the errors.
+/// `cleanup` runs before returning, e.g. to release the request parts the wrapper provided.
+fn generate_validation_rejection(
+    has_validation: bool,
+    cleanup: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if !has_validation {
+        return quote! {};
+    }
+    quote! {
+        if let Err(errors) = params.validate() {
+            #cleanup
+            return (::axum::http::StatusCode::UNPROCESSABLE_ENTITY, ::axum::Json(errors)).into_response();
+        }
+    }
+}
+
+fn generate_validate_impl(
+    params_ident: &syn::Ident,
+    validations: &[FieldValidation],
+) -> proc_macro2::TokenStream {
+    let struct_name = params_ident;
+
+    let mut checks = Vec::new();
+
+    for validation in validations {
+        let field = &validation.field;
+        let field_name = field.to_string();
+
+        for rule in &validation.rules {
+            let check = match rule {
+                ValidationRule::Length {
+                    min,
+                    max,
+                    equal,
+                    message,
+                } => {
+                    let (condition, default_message) = match (min, max, equal) {
+                        (_, _, Some(equal)) => (
+                            quote! { length == #equal },
+                            format!("length must be exactly {}", equal),
+                        ),
+                        (Some(min), Some(max), None) => (
+                            quote! { (#min..=#max).contains(&length) },
+                            format!("length must be between {} and {}", min, max),
+                        ),
+                        (Some(min), None, None) => (
+                            quote! { length >= #min },
+                            format!("length must be at least {}", min),
+                        ),
+                        (None, Some(max), None) => (
+                            quote! { length <= #max },
+                            format!("length must be at most {}", max),
+                        ),
+                        (None, None, None) => unreachable!("rejected by parse_validations"),
+                    };
+                    let message = message.clone().unwrap_or(default_message);
+                    quote! {
+                        if let Some(length) = ::yew_extra::ValidateLength::validate_length(&self.#field) {
+                            if !(#condition) {
+                                errors.add(#field_name, #message);
+                            }
+                        }
+                    }
+                }
+                ValidationRule::Range { min, max, message } => {
+                    let literal = |value: &f64| proc_macro2::Literal::f64_unsuffixed(*value);
+                    let (condition, default_message) = match (min, max) {
+                        (Some(min), Some(max)) => {
+                            let (min_lit, max_lit) = (literal(min), literal(max));
+                            (
+                                quote! { (#min_lit..=#max_lit).contains(&value) },
+                                format!("must be between {} and {}", min, max),
+                            )
+                        }
+                        (Some(min), None) => {
+                            let min_lit = literal(min);
+                            (
+                                quote! { value >= #min_lit },
+                                format!("must be at least {}", min),
+                            )
+                        }
+                        (None, Some(max)) => {
+                            let max_lit = literal(max);
+                            (
+                                quote! { value <= #max_lit },
+                                format!("must be at most {}", max),
+                            )
+                        }
+                        (None, None) => unreachable!("rejected by parse_validations"),
+                    };
+                    let message = message.clone().unwrap_or(default_message);
+                    quote! {
+                        if let Some(value) = ::yew_extra::ValidateRange::validate_range(&self.#field) {
+                            if !(#condition) {
+                                errors.add(#field_name, #message);
+                            }
+                        }
+                    }
+                }
+                ValidationRule::Regex { pattern, message } => {
+                    let message = message
+                        .clone()
+                        .unwrap_or_else(|| "does not match the required pattern".to_string());
+                    quote! {
+                        if let Some(value) = ::yew_extra::ValidateRegex::validate_regex(&self.#field) {
+                            // The macro already parsed the pattern, so compiling it cannot fail
+                            static PATTERN: ::std::sync::LazyLock<::yew_extra::validate::Regex> =
+                                ::std::sync::LazyLock::new(|| {
+                                    ::yew_extra::validate::Regex::new(#pattern)
+                                        .expect("validation regex checked at expansion time")
+                                });
+                            if !PATTERN.is_match(value) {
+                                errors.add(#field_name, #message);
+                            }
+                        }
+                    }
+                }
+            };
+            checks.push(check);
+        }
+    }
+
+    quote! {
+        impl #struct_name {
+            /// Checks the declared validation rules, returning every failure
+            pub fn validate(&self) -> Result<(), ::yew_extra::ValidationErrors> {
+                let mut errors = ::yew_extra::ValidationErrors::new();
+                #(#checks)*
+                errors.into_result()
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_server_handler(
+    fn_name: &syn::Ident,
+    params_ident: &syn::Ident,
+    vis: &syn::Visibility,
+    block: &syn::Block,
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    path_inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    _output: &ReturnType,
+    has_params: bool,
+    return_type: &proc_macro2::TokenStream,
+    status_type: Option<&proc_macro2::TokenStream>,
+    error_type: &proc_macro2::TokenStream,
+    path: &str,
+    method: &str,
+    has_validation: bool,
+    coalesce: bool,
+    blocking: bool,
+    server_timeout: Option<u64>,
+    stream_json: bool,
+    body_limit: Option<proc_macro2::TokenStream>,
+    nonce_max_age: Option<proc_macro2::TokenStream>,
+    context_param: Option<&syn::PatType>,
+    param_bindings: &[proc_macro2::TokenStream],
+    external: bool,
+    route_metadata: &proc_macro2::TokenStream,
+    feature_flag: Option<&str>,
+    content_type: Option<&str>,
+    transform: Option<&syn::Path>,
+    deprecation: Option<&proc_macro2::TokenStream>,
+    state_type: Option<&syn::Type>,
+    forwarded_attrs: &ForwardedAttrs,
+) -> proc_macro2::TokenStream {
+    let handler_attrs = forwarded_attrs.handler();
+    let fn_handler_name = syn::Ident::new(&format!("{}_handler", fn_name), fn_name.span());
+
+    // Without the task-keyed storage behind `extract()`, functions only see the request
+    // through a `RequestContext` parameter
+    let workers = cfg!(feature = "workers");
+    let provide_parts = |parts: proc_macro2::TokenStream| {
+        if workers {
+            quote! {}
+        } else {
+            quote! { ::yew_extra::provide_request_parts(#parts).await; }
+        }
+    };
+    let clear_parts = if workers {
+        quote! {}
+    } else {
+        quote! { ::yew_extra::clear_request_parts().await; }
+    };
+    // The status and headers the function set through `response_options()`, e.g. the cookie
+    // of `flash()`, go out with its response, and the work it queued with `after_response()`
+    // starts once that has been sent
+    let apply_headers = |response: proc_macro2::TokenStream| {
+        if workers {
+            quote! {}
+        } else {
+            quote! {
+                let #response = ::yew_extra::apply_response_headers(#response).await;
+                let #response = ::yew_extra::run_after_response(#response).await;
+            }
+        }
+    };
+    let apply_result_headers = apply_headers(quote! { result });
+    let apply_response_headers = apply_headers(quote! { response });
+
+    // The context is an extractor of its own, taken before the parameters since only the
+    // last extractor may read the body
+    let (context_arg, capture_context, context_value) = match context_param {
+        Some(context_param) => {
+            let pat = &context_param.pat;
+            let ty = &context_param.ty;
+            (
+                quote! { #pat: #ty, },
+                quote! { let request_context = ::yew_extra::RequestContext::new(parts.clone()); },
+                quote! { request_context, },
+            )
+        }
+        None => (quote! {}, quote! {}, quote! {}),
+    };
+
+    // The app state is the first extractor, and `state()` returns it in the function body
+    let (state_arg, state_value, state_accessor) = match state_type {
+        Some(state_type) => (
+            quote! { axum::extract::State(app_state): axum::extract::State<#state_type>, },
+            quote! { ::axum::extract::State(app_state), },
+            quote! {
+                #[allow(unused_variables)]
+                let state = move || ::std::clone::Clone::clone(&app_state);
+            },
+        ),
+        None => (quote! {}, quote! {}, quote! {}),
+    };
+
+    let params_arg = if has_params {
+        let struct_name = params_ident;
+        // Use Query for GET, Json for other methods
+        if method == "GET" {
+            quote! { axum::extract::Query(params): axum::extract::Query<#struct_name>, }
+        } else {
+            quote! { axum::Json(params): axum::Json<#struct_name>, }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Parameters named in the path are read from it into a struct of their own
+    let has_path_params = !path_inputs.is_empty();
+    let path_struct = syn::Ident::new(
+        &format!("{}PathParams", to_pascal_case(&fn_name.to_string())),
+        fn_name.span(),
+    );
+    let mut path_fields = Vec::new();
+    let mut path_names = Vec::new();
+    for input in path_inputs {
+        if let FnArg::Typed(pat_type) = input {
+            if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                let field_name = &pat_ident.ident;
+                let field_type = &pat_type.ty;
+                path_fields.push(quote! { pub #field_name: #field_type });
+                path_names.push(field_name);
+            }
+        }
+    }
+    let (path_struct_def, path_arg, path_value, extract_path) = if has_path_params {
+        (
+            quote! {
+                #[cfg(feature = "ssr")]
+                #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+                #vis struct #path_struct {
+                    #(#path_fields),*
+                }
+            },
+            quote! { axum::extract::Path(path_params): axum::extract::Path<#path_struct>, },
+            quote! { ::axum::extract::Path(path_params), },
+            quote! {
+                let mut parts = parts;
+                let path_params = match ::yew_extra::path_params::extract_path::<#path_struct>(&mut parts, #path).await {
+                    Ok(path_params) => path_params,
+                    Err(response) => return response,
+                };
+            },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {}, quote! {})
+    };
+
+    let param_extraction = if has_params || has_path_params {
+        let extract_params = if has_params {
+            let mut field_names = Vec::new();
+            for input in inputs {
+                if let FnArg::Typed(pat_type) = input {
+                    if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                        field_names.push(&pat_ident.ident);
+                    }
+                }
+            }
+            let struct_name = params_ident;
+            quote! { let #struct_name { #(#field_names),* } = params; }
+        } else {
+            quote! {}
+        };
+        let extract_path_params = if has_path_params {
+            quote! { let #path_struct { #(#path_names),* } = path_params; }
+        } else {
+            quote! {}
+        };
+        quote! {
+            #extract_params
+            #extract_path_params
+            #(#param_bindings)*
+        }
+    } else {
+        quote! {}
+    };
+
+    // Create a modified function body that extracts parameters and wraps return in Json
+    let original_stmts = &block.stmts;
+    // A status returned along with the data goes on the response
+    let (body_type, wrap_json, handler_output) = match status_type {
+        Some(status_type) => (
+            status_type.clone(),
+            quote! { result.map(|result| ::yew_extra::WithStatus::from(result).into_parts()) },
+            quote! { (::axum::http::StatusCode, axum::Json<#return_type>) },
+        ),
+        None => (
+            return_type.clone(),
+            quote! { result.map(axum::Json) },
+            quote! { axum::Json<#return_type> },
+        ),
+    };
+    let modified_block = if blocking {
+        // The body moves to a blocking thread along with its parameters
+        quote! {
+            {
+                let result: Result<#body_type, #error_type> = ::yew_extra::run_blocking(async move {
+                    #state_accessor
+                    #param_extraction
+
+                    // Original function body
+                    let result: Result<#body_type, #error_type> = async {
+                        #(#original_stmts)*
+                    }.await;
+                    result
+                }).await;
+
+                // Wrap successful result in Json
+                #wrap_json
+            }
+        }
+    } else {
+        quote! {
+            {
+                #state_accessor
+                #param_extraction
+
+                // Original function body
+                let result: Result<#body_type, #error_type> = async {
+                    #(#original_stmts)*
+                }.await;
+
+                // Wrap successful result in Json
+                #wrap_json
+            }
+        }
+    };
+
+    // Generate a wrapper function that converts Request<Body> to the handler's expected format
+    let wrapper_fn_name = syn::Ident::new(
+        &format!("{}_wrapper", fn_handler_name),
+        fn_handler_name.span(),
+    );
+
+    // Reject invalid parameters before the handler runs
+    let validate_params = generate_validation_rejection(has_validation, &clear_parts);
+
+    // Turns the handler's result into a response, streaming lists if requested
+    let respond = |call: proc_macro2::TokenStream| {
+        if stream_json {
+            quote! {
+                match #call.await {
+                    Ok(::axum::Json(items)) => ::yew_extra::json_stream::StreamingJson::new(items).into_response(),
+                    Err(e) => e.into_response(),
+                }
+            }
+        } else {
+            quote! { #call.await.into_response() }
+        }
+    };
+
+    // Runs the handler, racing it against the deadline if there is one
+    let run_with_deadline = |call: proc_macro2::TokenStream| {
+        let response = respond(call);
+        match server_timeout {
+            Some(timeout_ms) => quote! {
+                ::yew_extra::timeout::with_timeout(
+                    ::std::time::Duration::from_millis(#timeout_ms),
+                    async { #response },
+                )
+                .await
+            },
+            None => response,
+        }
+    };
+    let run_query_handler = run_with_deadline(quote! {
+        #fn_handler_name(#state_value #context_value #path_value ::axum::extract::Query(params))
+    });
+    let run_json_handler = run_with_deadline(quote! {
+        #fn_handler_name(#state_value #context_value #path_value ::axum::Json(params))
+    });
+    let run_handler =
+        run_with_deadline(quote! { #fn_handler_name(#state_value #context_value #path_value) });
+    let provide_owned_parts = provide_parts(quote! { parts });
+    let provide_cloned_parts = provide_parts(quote! { parts.clone() });
+
+    // Endpoints declared with `raw_body` read the whole body up front and keep it for
+    // `extract_body()`, the JSON parameters are parsed from the same bytes
+    let keep_body = match &body_limit {
+        Some(limit) => quote! {
+            let body = match ::yew_extra::read_request_body(body, #limit).await {
+                Ok(body) => body,
+                Err(response) => return response,
+            };
+            ::yew_extra::provide_request_body(body.clone()).await;
+        },
+        None => quote! {},
+    };
+    let body_binding = if body_limit.is_some() {
+        quote! { body }
+    } else {
+        quote! { _body }
+    };
+
+    // Audited calls keep a digest of their parameters
+    let record_params = if workers {
+        quote! {}
+    } else {
+        quote! { ::yew_extra::audit::record_params(&params).await; }
+    };
+
+    // Generate the extraction logic based on method and whether there are params
+    let extract_and_call = if has_params {
+        let struct_name = params_ident;
+
+        if method == "GET" {
+            // Extract query parameters for GET
+            quote! {
+                let (parts, #body_binding) = req.into_parts();
+                #capture_context
+                #extract_path
+                #keep_body
+
+                // Parse the query first so the parts can be handed over without a copy
+                let query = ::yew_extra::query::from_query_str::<#struct_name>(
+                    parts.uri.query().unwrap_or_default(),
+                );
+
+                // Provide parts to yew_extra context before calling the handler
+                #provide_owned_parts
+
+                let result = match query {
+                    Ok(params) => {
+                        #validate_params
+                        #run_query_handler
+                    },
+                    Err(e) => {
+                        ::axum::http::Response::builder()
+                            .status(::axum::http::StatusCode::BAD_REQUEST)
+                            .body(::axum::body::Body::from(format!("Invalid query parameters: {}", e)))
+                            .unwrap()
+                    }
+                };
+                #apply_result_headers
+
+                // Clear parts after handler completes
+                #clear_parts
+                result
+            }
+        } else if body_limit.is_some() {
+            // Parse the JSON parameters from the kept body
+            quote! {
+                let (parts, body) = req.into_parts();
+                #capture_context
+                #extract_path
+                #keep_body
+
+                // Provide parts to yew_extra context before calling the handler
+                #provide_owned_parts
+
+                let result = match ::axum::Json::<#struct_name>::from_bytes(&body) {
+                    Ok(::axum::Json(params)) => {
+                        #record_params
+                        #validate_params
+                        #run_json_handler
+                    },
+                    Err(e) => {
+                        ::axum::http::Response::builder()
+                            .status(::axum::http::StatusCode::BAD_REQUEST)
+                            .body(::axum::body::Body::from(format!("Invalid request: {}", e)))
+                            .unwrap()
+                    }
+                };
+                #apply_result_headers
+
+                // Clear parts after handler completes
+                #clear_parts
+                result
+            }
+        } else {
+            // Extract JSON body for POST/PUT/DELETE/PATCH
+            quote! {
+                use ::axum::extract::FromRequest;
+
+                let (parts, body) = req.into_parts();
+                #capture_context
+                #extract_path
+
+                // Provide parts to yew_extra context before calling the handler
+                #provide_cloned_parts
+
+                let req = ::axum::http::Request::from_parts(parts, body);
+
+                let result = match ::axum::Json::<#struct_name>::from_request(req, &()).await {
+                    Ok(::axum::Json(params)) => {
+                        #record_params
+                        #validate_params
+                        #run_json_handler
+                    },
+                    Err(e) => {
+                        ::axum::http::Response::builder()
+                            .status(::axum::http::StatusCode::BAD_REQUEST)
+                            .body(::axum::body::Body::from(format!("Invalid request: {}", e)))
+                            .unwrap()
+                    }
+                };
+                #apply_result_headers
+
+                // Clear parts after handler completes
+                #clear_parts
+                result
+            }
+        }
+    } else {
+        quote! {
+            // No parameters, but still provide Parts for extraction
+            let (parts, #body_binding) = req.into_parts();
+            #capture_context
+            #extract_path
+            #keep_body
+
+            // Provide parts to yew_extra context before calling the handler
+            #provide_owned_parts
+
+            let response = #run_handler;
+            #apply_response_headers
+
+            // Clear parts after handler completes
+            #clear_parts
+
+            response.into_response()
+        }
+    };
+
+    // Mutations sent with an idempotency key only run once per key, identical concurrent
+    // reads of coalesced endpoints share a single run
+    let call_handler = if method == "GET" && coalesce {
+        quote! {
+            let coalesce_key = ::yew_extra::coalesce::coalesce_key(&req);
+            ::yew_extra::coalesce::run_coalesced(coalesce_key, async move {
+                #extract_and_call
+            })
+            .await
+        }
+    } else if method == "GET" || workers {
+        extract_and_call
+    } else {
+        // Requests with a reused or stale nonce are rejected before anything runs
+        let check_nonce = match &nonce_max_age {
+            Some(max_age) => quote! {
+                if let Err(response) = ::yew_extra::nonce::check_nonce(&req, #max_age).await {
+                    return ::yew_extra::audit::finish(audit, response).await;
+                }
+            },
+            None => quote! {},
+        };
+        // The audit sink gets a record of every call of a mutation
+        quote! {
+            let mut req = req;
+            let audit = ::yew_extra::audit::begin(#path, &mut req);
+            #check_nonce
+            let idempotency_key = ::yew_extra::background_sync::idempotency_key(&req);
+            let response = ::yew_extra::background_sync::run_idempotent(idempotency_key, async move {
+                #extract_and_call
+            })
+            .await;
+            ::yew_extra::audit::finish(audit, response).await
+        }
+    };
+
+    // The request body is decoded before anything reads it, and the successful response
+    // encoded once everything else is done with it
+    let call_handler = match transform {
+        Some(transform) => {
+            let limit = match &body_limit {
+                Some(limit) => quote! { Some(#limit) },
+                None => quote! { None },
+            };
+            quote! {
+                let req = match ::yew_extra::transform::decode_request::<#transform>(req, #limit).await {
+                    Ok(req) => req,
+                    Err(response) => return response,
+                };
+                let response = async move { #call_handler }.await;
+                ::yew_extra::transform::encode_response::<#transform>(response).await
+            }
+        }
+        None => call_handler,
+    };
+
+    // The inner block keeps the early returns of the handler call from skipping the stamp
+    let call_handler = match content_type {
+        Some(content_type) => quote! {
+            let response = async move { #call_handler }.await;
+            ::yew_extra::content_type::with_content_type(response, #content_type)
+        },
+        None => call_handler,
+    };
+
+    // Every response of a deprecated endpoint announces its retirement
+    let call_handler = match deprecation {
+        Some(deprecation) => quote! {
+            let response = async move { #call_handler }.await;
+            ::yew_extra::deprecation::with_deprecation(response, &#deprecation)
+        },
+        None => call_handler,
+    };
+
+    // Endpoints behind a flag that's off answer like unknown paths, before anything runs
+    let check_flag = match feature_flag {
+        Some(flag) => quote! {
+            if let Err(response) = ::yew_extra::feature_flags::check_flag(&req, #flag).await {
+                return response;
+            }
+        },
+        None => quote! {},
+    };
+
+    // Convert method string to TokenStream identifier
+    let method_ident = syn::Ident::new(method, proc_macro2::Span::call_site());
+
+    // Generate inventory submission for automatic registration
+    // This creates a wrapper that can work with raw Request<Body>
+    // The inventory submission is only for non-test builds
+    // The wrapper of an external endpoint is only used if the other service mounts it
+    let allow_unused = if external {
+        quote! { #[allow(dead_code)] }
+    } else {
+        quote! {}
+    };
+    // Every request gets storage of its own for the parts `extract()` reads, bound to the
+    // task handling it
+    let handler_future = match state_type {
+        Some(state_type) => {
+            let wrong_state = format!(
+                "{} {} needs an app state of type {}",
+                method,
+                path,
+                quote!(#state_type)
+            );
+            quote! {
+                async move {
+                    use ::axum::response::IntoResponse;
+                    let Some(app_state) = app_state else {
+                        return ::axum::http::Response::builder()
+                            .status(::axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(::axum::body::Body::from(#wrong_state))
+                            .unwrap();
+                    };
+                    #check_flag
+                    #call_handler
+                }
+            }
+        }
+        None => quote! {
+            async move {
+                use ::axum::response::IntoResponse;
+                #check_flag
+                #call_handler
+            }
+        },
+    };
+    let scoped_handler = if workers {
+        handler_future
+    } else {
+        quote! { ::yew_extra::request_scope(#handler_future) }
+    };
+
+    let inventory_submission = match state_type {
+        // Stateful endpoints run through a second wrapper that's handed the router's state,
+        // the plain one tells routers built without it what's missing
+        Some(state_type) => {
+            let state_wrapper_fn_name = syn::Ident::new(
+                &format!("{}_state_wrapper", fn_handler_name),
+                fn_handler_name.span(),
+            );
+            let missing_state = format!(
+                "{} {} needs the app state, build the router with build_router_with_state()",
+                method, path
+            );
+            quote! {
+                #[cfg(all(feature = "ssr", not(test)))]
+                #allow_unused
+                #vis fn #wrapper_fn_name(
+                    req: ::axum::http::Request<::axum::body::Body>
+                ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ::axum::http::Response<::axum::body::Body>> + Send>> {
+                    let _ = req;
+                    Box::pin(async move {
+                        ::axum::http::Response::builder()
+                            .status(::axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(::axum::body::Body::from(#missing_state))
+                            .unwrap()
+                    })
+                }
+
+                #[cfg(all(feature = "ssr", not(test)))]
+                #allow_unused
+                #vis fn #state_wrapper_fn_name(
+                    req: ::axum::http::Request<::axum::body::Body>,
+                    state: &(dyn ::std::any::Any + Send + Sync),
+                ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ::axum::http::Response<::axum::body::Body>> + Send>> {
+                    let app_state = state.downcast_ref::<#state_type>().cloned();
+                    Box::pin(#scoped_handler)
+                }
+            }
+        }
+        None => quote! {
+            // Only generate the wrapper and inventory submission in non-test builds
+            // The wrapper keeps the function's visibility so integration tests can call it
+            // through yew_extra::test::call_handler
+            #[cfg(all(feature = "ssr", not(test)))]
+            #allow_unused
+            #vis fn #wrapper_fn_name(
+                req: ::axum::http::Request<::axum::body::Body>
+            ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ::axum::http::Response<::axum::body::Body>> + Send>> {
+                Box::pin(#scoped_handler)
+            }
+        },
+    };
+
+    let state_handler = state_type.map(|_| {
+        let state_wrapper_fn_name = syn::Ident::new(
+            &format!("{}_state_wrapper", fn_handler_name),
+            fn_handler_name.span(),
+        );
+        quote! { .state_handler(#state_wrapper_fn_name) }
+    });
+
+    // Edge builds mount the wrappers themselves, there is no link-time registry there.
+    // External endpoints are served by another service.
+    let route_registration = if workers || external {
+        quote! {}
+    } else {
+        quote! {
+            #[cfg(all(feature = "ssr", not(test)))]
+            ::inventory::submit! {
+                crate::route_registry::RouteInfo::new(
+                    #path,
+                    ::axum::http::Method::#method_ident,
+                    #wrapper_fn_name
+                ) #state_handler #route_metadata
+            }
+        }
+    };
+
+    quote! {
+        #path_struct_def
+
+        #[cfg(feature = "ssr")]
+        #handler_attrs
+        #vis async fn #fn_handler_name(
+            #state_arg
+            #context_arg
+            #path_arg
+            #params_arg
+        ) -> Result<#handler_output, #error_type> #modified_block
+
+        #inventory_submission
+
+        #route_registration
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_client_function(
+    fn_name: &syn::Ident,
+    params_ident: &syn::Ident,
+    vis: &syn::Visibility,
+    path: &str,
+    return_type: &proc_macro2::TokenStream,
+    has_params: bool,
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    path_params: &[syn::Ident],
+    method: &str,
+    has_validation: bool,
+    background_sync: bool,
+    idempotent: bool,
+    nonce: bool,
+    retry: Option<u32>,
+    optimistic: Option<&syn::Path>,
+    schema_check: bool,
+    fast_json: bool,
+    transform: Option<&syn::Path>,
+    deprecation: Option<&proc_macro2::TokenStream>,
+    mock: Option<&MockOptions>,
+    fetch_options: &proc_macro2::TokenStream,
+    host_url: &proc_macro2::TokenStream,
+    forwarded_attrs: &ForwardedAttrs,
+) -> proc_macro2::TokenStream {
+    let client_attrs = forwarded_attrs.client();
+
+    // Run the same checks as the server before sending anything
+    let validate_params = if has_validation {
+        quote! {
+            params.validate().map_err(|e| e.to_string())?;
+        }
+    } else {
+        quote! {}
+    };
+
+    let nonce_headers = generate_nonce_headers(nonce);
+
+    // Queued or retried requests may arrive twice, so the server needs a key to recognize them
+    let idempotency_header = if background_sync || idempotent {
+        quote! {
+            .header(
+                ::yew_extra::background_sync::IDEMPOTENCY_HEADER,
+                &::yew_extra::background_sync::new_idempotency_key(),
+            )
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate function parameters
+    let mut params = Vec::new();
+    let mut arg_names = Vec::new();
+    for input in inputs {
+        if let FnArg::Typed(pat_type) = input {
+            if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                let param_name = &pat_ident.ident;
+                let param_type = &pat_type.ty;
+                params.push(quote! { #param_name: #param_type });
+                arg_names.push(param_name);
+            }
+        }
+    }
+    let func_params = quote! { #(#params),* };
+    let func_params_with_options = quote! { #(#params,)* options: ::yew_extra::RequestOptions };
+
+    // Convert method to lowercase for gloo_net
+    let method_lower = method.to_lowercase();
+    let method_fn = syn::Ident::new(&method_lower, proc_macro2::Span::call_site());
+
+    let base_url = quote! { options.base_url.clone().unwrap_or_else(|| #host_url) };
+    let (fill_path, request_path) = generate_request_path(path, path_params);
+    let apply_request_options = generate_request_options_application();
+    let encode_body = match transform {
+        Some(transform) => quote! {
+            let body = ::yew_extra::transform::request_body::<#transform>(body)?;
+        },
+        None => quote! {},
+    };
+
+    // Generate request body creation
+    let request_body = if has_params && method != "GET" {
+        let struct_name = params_ident;
+        let mut field_names = Vec::new();
+        for input in inputs {
+            if let FnArg::Typed(pat_type) = input {
+                if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                    if !path_params.contains(&pat_ident.ident) {
+                        field_names.push(&pat_ident.ident);
+                    }
+                }
+            }
+        }
+        quote! {
+            #fill_path
+            let params = #struct_name {
+                #(#field_names),*
+            };
+            #validate_params
+            let body = serde_json::to_string(&params)
+                .map_err(|e| format!("Failed to serialize parameters: {}", e))?;
+            #encode_body
+
+            let request = gloo_net::http::Request::#method_fn(&format!("{}{}", #base_url, #request_path))
+                .header("Content-Type", "application/json")
+                #fetch_options
+                #idempotency_header
+                #nonce_headers;
+            #apply_request_options
+            let request = request
+                .body(body)
+                .map_err(|e| format!("Failed to create request: {}", e))?;
+        }
+    } else if has_params && method == "GET" {
+        // Build query string for GET requests
+        let struct_name = params_ident;
+        let mut field_names = Vec::new();
+        for input in inputs {
+            if let FnArg::Typed(pat_type) = input {
+                if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                    if !path_params.contains(&pat_ident.ident) {
+                        field_names.push(&pat_ident.ident);
+                    }
+                }
+            }
+        }
+        quote! {
+            #fill_path
+            let params = #struct_name {
+                #(#field_names),*
+            };
+            #validate_params
+
+            // Serialize to query string
+            let query_string = ::yew_extra::query::to_query_string(&params)
+                .map_err(|e| format!("Failed to serialize query parameters: {}", e))?;
+
+            let url = format!("{}{}?{}", #base_url, #request_path, query_string);
+
+            let request = gloo_net::http::Request::#method_fn(&url)
+                .header("Content-Type", "application/json")
+                #fetch_options;
+            #apply_request_options
+        }
+    } else {
+        quote! {
+            #fill_path
+            let request = gloo_net::http::Request::#method_fn(&format!("{}{}", #base_url, #request_path))
+                .header("Content-Type", "application/json")
+                #fetch_options
+                #idempotency_header
+                #nonce_headers;
+            #apply_request_options
+        }
+    };
+
+    // Generate the function name for the direct call version
+    let async_fn_name = syn::Ident::new(&fn_name.to_string(), fn_name.span());
+    let with_options_fn_name =
+        syn::Ident::new(&format!("{}_with_options", fn_name), fn_name.span());
+    let with_response_fn_name =
+        syn::Ident::new(&format!("{}_with_response", fn_name), fn_name.span());
+
+    let send_request = generate_client_response_handling(
+        return_type,
+        method,
+        path,
+        background_sync,
+        retry,
+        true,
+        schema_check,
+        fast_json,
+        transform,
+    );
+
+    // Patch the cache before sending, and undo the patch if the mutation fails
+    let settle_optimistic = |call: proc_macro2::TokenStream| match optimistic {
+        Some(optimistic) => quote! {
+            let mut optimistic = ::yew_extra::cache::OptimisticUpdate::new();
+            #optimistic(#(&#arg_names,)* &mut optimistic);
+            let result = async { #call }.await;
+            optimistic.settle(result.is_ok());
+            result
+        },
+        None => call,
+    };
+    let warn_deprecated = generate_deprecation_warning(method, path, deprecation);
+    let send_request = settle_optimistic(quote! {
+        #warn_deprecated
+        #request_body
+
+        #send_request
+    });
+
+    // With a fixture, `mock` builds get a version that returns it instead of sending a request
+    let (real_cfg, mock_function) = match mock {
+        Some(mock) => {
+            let call_fixture = settle_optimistic(generate_mock_call(mock, &arg_names));
+            (
+                quote! { #[cfg(all(not(feature = "ssr"), not(feature = "mock")))] },
+                quote! {
+                    #[cfg(all(not(feature = "ssr"), feature = "mock"))]
+                    #client_attrs
+                    #vis async fn #with_response_fn_name(
+                        #func_params_with_options
+                    ) -> Result<(#return_type, ::yew_extra::ResponseMeta), String> {
+                        let _ = options;
+                        let result: Result<#return_type, String> = { #call_fixture };
+                        result.map(|data| (data, ::yew_extra::ResponseMeta::new(200, Vec::new())))
+                    }
+                },
+            )
+        }
+        None => (quote! { #[cfg(not(feature = "ssr"))] }, quote! {}),
+    };
+
+    quote! {
+        #[cfg(not(feature = "ssr"))]
+        #client_attrs
+        #vis async fn #async_fn_name(#func_params) -> Result<#return_type, String> {
+            #with_options_fn_name(#(#arg_names,)* ::yew_extra::RequestOptions::default()).await
+        }
+
+        #[cfg(not(feature = "ssr"))]
+        #client_attrs
+        #vis async fn #with_options_fn_name(#func_params_with_options) -> Result<#return_type, String> {
+            #with_response_fn_name(#(#arg_names,)* options)
+                .await
+                .map(|(data, _)| data)
+        }
+
+        #real_cfg
+        #client_attrs
+        #vis async fn #with_response_fn_name(
+            #func_params_with_options
+        ) -> Result<(#return_type, ::yew_extra::ResponseMeta), String> {
+            #send_request
+        }
+
+        #mock_function
+    }
+}
+
+/// Generates `prefetch_<function>`, which fetches a GET endpoint into the global cache
+/// under the key its hook reads, for `yew_extra::cache::warm_cache`, and
+/// `resource_<function>`, a `yew_extra::resource::Resource` over the same entry for code
+/// outside components
+#[allow(clippy::too_many_arguments)]
+fn generate_prefetch_function(
+    fn_name: &syn::Ident,
+    params_ident: &syn::Ident,
+    vis: &syn::Visibility,
+    path: &str,
+    return_type: &proc_macro2::TokenStream,
+    has_params: bool,
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    path_params: &[syn::Ident],
+    shared_data: bool,
+    host_url: &proc_macro2::TokenStream,
+    forwarded_attrs: &ForwardedAttrs,
+) -> proc_macro2::TokenStream {
+    let client_attrs = forwarded_attrs.client();
+    let prefetch_fn_name = syn::Ident::new(&format!("prefetch_{}", fn_name), fn_name.span());
+    let resource_fn_name = syn::Ident::new(&format!("resource_{}", fn_name), fn_name.span());
+    let with_options_fn_name =
+        syn::Ident::new(&format!("{}_with_options", fn_name), fn_name.span());
+
+    let mut params = Vec::new();
+    let mut arg_names = Vec::new();
+    for input in inputs {
+        if let FnArg::Typed(pat_type) = input {
+            if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                let param_name = &pat_ident.ident;
+                let param_type = &pat_type.ty;
+                params.push(quote! { #param_name: #param_type });
+                arg_names.push(param_name);
+            }
+        }
+    }
+
+    // The same URL as the hook's, so the same cache key
+    let (fill_path, request_path) = generate_request_path(path, path_params);
+    let request_url = if has_params {
+        let field_names: Vec<_> = arg_names
+            .iter()
+            .filter(|name| !path_params.contains(name))
+            .collect();
+        quote! {
+            #fill_path
+            let params = #params_ident { #(#field_names),* };
+            let query_string = ::yew_extra::query::to_query_string(&params).unwrap_or_default();
+            let #params_ident { #(#field_names),* } = params;
+            let request_url = format!("{}{}?{}", #host_url, #request_path, query_string);
+        }
+    } else {
+        quote! {
+            #fill_path
+            let request_url = format!("{}{}", #host_url, #request_path);
+        }
+    };
+    let (wrap_data, data_type) = if shared_data {
+        (
+            quote! { let fetched_data = ::std::rc::Rc::new(fetched_data); },
+            quote! { ::std::rc::Rc<#return_type> },
+        )
+    } else {
+        (quote! {}, quote! { #return_type })
+    };
+
+    quote! {
+        #[cfg(not(feature = "ssr"))]
+        #client_attrs
+        #vis fn #prefetch_fn_name(#(#params),*) -> ::yew_extra::cache::Prefetch {
+            #request_url
+            let cache_key = ::yew_extra::cache::request_key(
+                &request_url,
+                &::yew_extra::RequestOptions::default(),
+            );
+            ::yew_extra::cache::Prefetch::new(cache_key.clone(), async move {
+                let fetched_data: #return_type = #with_options_fn_name(
+                    #(#arg_names,)*
+                    ::yew_extra::RequestOptions::default(),
+                )
+                .await?;
+                #wrap_data
+                ::yew_extra::cache::insert(cache_key.clone(), fetched_data);
+                ::yew_extra::cache::notify(&cache_key);
+                Ok(())
+            })
+        }
+
+        #[cfg(not(feature = "ssr"))]
+        #client_attrs
+        #vis fn #resource_fn_name(#(#params),*) -> ::yew_extra::resource::Resource<#data_type> {
+            #request_url
+            let cache_key = ::yew_extra::cache::request_key(
+                &request_url,
+                &::yew_extra::RequestOptions::default(),
+            );
+            ::yew_extra::resource::Resource::new(cache_key, move || {
+                #(let #arg_names = ::std::clone::Clone::clone(&#arg_names);)*
+                async move {
+                    let fetched_data: #return_type = #with_options_fn_name(
+                        #(#arg_names,)*
+                        ::yew_extra::RequestOptions::default(),
+                    )
+                    .await?;
+                    #wrap_data
+                    Ok(fetched_data)
+                }
+            })
+        }
+    }
+}
+
+/// Generates `use_*_mutation`, which sends the request when its `trigger` is called instead
+/// of when the component mounts. It takes the path parameters, `trigger` the parameters
+/// struct.
+#[allow(clippy::too_many_arguments)]
+fn generate_mutation_hook(
+    fn_name: &syn::Ident,
+    params_ident: &syn::Ident,
+    vis: &syn::Visibility,
+    return_type: &proc_macro2::TokenStream,
+    has_params: bool,
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    path_params: &[syn::Ident],
+    forwarded_attrs: &ForwardedAttrs,
+) -> proc_macro2::TokenStream {
+    let hook_attrs = forwarded_attrs.client();
+    let hook_name = syn::Ident::new(&format!("use_{}_mutation", fn_name), fn_name.span());
+
+    let mut hook_params = Vec::new();
+    let mut arg_names = Vec::new();
+    let mut field_names = Vec::new();
+    for input in inputs {
+        if let FnArg::Typed(pat_type) = input {
+            if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                let param_name = &pat_ident.ident;
+                let param_type = &pat_type.ty;
+                if path_params.contains(param_name) {
+                    hook_params.push(quote! { #param_name: #param_type });
+                } else {
+                    field_names.push(param_name);
+                }
+                arg_names.push(param_name);
+            }
+        }
+    }
+
+    let (input_type, input_pattern) = if has_params {
+        (
+            quote! { #params_ident },
+            quote! { #params_ident { #(#field_names),* } },
+        )
+    } else {
+        (quote! { () }, quote! { () })
+    };
+
+    quote! {
+        #[yew::hook]
+        #hook_attrs
+        #vis fn #hook_name(#(#hook_params),*) -> ::yew_extra::action::Mutation<#input_type, #return_type> {
+            #[cfg(not(feature = "ssr"))]
+            let action = ::yew_extra::action::use_action(move |#input_pattern: #input_type| {
+                #(let #path_params = ::std::clone::Clone::clone(&#path_params);)*
+                #fn_name(#(#arg_names),*)
+            });
+
+            // The server only renders the idle state, mutations are sent from the browser
+            #[cfg(feature = "ssr")]
+            let action = {
+                #(let _ = &#path_params;)*
+                ::yew_extra::action::use_action(|_: #input_type| async {
+                    Err::<#return_type, String>("Mutations are sent from the browser".to_string())
+                })
+            };
+
+            ::yew_extra::action::Mutation::new(action)
+        }
+    }
+}
+
+/// Calls the mock fixture with the endpoint's arguments after the simulated latency
+fn generate_mock_call(mock: &MockOptions, arg_names: &[&syn::Ident]) -> proc_macro2::TokenStream {
+    let fixture = &mock.fixture;
+    let latency = match mock.latency_ms {
+        Some(ms) => quote! { ::yew_extra::mock::delay(#ms).await; },
+        None => quote! {},
+    };
+
+    quote! {
+        {
+            #latency
+            #fixture(#(#arg_names),*)
+        }
+    }
+}
+
+/// Headers of endpoints declared with `nonce`, fresh for every request
+fn generate_nonce_headers(nonce: bool) -> proc_macro2::TokenStream {
+    if nonce {
+        quote! {
+            .header(::yew_extra::nonce::NONCE_HEADER, &::yew_extra::nonce::new_nonce())
+            .header(::yew_extra::nonce::TIMESTAMP_HEADER, &::yew_extra::nonce::timestamp())
+        }
+    } else {
+        quote! {}
+    }
+}
+
+/// The names of the parameters in a path: `{id}` and `{*rest}` segments, or `:id` and
+/// `*rest` in the syntax of Axum 0.7. Either is fine with either version, `routes()`
+/// translates the path for the router.
+///
+/// The same parsing as `yew_extra::path_params::parameter_names()`, which the macro can't
+/// call: a proc-macro crate depending on the runtime crate gets a copy of its own, built
+/// without the app's features.
+fn path_parameter_names(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter_map(|segment| {
+            segment
+                .strip_prefix("{*")
+                .or_else(|| segment.strip_prefix('{'))
+                .and_then(|name| name.strip_suffix('}'))
+                .or_else(|| segment.strip_prefix(':'))
+                .or_else(|| segment.strip_prefix('*'))
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// The path the clients request: a statement binding `request_path` to the path with its
+/// parameters filled in, and the expression to use, or the path itself without parameters
+fn generate_request_path(
+    path: &str,
+    path_params: &[syn::Ident],
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    if path_params.is_empty() {
+        return (quote! {}, quote! { #path });
+    }
+    let names = path_params.iter().map(|param| param.to_string());
+    (
+        quote! {
+            let request_path = ::yew_extra::path_params::fill_path(
+                #path,
+                &[#((#names, ::yew_extra::path_params::segment(&#path_params))),*],
+            );
+        },
+        quote! { request_path },
+    )
+}
+
+/// Warns once in the console that a deprecated endpoint is still called
+fn generate_deprecation_warning(
+    method: &str,
+    path: &str,
+    deprecation: Option<&proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    match deprecation {
+        Some(deprecation) => quote! {
+            ::yew_extra::deprecation::warn_once(#method, #path, &#deprecation);
+        },
+        None => quote! {},
+    }
+}
+
+/// Adds the headers and query parameters of the per-call `options` to `request`
+fn generate_request_options_application() -> proc_macro2::TokenStream {
+    quote! {
+        let request = options
+            .headers
+            .iter()
+            .fold(request, |request, (name, value)| request.header(name, value));
+        let request = if options.query.is_empty() {
+            request
+        } else {
+            request.query(options.query.iter().map(|(name, value)| (name.as_str(), value)))
+        };
+    }
+}
+
+/// Parses the body of a successful `response` into `Result<T, String>`.
+///
+/// With `schema_check`, debug builds validate the JSON against the schema of `T` first.
+/// With `fast_json`, the browser's `JSON.parse` parses it instead of `serde_json`. With a
+/// `transform`, the body is decoded first.
+fn generate_json_parse(
+    return_type: &proc_macro2::TokenStream,
+    schema_check: bool,
+    fast_json: bool,
+    transform: Option<&syn::Path>,
+) -> proc_macro2::TokenStream {
+    let parse_text = if fast_json {
+        quote! { ::yew_extra::fetch::parse_json_text::<#return_type>(&text) }
+    } else {
+        quote! {
+            serde_json::from_str::<#return_type>(&text)
+                .map_err(|e| format!("Failed to parse response: {}", e))
+        }
+    };
+
+    let read_text = match transform {
+        Some(transform) => quote! {
+            let body = response
+                .binary()
+                .await
+                .map_err(|e| format!("Failed to read response: {}", e))?;
+            let text = ::yew_extra::transform::response_text::<#transform>(body)?;
+        },
+        None => quote! {
+            let text = response
+                .text()
+                .await
+                .map_err(|e| format!("Failed to read response: {}", e))?;
+        },
+    };
+
+    if schema_check {
+        quote! {
+            async {
+                #read_text
+                #[cfg(debug_assertions)]
+                ::yew_extra::schema::validate_json::<#return_type>(&text)?;
+                #parse_text
+            }
+            .await
+        }
+    } else if transform.is_some() {
+        quote! {
+            async {
+                #read_text
+                #parse_text
+            }
+            .await
+        }
+    } else if fast_json {
+        quote! { ::yew_extra::fetch::parse_json_response::<#return_type>(response).await }
+    } else {
+        quote! {
+            response
+                .json::<#return_type>()
+                .await
+                .map_err(|e| format!("Failed to parse response: {}", e))
+        }
+    }
+}
+
+/// Reads the error message of a non-2xx `response`
+///
+/// The server's message is taken from the `error` or `message` field of a JSON body, or the
+/// body as a whole. With the `minimal_errors` feature of `yew_extra`, only the status is
+/// reported, and the optimizer drops the string handling and `serde_json::Value` probing
+/// from the wasm bundle.
+fn generate_error_message() -> proc_macro2::TokenStream {
+    quote! {
+        {
+            let status = response.status();
+            if ::yew_extra::MINIMAL_ERRORS {
+                format!("Request failed with status {}", status)
+            } else {
+                // Handle error response - try to get the error message from the response
+                match response.text().await {
+                    Ok(text) => {
+                        // Try to parse as JSON error message, localized errors are
+                        // rendered in the user's language if the client can
+                        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&text) {
+                            if let Some(msg) = ::yew_extra::i18n::localize_error(&json) {
+                                msg
+                            } else if let Some(msg) = json.get("error").and_then(|v| v.as_str()) {
+                                msg.to_string()
+                            } else if let Some(msg) = json.get("message").and_then(|v| v.as_str()) {
+                                msg.to_string()
+                            } else {
+                                text
+                            }
+                        } else {
+                            text
+                        }
+                    }
+                    Err(_) => format!("Request failed with status {}", status)
+                }
+            }
+        }
+    }
+}
+
+/// Sends `request` and converts the response into `Result<T, String>` for client functions
+///
+/// With `with_meta`, the result is `Result<(T, ResponseMeta), String>` instead.
+#[allow(clippy::too_many_arguments)]
+fn generate_client_response_handling(
+    return_type: &proc_macro2::TokenStream,
+    method: &str,
+    path: &str,
+    background_sync: bool,
+    retry: Option<u32>,
+    with_meta: bool,
+    schema_check: bool,
+    fast_json: bool,
+    transform: Option<&syn::Path>,
+) -> proc_macro2::TokenStream {
+    // The service worker answers a queued request with 202 and a marker header
+    let queued_check = if background_sync {
+        quote! {
+            if response.status() == 202
+                && response
+                    .headers()
+                    .get(::yew_extra::background_sync::QUEUED_HEADER)
+                    .is_some()
+            {
+                return Err(::yew_extra::background_sync::QUEUED_ERROR.to_string());
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let parse_json = generate_json_parse(return_type, schema_check, fast_json, transform);
+    let error_message = generate_error_message();
+    let parse_body = if with_meta {
+        quote! {
+            let meta = ::yew_extra::ResponseMeta::new(response.status(), response.headers().entries());
+            #parse_json.map(|data| (data, meta))
+        }
+    } else {
+        quote! { #parse_json }
+    };
+
+    let send = generate_send(retry);
+    let follow_redirect = generate_redirect_check();
+    let report_fetch_failure = generate_failure_report(method, path, quote! { None });
+    let report_status_failure =
+        generate_failure_report(method, path, quote! { Some(response.status()) });
+    quote! {
+        let response = #send.map_err(|e| {
+            let error_msg = ::yew_extra::network::fetch_error(e);
+            #report_fetch_failure
+            error_msg
+        })?;
+
+        #queued_check
+
+        #follow_redirect
+
+        // Check if the response status is successful (2xx)
+        if response.ok() {
+            #parse_body
+        } else {
+            let error_msg = #error_message;
+            #report_status_failure
+            Err(error_msg)
         }
     }
 }
 
-fn generate_server_handler(
-    fn_name: &syn::Ident,
+/// Sends the user on when the server function called `yew_extra::redirect()`. The
+/// response is still handled as usual.
+fn generate_redirect_check() -> proc_macro2::TokenStream {
+    quote! {
+        ::yew_extra::redirect::follow_header(|name| response.headers().get(name));
+    }
+}
+
+/// Passes a failed request to the app's error handler, with its message in `error_msg`
+fn generate_failure_report(
+    method: &str,
+    path: &str,
+    status: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        ::yew_extra::error_handler::report_failure(::yew_extra::error_handler::RequestFailure {
+            method: #method,
+            path: #path,
+            status: #status,
+            message: error_msg.clone(),
+        });
+    }
+}
+
+/// Sends `request` over HTTP, or through yew_extra's pluggable transport (custom backend,
+/// Tauri IPC) when the `transport` feature is enabled. Retried requests always go through
+/// yew_extra's retry loop, which picks the transport itself.
+fn generate_send(retry: Option<u32>) -> proc_macro2::TokenStream {
+    match retry {
+        Some(retries) => quote! { ::yew_extra::retry::send_with_retries(request, #retries).await },
+        None if cfg!(feature = "transport") => {
+            quote! { ::yew_extra::transport::send(request).await }
+        }
+        None => quote! { request.send().await },
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_client_hook(
+    hook_name: &syn::Ident,
     vis: &syn::Visibility,
-    block: &syn::Block,
-    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
-    _output: &ReturnType,
-    has_params: bool,
-    return_type: &proc_macro2::TokenStream,
-    error_type: &proc_macro2::TokenStream,
     path: &str,
+    return_type: &proc_macro2::TokenStream,
+    has_params: bool,
+    params_ident: &syn::Ident,
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    path_params: &[syn::Ident],
     method: &str,
+    has_validation: bool,
+    schema_check: bool,
+    fast_json: bool,
+    transform: Option<&syn::Path>,
+    deprecation: Option<&proc_macro2::TokenStream>,
+    shared_data: bool,
+    retry: Option<u32>,
+    idempotency_key: bool,
+    nonce: bool,
+    placeholder_data: Option<&syn::Path>,
+    key: Option<&syn::Path>,
+    poll_interval: Option<u64>,
+    mock: Option<&MockOptions>,
+    fetch_options: &proc_macro2::TokenStream,
+    host_url: &proc_macro2::TokenStream,
+    mount_deprecation: Option<&proc_macro2::TokenStream>,
+    forwarded_attrs: &ForwardedAttrs,
 ) -> proc_macro2::TokenStream {
-    let fn_handler_name =
-        syn::Ident::new(&format!("{}_handler", fn_name.to_string()), fn_name.span());
+    let hook_attrs = forwarded_attrs.client();
+    // A deprecated endpoint already warns about its hooks
+    let mount_deprecation = match mount_deprecation {
+        Some(deprecation) if !forwarded_attrs.is_deprecated() => quote! {
+            #deprecation
+            #[allow(deprecated)]
+        },
+        _ => quote! {},
+    };
 
-    let params_arg = if has_params {
-        let struct_name = syn::Ident::new(
-            &format!("{}Params", to_pascal_case(&fn_name.to_string())),
-            fn_name.span(),
-        );
-        // Use Query for GET, Json for other methods
+    // Run the same checks as the server before sending anything
+    let validate_params = if has_validation {
+        quote! {
+            if let Err(e) = params.validate() {
+                fail(e.to_string());
+                is_loading.set(false);
+                is_updating.set(false);
+                return;
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let mut params = Vec::new();
+    let mut arg_names = Vec::new();
+    let mut field_names = Vec::new();
+    for input in inputs {
+        if let FnArg::Typed(pat_type) = input {
+            if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                let param_name = &pat_ident.ident;
+                let param_type = &pat_type.ty;
+                params.push(quote! { #param_name: #param_type });
+                arg_names.push(param_name);
+                if !path_params.contains(param_name) {
+                    field_names.push(param_name);
+                }
+            }
+        }
+    }
+    let hook_params = quote! { #(#params),* };
+    let hook_params_with_options = quote! { #(#params,)* options: ::yew_extra::RequestOptions };
+    let hook_with_options_name =
+        syn::Ident::new(&format!("{}_with_options", hook_name), hook_name.span());
+    let hook_with_handle_name =
+        syn::Ident::new(&format!("{}_with_handle", hook_name), hook_name.span());
+
+    // Convert method to lowercase for gloo_net
+    let method_lower = method.to_lowercase();
+    let method_fn = syn::Ident::new(&method_lower, proc_macro2::Span::call_site());
+
+    let parse_json = generate_json_parse(return_type, schema_check, fast_json, transform);
+    let error_message = generate_error_message();
+    let base_url = quote! { options.base_url.clone().unwrap_or_else(|| #host_url) };
+    let (fill_path, request_path) = generate_request_path(path, path_params);
+    let apply_request_options = generate_request_options_application();
+
+    // Every run of the effect is a new call with a nonce of its own
+    let nonce_headers = generate_nonce_headers(nonce);
+
+    // Every run of the effect is a new call, but its retries share the key
+    let idempotency_header = if idempotency_key {
+        quote! {
+            .header(
+                ::yew_extra::background_sync::IDEMPOTENCY_HEADER,
+                &::yew_extra::background_sync::new_idempotency_key(),
+            )
+        }
+    } else {
+        quote! {}
+    };
+    let send = generate_send(retry);
+    let follow_redirect = generate_redirect_check();
+    let report_fetch_failure = generate_failure_report(method, path, quote! { None });
+    let report_status_failure =
+        generate_failure_report(method, path, quote! { Some(response.status()) });
+    let encode_body = match transform {
+        Some(transform) => quote! {
+            let body = match ::yew_extra::transform::request_body::<#transform>(body) {
+                Ok(body) => body,
+                Err(e) => {
+                    fail(e);
+                    return;
+                }
+            };
+        },
+        None => quote! {},
+    };
+
+    // The parameters struct is built once per render, in the deps, and sent from there
+    let request_body = if has_params && method != "GET" {
+        quote! {
+            #fill_path
+            #validate_params
+            let body = serde_json::to_string(params).unwrap();
+            #encode_body
+            let request = gloo_net::http::Request::#method_fn(
+                &format!("{}{}", #base_url, #request_path)
+            )
+            .header("Content-Type", "application/json")
+            #fetch_options
+            #idempotency_header
+            #nonce_headers;
+            #apply_request_options
+            let request = match request.body(body) {
+                Ok(req) => req,
+                Err(e) => {
+                    fail(format!("Failed to create request: {}", e));
+                    return;
+                }
+            };
+        }
+    } else if has_params && method == "GET" {
+        // Build query string for GET requests
+        quote! {
+            #fill_path
+            #validate_params
+            let query_string = match ::yew_extra::query::to_query_string(params) {
+                Ok(qs) => qs,
+                Err(e) => {
+                    fail(format!("Failed to serialize query parameters: {}", e));
+                    return;
+                }
+            };
+            let request_url = format!("{}{}?{}", #base_url, #request_path, query_string);
+            let request = gloo_net::http::Request::#method_fn(&request_url)
+            .header("Content-Type", "application/json")
+            #fetch_options;
+            #apply_request_options
+        }
+    } else {
+        quote! {
+            #fill_path
+            let request_url = format!("{}{}", #base_url, #request_path);
+            let request = gloo_net::http::Request::#method_fn(&request_url)
+            .header("Content-Type", "application/json")
+            #fetch_options
+            #idempotency_header
+            #nonce_headers;
+            #apply_request_options
+        }
+    };
+
+    // Refetch when the parameters or the request options change, or when the component
+    // calls `HookHandle::refetch`. The parameters are moved into a shared tuple, so each render
+    // compares them without copying and the effect only clones the `Rc`. The ones sent in the
+    // body or query are moved into their struct right away, so the request serializes them
+    // from there instead of copying them per fetch. With a key function, only the key and the
+    // options are compared.
+    let (dep_values, dep_names) = if has_params {
+        (
+            quote! { (#params_ident { #(#field_names),* }, #(#path_params,)* options,) },
+            quote! { (params, #(#path_params,)* options,) },
+        )
+    } else {
+        (
+            quote! { (#(#arg_names,)* options,) },
+            quote! { (#(#arg_names,)* options,) },
+        )
+    };
+    let deps = match key {
+        Some(key) => quote! {
+            ::yew_extra::hooks::KeyedDeps::new(
+                (#key(#(&#arg_names),*), options.clone()),
+                #dep_values,
+            )
+        },
+        None => quote! { ::std::rc::Rc::new(#dep_values) },
+    };
+    let bind_deps = quote! { let #dep_names = &*deps; };
+
+    // With `shared_data`, renders clone the `Rc` instead of the data
+    let data_type = if shared_data {
+        quote! { ::std::rc::Rc<#return_type> }
+    } else {
+        quote! { #return_type }
+    };
+    let wrap_data = if shared_data {
+        quote! { let fetched_data = ::std::rc::Rc::new(fetched_data); }
+    } else {
+        quote! {}
+    };
+
+    // Check if return type looks like a Vec
+    let is_vec = quote!(#return_type).to_string().contains("Vec");
+
+    let data_handling = if is_vec {
+        quote! {
+            if fetched_data.is_empty() {
+                state.set(DataState::Empty);
+            } else {
+                state.set(DataState::Data(fetched_data));
+            }
+        }
+    } else {
+        quote! {
+            state.set(DataState::Data(fetched_data));
+        }
+    };
+
+    // Placeholder data is shown from the first render, with `is_loading` still set until the
+    // real response arrives. The server renders the same initial state, so hydration matches.
+    let initial_state = match placeholder_data {
+        Some(placeholder) => quote! {
+            let state = ::yew_extra::hooks::use_server_value(|| match #placeholder(#(&#arg_names),*) {
+                Some(fetched_data) => {
+                    #wrap_data
+                    DataState::Data(fetched_data)
+                }
+                None => DataState::<#data_type>::Loading,
+            });
+
+            let is_loading = ::yew_extra::hooks::use_server_value(|| !matches!(*state, DataState::Loading));
+        },
+        None => quote! {
+            let state = ::yew_extra::hooks::use_server_value(|| DataState::<#data_type>::Loading);
+
+            let is_loading = ::yew_extra::hooks::use_server_value(|| false);
+        },
+    };
+
+    // Reads share their responses through yew_extra's cache: a cached response is shown
+    // right away and only refetched once it is stale. The hook also follows changes made
+    // to the cached response, e.g. by optimistic updates.
+    let (read_cache, write_cache) = if method == "GET" {
+        (
+            quote! {
+                let cache_key = ::yew_extra::cache::request_key(&request_url, options);
+                *subscription.borrow_mut() = vec![
+                    cache_scope.subscribe(cache_key.clone(), {
+                        let state = state.clone();
+                        let cache_scope = cache_scope.clone();
+                        let cache_key = cache_key.clone();
+                        move || {
+                            if let Some(cached) = cache_scope.get::<#data_type>(&cache_key) {
+                                let fetched_data = cached.value;
+                                #data_handling
+                            }
+                        }
+                    }),
+                    // Invalidating the response, e.g. after a mutation, refetches it
+                    cache_scope.subscribe_invalidation(cache_key.clone(), {
+                        let refetch = refetch.callback();
+                        move || refetch.emit(())
+                    }),
+                ];
+                if let Some(cached) = cache_scope.get::<#data_type>(&cache_key) {
+                    let fetched_data = cached.value;
+                    #data_handling
+                    is_loading.set(false);
+                    // A refetch asked for by the component skips the cached response
+                    if !cached.is_stale && !refetching {
+                        is_updating.set(false);
+                        return;
+                    }
+                }
+            },
+            quote! {
+                cache_scope.insert(cache_key, fetched_data.clone());
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    // Debug builds compare the initial state with the one the server rendered
+    let hook_label = hook_name.to_string();
+    let fingerprint_data = if shared_data {
+        quote! { &**data }
+    } else {
+        quote! { data }
+    };
+    let hydration_check = quote! {
+        ::yew_extra::hydration::use_hydration_check(#hook_label, || {
+            #[allow(unreachable_patterns)]
+            match &*state {
+                DataState::Loading => "loading".to_string(),
+                DataState::Empty => "empty".to_string(),
+                DataState::Data(data) => {
+                    format!("data {}", ::yew_extra::hydration::fingerprint(#fingerprint_data))
+                }
+                DataState::Error(error) => format!("error {}", error),
+                _ => "other".to_string(),
+            }
+        });
+    };
+
+    // GET hooks use the cache of the closest `QueryScope`, the global one otherwise. The
+    // subscriptions to the cached response and its invalidation end with the effect.
+    let (declare_subscription, clone_subscription, keep_unsubscribe, unsubscribe, ssr_unused) =
         if method == "GET" {
-            quote! { axum::extract::Query(params): axum::extract::Query<#struct_name>, }
+            (
+                quote! {
+                    let cache_scope = yew::use_context::<::yew_extra::cache::CacheScope>()
+                        .unwrap_or_else(::yew_extra::cache::CacheScope::global);
+                    let subscription =
+                        yew::use_mut_ref(Vec::<::yew_extra::cache::CacheSubscription>::new);
+                },
+                quote! {
+                    let cache_scope = cache_scope.clone();
+                    let subscription = subscription.clone();
+                    let refetch = refetch.clone();
+                },
+                quote! { let unsubscribe = subscription.clone(); },
+                quote! { unsubscribe.borrow_mut().clear(); },
+                quote! { &cache_scope, &subscription, &refetch, },
+            )
         } else {
-            quote! { axum::Json(params): axum::Json<#struct_name>, }
+            (quote! {}, quote! {}, quote! {}, quote! {}, quote! {})
+        };
+    // Polling refetches through the same callback, while the page is visible
+    let poll = match poll_interval {
+        Some(interval) => {
+            let interval = u32::try_from(interval).unwrap_or(u32::MAX);
+            quote! { ::yew_extra::poll::use_poll(#interval, refetch.callback()); }
         }
+        None => quote! {},
+    };
+
+    // Whether this run was asked for with `HookHandle::refetch`, read before the fetch starts
+    let (take_refetch, mock_unused) = if method == "GET" {
+        (
+            quote! { let refetching = refetch.take_pending(); },
+            quote! { let _ = refetching; },
+        )
     } else {
-        quote! {}
+        (quote! {}, quote! {})
+    };
+
+    // The callbacks of the latest render are read when the fetch settles
+    let notify_success = quote! {
+        let settled = callbacks.borrow().clone();
+        settled.success();
+    };
+
+    let warn_deprecated = generate_deprecation_warning(method, path, deprecation);
+    let fetch_request = if method == "GET" {
+        // Hooks asking for the same response at once share one request
+        quote! {
+            #warn_deprecated
+            #request_body
+            #read_cache
+
+            let fetched = cache_scope.dedupe(&cache_key, async {
+                match #send {
+                    Ok(response) => {
+                        #follow_redirect
+
+                        // Check if the response status is successful (2xx)
+                        if response.ok() {
+                            #parse_json
+                        } else {
+                            let error_msg = #error_message;
+                            #report_status_failure
+                            Err(error_msg)
+                        }
+                    }
+                    Err(e) => {
+                        let error_msg = ::yew_extra::network::fetch_error(e);
+                        #report_fetch_failure
+                        Err(error_msg)
+                    }
+                }
+            })
+            .await;
+            match fetched {
+                Ok(fetched_data) => {
+                    #wrap_data
+                    #write_cache
+                    #data_handling
+                    #notify_success
+                }
+                Err(e) => {
+                    // Without a connection there was no response to fail on, so the
+                    // request itself couldn't be sent
+                    is_offline.set(!::yew_extra::network::is_online());
+                    fail(e);
+                }
+            }
+        }
+    } else {
+        quote! {
+            #warn_deprecated
+            #request_body
+
+            match #send {
+                Ok(response) => {
+                    #follow_redirect
+
+                    // Check if the response status is successful (2xx)
+                    if response.ok() {
+                        match #parse_json {
+                            Ok(fetched_data) => {
+                                #wrap_data
+                                #data_handling
+                                #notify_success
+                            }
+                            Err(e) => {
+                                fail(e);
+                            }
+                        }
+                    } else {
+                        let error_msg = #error_message;
+                        #report_status_failure
+                        fail(error_msg);
+                    }
+                }
+                Err(e) => {
+                    is_offline.set(!::yew_extra::network::is_online());
+                    let error_msg = ::yew_extra::network::fetch_error(e);
+                    #report_fetch_failure
+                    fail(error_msg);
+                }
+            }
+        }
+    };
+
+    // With a fixture, `mock` builds call it instead of sending the request
+    let hook_request = match mock {
+        Some(mock) => {
+            let call_fixture = generate_mock_call(mock, &arg_names);
+            // Fixtures take the parameters by value, like the server function
+            let take_params = if has_params {
+                quote! {
+                    let #params_ident { #(#field_names),* } = ::std::clone::Clone::clone(params);
+                    #(let #path_params = ::std::clone::Clone::clone(#path_params);)*
+                }
+            } else {
+                quote! { #(let #arg_names = #arg_names.clone();)* }
+            };
+            quote! {
+                #[cfg(not(feature = "mock"))]
+                {
+                    #fetch_request
+                }
+
+                #[cfg(feature = "mock")]
+                {
+                    let _ = &options;
+                    #mock_unused
+                    #take_params
+                    match #call_fixture {
+                        Ok(fetched_data) => {
+                            #wrap_data
+                            #data_handling
+                            #notify_success
+                        }
+                        Err(e) => {
+                            fail(e);
+                        }
+                    }
+                }
+            }
+        }
+        None => fetch_request,
     };
 
-    let param_extraction = if has_params {
-        let mut field_names = Vec::new();
-        for input in inputs {
-            if let FnArg::Typed(pat_type) = input {
-                if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                    field_names.push(&pat_ident.ident);
+    quote! {
+
+        #[yew::hook]
+        #hook_attrs
+        #mount_deprecation
+        #vis fn #hook_name(#hook_params) -> ApiHook<#data_type> {
+            #hook_with_options_name(#(#arg_names,)* ::yew_extra::RequestOptions::default())
+        }
+
+        #[yew::hook]
+        #hook_attrs
+        #mount_deprecation
+        #vis fn #hook_with_options_name(#hook_params_with_options) -> ApiHook<#data_type> {
+            #hook_with_handle_name(#(#arg_names,)* options).0
+        }
+
+        // One body for the server and the browser, so both render the same initial state;
+        // only the browser runs the effect fetching the data. The handle is returned next to
+        // `ApiHook`, so the user-defined struct doesn't need fields for it.
+        #[yew::hook]
+        #hook_attrs
+        #mount_deprecation
+        #vis fn #hook_with_handle_name(
+            #hook_params_with_options
+        ) -> (ApiHook<#data_type>, ::yew_extra::hooks::HookHandle) {
+            #initial_state
+            let is_updating = ::yew_extra::hooks::use_server_value(|| false);
+            let is_offline = ::yew_extra::hooks::use_server_value(|| false);
+            #hydration_check
+
+            let callbacks = yew::use_mut_ref(::yew_extra::SettleCallbacks::default);
+            *callbacks.borrow_mut() = options.callbacks.clone();
+            let refetch = ::yew_extra::hooks::use_refetch();
+            #poll
+            #declare_subscription
+
+            {
+                let state = state.clone();
+                let is_loading = is_loading.clone();
+                let is_updating = is_updating.clone();
+                let is_offline = is_offline.clone();
+                let callbacks = callbacks.clone();
+                #clone_subscription
+
+                ::yew_extra::hooks::use_isomorphic_effect((#deps, refetch.requests()), move |deps| {
+                    #keep_unsubscribe
+
+                    #[cfg(not(feature = "ssr"))]
+                    {
+                        let deps = deps.0.clone();
+                        #take_refetch
+
+                        // Check if this is the first load
+                        let is_first_load = matches!(*state, DataState::Loading);
+
+                        // Set appropriate loading flag
+                        if is_first_load {
+                            is_loading.set(true);
+                            is_updating.set(true);
+                        } else {
+                            is_updating.set(true);
+                        }
+                        is_offline.set(false);
+
+                        wasm_bindgen_futures::spawn_local(async move {
+                            #bind_deps
+                            let fail = |error: String| {
+                                let settled = callbacks.borrow().clone();
+                                settled.error(&error);
+                                state.set(DataState::Error(error));
+                            };
+                            #hook_request
+
+                            // Clear loading flags after request completes
+                            is_loading.set(false);
+                            is_updating.set(false);
+                        });
+                    }
+
+                    // The effect never runs on the server
+                    #[cfg(feature = "ssr")]
+                    let _ = (deps, &state, &is_loading, &is_updating, &is_offline, &callbacks, #ssr_unused);
+
+                    move || {
+                        #unsubscribe
+                    }
+                });
+            }
+
+            let hook = ApiHook {
+                state: (*state).clone(),
+                is_loading: *is_loading,
+                is_updating: *is_updating,
+            };
+            let handle = ::yew_extra::hooks::HookHandle {
+                refetch: refetch.callback(),
+                is_offline: *is_offline,
+            };
+            (hook, handle)
+        }
+    }
+}
+
+fn is_request_context_type(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "RequestContext";
+        }
+    }
+    false
+}
+
+/// Takes the `RequestContext` parameter, if any, out of the parameters sent by the client
+fn split_context_param(
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+) -> syn::Result<(
+    Option<syn::PatType>,
+    syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+)> {
+    let mut context_param: Option<syn::PatType> = None;
+    let mut rest = syn::punctuated::Punctuated::new();
+    for input in inputs {
+        match input {
+            FnArg::Typed(pat_type) if is_request_context_type(&pat_type.ty) => {
+                if context_param.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &pat_type.ty,
+                        "Server functions take at most one `RequestContext` parameter",
+                    ));
+                }
+                context_param = Some(pat_type.clone());
+            }
+            other => rest.push(other.clone()),
+        }
+    }
+    Ok((context_param, rest))
+}
+
+fn is_uploaded_file_type(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "UploadedFile";
+        }
+    }
+    false
+}
+
+/// Generates an endpoint that receives a file through yew_extra's chunked upload protocol.
+///
+/// The function takes a `yew_extra::UploadedFile` parameter. The other parameters (plus the
+/// `upload_id`) are sent as JSON in the completion request once all chunks have arrived.
+fn generate_upload_endpoint(
+    input: &ItemFn,
+    params_ident: &syn::Ident,
+    path: &str,
+    return_type: &proc_macro2::TokenStream,
+    schema_check: bool,
+    fast_json: bool,
+    route_metadata: &proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let fn_name = &input.sig.ident;
+    let vis = &input.vis;
+    let host_url = quote! { ::yew_extra::base_path::api_base(::std::option_env!("YEW_API_BASE")) };
+
+    // Split the file parameter from the ones sent in the completion request
+    let mut file_ident = None;
+    let mut param_inputs: syn::punctuated::Punctuated<FnArg, syn::token::Comma> =
+        syn::punctuated::Punctuated::new();
+    param_inputs.push(syn::parse_quote! { upload_id: String });
+
+    let mut call_args = Vec::new();
+    let mut client_params = Vec::new();
+
+    for arg in &input.sig.inputs {
+        if let FnArg::Typed(pat_type) = arg {
+            let Pat::Ident(pat_ident) = &*pat_type.pat else {
+                return Err(syn::Error::new_spanned(
+                    &pat_type.pat,
+                    "Upload endpoints only support simple `name: Type` parameters",
+                ));
+            };
+            let ident = &pat_ident.ident;
+            // The completion request carries the session in a field of this name
+            if ident == "upload_id" {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "`upload_id` is used by the upload protocol, rename this parameter",
+                ));
+            }
+
+            if is_uploaded_file_type(&pat_type.ty) {
+                if file_ident.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &pat_type.ty,
+                        "Upload endpoints take exactly one `UploadedFile` parameter",
+                    ));
                 }
+                file_ident = Some(ident.clone());
+                client_params.push(quote! { #ident: ::yew_extra::upload::File });
+            } else {
+                param_inputs.push(arg.clone());
+                let ty = &pat_type.ty;
+                client_params.push(quote! { #ident: #ty });
             }
+            call_args.push(ident.clone());
         }
-        let struct_name = syn::Ident::new(
-            &format!("{}Params", to_pascal_case(&fn_name.to_string())),
+    }
+
+    let file_ident = file_ident.ok_or_else(|| {
+        syn::Error::new(
             fn_name.span(),
-        );
-        quote! {
-            let #struct_name { #(#field_names),* } = params;
-        }
-    } else {
-        quote! {}
-    };
+            "Upload endpoints need a parameter of type `yew_extra::UploadedFile`",
+        )
+    })?;
 
-    // Create a modified function body that extracts parameters and wraps return in Json
-    let original_stmts = &block.stmts;
-    let modified_block = quote! {
-        {
-            #param_extraction
+    let field_names: Vec<&syn::Ident> = param_inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(pat_type) => match &*pat_type.pat {
+                Pat::Ident(pat_ident) if pat_ident.ident != "upload_id" => Some(&pat_ident.ident),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
 
-            // Original function body
-            let result: Result<#return_type, #error_type> = async {
-                #(#original_stmts)*
-            }.await;
+    let struct_name = params_ident;
 
-            // Wrap successful result in Json
-            result.map(axum::Json)
-        }
+    let validations = parse_validations(&param_inputs)?;
+    let has_validation = !validations.is_empty();
+    let forwarded_attrs = ForwardedAttrs::new(&input.attrs);
+    let param_struct = generate_param_struct(
+        fn_name,
+        params_ident,
+        &param_inputs,
+        false,
+        false,
+        false,
+        &forwarded_attrs,
+    );
+    let client_attrs = forwarded_attrs.client();
+    let validate_impl = if has_validation {
+        generate_validate_impl(params_ident, &validations)
+    } else {
+        quote! {}
     };
+    let emitted_fn = strip_validate_attrs(input);
 
-    // Generate a wrapper function that converts Request<Body> to the handler's expected format
-    let wrapper_fn_name = syn::Ident::new(
-        &format!("{}_wrapper", fn_handler_name),
-        fn_handler_name.span(),
-    );
+    // Server side: the generic protocol routes plus the completion route
+    let create_path = format!("{}/create", path);
+    let offset_path = format!("{}/offset", path);
+    let append_path = format!("{}/append", path);
 
-    // Generate the extraction logic based on method and whether there are params
-    let extract_and_call = if has_params {
-        let struct_name = syn::Ident::new(
-            &format!("{}Params", to_pascal_case(&fn_name.to_string())),
+    let wrapper_name = |suffix: &str| {
+        syn::Ident::new(
+            &format!("{}_upload_{}_wrapper", fn_name, suffix),
             fn_name.span(),
-        );
+        )
+    };
+    let create_wrapper = wrapper_name("create");
+    let offset_wrapper = wrapper_name("offset");
+    let append_wrapper = wrapper_name("append");
+    let complete_wrapper = wrapper_name("complete");
 
-        if method == "GET" {
-            // Extract query parameters for GET
-            quote! {
-                use ::axum::extract::FromRequestParts;
+    let validate_params = generate_validation_rejection(
+        has_validation,
+        &quote! { ::yew_extra::clear_request_parts().await; },
+    );
 
-                let (mut parts, _body) = req.into_parts();
+    let server = quote! {
+        #[cfg(all(feature = "ssr", not(test)))]
+        fn #create_wrapper(
+            req: ::axum::http::Request<::axum::body::Body>
+        ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ::axum::http::Response<::axum::body::Body>> + Send>> {
+            Box::pin(::yew_extra::upload::handle_create(req))
+        }
 
-                // Provide parts to yew_extra context before calling the handler
-                ::yew_extra::provide_request_parts(parts.clone()).await;
+        #[cfg(all(feature = "ssr", not(test)))]
+        fn #offset_wrapper(
+            req: ::axum::http::Request<::axum::body::Body>
+        ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ::axum::http::Response<::axum::body::Body>> + Send>> {
+            Box::pin(::yew_extra::upload::handle_offset(req))
+        }
 
-                let result = match ::axum::extract::Query::<#struct_name>::from_request_parts(&mut parts, &()).await {
-                    Ok(::axum::extract::Query(params)) => {
-                        let response = #fn_handler_name(::axum::extract::Query(params)).await;
-                        response.into_response()
-                    },
-                    Err(e) => {
-                        ::axum::http::Response::builder()
-                            .status(::axum::http::StatusCode::BAD_REQUEST)
-                            .body(::axum::body::Body::from(format!("Invalid query parameters: {}", e)))
-                            .unwrap()
-                    }
-                };
+        #[cfg(all(feature = "ssr", not(test)))]
+        fn #append_wrapper(
+            req: ::axum::http::Request<::axum::body::Body>
+        ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ::axum::http::Response<::axum::body::Body>> + Send>> {
+            Box::pin(::yew_extra::upload::handle_append(req))
+        }
 
-                // Clear parts after handler completes
-                ::yew_extra::clear_request_parts().await;
-                result
-            }
-        } else {
-            // Extract JSON body for POST/PUT/DELETE/PATCH
-            quote! {
+        #[cfg(all(feature = "ssr", not(test)))]
+        fn #complete_wrapper(
+            req: ::axum::http::Request<::axum::body::Body>
+        ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ::axum::http::Response<::axum::body::Body>> + Send>> {
+            Box::pin(::yew_extra::request_scope(async move {
                 use ::axum::extract::FromRequest;
+                use ::axum::response::IntoResponse;
 
+                let mut req = req;
+                let audit = ::yew_extra::audit::begin(#path, &mut req);
                 let (parts, body) = req.into_parts();
 
-                // Provide parts to yew_extra context before calling the handler
+                // Provide parts to yew_extra context before calling the function
                 ::yew_extra::provide_request_parts(parts.clone()).await;
 
                 let req = ::axum::http::Request::from_parts(parts, body);
 
                 let result = match ::axum::Json::<#struct_name>::from_request(req, &()).await {
-                    Ok(params) => {
-                        let response = #fn_handler_name(params).await;
-                        response.into_response()
+                    Ok(::axum::Json(params)) => {
+                        ::yew_extra::audit::record_params(&params).await;
+                        #validate_params
+                        let #struct_name { upload_id, #(#field_names),* } = params;
+
+                        match ::yew_extra::upload::take_upload(&upload_id).await {
+                            Some(#file_ident) => {
+                                #fn_name(#(#call_args),*).await.map(::axum::Json).into_response()
+                            }
+                            None => {
+                                ::axum::http::Response::builder()
+                                    .status(::axum::http::StatusCode::NOT_FOUND)
+                                    .body(::axum::body::Body::from("Upload not found or incomplete"))
+                                    .unwrap()
+                            }
+                        }
                     },
                     Err(e) => {
                         ::axum::http::Response::builder()
@@ -355,445 +5373,1004 @@ fn generate_server_handler(
                     }
                 };
 
-                // Clear parts after handler completes
+                // Clear parts after the function completes
                 ::yew_extra::clear_request_parts().await;
-                result
-            }
+                ::yew_extra::audit::finish(audit, result).await
+            }))
         }
-    } else {
-        quote! {
-            // No parameters, but still provide Parts for extraction
-            let (parts, _body) = req.into_parts();
-
-            // Provide parts to yew_extra context before calling the handler
-            ::yew_extra::provide_request_parts(parts).await;
 
-            let response = #fn_handler_name().await;
-
-            // Clear parts after handler completes
-            ::yew_extra::clear_request_parts().await;
-
-            response.into_response()
+        #[cfg(all(feature = "ssr", not(test)))]
+        ::inventory::submit! {
+            crate::route_registry::RouteInfo::new(
+                #create_path,
+                ::axum::http::Method::POST,
+                #create_wrapper
+            ) #route_metadata
         }
-    };
 
-    // Convert method string to TokenStream identifier
-    let method_ident = syn::Ident::new(&method, proc_macro2::Span::call_site());
+        #[cfg(all(feature = "ssr", not(test)))]
+        ::inventory::submit! {
+            crate::route_registry::RouteInfo::new(
+                #offset_path,
+                ::axum::http::Method::GET,
+                #offset_wrapper
+            ) #route_metadata
+        }
 
-    // Generate inventory submission for automatic registration
-    // This creates a wrapper that can work with raw Request<Body>
-    // The inventory submission is only for non-test builds
-    let inventory_submission = quote! {
-        // Only generate the wrapper and inventory submission in non-test builds
         #[cfg(all(feature = "ssr", not(test)))]
-        fn #wrapper_fn_name(
-            req: ::axum::http::Request<::axum::body::Body>
-        ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ::axum::http::Response<::axum::body::Body>> + Send>> {
-            Box::pin(async move {
-                use ::axum::response::IntoResponse;
-                #extract_and_call
-            })
+        ::inventory::submit! {
+            crate::route_registry::RouteInfo::new(
+                #append_path,
+                ::axum::http::Method::PATCH,
+                #append_wrapper
+            ) #route_metadata
         }
 
         #[cfg(all(feature = "ssr", not(test)))]
         ::inventory::submit! {
             crate::route_registry::RouteInfo::new(
                 #path,
-                ::axum::http::Method::#method_ident,
-                #wrapper_fn_name
-            )
+                ::axum::http::Method::POST,
+                #complete_wrapper
+            ) #route_metadata
         }
     };
 
-    quote! {
-        #[cfg(feature = "ssr")]
-        #vis async fn #fn_handler_name(
-            #params_arg
-            // axum::extract::State(state): axum::extract::State<AppState>
-        ) -> Result<axum::Json<#return_type>, #error_type> #modified_block
-
-        #inventory_submission
-    }
-}
-
-fn generate_client_function(
-    fn_name: &syn::Ident,
-    vis: &syn::Visibility,
-    path: &str,
-    return_type: &proc_macro2::TokenStream,
-    has_params: bool,
-    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
-    method: &str,
-) -> proc_macro2::TokenStream {
-    // let host_url = quote! { "http://localhost:4000" };
-    let host_url = quote! { "" };
-
-    // Generate function parameters
-    let func_params = if has_params {
-        let mut params = Vec::new();
-        for input in inputs {
-            if let FnArg::Typed(pat_type) = input {
-                if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                    let param_name = &pat_ident.ident;
-                    let param_type = &pat_type.ty;
-                    params.push(quote! { #param_name: #param_type });
-                }
-            }
+    // Client side: upload the chunks, then complete with the remaining parameters
+    let validate_client = if has_validation {
+        quote! {
+            params.validate().map_err(|e| e.to_string())?;
         }
-        quote! { #(#params),* }
     } else {
         quote! {}
     };
+    let send_request = generate_client_response_handling(
+        return_type,
+        "POST",
+        path,
+        false,
+        None,
+        false,
+        schema_check,
+        fast_json,
+        None,
+    );
 
-    // Convert method to lowercase for gloo_net
-    let method_lower = method.to_lowercase();
-    let method_fn = syn::Ident::new(&method_lower, proc_macro2::Span::call_site());
-
-    // Generate request body creation
-    let request_body = if has_params && method != "GET" {
-        let struct_name = syn::Ident::new(
-            &format!("{}Params", to_pascal_case(&fn_name.to_string())),
-            fn_name.span(),
-        );
-        let mut field_names = Vec::new();
-        for input in inputs {
-            if let FnArg::Typed(pat_type) = input {
-                if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                    field_names.push(&pat_ident.ident);
-                }
-            }
-        }
-        quote! {
-            let params = #struct_name {
+    let client = quote! {
+        #[cfg(not(feature = "ssr"))]
+        #client_attrs
+        #vis async fn #fn_name(#(#client_params),*) -> Result<#return_type, String> {
+            let mut params = #struct_name {
+                upload_id: String::new(),
                 #(#field_names),*
             };
+
+            // Validate before spending time on the upload
+            #validate_client
+
+            params.upload_id = ::yew_extra::upload::upload_file(
+                &format!("{}{}", #host_url, #path),
+                &#file_ident,
+            )
+            .await?;
+
             let body = serde_json::to_string(&params)
                 .map_err(|e| format!("Failed to serialize parameters: {}", e))?;
 
-            let request = gloo_net::http::Request::#method_fn(&format!("{}{}", #host_url, #path))
+            let request = gloo_net::http::Request::post(&format!("{}{}", #host_url, #path))
                 .header("Content-Type", "application/json")
                 .body(body)
                 .map_err(|e| format!("Failed to create request: {}", e))?;
+
+            #send_request
         }
-    } else if has_params && method == "GET" {
-        // Build query string for GET requests
-        let struct_name = syn::Ident::new(
-            &format!("{}Params", to_pascal_case(&fn_name.to_string())),
-            fn_name.span(),
-        );
-        let mut field_names = Vec::new();
-        for input in inputs {
-            if let FnArg::Typed(pat_type) = input {
-                if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                    field_names.push(&pat_ident.ident);
-                }
-            }
-        }
-        quote! {
-            let params = #struct_name {
-                #(#field_names),*
-            };
+    };
 
-            // Serialize to query string
-            let query_string = serde_urlencoded::to_string(&params)
-                .map_err(|e| format!("Failed to serialize query parameters: {}", e))?;
+    Ok(quote! {
+        #[cfg(feature = "ssr")]
+        #emitted_fn
 
-            let url = format!("{}{}?{}", #host_url, #path, query_string);
+        #param_struct
 
-            let request = gloo_net::http::Request::#method_fn(&url)
-                .header("Content-Type", "application/json");
-        }
-    } else {
-        quote! {
-            let request = gloo_net::http::Request::#method_fn(&format!("{}{}", #host_url, #path))
-                .header("Content-Type", "application/json");
-        }
-    };
+        #validate_impl
 
-    // Generate the function name for the direct call version
-    let async_fn_name = syn::Ident::new(&format!("{}", fn_name.to_string()), fn_name.span());
+        #server
 
-    quote! {
-        #[cfg(not(feature = "ssr"))]
-        #vis async fn #async_fn_name(#func_params) -> Result<#return_type, String> {
-            #request_body
+        #client
+    })
+}
 
-            let response = request
-                .send()
-                .await
-                .map_err(|e| format!("Failed to fetch data: {}", e))?;
-
-            // Check if the response status is successful (2xx)
-            if response.ok() {
-                response
-                    .json::<#return_type>()
-                    .await
-                    .map_err(|e| format!("Failed to parse response: {}", e))
-            } else {
-                // Handle error response - try to get the error message from the response
-                let status = response.status();
-                let error_msg = match response.text().await {
-                    Ok(text) => {
-                        // Try to parse as JSON error message
-                        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&text) {
-                            if let Some(msg) = json.get("error").and_then(|v| v.as_str()) {
-                                msg.to_string()
-                            } else if let Some(msg) = json.get("message").and_then(|v| v.as_str()) {
-                                msg.to_string()
-                            } else {
-                                text
-                            }
-                        } else {
-                            text
-                        }
-                    }
-                    Err(_) => format!("Request failed with status {}", status)
-                };
-                Err(error_msg)
-            }
+/// The `T` of an `impl Stream<Item = T>` return type
+fn stream_item_type(ty: &proc_macro2::TokenStream) -> Option<proc_macro2::TokenStream> {
+    let Ok(Type::ImplTrait(impl_trait)) = syn::parse2::<Type>(ty.clone()) else {
+        return None;
+    };
+    impl_trait.bounds.iter().find_map(|bound| {
+        let syn::TypeParamBound::Trait(trait_bound) = bound else {
+            return None;
+        };
+        let segment = trait_bound.path.segments.last()?;
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        if segment.ident != "Stream" {
+            return None;
         }
-    }
+        args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::AssocType(assoc) if assoc.ident == "Item" => {
+                let item_type = &assoc.ty;
+                Some(quote! { #item_type })
+            }
+            _ => None,
+        })
+    })
 }
 
-fn generate_client_hook(
-    hook_name: &syn::Ident,
-    vis: &syn::Visibility,
+/// Generates an endpoint streaming the items of an `impl Stream<Item = T>` as
+/// newline-delimited JSON.
+///
+/// The browser gets a client function collecting the items into a `Vec<T>`, and a
+/// `use_*_stream` hook appending them to its `DataState::Data(Vec<T>)` as they arrive.
+#[allow(clippy::too_many_arguments)]
+fn generate_stream_endpoint(
+    input: &ItemFn,
+    params_ident: &syn::Ident,
     path: &str,
-    return_type: &proc_macro2::TokenStream,
-    has_params: bool,
-    fn_name: &syn::Ident,
-    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
     method: &str,
+    item_type: &proc_macro2::TokenStream,
+    body_inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    path_inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    param_bindings: &[proc_macro2::TokenStream],
+    context_param: Option<&syn::PatType>,
+    validations: &[FieldValidation],
+    route_metadata: &proc_macro2::TokenStream,
+    fetch_options: &proc_macro2::TokenStream,
+    host_url: &proc_macro2::TokenStream,
 ) -> proc_macro2::TokenStream {
-    // let host_url = quote! { "http://localhost:4000" };
-    let host_url = quote! { "" };
+    let fn_name = &input.sig.ident;
+    let vis = &input.vis;
+    let fn_output = &input.sig.output;
+    let original_stmts = &input.block.stmts;
+    let forwarded_attrs = ForwardedAttrs::new(&input.attrs);
+    let handler_attrs = forwarded_attrs.handler();
+    let client_attrs = forwarded_attrs.client();
+    let has_params = !body_inputs.is_empty();
+    let has_validation = !validations.is_empty();
+    let struct_name = params_ident;
 
-    let hook_params = if has_params {
-        let mut params = Vec::new();
-        for input in inputs {
-            if let FnArg::Typed(pat_type) = input {
-                if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                    let param_name = &pat_ident.ident;
-                    let param_type = &pat_type.ty;
-                    params.push(quote! { #param_name: #param_type });
-                }
-            }
-        }
-        quote! { #(#params),* }
+    let param_struct = if has_params {
+        generate_param_struct(
+            fn_name,
+            params_ident,
+            body_inputs,
+            method == "GET",
+            false,
+            false,
+            &forwarded_attrs,
+        )
+    } else {
+        quote! {}
+    };
+    let validate_impl = if has_validation {
+        generate_validate_impl(params_ident, validations)
     } else {
         quote! {}
     };
+    let emitted_fn = strip_validate_attrs(input);
+
+    // The parameters by name and type, path parameters first
+    let typed = |inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>| {
+        inputs
+            .iter()
+            .filter_map(|input| match input {
+                FnArg::Typed(pat_type) => match &*pat_type.pat {
+                    Pat::Ident(pat_ident) => {
+                        Some((pat_ident.ident.clone(), (*pat_type.ty).clone()))
+                    }
+                    _ => None,
+                },
+                FnArg::Receiver(_) => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    let path_params = typed(path_inputs);
+    let body_params = typed(body_inputs);
+    let path_names: Vec<&syn::Ident> = path_params.iter().map(|(name, _)| name).collect();
+    let field_names: Vec<&syn::Ident> = body_params.iter().map(|(name, _)| name).collect();
+    let arg_names: Vec<&syn::Ident> = path_names.iter().chain(&field_names).copied().collect();
+    let args: Vec<proc_macro2::TokenStream> = path_params
+        .iter()
+        .chain(&body_params)
+        .map(|(name, ty)| quote! { #name: #ty })
+        .collect();
 
-    // Convert method to lowercase for gloo_net
-    let method_lower = method.to_lowercase();
-    let method_fn = syn::Ident::new(&method_lower, proc_macro2::Span::call_site());
+    // Server side: the body runs in a handler returning the stream, which the response
+    // writes out after the request is done
+    let fn_handler_name = syn::Ident::new(&format!("{}_handler", fn_name), fn_name.span());
+    let wrapper_fn_name = syn::Ident::new(
+        &format!("{}_wrapper", fn_handler_name),
+        fn_handler_name.span(),
+    );
+    let method_ident = syn::Ident::new(method, proc_macro2::Span::call_site());
 
-    let request_body = if has_params && method != "GET" {
-        let struct_name = syn::Ident::new(
-            &format!("{}Params", to_pascal_case(&fn_name.to_string())),
+    let (path_struct_def, extract_path) = if path_params.is_empty() {
+        (quote! {}, quote! {})
+    } else {
+        let path_struct = syn::Ident::new(
+            &format!("{}PathParams", to_pascal_case(&fn_name.to_string())),
             fn_name.span(),
         );
-        let mut field_names = Vec::new();
-        for input in inputs {
-            if let FnArg::Typed(pat_type) = input {
-                if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                    field_names.push(&pat_ident.ident);
+        let path_fields = path_params
+            .iter()
+            .map(|(name, ty)| quote! { pub #name: #ty });
+        (
+            quote! {
+                #[cfg(feature = "ssr")]
+                #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+                #vis struct #path_struct {
+                    #(#path_fields),*
                 }
-            }
-        }
-        quote! {
-            let params = #struct_name {
-                #(#field_names: #field_names.clone()),*
-            };
-            let body = serde_json::to_string(&params).unwrap();
-            let request = match gloo_net::http::Request::#method_fn(
-                &format!("{}{}", #host_url, #path)
+            },
+            quote! {
+                let mut parts = parts;
+                let #path_struct { #(#path_names),* } =
+                    match ::yew_extra::path_params::extract_path::<#path_struct>(&mut parts, #path).await {
+                        Ok(path_params) => path_params,
+                        Err(response) => return response,
+                    };
+            },
+        )
+    };
+    let (context_arg, capture_context, context_value) = match context_param {
+        Some(context_param) => {
+            let pat = &context_param.pat;
+            let ty = &context_param.ty;
+            (
+                quote! { #pat: #ty, },
+                quote! { let request_context = ::yew_extra::RequestContext::new(parts.clone()); },
+                quote! { request_context, },
             )
-            .header("Content-Type", "application/json")
-            .body(body) {
-                Ok(req) => req,
-                Err(e) => {
-                    state.set(DataState::Error(format!("Failed to create request: {}", e)));
-                    return;
-                }
-            };
         }
-    } else if has_params && method == "GET" {
-        // Build query string for GET requests
-        let struct_name = syn::Ident::new(
-            &format!("{}Params", to_pascal_case(&fn_name.to_string())),
-            fn_name.span(),
-        );
-        let mut field_names = Vec::new();
-        for input in inputs {
-            if let FnArg::Typed(pat_type) = input {
-                if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                    field_names.push(&pat_ident.ident);
-                }
-            }
+        None => (quote! {}, quote! {}, quote! {}),
+    };
+
+    let validate_params = generate_validation_rejection(has_validation, &quote! {});
+    // Reads send their parameters in the query, the other methods in a JSON body
+    let read_params = if !has_params {
+        quote! {
+            let _ = body;
+            ::yew_extra::provide_request_parts(parts).await;
         }
+    } else if method == "GET" {
         quote! {
-            let params = #struct_name {
-                #(#field_names: #field_names.clone()),*
-            };
-            let query_string = match serde_urlencoded::to_string(&params) {
-                Ok(qs) => qs,
+            let _ = body;
+            let query = ::yew_extra::query::from_query_str::<#struct_name>(
+                parts.uri.query().unwrap_or_default(),
+            );
+            ::yew_extra::provide_request_parts(parts).await;
+            let params = match query {
+                Ok(params) => params,
                 Err(e) => {
-                    state.set(DataState::Error(format!("Failed to serialize query parameters: {}", e)));
-                    return;
+                    return ::axum::http::Response::builder()
+                        .status(::axum::http::StatusCode::BAD_REQUEST)
+                        .body(::axum::body::Body::from(format!("Invalid query parameters: {}", e)))
+                        .unwrap();
                 }
             };
-            let request = gloo_net::http::Request::#method_fn(
-                &format!("{}{}?{}", #host_url, #path, query_string)
-            )
-            .header("Content-Type", "application/json");
+            #validate_params
+            let #struct_name { #(#field_names),* } = params;
         }
     } else {
         quote! {
-            let request = gloo_net::http::Request::#method_fn(
-                &format!("{}{}", #host_url, #path)
-            )
-            .header("Content-Type", "application/json");
-        }
-    };
+            use ::axum::extract::FromRequest;
 
-    let deps = if has_params {
-        let mut dep_names = Vec::new();
-        for input in inputs {
-            if let FnArg::Typed(pat_type) = input {
-                if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                    dep_names.push(&pat_ident.ident);
+            ::yew_extra::provide_request_parts(parts.clone()).await;
+            let req = ::axum::http::Request::from_parts(parts, body);
+            let params = match ::axum::Json::<#struct_name>::from_request(req, &()).await {
+                Ok(::axum::Json(params)) => params,
+                Err(e) => {
+                    return ::axum::http::Response::builder()
+                        .status(::axum::http::StatusCode::BAD_REQUEST)
+                        .body(::axum::body::Body::from(format!("Invalid request: {}", e)))
+                        .unwrap();
                 }
-            }
+            };
+            ::yew_extra::audit::record_params(&params).await;
+            #validate_params
+            let #struct_name { #(#field_names),* } = params;
         }
-        quote! { (#(#dep_names.clone()),*) }
-    } else {
-        quote! { () }
     };
+    let handle_request = quote! {
+        let response = async move {
+            let (parts, body) = req.into_parts();
+            #extract_path
+            #capture_context
+            #read_params
 
-    // Check if return type looks like a Vec
-    let is_vec = quote!(#return_type).to_string().contains("Vec");
-
-    let data_handling = if is_vec {
+            let response = match #fn_handler_name(#context_value #(#arg_names),*).await {
+                Ok(items) => ::yew_extra::ndjson::NdJson::new(items).into_response(),
+                Err(e) => e.into_response(),
+            };
+            let response = ::yew_extra::apply_response_headers(response).await;
+            ::yew_extra::run_after_response(response).await
+        }
+        .await;
+        ::yew_extra::clear_request_parts().await;
+    };
+    // The audit sink gets a record of every call of a mutation
+    let handle_request = if method == "GET" {
         quote! {
-            if fetched_data.is_empty() {
-                state.set(DataState::Empty);
-            } else {
-                state.set(DataState::Data(fetched_data));
-            }
+            #handle_request
+            response
         }
     } else {
         quote! {
-            state.set(DataState::Data(fetched_data));
+            let mut req = req;
+            let audit = ::yew_extra::audit::begin(#path, &mut req);
+            #handle_request
+            ::yew_extra::audit::finish(audit, response).await
         }
     };
 
-    quote! {
+    let server = quote! {
+        #path_struct_def
 
         #[cfg(feature = "ssr")]
-        #[yew::hook]
-        #vis fn #hook_name(#hook_params) -> ApiHook<#return_type> {
-            let state = yew::use_state(|| DataState::<#return_type>::Loading);
+        #handler_attrs
+        #vis async fn #fn_handler_name(#context_arg #(#args),*) #fn_output {
+            #(#param_bindings)*
+            #(#original_stmts)*
+        }
 
-            let is_loading = yew::use_state(|| false);
-            let is_updating = yew::use_state(|| false);
+        #[cfg(all(feature = "ssr", not(test)))]
+        #vis fn #wrapper_fn_name(
+            req: ::axum::http::Request<::axum::body::Body>
+        ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = ::axum::http::Response<::axum::body::Body>> + Send>> {
+            Box::pin(::yew_extra::request_scope(async move {
+                use ::axum::response::IntoResponse;
+                #handle_request
+            }))
+        }
 
-            ApiHook {
-                state: (*state).clone(),
-                is_loading: (*is_loading).clone(),
-                is_updating: (*is_updating).clone(),
-            }
+        #[cfg(all(feature = "ssr", not(test)))]
+        ::inventory::submit! {
+            crate::route_registry::RouteInfo::new(
+                #path,
+                ::axum::http::Method::#method_ident,
+                #wrapper_fn_name
+            ) #route_metadata
+        }
+    };
+
+    // Client side: the function and the hook send the same request, failing with `?`
+    let method_fn = syn::Ident::new(&method.to_lowercase(), proc_macro2::Span::call_site());
+    let path_idents: Vec<syn::Ident> = path_names.iter().map(|name| (*name).clone()).collect();
+    let (fill_path, request_path) = generate_request_path(path, &path_idents);
+    let validate_client = if has_validation {
+        quote! {
+            params.validate().map_err(|e| e.to_string())?;
+        }
+    } else {
+        quote! {}
+    };
+    let build_request = if !has_params {
+        quote! {
+            #fill_path
+            let request = gloo_net::http::Request::#method_fn(&format!("{}{}", #host_url, #request_path))
+                .header("Accept", ::yew_extra::ndjson::CONTENT_TYPE)
+                #fetch_options;
+        }
+    } else if method == "GET" {
+        quote! {
+            #fill_path
+            let params = #struct_name {
+                #(#field_names: ::std::clone::Clone::clone(#field_names)),*
+            };
+            #validate_client
+            let query_string = ::yew_extra::query::to_query_string(&params)
+                .map_err(|e| format!("Failed to serialize query parameters: {}", e))?;
+            let request = gloo_net::http::Request::#method_fn(
+                &format!("{}{}?{}", #host_url, #request_path, query_string)
+            )
+            .header("Accept", ::yew_extra::ndjson::CONTENT_TYPE)
+            #fetch_options;
+        }
+    } else {
+        quote! {
+            #fill_path
+            let params = #struct_name {
+                #(#field_names: ::std::clone::Clone::clone(#field_names)),*
+            };
+            #validate_client
+            let body = serde_json::to_string(&params)
+                .map_err(|e| format!("Failed to serialize parameters: {}", e))?;
+            let request = gloo_net::http::Request::#method_fn(&format!("{}{}", #host_url, #request_path))
+                .header("Content-Type", "application/json")
+                .header("Accept", ::yew_extra::ndjson::CONTENT_TYPE)
+                #fetch_options
+                .body(body)
+                .map_err(|e| format!("Failed to create request: {}", e))?;
+        }
+    };
+    let send = generate_send(None);
+    let follow_redirect = generate_redirect_check();
+    let error_message = generate_error_message();
+    let report_fetch_failure = generate_failure_report(method, path, quote! { None });
+    let report_status_failure =
+        generate_failure_report(method, path, quote! { Some(response.status()) });
+    let send_request = quote! {
+        #build_request
+        let response = #send.map_err(|e| {
+            let error_msg = ::yew_extra::network::fetch_error(e);
+            #report_fetch_failure
+            error_msg
+        })?;
+        #follow_redirect
+        if !response.ok() {
+            let error_msg = #error_message;
+            #report_status_failure
+            return Err(error_msg);
         }
+    };
 
+    let hook_name = syn::Ident::new(&format!("use_{}_stream", fn_name), fn_name.span());
+    let hook_with_handle_name = syn::Ident::new(
+        &format!("use_{}_stream_with_handle", fn_name),
+        fn_name.span(),
+    );
+    let client = quote! {
         #[cfg(not(feature = "ssr"))]
+        #client_attrs
+        #vis async fn #fn_name(#(#args),*) -> Result<Vec<#item_type>, String> {
+            #(let #arg_names = &#arg_names;)*
+            #send_request
+
+            let mut items = Vec::new();
+            ::yew_extra::ndjson::read_items(response, |chunk: Vec<#item_type>| {
+                items.extend(chunk);
+                ::std::ops::ControlFlow::Continue(())
+            })
+            .await?;
+            Ok(items)
+        }
+
         #[yew::hook]
-        #vis fn #hook_name(#hook_params) -> ApiHook<#return_type> {
-            let state = yew::use_state(|| DataState::<#return_type>::Loading);
+        #client_attrs
+        #vis fn #hook_name(#(#args),*) -> ApiHook<::yew_extra::ndjson::StreamItems<#item_type>> {
+            #hook_with_handle_name(#(#arg_names),*).0
+        }
 
-            let is_loading = yew::use_state(|| false);
-            let is_updating = yew::use_state(|| false);
+        // The server renders the loading state, only the browser reads the stream
+        #[yew::hook]
+        #client_attrs
+        #vis fn #hook_with_handle_name(
+            #(#args),*
+        ) -> (
+            ApiHook<::yew_extra::ndjson::StreamItems<#item_type>>,
+            ::yew_extra::hooks::HookHandle,
+        ) {
+            let state = ::yew_extra::hooks::use_server_value(|| {
+                DataState::<::yew_extra::ndjson::StreamItems<#item_type>>::Loading
+            });
+            let is_loading = ::yew_extra::hooks::use_server_value(|| false);
+            let is_updating = ::yew_extra::hooks::use_server_value(|| false);
+            let is_offline = ::yew_extra::hooks::use_server_value(|| false);
+            let refetch = ::yew_extra::hooks::use_refetch();
 
             {
                 let state = state.clone();
                 let is_loading = is_loading.clone();
                 let is_updating = is_updating.clone();
+                let is_offline = is_offline.clone();
 
-                yew::use_effect_with(#deps, move |_| {
-                    // Check if this is the first load
-                    let is_first_load = matches!(*state, DataState::Loading);
+                ::yew_extra::hooks::use_isomorphic_effect(
+                    (::std::rc::Rc::new((#(#arg_names,)*)), refetch.requests()),
+                    move |deps| {
+                        // Items of a stream the component no longer shows are dropped
+                        let cancelled = ::std::rc::Rc::new(::std::cell::Cell::new(false));
 
-                    // Set appropriate loading flag
-                    if is_first_load {
-                        is_loading.set(true);
-                        is_updating.set(true);
-                    } else {
-                        is_updating.set(true);
-                    }
+                        #[cfg(not(feature = "ssr"))]
+                        {
+                            let deps = deps.0.clone();
+                            let cancelled = cancelled.clone();
+                            is_loading.set(matches!(*state, DataState::Loading));
+                            is_updating.set(true);
+                            is_offline.set(false);
 
-                    wasm_bindgen_futures::spawn_local(async move {
-                        #request_body
+                            wasm_bindgen_futures::spawn_local(async move {
+                                let (#(#arg_names,)*) = &*deps;
+                                let result = async {
+                                    #send_request
 
-                        match request.send().await {
-                            Ok(response) => {
-                                // Check if the response status is successful (2xx)
-                                if response.ok() {
-                                    match response.json::<#return_type>().await {
-                                        Ok(fetched_data) => {
-                                            #data_handling
-                                        }
-                                        Err(e) => {
-                                            state.set(DataState::Error(format!(
-                                                "Failed to parse response: {}",
-                                                e
-                                            )));
+                                    // Every chunk shows the items received so far. They
+                                    // share one buffer, so a chunk only copies its own items.
+                                    let mut items = ::yew_extra::ndjson::StreamItems::default();
+                                    ::yew_extra::ndjson::read_items(response, |chunk: Vec<#item_type>| {
+                                        if cancelled.get() {
+                                            return ::std::ops::ControlFlow::Break(());
                                         }
+                                        items.extend(chunk);
+                                        state.set(DataState::Data(items.clone()));
+                                        ::std::ops::ControlFlow::Continue(())
+                                    })
+                                    .await?;
+                                    Ok::<_, String>(items)
+                                }
+                                .await;
+                                if cancelled.get() {
+                                    return;
+                                }
+
+                                match result {
+                                    Ok(items) if items.is_empty() => state.set(DataState::Empty),
+                                    Ok(_) => {}
+                                    Err(error) => {
+                                        is_offline.set(!::yew_extra::network::is_online());
+                                        state.set(DataState::Error(error));
                                     }
-                                } else {
-                                    // Handle error response - try to get the error message from the response
-                                    let status = response.status();
-                                    let error_msg = match response.text().await {
-                                        Ok(text) => {
-                                            // Try to parse as JSON error message
-                                            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&text) {
-                                                if let Some(msg) = json.get("error").and_then(|v| v.as_str()) {
-                                                    msg.to_string()
-                                                } else if let Some(msg) = json.get("message").and_then(|v| v.as_str()) {
-                                                    msg.to_string()
-                                                } else {
-                                                    text
-                                                }
-                                            } else {
-                                                text
-                                            }
-                                        }
-                                        Err(_) => format!("Request failed with status {}", status)
-                                    };
-                                    state.set(DataState::Error(error_msg));
                                 }
-                            }
-                            Err(e) => {
-                                state.set(DataState::Error(format!(
-                                    "Failed to fetch data: {}",
-                                    e
-                                )));
-                            }
+                                is_loading.set(false);
+                                is_updating.set(false);
+                            });
                         }
 
-                        // Clear loading flags after request completes
-                        is_loading.set(false);
-                        is_updating.set(false);
-                    });
-                    || ()
-                });
+                        // The effect never runs on the server
+                        #[cfg(feature = "ssr")]
+                        let _ = (deps, &state, &is_loading, &is_updating, &is_offline);
+
+                        move || cancelled.set(true)
+                    },
+                );
             }
 
-            ApiHook {
+            let hook = ApiHook {
                 state: (*state).clone(),
                 is_loading: *is_loading,
                 is_updating: *is_updating,
+            };
+            let handle = ::yew_extra::hooks::HookHandle {
+                refetch: refetch.callback(),
+                is_offline: *is_offline,
+            };
+            (hook, handle)
+        }
+    };
+
+    quote! {
+        #[cfg(feature = "ssr")]
+        #emitted_fn
+
+        #param_struct
+
+        #validate_impl
+
+        #server
+
+        #client
+    }
+}
+
+/// The kind of input control generated for a form field
+enum FormFieldKind {
+    Text,
+    Number,
+    Checkbox,
+    Select,
+}
+
+/// A form field's input control, for a parameter type the form can edit
+struct FormField<'a> {
+    kind: FormFieldKind,
+    /// The parameter type, or the `T` of an `Option<T>` parameter
+    ty: &'a Type,
+    /// `Option` parameters may be left empty, which submits `None`
+    optional: bool,
+}
+
+fn form_field(ty: &Type) -> syn::Result<FormField<'_>> {
+    match option_inner_type(ty) {
+        Some(inner) => {
+            let kind = form_field_kind(inner)?;
+            if matches!(kind, FormFieldKind::Checkbox) || option_inner_type(inner).is_some() {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "`form = true` can't render this optional field, a checkbox has no empty state",
+                ));
+            }
+            Ok(FormField {
+                kind,
+                ty: inner,
+                optional: true,
+            })
+        }
+        None => Ok(FormField {
+            kind: form_field_kind(ty)?,
+            ty,
+            optional: false,
+        }),
+    }
+}
+
+/// The `T` of an `Option<T>` type
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+fn form_field_kind(ty: &Type) -> syn::Result<FormFieldKind> {
+    let unsupported = || {
+        syn::Error::new_spanned(
+            ty,
+            "`form = true` can't render a field of this type, use String, a number, bool, a `yew_extra::FormSelect` type or an Option of one",
+        )
+    };
+    match ty {
+        Type::Paren(paren) => form_field_kind(&paren.elem),
+        Type::Group(group) => form_field_kind(&group.elem),
+        Type::Path(type_path) => {
+            let segment = type_path.path.segments.last().ok_or_else(unsupported)?;
+            match segment.ident.to_string().as_str() {
+                "String" => Ok(FormFieldKind::Text),
+                "bool" => Ok(FormFieldKind::Checkbox),
+                "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+                | "u128" | "usize" | "f32" | "f64" => Ok(FormFieldKind::Number),
+                // A single input can't edit a collection
+                "Vec" | "VecDeque" | "LinkedList" | "HashMap" | "BTreeMap" | "HashSet"
+                | "BTreeSet" | "BinaryHeap" => Err(unsupported()),
+                _ => Ok(FormFieldKind::Select),
+            }
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+fn generate_form_component(
+    fn_name: &syn::Ident,
+    params_ident: &syn::Ident,
+    vis: &syn::Visibility,
+    return_type: &proc_macro2::TokenStream,
+    has_params: bool,
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+    path_params: &[syn::Ident],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let pascal_name = to_pascal_case(&fn_name.to_string());
+    let component_name = syn::Ident::new(&format!("{}FormFields", pascal_name), fn_name.span());
+    let props_name = syn::Ident::new(&format!("{}FormFieldsProps", pascal_name), fn_name.span());
+    let component_fn_name = syn::Ident::new(&format!("{}_form_fields", fn_name), fn_name.span());
+    let mutation_hook = syn::Ident::new(&format!("use_{}_mutation", fn_name), fn_name.span());
+
+    let mut state_decls = Vec::new();
+    let mut field_views = Vec::new();
+    let mut value_reads = Vec::new();
+    let mut field_names = Vec::new();
+    let mut body_fields = Vec::new();
+    let mut path_values = Vec::new();
+    let mut path_fields = Vec::new();
+
+    for input in inputs {
+        if let FnArg::Typed(pat_type) = input {
+            if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                let field_name = &pat_ident.ident;
+                let FormField {
+                    kind,
+                    ty: field_type,
+                    optional,
+                } = form_field(&pat_type.ty)?;
+                let field_label = field_name.to_string();
+                let field_id = format!("{}-{}", fn_name, field_name);
+                let view_ident =
+                    syn::Ident::new(&format!("{}_field", field_name), field_name.span());
+
+                // The value sent for the field, or an early return when it doesn't parse
+                let value = match (&kind, optional) {
+                    (FormFieldKind::Text, false)
+                    | (FormFieldKind::Select, _)
+                    | (FormFieldKind::Checkbox, _) => None,
+                    (FormFieldKind::Text, true) => Some(quote! {
+                        Some((*#field_name).clone()).filter(|value| !value.is_empty())
+                    }),
+                    (FormFieldKind::Number, false) => Some(quote! {
+                        (*#field_name).trim().parse::<#field_type>()
+                    }),
+                    (FormFieldKind::Number, true) => Some(quote! {
+                        match (*#field_name).trim() {
+                            "" => Ok(None),
+                            value => value.parse::<#field_type>().map(Some),
+                        }
+                    }),
+                };
+                let plain_value = match kind {
+                    FormFieldKind::Checkbox => quote! { *#field_name },
+                    _ => quote! { (*#field_name).clone() },
+                };
+                value_reads.push(match (&kind, &value) {
+                    (FormFieldKind::Number, Some(value)) => quote! {
+                        let #field_name = match #value {
+                            Ok(value) => value,
+                            Err(_) => {
+                                invalid.set(Some(format!("Invalid number for field '{}'", #field_label)));
+                                return;
+                            }
+                        };
+                    },
+                    (_, Some(value)) => quote! { let #field_name = #value; },
+                    (_, None) => quote! { let #field_name = #plain_value; },
+                });
+
+                // Path parameters are arguments of the mutation hook, so they're read on
+                // every render; a number that doesn't parse is caught on submit
+                if path_params.contains(field_name) {
+                    path_values.push(match (&kind, value) {
+                        (FormFieldKind::Number, Some(value)) => {
+                            quote! { #value.unwrap_or_default() }
+                        }
+                        (_, Some(value)) => value,
+                        (_, None) => plain_value,
+                    });
+                    path_fields.push(field_name);
+                } else {
+                    body_fields.push(field_name);
+                }
+
+                match kind {
+                    FormFieldKind::Text => {
+                        state_decls.push(if optional {
+                            quote! { let #field_name = yew::use_state(String::new); }
+                        } else {
+                            quote! {
+                                let #field_name = yew::use_state(<#field_type as Default>::default);
+                            }
+                        });
+                        field_views.push(quote! {
+                            let #view_ident = {
+                                let state = #field_name.clone();
+                                let oninput = yew::Callback::from(move |e: yew::InputEvent| {
+                                    let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                    state.set(input.value());
+                                });
+                                yew::html! {
+                                    <div class="form-field">
+                                        <label for={#field_id}>{ #field_label }</label>
+                                        <input id={#field_id} name={#field_label} type="text"
+                                            value={(*#field_name).clone()} {oninput} />
+                                    </div>
+                                }
+                            };
+                        });
+                    }
+                    FormFieldKind::Number => {
+                        // Numbers are kept as text while editing and parsed on submit
+                        state_decls.push(if optional {
+                            quote! { let #field_name = yew::use_state(String::new); }
+                        } else {
+                            quote! {
+                                let #field_name = yew::use_state(|| <#field_type as Default>::default().to_string());
+                            }
+                        });
+                        field_views.push(quote! {
+                            let #view_ident = {
+                                let state = #field_name.clone();
+                                let oninput = yew::Callback::from(move |e: yew::InputEvent| {
+                                    let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                    state.set(input.value());
+                                });
+                                yew::html! {
+                                    <div class="form-field">
+                                        <label for={#field_id}>{ #field_label }</label>
+                                        <input id={#field_id} name={#field_label} type="number"
+                                            value={(*#field_name).clone()} {oninput} />
+                                    </div>
+                                }
+                            };
+                        });
+                    }
+                    FormFieldKind::Checkbox => {
+                        state_decls.push(quote! {
+                            let #field_name = yew::use_state(<#field_type as Default>::default);
+                        });
+                        field_views.push(quote! {
+                            let #view_ident = {
+                                let state = #field_name.clone();
+                                let onchange = yew::Callback::from(move |e: yew::Event| {
+                                    let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                    state.set(input.checked());
+                                });
+                                yew::html! {
+                                    <div class="form-field">
+                                        <label for={#field_id}>{ #field_label }</label>
+                                        <input id={#field_id} name={#field_label} type="checkbox"
+                                            checked={*#field_name} {onchange} />
+                                    </div>
+                                }
+                            };
+                        });
+                    }
+                    FormFieldKind::Select => {
+                        // Any other type is rendered as a select over its FormSelect options,
+                        // with an empty first choice when the field is optional
+                        let (state_type, selected, set_option, empty_option) = if optional {
+                            (
+                                quote! { Option<#field_type> },
+                                quote! { (*#field_name).as_ref() == Some(option) },
+                                quote! { state.set(option) },
+                                quote! {
+                                    <option value="" selected={(*#field_name).is_none()}></option>
+                                },
+                            )
+                        } else {
+                            (
+                                quote! { #field_type },
+                                quote! { *option == *#field_name },
+                                quote! {
+                                    if let Some(option) = option {
+                                        state.set(option);
+                                    }
+                                },
+                                quote! {},
+                            )
+                        };
+                        state_decls.push(quote! {
+                            let #field_name = yew::use_state(<#state_type as Default>::default);
+                        });
+                        field_views.push(quote! {
+                            let #view_ident = {
+                                let state = #field_name.clone();
+                                let options = <#field_type as ::yew_extra::FormSelect>::options();
+                                let onchange = {
+                                    let options = options.clone();
+                                    yew::Callback::from(move |e: yew::Event| {
+                                        let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+                                        let option = select
+                                            .value()
+                                            .parse::<usize>()
+                                            .ok()
+                                            .and_then(|index| options.get(index).cloned());
+                                        #set_option
+                                    })
+                                };
+                                yew::html! {
+                                    <div class="form-field">
+                                        <label for={#field_id}>{ #field_label }</label>
+                                        <select id={#field_id} name={#field_label} {onchange}>
+                                            #empty_option
+                                            { for options.iter().enumerate().map(|(index, option)| yew::html! {
+                                                <option value={index.to_string()} selected={#selected}>
+                                                    { ::yew_extra::FormSelect::label(option) }
+                                                </option>
+                                            }) }
+                                        </select>
+                                    </div>
+                                }
+                            };
+                        });
+                    }
+                }
+
+                field_names.push(field_name);
+            }
+        }
+    }
+
+    let view_idents: Vec<syn::Ident> = field_names
+        .iter()
+        .map(|field_name| syn::Ident::new(&format!("{}_field", field_name), field_name.span()))
+        .collect();
+    let mutation_input = if has_params {
+        quote! { #params_ident { #(#body_fields),* } }
+    } else {
+        quote! { () }
+    };
+
+    Ok(quote! {
+        #[derive(yew::Properties, PartialEq)]
+        #vis struct #props_name {
+            /// Called with the server response after a successful submit
+            #[prop_or_default]
+            pub on_success: Option<yew::Callback<#return_type>>,
+            /// Called with the error message when the submit fails
+            #[prop_or_default]
+            pub on_error: Option<yew::Callback<String>>,
+            /// Text of the submit button (defaults to "Submit")
+            #[prop_or_default]
+            pub submit_label: Option<yew::AttrValue>,
+        }
+
+        #[yew::function_component(#component_name)]
+        #vis fn #component_fn_name(props: &#props_name) -> yew::Html {
+            use yew::TargetCast;
+
+            #(#state_decls)*
+
+            // Submits go through the endpoint's mutation hook, which tracks the pending call and its error
+            let mutation = #mutation_hook(#(#path_values),*);
+            let invalid = yew::use_state(|| None::<String>);
+
+            let onsubmit = {
+                #(let #field_names = #field_names.clone();)*
+                let action = mutation.action.clone();
+                let invalid = invalid.clone();
+                let on_success = props.on_success.clone();
+                let on_error = props.on_error.clone();
+
+                yew::Callback::from(move |e: yew::SubmitEvent| {
+                    e.prevent_default();
+
+                    #(#value_reads)*
+                    #(let _ = #path_fields;)*
+                    invalid.set(None);
+
+                    let action = action.clone();
+                    let on_success = on_success.clone();
+                    let on_error = on_error.clone();
+                    let input = #mutation_input;
+                    yew::platform::spawn_local(async move {
+                        match action.run(input).await {
+                            Ok(data) => {
+                                if let Some(on_success) = on_success {
+                                    on_success.emit(data);
+                                }
+                            }
+                            Err(e) => {
+                                if let Some(on_error) = on_error {
+                                    on_error.emit(e);
+                                }
+                            }
+                        }
+                    });
+                })
+            };
+
+            #(#field_views)*
+
+            let submit_label = props
+                .submit_label
+                .clone()
+                .unwrap_or_else(|| yew::AttrValue::from("Submit"));
+            let error = (*invalid).clone().or_else(|| mutation.error.clone());
+
+            yew::html! {
+                <form class="form-fields" {onsubmit}>
+                    #( { #view_idents } )*
+                    if let Some(message) = error {
+                        <p class="form-error">{ message }</p>
+                    }
+                    <button type="submit" disabled={mutation.is_pending}>{ submit_label }</button>
+                </form>
+            }
+        }
+    })
+}
+
+fn to_snake_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut result = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev_lower =
+                i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_ascii_digit());
+            let next_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            let prev_upper = i > 0 && chars[i - 1].is_uppercase();
+            if prev_lower || (prev_upper && next_lower) {
+                result.push('_');
             }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(*c);
         }
     }
+    result
 }
 
 fn to_pascal_case(s: &str) -> String {
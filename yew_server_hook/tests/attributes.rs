@@ -0,0 +1,44 @@
+// Integration test to verify the function's docs and attributes reach the generated items
+#![deny(deprecated, unused_variables)]
+
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+/// Returns the legacy report.
+///
+/// Superseded by the v2 reports.
+#[yewserverhook(path = "/api/legacy/report", method = "GET")]
+#[deprecated(note = "use get_report_v2")]
+#[allow(unused_variables)]
+pub async fn get_legacy_report(year: u32, draft: bool) -> Result<String, String> {
+    Ok(format!("Report {}", year))
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_endpoint_is_still_callable() {
+    let params = GetLegacyReportParams {
+        year: 2024,
+        draft: false,
+    };
+    assert_eq!(params.year, 2024);
+
+    #[cfg(not(feature = "ssr"))]
+    drop(get_legacy_report(2024, false));
+}
@@ -0,0 +1,75 @@
+// Integration test to verify `blocking = true` runs the body on the blocking thread pool
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/checksum", method = "GET", blocking = true)]
+pub async fn get_checksum(text: &str, rounds: u32) -> Result<(u64, String), String> {
+    let method: axum::http::Method = yew_extra::extract().await.map_err(|e| e.to_string())?;
+
+    let mut hash = 0u64;
+    for _ in 0..rounds {
+        hash = text.bytes().fold(hash, |hash, byte| {
+            hash.wrapping_mul(31).wrapping_add(u64::from(byte))
+        });
+    }
+    let thread = std::thread::current()
+        .name()
+        .unwrap_or_default()
+        .to_string();
+    Ok((hash, format!("{} on {}", method, thread)))
+}
+
+#[test]
+fn test_params_struct() {
+    let params = GetChecksumParams {
+        text: "abc".to_string(),
+        rounds: 2,
+    };
+    assert_eq!(params.rounds, 2);
+}
+
+#[cfg(feature = "ssr")]
+#[tokio::test]
+async fn test_body_runs_on_blocking_thread_with_request_parts() {
+    yew_extra::request_scope(async {
+        let (parts, _body) = axum::http::Request::get("/api/checksum")
+            .body(())
+            .unwrap()
+            .into_parts();
+        yew_extra::provide_request_parts(parts).await;
+
+        let params = GetChecksumParams {
+            text: "abc".to_string(),
+            rounds: 1,
+        };
+        let axum::Json((hash, context)) = get_checksum_handler(axum::extract::Query(params))
+            .await
+            .unwrap();
+
+        yew_extra::clear_request_parts().await;
+
+        assert_eq!(hash, 96354);
+        assert_ne!(
+            context,
+            format!("GET on {}", std::thread::current().name().unwrap())
+        );
+        assert!(context.starts_with("GET on "));
+    })
+    .await;
+}
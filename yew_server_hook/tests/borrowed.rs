@@ -0,0 +1,99 @@
+// Integration test to verify borrowed parameters are sent as their owned types
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sort {
+    pub field: String,
+    pub descending: bool,
+}
+
+// Explicit lifetimes are accepted as well as elided ones
+#[allow(clippy::needless_lifetimes)]
+#[yewserverhook(path = "/api/search", method = "POST")]
+pub async fn search<'a>(
+    query: &'a str,
+    tags: &[String],
+    sort: &Sort,
+    locale: std::borrow::Cow<'_, str>,
+) -> Result<String, String> {
+    Ok(format!(
+        "{} [{}] by {}{} ({})",
+        query.trim(),
+        tags.join(", "),
+        sort.field,
+        if sort.descending { " desc" } else { "" },
+        locale
+    ))
+}
+
+#[test]
+fn test_params_use_owned_types() {
+    let params = SearchParams {
+        query: "rust ".to_string(),
+        tags: vec!["web".to_string(), "wasm".to_string()],
+        sort: Sort {
+            field: "date".to_string(),
+            descending: true,
+        },
+        locale: "en".to_string(),
+    };
+    assert_eq!(params.tags.len(), 2);
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn test_client_takes_owned_values() {
+    drop(search(
+        "rust".to_string(),
+        vec![],
+        Sort {
+            field: "date".to_string(),
+            descending: false,
+        },
+        "en".to_string(),
+    ));
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn test_handler_borrows_params() {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    let handler = search_handler(axum::Json(SearchParams {
+        query: "rust ".to_string(),
+        tags: vec!["web".to_string(), "wasm".to_string()],
+        sort: Sort {
+            field: "date".to_string(),
+            descending: true,
+        },
+        locale: "en".to_string(),
+    }));
+
+    let mut handler = pin!(handler);
+    let Poll::Ready(Ok(axum::Json(result))) = handler
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    else {
+        panic!("handler did not complete successfully");
+    };
+    assert_eq!(result, "rust [web, wasm] by date desc (en)");
+}
@@ -0,0 +1,76 @@
+// Integration test to verify GET hooks share their responses through yew_extra's cache
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/tags", method = "GET")]
+pub async fn get_tags(prefix: String) -> Result<Vec<String>, String> {
+    Ok(vec![format!("{}-tag", prefix)])
+}
+
+#[yewserverhook(path = "/api/tags", method = "POST")]
+pub async fn add_tag(tag: String) -> Result<String, String> {
+    Ok(tag)
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn test_prefetch_uses_the_hook_cache_key() {
+    let prefetch = prefetch_get_tags("rust".to_string());
+    assert_eq!(
+        prefetch.key(),
+        yew_extra::cache::request_key("/api/tags?prefix=rust", &Default::default())
+    );
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn test_resource_reads_the_hook_cache_entry() {
+    let key = yew_extra::cache::request_key("/api/tags?prefix=rust", &Default::default());
+    let resource = resource_get_tags("rust".to_string());
+    assert_eq!(resource.key(), key);
+
+    yew_extra::cache::insert(key, vec!["rust-tag".to_string()]);
+    assert_eq!(resource.get().unwrap(), ["rust-tag"]);
+}
+
+#[yew::function_component]
+fn Tags() -> yew::Html {
+    // The handle comes next to `ApiHook`, which keeps only the fields the app declares
+    let (tags, handle) =
+        use_get_tags_with_handle("rust".to_string(), yew_extra::RequestOptions::default());
+    let refetch: yew::Callback<()> = handle.refetch;
+    let loading = matches!(tags.state, DataState::Loading);
+
+    yew::html! { <p>{ format!("{} {} {}", loading, refetch == refetch.clone(), handle.is_offline) }</p> }
+}
+
+// Renders a component the way the server does, without running effects
+fn render<C: yew::BaseComponent>(props: C::Properties) -> String {
+    let renderer = yew::LocalServerRenderer::<C>::with_props(props).hydratable(false);
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(renderer.render())
+}
+
+#[test]
+fn test_hooks_return_a_handle() {
+    // Nothing was sent yet, so nothing failed offline
+    assert_eq!(render::<Tags>(()), "<p>true true false</p>");
+}
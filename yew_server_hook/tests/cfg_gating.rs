@@ -0,0 +1,47 @@
+// Integration test to verify `#[cfg(...)]` on a server function gates everything generated for it
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+// Both endpoints generate the same items, which only compiles if the disabled one generates none
+#[yewserverhook(path = "/api/admin/stats", method = "GET")]
+#[cfg(any())]
+pub async fn get_admin_stats(include_archived: bool) -> Result<Vec<u32>, String> {
+    Ok(vec![u32::from(include_archived)])
+}
+
+#[yewserverhook(path = "/api/admin/stats", method = "GET")]
+#[cfg(not(any()))]
+pub async fn get_admin_stats(days: u32) -> Result<u32, String> {
+    Ok(days)
+}
+
+#[yewserverhook(path = "/api/admin/purge", method = "POST", form = true)]
+#[cfg(any())]
+pub async fn purge_records(older_than_days: u32) -> Result<u32, String> {
+    Ok(older_than_days)
+}
+
+#[test]
+fn test_enabled_endpoint_is_generated() {
+    let params = GetAdminStatsParams { days: 7 };
+    assert_eq!(params.days, 7);
+
+    #[cfg(not(feature = "ssr"))]
+    drop(get_admin_stats(7));
+}
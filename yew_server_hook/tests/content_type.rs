@@ -0,0 +1,32 @@
+// Integration test to verify `content_type` reaches the wrapper's responses
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use yew_extra::test::{call_handler, TestRequest};
+use yew_server_hook_fixture::content_type::{
+    get_carts_handler_wrapper, get_orders_handler_wrapper,
+};
+
+#[tokio::test]
+async fn test_wrapper_stamps_the_media_type() {
+    let response = call_handler::<Vec<u32>, _, _>(
+        get_orders_handler_wrapper,
+        TestRequest::get("/api/orders?page=2"),
+    )
+    .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        response.headers[CONTENT_TYPE],
+        "application/vnd.shop.v2+json"
+    );
+    assert_eq!(response.data.unwrap(), [2]);
+}
+
+#[tokio::test]
+async fn test_endpoints_without_a_media_type_keep_json() {
+    let response =
+        call_handler::<Vec<u32>, _, _>(get_carts_handler_wrapper, TestRequest::get("/api/carts"))
+            .await;
+
+    assert_eq!(response.headers[CONTENT_TYPE], "application/json");
+}
@@ -0,0 +1,52 @@
+// Integration test to verify `debug_expand` writes the generated code to the target directory
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    pub total: u64,
+}
+
+#[yewserverhook(path = "/api/report", method = "GET", debug_expand = true)]
+pub async fn get_report(year: u32) -> Result<Report, String> {
+    Ok(Report { total: year as u64 })
+}
+
+#[test]
+fn test_expansion_is_written() {
+    let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target"));
+    let file = target_dir
+        .join("yew_server_hook")
+        .join("debug_expand")
+        .join("get_report.rs");
+
+    let expansion = std::fs::read_to_string(&file).unwrap();
+    assert!(
+        expansion.starts_with("// Expansion of #[yewserverhook] on `get_report` (GET /api/report)")
+    );
+    // The file is the formatted code, ready to read or paste into a module
+    let items = syn::parse_file(&expansion).unwrap().items;
+    assert!(items.iter().any(|item| matches!(
+        item,
+        syn::Item::Struct(params) if params.ident == "GetReportParams"
+    )));
+}
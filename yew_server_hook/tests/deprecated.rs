@@ -0,0 +1,80 @@
+// Integration test to verify endpoints declared with `deprecated` announce their retirement
+// in their responses and route metadata
+use axum::http::header::LINK;
+use axum::http::{Method, StatusCode};
+use yew_extra::deprecation::Deprecation;
+use yew_extra::route_registry::find_route;
+use yew_extra::test::{call_handler, TestRequest};
+use yew_server_hook_fixture::deprecated::{
+    create_user_handler_wrapper, list_users_handler_wrapper, list_users_v2_handler_wrapper,
+    CreateUserParams,
+};
+
+fn list_users_deprecation() -> Deprecation {
+    Deprecation::new()
+        .since("2025-06-01")
+        .sunset("2025-12-01")
+        .replacement("/api/v2/users")
+}
+
+#[tokio::test]
+async fn test_responses_carry_the_headers() {
+    let response = call_handler::<Vec<String>, _, _>(
+        list_users_handler_wrapper,
+        TestRequest::get("/api/users"),
+    )
+    .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    let deprecation = list_users_deprecation();
+    assert_eq!(
+        response.headers["deprecation"],
+        deprecation.deprecation_header()
+    );
+    assert_eq!(
+        response.headers["sunset"],
+        deprecation.sunset_header().unwrap()
+    );
+    assert_eq!(
+        response.headers[LINK],
+        "</api/v2/users>; rel=\"successor-version\""
+    );
+    assert_eq!(response.data.unwrap(), ["Ada"]);
+}
+
+#[test]
+fn test_route_metadata_lists_the_deprecation() {
+    let route = find_route(&Method::GET, "/api/users").unwrap();
+    assert_eq!(route.metadata.deprecation, Some(list_users_deprecation()));
+
+    let route = find_route(&Method::GET, "/api/v2/users").unwrap();
+    assert_eq!(route.metadata.deprecation, None);
+}
+
+#[tokio::test]
+async fn test_bare_deprecated() {
+    let response = call_handler::<String, _, _>(
+        create_user_handler_wrapper,
+        TestRequest::post("/api/users").json(&CreateUserParams {
+            name: "Ada".to_string(),
+        }),
+    )
+    .await;
+
+    assert_eq!(response.headers["deprecation"], "true");
+    assert!(!response.headers.contains_key("sunset"));
+    assert!(!response.headers.contains_key(LINK));
+    assert_eq!(response.data.unwrap(), "Ada");
+}
+
+#[tokio::test]
+async fn test_other_endpoints_are_unchanged() {
+    let response = call_handler::<Vec<String>, _, _>(
+        list_users_v2_handler_wrapper,
+        TestRequest::get("/api/v2/users"),
+    )
+    .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(!response.headers.contains_key("deprecation"));
+}
@@ -0,0 +1,32 @@
+// Checks the errors reported for signatures the macro can't turn into an endpoint
+#[test]
+fn test_unsupported_signatures() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
+
+// Checks the errors reported for options that don't fit the endpoint or each other
+#[test]
+fn test_rejected_options() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/options/*.rs");
+}
+
+// Checks that `#[deprecated]` reaches the generated client functions. The generated server
+// code needs the full server setup, so this only runs for client builds.
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn test_forwarded_attributes() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/attributes/*.rs");
+}
+
+// Checks that the hooks sending declared mutations on mount point to the triggered
+// `use_*_mutation` hook, and that only reads get the cache helpers. Client builds only,
+// like the attributes above.
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn test_mutation_hooks() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/mutations/*.rs");
+}
@@ -0,0 +1,33 @@
+// Integration test to verify endpoints served by another service
+use axum::http::Method;
+use yew_extra::route_registry::find_route;
+use yew_extra::test::{call_handler, TestRequest};
+use yew_server_hook_fixture::external_base::{
+    local_handler_wrapper, signed_url_handler_wrapper, SignedUrlParams,
+};
+
+#[test]
+fn test_only_local_endpoints_are_registered() {
+    assert!(find_route(&Method::POST, "/files/signed-url").is_none());
+    assert!(find_route(&Method::GET, "/api/local").is_some());
+}
+
+#[tokio::test]
+async fn test_other_service_can_mount_the_wrapper() {
+    let response = call_handler::<String, _, _>(
+        signed_url_handler_wrapper,
+        TestRequest::post("/files/signed-url").json(&SignedUrlParams {
+            name: "report.pdf".to_string(),
+        }),
+    )
+    .await;
+
+    assert_eq!(
+        response.data.unwrap(),
+        "https://files.example.com/report.pdf?signature=test"
+    );
+
+    let response =
+        call_handler::<String, _, _>(local_handler_wrapper, TestRequest::get("/api/local")).await;
+    assert_eq!(response.data.unwrap(), "here");
+}
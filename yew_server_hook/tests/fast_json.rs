@@ -0,0 +1,34 @@
+// Integration test to verify `fast_json` endpoints still answer plain JSON. Only the browser
+// parses them differently, through JSON.parse.
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use yew_extra::test::{call_handler, TestRequest};
+use yew_server_hook_fixture::fast_json::{
+    add_point_handler_wrapper, get_points_handler_wrapper, Point,
+};
+
+#[tokio::test]
+async fn test_reads_answer_json() {
+    let response = call_handler::<Vec<Point>, _, _>(
+        get_points_handler_wrapper,
+        TestRequest::get("/api/points?limit=3"),
+    )
+    .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.headers[CONTENT_TYPE], "application/json");
+    assert_eq!(response.data.unwrap(), [Point { x: 0.0, y: 3.0 }]);
+}
+
+#[tokio::test]
+async fn test_mutations_answer_json() {
+    let point = Point { x: 1.5, y: -2.0 };
+    let response = call_handler::<Point, _, _>(
+        add_point_handler_wrapper,
+        TestRequest::post("/api/points").json(&serde_json::json!({ "point": point })),
+    )
+    .await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.data.unwrap(), point);
+}
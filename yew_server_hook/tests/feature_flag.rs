@@ -0,0 +1,49 @@
+// Integration test to verify `feature_flag` gates the wrapper at request time
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use yew_extra::feature_flags::{clear_flag_provider, set_flag_provider, FlagFuture};
+use yew_extra::test::{call_handler, TestRequest};
+use yew_server_hook_fixture::feature_flag::{
+    get_billing_handler_wrapper, get_invoices_handler_wrapper,
+};
+
+// The provider is global, so the cases run one after the other
+#[tokio::test]
+async fn test_wrapper_checks_the_flag_first() {
+    let billing = || {
+        call_handler::<String, _, _>(
+            get_billing_handler_wrapper,
+            TestRequest::get("/api/billing"),
+        )
+    };
+
+    // Without a provider every flag is off, and the endpoint answers like an unknown path
+    clear_flag_provider();
+    assert_eq!(billing().await.status, StatusCode::NOT_FOUND);
+
+    set_flag_provider(|flag: String, request: &Parts| -> FlagFuture {
+        let enabled = flag == "new_billing" && request.headers.contains_key("x-beta");
+        Box::pin(async move { Ok(enabled) })
+    });
+    assert_eq!(billing().await.status, StatusCode::NOT_FOUND);
+    let response = call_handler::<String, _, _>(
+        get_billing_handler_wrapper,
+        TestRequest::get("/api/billing").header("x-beta", "1"),
+    )
+    .await;
+    assert_eq!(response.data.unwrap(), "new");
+
+    set_flag_provider(|_flag: String, _request: &Parts| -> FlagFuture {
+        Box::pin(async { Err("flag service down".to_string()) })
+    });
+    assert_eq!(billing().await.status, StatusCode::SERVICE_UNAVAILABLE);
+
+    // Endpoints without a flag are always on
+    let response = call_handler::<Vec<String>, _, _>(
+        get_invoices_handler_wrapper,
+        TestRequest::get("/api/invoices"),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::OK);
+    clear_flag_provider();
+}
@@ -0,0 +1,16 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(
+    path = "/api/orders",
+    method = "GET",
+    content_type = "application/vnd.shop.v2+json"
+)]
+pub async fn get_orders(page: u32) -> Result<Vec<u32>, String> {
+    Ok(vec![page])
+}
+
+#[yewserverhook(path = "/api/carts", method = "GET")]
+pub async fn get_carts() -> Result<Vec<u32>, String> {
+    Ok(Vec::new())
+}
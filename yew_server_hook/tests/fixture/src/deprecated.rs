@@ -0,0 +1,25 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(
+    path = "/api/users",
+    method = "GET",
+    deprecated(
+        since = "2025-06-01",
+        sunset = "2025-12-01",
+        replacement = "/api/v2/users"
+    )
+)]
+pub async fn list_users() -> Result<Vec<String>, String> {
+    Ok(vec!["Ada".to_string()])
+}
+
+#[yewserverhook(path = "/api/users", method = "POST", deprecated)]
+pub async fn create_user(name: String) -> Result<String, String> {
+    Ok(name)
+}
+
+#[yewserverhook(path = "/api/v2/users", method = "GET")]
+pub async fn list_users_v2() -> Result<Vec<String>, String> {
+    Ok(vec!["Ada".to_string()])
+}
@@ -0,0 +1,16 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(
+    path = "/files/signed-url",
+    method = "POST",
+    base = "https://files.example.com/"
+)]
+pub async fn signed_url(name: String) -> Result<String, String> {
+    Ok(format!("https://files.example.com/{}?signature=test", name))
+}
+
+#[yewserverhook(path = "/api/local", method = "GET")]
+pub async fn local() -> Result<String, String> {
+    Ok("here".to_string())
+}
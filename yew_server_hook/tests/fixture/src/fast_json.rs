@@ -0,0 +1,23 @@
+use crate::{ApiHook, DataState};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[yewserverhook(path = "/api/points", method = "GET", fast_json = true)]
+pub async fn get_points(limit: u32) -> Result<Vec<Point>, String> {
+    Ok(vec![Point {
+        x: 0.0,
+        y: f64::from(limit),
+    }])
+}
+
+#[yewserverhook(path = "/api/points", fast_json = true, schema_check = true)]
+pub async fn add_point(point: Point) -> Result<Point, String> {
+    Ok(point)
+}
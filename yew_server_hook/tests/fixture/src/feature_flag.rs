@@ -0,0 +1,12 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(path = "/api/billing", method = "GET", feature_flag = "new_billing")]
+pub async fn get_billing() -> Result<String, String> {
+    Ok("new".to_string())
+}
+
+#[yewserverhook(path = "/api/invoices", method = "GET")]
+pub async fn get_invoices() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
@@ -0,0 +1,23 @@
+use crate::{ApiHook, DataState};
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, yew_extra::schema::JsonSchema)]
+#[schemars(crate = "yew_extra::schema::schemars")]
+pub struct Note {
+    pub id: u32,
+    pub text: String,
+}
+
+#[yewserverhook(path = "/api/notes", method = "GET", graphql = true)]
+pub async fn list_notes(search: Option<String>) -> Result<Vec<Note>, String> {
+    Ok(vec![Note {
+        id: 1,
+        text: search.unwrap_or_default(),
+    }])
+}
+
+#[yewserverhook(path = "/api/notes/clear", graphql = true)]
+pub async fn clear_notes() -> Result<u32, String> {
+    Ok(0)
+}
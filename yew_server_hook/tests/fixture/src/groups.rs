@@ -0,0 +1,28 @@
+use yew_server_hook::yewserverhook_group;
+
+#[yewserverhook_group(prefix = "/api/admin/", method = "GET", credentials = "include")]
+pub mod admin {
+    pub use crate::{ApiHook, DataState};
+    use yew_server_hook::{yewserverhook, yewserverhook_group};
+
+    #[yewserverhook(path = "/users")]
+    pub async fn list_users() -> Result<Vec<String>, String> {
+        Ok(vec!["alice".to_string()])
+    }
+
+    #[yewserverhook(path = "users", method = "DELETE")]
+    pub async fn delete_user(id: u32) -> Result<u32, String> {
+        Ok(id)
+    }
+
+    #[yewserverhook_group(prefix = "/reports")]
+    pub mod reports {
+        pub use super::{ApiHook, DataState};
+        use yew_server_hook::yewserverhook;
+
+        #[yewserverhook(path = "/daily")]
+        pub async fn daily_report() -> Result<u32, String> {
+            Ok(1)
+        }
+    }
+}
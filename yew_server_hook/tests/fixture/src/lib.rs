@@ -0,0 +1,53 @@
+//! Endpoints of the integration tests in `tests/`, built as a library with the `ssr`
+//! feature.
+//!
+//! The handler wrappers and route registrations aren't generated in `cfg(test)` builds, so
+//! tests driving endpoints through `yew_extra::test::call_handler` or a router take them
+//! from here, like an app's tests take them from the app crate.
+
+use serde::{Deserialize, Serialize};
+
+pub use yew_extra::route_registry;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+pub mod content_type;
+pub mod deprecated;
+pub mod external_base;
+pub mod fast_json;
+pub mod feature_flag;
+#[cfg(not(feature = "workers"))]
+pub mod graphql;
+pub mod groups;
+#[cfg(not(feature = "workers"))]
+pub mod nonce;
+pub mod path_params;
+pub mod profile;
+pub mod query_params;
+#[cfg(not(feature = "workers"))]
+pub mod raw_body;
+pub mod request_context;
+#[cfg(not(feature = "workers"))]
+pub mod retry;
+pub mod route_metadata;
+pub mod state;
+pub mod stream;
+pub mod transform;
+pub mod transport;
+#[cfg(not(feature = "workers"))]
+pub mod with_status;
+pub mod ws;
@@ -0,0 +1,12 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(path = "/api/transfers", method = "POST", nonce = true)]
+pub async fn create_transfer(to: String, cents: u64) -> Result<String, String> {
+    Ok(format!("{} to {}", cents, to))
+}
+
+#[yewserverhook(path = "/api/account", method = "DELETE", nonce = "30s")]
+pub async fn delete_account() -> Result<(), String> {
+    Ok(())
+}
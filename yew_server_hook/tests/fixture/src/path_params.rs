@@ -0,0 +1,18 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(path = "/api/users/{id}", method = "GET")]
+pub async fn get_user(id: u32) -> Result<String, String> {
+    Ok(format!("user {}", id))
+}
+
+#[yewserverhook(path = "/api/users/{id}/posts", method = "GET")]
+pub async fn list_posts(id: u32, page: u32) -> Result<Vec<String>, String> {
+    Ok(vec![format!("post of {} on page {}", id, page)])
+}
+
+// The Axum 0.7 syntax is translated for the router
+#[yewserverhook(path = "/api/users/:id", method = "PUT")]
+pub async fn rename_user(name: String, id: u32) -> Result<String, String> {
+    Ok(format!("{} is now {}", id, name))
+}
@@ -0,0 +1,24 @@
+use crate::{ApiHook, DataState};
+use std::sync::atomic::{AtomicBool, Ordering};
+use yew_server_hook::yewserverhook;
+
+/// Set by the work `save_profile` queues for after its response
+pub static PROFILE_INDEXED: AtomicBool = AtomicBool::new(false);
+
+#[yewserverhook(path = "/api/profile", method = "GET")]
+pub async fn get_profile() -> Result<String, String> {
+    Ok("ada".to_string())
+}
+
+#[yewserverhook(path = "/api/profile", method = "POST")]
+pub async fn save_profile(name: String) -> Result<String, String> {
+    yew_extra::flash::flash("Profile saved")
+        .await
+        .map_err(|e| e.to_string())?;
+    yew_extra::after_response(async {
+        PROFILE_INDEXED.store(true, Ordering::SeqCst);
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(name)
+}
@@ -0,0 +1,7 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(path = "/api/search", method = "GET")]
+pub async fn search(q: String, tag: Vec<String>, page: Option<u32>) -> Result<String, String> {
+    Ok(format!("{} [{}] {:?}", q, tag.join(","), page))
+}
@@ -0,0 +1,18 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(path = "/api/webhooks/payments", method = "POST", raw_body = "64KB")]
+pub async fn payment_webhook() -> Result<usize, String> {
+    let body = yew_extra::extract_body().await.map_err(|e| e.to_string())?;
+    Ok(body.len())
+}
+
+#[yewserverhook(path = "/api/raw-notes", method = "POST", raw_body = true)]
+pub async fn add_note(title: String) -> Result<String, String> {
+    let body: serde_json::Value = yew_extra::extract_json().await.map_err(|e| e.to_string())?;
+    Ok(format!(
+        "{} ({} fields)",
+        title,
+        body.as_object().map_or(0, |o| o.len())
+    ))
+}
@@ -0,0 +1,14 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(path = "/api/greeting", method = "POST")]
+pub async fn greet(mut cx: yew_extra::RequestContext, name: String) -> Result<String, String> {
+    let method: axum::http::Method = cx.extract().await.map_err(|e| e.to_string())?;
+    // Read through the request scope of the wrapper as well
+    let headers: axum::http::HeaderMap = yew_extra::extract().await.map_err(|e| e.to_string())?;
+    let host = headers
+        .get("host")
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or("unknown");
+    Ok(format!("{} {} from {}", method, name, host))
+}
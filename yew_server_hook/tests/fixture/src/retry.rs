@@ -0,0 +1,17 @@
+use crate::{ApiHook, DataState};
+use std::sync::atomic::{AtomicU32, Ordering};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(path = "/api/stock", method = "GET", retry = 3)]
+pub async fn get_stock(sku: String) -> Result<u32, String> {
+    Ok(sku.len() as u32)
+}
+
+/// How often the body of `place_order` ran
+pub static ORDERS_PLACED: AtomicU32 = AtomicU32::new(0);
+
+#[yewserverhook(path = "/api/checkout", method = "POST", retry = 2, idempotent = true)]
+pub async fn place_order(sku: String, quantity: u32) -> Result<String, String> {
+    let order = ORDERS_PLACED.fetch_add(1, Ordering::SeqCst) + 1;
+    Ok(format!("order {}: {} x {}", order, quantity, sku))
+}
@@ -0,0 +1,23 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(
+    path = "/api/invoices",
+    method = "POST",
+    auth = "required",
+    tags = ["billing", "audit"],
+    owner = "payments"
+)]
+pub async fn create_invoice(amount: u32) -> Result<u32, String> {
+    Ok(amount)
+}
+
+#[yewserverhook(path = "/api/status", method = "GET", tags = "public")]
+pub async fn status() -> Result<String, String> {
+    Ok("up".to_string())
+}
+
+#[yewserverhook(path = "/api/plain", method = "GET")]
+pub async fn plain() -> Result<String, String> {
+    Ok("plain".to_string())
+}
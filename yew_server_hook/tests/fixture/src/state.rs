@@ -0,0 +1,17 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub greeting: String,
+}
+
+#[yewserverhook(path = "/api/greeting", method = "GET", state = AppState)]
+pub async fn get_greeting() -> Result<String, String> {
+    Ok(state().greeting)
+}
+
+#[yewserverhook(path = "/api/greet", method = "POST", state = "AppState")]
+pub async fn greet(name: String) -> Result<String, String> {
+    Ok(format!("{}, {}", state().greeting, name))
+}
@@ -0,0 +1,26 @@
+use crate::{ApiHook, DataState};
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogLine {
+    pub job: u32,
+    pub text: String,
+}
+
+#[yewserverhook(path = "/api/jobs/{job}/logs", method = "GET")]
+pub async fn job_logs(job: u32, level: &str) -> Result<impl Stream<Item = LogLine>, String> {
+    let prefix = level.to_uppercase();
+    Ok(futures_util::stream::iter((1..=2).map(move |line| {
+        LogLine {
+            job,
+            text: format!("{} line {}", prefix, line),
+        }
+    })))
+}
+
+#[yewserverhook(path = "/api/jobs/replay", method = "POST")]
+pub async fn replay_jobs(jobs: Vec<u32>) -> Result<impl Stream<Item = u32>, String> {
+    Ok(futures_util::stream::iter(jobs))
+}
@@ -0,0 +1,33 @@
+use crate::{ApiHook, DataState};
+use yew_extra::transform::PayloadTransform;
+use yew_server_hook::yewserverhook;
+
+/// Reverses the bytes of the bodies
+pub struct Reverse;
+
+impl PayloadTransform for Reverse {
+    fn encode(mut body: Vec<u8>) -> Result<Vec<u8>, String> {
+        body.reverse();
+        Ok(body)
+    }
+
+    fn decode(mut body: Vec<u8>) -> Result<Vec<u8>, String> {
+        body.reverse();
+        Ok(body)
+    }
+}
+
+#[yewserverhook(path = "/api/secret-notes", method = "POST", transform = "Reverse")]
+pub async fn save_note(text: String) -> Result<String, String> {
+    Ok(text.to_uppercase())
+}
+
+#[yewserverhook(path = "/api/secret-notes", method = "GET", transform = "Reverse")]
+pub async fn get_notes(limit: u32) -> Result<Vec<String>, String> {
+    Ok(vec![limit.to_string()])
+}
+
+#[yewserverhook(path = "/api/secret-notes/plain", method = "POST")]
+pub async fn save_plain_note(text: String) -> Result<String, String> {
+    Ok(text)
+}
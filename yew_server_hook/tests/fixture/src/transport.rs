@@ -0,0 +1,7 @@
+use crate::{ApiHook, DataState};
+use yew_server_hook::yewserverhook;
+
+#[yewserverhook(path = "/api/notes", method = "POST")]
+pub async fn add_note(text: String) -> Result<String, String> {
+    Ok(text.to_uppercase())
+}
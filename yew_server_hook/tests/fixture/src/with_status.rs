@@ -0,0 +1,20 @@
+use crate::{ApiHook, DataState};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use yew_extra::WithStatus;
+use yew_server_hook::yewserverhook;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Order {
+    pub id: u32,
+}
+
+#[yewserverhook(path = "/api/orders")]
+pub async fn create_order(id: u32) -> Result<WithStatus<Order>, String> {
+    Ok(WithStatus::created(Order { id }))
+}
+
+#[yewserverhook(path = "/api/exports", blocking = true)]
+pub async fn start_export(name: String) -> Result<(StatusCode, String), String> {
+    Ok((StatusCode::ACCEPTED, name))
+}
@@ -0,0 +1,24 @@
+use futures_util::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use yew_extra::ws::Inbound;
+use yew_server_hook::yewserverws;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatIn {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatOut {
+    pub from: String,
+    pub text: String,
+}
+
+/// Messages of the chat room
+#[yewserverws(path = "/ws/chat")]
+pub async fn chat(inbound: Inbound<ChatIn>) -> Result<impl Stream<Item = ChatOut>, String> {
+    Ok(inbound.map(|message| ChatOut {
+        from: "ada".to_string(),
+        text: message.text,
+    }))
+}
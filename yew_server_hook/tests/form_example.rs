@@ -0,0 +1,149 @@
+use yew_server_hook::yewserverhook;
+
+// Integration test rendering the generated form components
+use serde::{Deserialize, Serialize};
+use yew_extra::FormSelect;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+// Enums are rendered as a select, so they provide their options
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum Role {
+    #[default]
+    Member,
+    Admin,
+}
+
+impl FormSelect for Role {
+    fn options() -> Vec<Self> {
+        vec![Role::Member, Role::Admin]
+    }
+
+    fn label(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+// Generates a CreateMemberFormFields component with a text, number, checkbox and select input
+#[yewserverhook(path = "/api/members", form = true)]
+pub async fn create_member(
+    name: String,
+    age: u32,
+    active: bool,
+    role: Role,
+) -> Result<String, String> {
+    Ok(format!(
+        "Created {} ({}, {}, {:?})",
+        name, age, active, role
+    ))
+}
+
+// Forms work with any mutation method
+#[yewserverhook(path = "/api/member", method = "PUT", form = true)]
+pub async fn rename_member(id: String, name: String) -> Result<String, String> {
+    Ok(format!("Renamed {} to {}", id, name))
+}
+
+// Optional fields may be left empty, borrowed ones are edited as their owned type, and the
+// path parameter is passed to the mutation hook the form submits through
+#[yewserverhook(path = "/api/members/{id}/profile", method = "PATCH", form = true)]
+pub async fn update_profile(
+    id: u32,
+    nickname: Option<String>,
+    age: Option<u8>,
+    role: Option<Role>,
+    bio: &str,
+) -> Result<String, String> {
+    Ok(format!(
+        "Updated {}: {:?} {:?} {:?} {}",
+        id, nickname, age, role, bio
+    ))
+}
+
+#[yew::function_component]
+pub fn CreateMemberPage() -> yew::Html {
+    let on_success = yew::Callback::from(|_message: String| {});
+
+    yew::html! {
+        <CreateMemberFormFields {on_success} submit_label="Create member" />
+    }
+}
+
+#[test]
+fn test_form_select_options() {
+    let labels: Vec<String> = Role::options().iter().map(FormSelect::label).collect();
+    assert_eq!(labels, vec!["Member".to_string(), "Admin".to_string()]);
+}
+
+// Renders the generated form the way the server does, without running the submit
+fn render<C: yew::BaseComponent>(props: C::Properties) -> String {
+    let renderer = yew::LocalServerRenderer::<C>::with_props(props).hydratable(false);
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(renderer.render())
+}
+
+#[test]
+fn test_form_renders_an_input_per_parameter() {
+    let html = render::<CreateMemberPage>(());
+
+    assert!(html.starts_with(r#"<form class="form-fields">"#));
+    for field in [
+        r#"<label for="create_member-name">name</label><input value="" id="create_member-name" name="name" type="text">"#,
+        r#"<input value="0" id="create_member-age" name="age" type="number">"#,
+        r#"<input id="create_member-active" name="active" type="checkbox">"#,
+        r#"<select id="create_member-role" name="role"><option value="0" selected="selected">Member</option><option value="1">Admin</option></select>"#,
+    ] {
+        assert!(html.contains(field), "{} not in {}", field, html);
+    }
+    // The fields are submitted by the form's only button
+    assert!(html.ends_with(r#"<button type="submit">Create member</button></form>"#));
+    assert_eq!(html.matches("<button").count(), 1);
+}
+
+#[test]
+fn test_form_submit_label_defaults_to_submit() {
+    let html = render::<RenameMemberFormFields>(RenameMemberFormFieldsProps {
+        on_success: None,
+        on_error: None,
+        submit_label: None,
+    });
+
+    assert!(html.contains(r#"name="id" type="text""#));
+    assert!(html.contains(r#"name="name" type="text""#));
+    assert!(html.ends_with(r#"<button type="submit">Submit</button></form>"#));
+}
+
+#[test]
+fn test_form_leaves_optional_fields_empty() {
+    let html = render::<UpdateProfileFormFields>(UpdateProfileFormFieldsProps {
+        on_success: None,
+        on_error: None,
+        submit_label: None,
+    });
+
+    for field in [
+        r#"<input value="0" id="update_profile-id" name="id" type="number">"#,
+        r#"<input value="" id="update_profile-nickname" name="nickname" type="text">"#,
+        r#"<input value="" id="update_profile-age" name="age" type="number">"#,
+        r#"<select id="update_profile-role" name="role"><option value="" selected="selected"></option><option value="0">Member</option><option value="1">Admin</option></select>"#,
+        r#"<input value="" id="update_profile-bio" name="bio" type="text">"#,
+    ] {
+        assert!(html.contains(field), "{} not in {}", field, html);
+    }
+}
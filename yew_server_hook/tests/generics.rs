@@ -0,0 +1,123 @@
+// Integration test to verify generic server functions expand into one endpoint per instance
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+pub trait Record: Serialize + DeserializeOwned + Clone + std::fmt::Debug + PartialEq {
+    const TABLE: &'static str;
+
+    fn sample(id: u32) -> Self;
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: u32,
+    pub name: String,
+}
+
+impl Record for User {
+    const TABLE: &'static str = "users";
+
+    fn sample(id: u32) -> Self {
+        User {
+            id,
+            name: format!("User {}", id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Post {
+    pub id: u32,
+    pub title: String,
+}
+
+impl Record for Post {
+    const TABLE: &'static str = "posts";
+
+    fn sample(id: u32) -> Self {
+        Post {
+            id,
+            title: format!("Post {}", id),
+        }
+    }
+}
+
+#[yewserverhook(
+    path = "/api/records",
+    method = "GET",
+    instance(name = "users", T = "User"),
+    instance(name = "posts", T = "Post", path = "/api/blog/posts")
+)]
+pub async fn get_record<T>(id: u32) -> Result<T, String>
+where
+    T: Record,
+{
+    if T::TABLE.is_empty() {
+        return Err("Unknown table".to_string());
+    }
+    Ok(T::sample(id))
+}
+
+#[test]
+fn test_instances_get_their_own_params() {
+    let users = GetRecordUsersParams { id: 1 };
+    let posts = GetRecordPostsParams { id: 2 };
+    assert_eq!(users.id + posts.id, 3);
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn test_instances_get_client_functions() {
+    drop(get_record_users(1));
+    drop(get_record_posts(2));
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn test_instance_handlers_use_concrete_types() {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    fn resolve<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future did not complete immediately"),
+        }
+    }
+
+    let axum::Json(user) = resolve(get_record_users_handler(axum::extract::Query(
+        GetRecordUsersParams { id: 1 },
+    )))
+    .unwrap();
+    assert_eq!(user, User::sample(1));
+
+    let axum::Json(post) = resolve(get_record_posts_handler(axum::extract::Query(
+        GetRecordPostsParams { id: 2 },
+    )))
+    .unwrap();
+    assert_eq!(post, Post::sample(2));
+
+    // The generic function stays callable from server code
+    assert_eq!(resolve(get_record::<User>(3)).unwrap(), User::sample(3));
+}
@@ -0,0 +1,39 @@
+// Integration test to verify `graphql` endpoints are exposed through the GraphQL facade
+use axum::body::{to_bytes, Body};
+use axum::http::Request;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use yew_server_hook_fixture::graphql::Note;
+
+async fn execute(query: &str) -> Value {
+    let request = Request::post("/graphql")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "query": query }).to_string()))
+        .unwrap();
+    let response = yew_extra::graphql::router("/graphql")
+        .oneshot(request)
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_reads_are_queries() {
+    let response = execute(r#"{ listNotes(search: "milk") { id text } }"#).await;
+
+    let notes: Vec<Note> = serde_json::from_value(response["data"]["listNotes"].clone()).unwrap();
+    assert_eq!(
+        notes,
+        [Note {
+            id: 1,
+            text: "milk".to_string()
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_mutations_call_their_wrapper() {
+    let response = execute("mutation { clearNotes }").await;
+    assert_eq!(response["data"], json!({ "clearNotes": 0 }));
+}
@@ -0,0 +1,42 @@
+// Integration test to verify `#[yewserverhook_group]` applies its settings to the contained endpoints
+use axum::body::to_bytes;
+use axum::http::StatusCode;
+use tower::ServiceExt;
+use yew_extra::test::{test_router, TestRequest};
+use yew_server_hook_fixture::groups::admin::DeleteUserParams;
+
+async fn send(request: TestRequest) -> (StatusCode, String) {
+    let response = test_router().oneshot(request.into_request()).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_group_prefix_and_defaults() {
+    let (status, body) = send(TestRequest::get("/api/admin/users")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, r#"["alice"]"#);
+}
+
+#[tokio::test]
+async fn test_endpoint_overrides_group_defaults() {
+    let request = TestRequest::delete("/api/admin/users").json(&DeleteUserParams { id: 3 });
+    assert_eq!(send(request).await, (StatusCode::OK, "3".to_string()));
+
+    // The group's GET applies to the other endpoints only
+    let request = TestRequest::post("/api/admin/users").json(&DeleteUserParams { id: 3 });
+    assert_eq!(send(request).await.0, StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[tokio::test]
+async fn test_nested_groups_combine_prefixes() {
+    assert_eq!(
+        send(TestRequest::get("/api/admin/reports/daily")).await,
+        (StatusCode::OK, "1".to_string())
+    );
+    assert_eq!(
+        send(TestRequest::get("/api/reports/daily")).await.0,
+        StatusCode::NOT_FOUND
+    );
+}
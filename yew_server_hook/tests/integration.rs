@@ -75,7 +75,40 @@ pub async fn search_items(query: String, limit: usize) -> Result<Vec<TestData>,
         .collect())
 }
 
+// Test that the macro expands for a GET endpoint cached by the service worker
+#[yewserverhook(
+    path = "/api/cached",
+    method = "GET",
+    sw_cache = "stale-while-revalidate"
+)]
+pub async fn get_cached_data() -> Result<Vec<TestData>, AppError> {
+    Ok(vec![])
+}
+
+// Test that the macro expands for a mutation queued by the service worker while offline
+#[yewserverhook(path = "/api/queued", background_sync = true)]
+pub async fn create_queued_data(value: String) -> Result<TestData, AppError> {
+    Ok(TestData { id: 1, value })
+}
+
+// Test that the macro expands with fetch options
+#[yewserverhook(
+    path = "/api/session",
+    method = "GET",
+    credentials = "include",
+    cache = "no-store",
+    mode = "cors",
+    redirect = "manual"
+)]
+pub async fn get_session_data() -> Result<TestData, AppError> {
+    Ok(TestData {
+        id: 1,
+        value: "session".to_string(),
+    })
+}
+
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn test_macro_expansion() {
     // This test just verifies that the macro expands without compile errors
     // The actual functionality would be tested in an integration environment
@@ -106,3 +139,19 @@ fn test_data_state_variants() {
     let empty: DataState<Vec<i32>> = DataState::Empty;
     assert!(matches!(empty, DataState::Empty));
 }
+
+#[test]
+#[cfg(not(feature = "ssr"))]
+fn test_request_options_variants() {
+    // Every client function gets a variant taking per-call request options
+    let options = yew_extra::RequestOptions::new()
+        .header("If-Match", "\"v1\"")
+        .base_url("https://api.example.com");
+
+    let _with_options = search_items_with_options("query".to_string(), 10, options.clone());
+    let _without_options = search_items("query".to_string(), 10);
+    let _no_params = get_test_data_with_options(options.clone());
+
+    // ... and a variant returning the response metadata alongside the data
+    let _with_response = get_test_by_id_with_response(1, options);
+}
@@ -0,0 +1,67 @@
+// Integration test to verify `key` lets hooks take parameters they can't compare
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+/// Not `PartialEq`, so the hook can't compare it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Query {
+    pub revision: u64,
+    pub points: Vec<(f64, f64)>,
+}
+
+/// The query only changes along with its revision
+fn query_revision(query: &Query, zoom: &u8) -> (u64, u8) {
+    (query.revision, *zoom)
+}
+
+#[yewserverhook(path = "/api/plot", method = "GET", key = "query_revision")]
+pub async fn plot(query: Query, zoom: u8) -> Result<String, String> {
+    Ok(format!("{} points at {}", query.points.len(), zoom))
+}
+
+#[derive(PartialEq, yew::Properties)]
+struct PlotProps {
+    revision: u64,
+}
+
+// The query is rebuilt on every render, only its revision tells the hook it changed
+#[yew::function_component]
+fn Plot(props: &PlotProps) -> yew::Html {
+    let query = Query {
+        revision: props.revision,
+        points: vec![(0.0, 1.0), (1.0, 2.0)],
+    };
+    let plot = use_plot(query, 2);
+
+    yew::html! { <p>{ matches!(plot.state, DataState::Loading) }</p> }
+}
+
+// Renders a component the way the server does, without running effects
+fn render<C: yew::BaseComponent>(props: C::Properties) -> String {
+    let renderer = yew::LocalServerRenderer::<C>::with_props(props).hydratable(false);
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(renderer.render())
+}
+
+#[test]
+fn test_hook_takes_parameters_it_cant_compare() {
+    assert_eq!(render::<Plot>(PlotProps { revision: 1 }), "<p>true</p>");
+}
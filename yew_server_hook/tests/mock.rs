@@ -0,0 +1,95 @@
+// Integration test to verify mock fixtures replace the server in `mock` builds
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "mock")]
+use std::future::Future;
+#[cfg(feature = "mock")]
+use std::pin::pin;
+#[cfg(feature = "mock")]
+use std::task::{Context, Poll, Waker};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Product {
+    pub id: u32,
+    pub name: String,
+}
+
+pub mod fixtures {
+    use super::Product;
+
+    pub fn products() -> Result<Vec<Product>, String> {
+        Ok(vec![Product {
+            id: 1,
+            name: "Fixture".to_string(),
+        }])
+    }
+
+    pub fn product(id: u32) -> Result<Product, String> {
+        if id == 0 {
+            return Err("Product not found".to_string());
+        }
+        Ok(Product {
+            id,
+            name: format!("Fixture {}", id),
+        })
+    }
+}
+
+#[yewserverhook(path = "/api/products", method = "GET", mock = "fixtures::products")]
+pub async fn list_products() -> Result<Vec<Product>, String> {
+    Ok(vec![])
+}
+
+#[yewserverhook(
+    path = "/api/product",
+    method = "GET",
+    mock = "fixtures::product",
+    mock_latency = 300
+)]
+pub async fn get_product(id: u32) -> Result<Product, String> {
+    Err(format!("Product {} not found", id))
+}
+
+/// Polls a future that is expected to complete without waiting
+#[cfg(feature = "mock")]
+fn resolve<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future did not complete immediately"),
+    }
+}
+
+#[test]
+fn test_mock_fixture_replaces_request() {
+    let products = list_products();
+
+    #[cfg(feature = "mock")]
+    assert_eq!(resolve(products).unwrap(), fixtures::products().unwrap());
+
+    // Without the feature the real client is generated, which needs a browser to run
+    #[cfg(not(feature = "mock"))]
+    drop(products);
+
+    // Fixtures with a simulated latency can't be resolved outside a browser
+    drop(get_product(1));
+}
@@ -0,0 +1,72 @@
+// Integration test to verify mutations get a `use_*_mutation` hook sending them on demand
+use serde::{Deserialize, Serialize};
+use yew_extra::action::Mutation;
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/notes", method = "POST")]
+pub async fn create_note(title: String, pinned: bool) -> Result<u32, String> {
+    Ok(title.len() as u32 + pinned as u32)
+}
+
+#[yewserverhook(path = "/api/notes/{id}", method = "DELETE")]
+pub async fn delete_note(id: u32) -> Result<(), String> {
+    let _ = id;
+    Ok(())
+}
+
+#[yewserverhook(path = "/api/notes", method = "GET")]
+pub async fn list_notes() -> Result<Vec<String>, String> {
+    Ok(vec![])
+}
+
+// Uses both hooks the way a page would, with their types spelled out
+#[yew::function_component]
+fn NotePage() -> yew::Html {
+    let create: Mutation<CreateNoteParams, u32> = use_create_note_mutation();
+    let delete: Mutation<(), ()> = use_delete_note_mutation(7);
+    let onclick = create.trigger.reform(|_| CreateNoteParams {
+        title: "Groceries".to_string(),
+        pinned: true,
+    });
+
+    yew::html! {
+        <>
+            <button {onclick} disabled={create.is_pending}>{ format!("{:?}", create.data) }</button>
+            <span>{ format!("{} {:?}", delete.is_pending, delete.error) }</span>
+        </>
+    }
+}
+
+// Renders a component the way the server does, without running effects
+fn render<C: yew::BaseComponent>(props: C::Properties) -> String {
+    let renderer = yew::LocalServerRenderer::<C>::with_props(props).hydratable(false);
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(renderer.render())
+}
+
+#[test]
+fn test_mutations_are_idle_until_triggered() {
+    // Rendering doesn't send anything, the hooks wait for their trigger
+    assert_eq!(
+        render::<NotePage>(()),
+        "<button>None</button><span>false None</span>"
+    );
+}
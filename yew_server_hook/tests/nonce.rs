@@ -0,0 +1,59 @@
+// Integration test to verify `nonce` rejects replayed mutations
+use axum::http::StatusCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+use yew_extra::nonce::{NONCE_HEADER, TIMESTAMP_HEADER};
+use yew_extra::test::{call_handler, TestRequest};
+use yew_server_hook_fixture::nonce::{
+    create_transfer_handler_wrapper, delete_account_handler_wrapper, CreateTransferParams,
+};
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn transfer(nonce: &str, timestamp: u64) -> TestRequest {
+    TestRequest::post("/api/transfers")
+        .header(NONCE_HEADER, nonce)
+        .header(TIMESTAMP_HEADER, &timestamp.to_string())
+        .json(&CreateTransferParams {
+            to: "ada".to_string(),
+            cents: 500,
+        })
+}
+
+#[tokio::test]
+async fn test_wrapper_checks_the_nonce() {
+    let response =
+        call_handler::<String, _, _>(create_transfer_handler_wrapper, transfer("n-1", now())).await;
+    assert_eq!(response.data.unwrap(), "500 to ada");
+
+    // A replayed request doesn't run again
+    let response =
+        call_handler::<String, _, _>(create_transfer_handler_wrapper, transfer("n-1", now())).await;
+    assert_eq!(response.status, StatusCode::CONFLICT);
+
+    let request = TestRequest::post("/api/transfers").json(&CreateTransferParams {
+        to: "ada".to_string(),
+        cents: 500,
+    });
+    let response = call_handler::<String, _, _>(create_transfer_handler_wrapper, request).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_max_age_is_configurable() {
+    // Within the default five minutes, but not the endpoint's 30s
+    let request = TestRequest::delete("/api/account")
+        .header(NONCE_HEADER, "n-2")
+        .header(TIMESTAMP_HEADER, &(now() - 60).to_string());
+    let response = call_handler::<(), _, _>(delete_account_handler_wrapper, request).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+
+    let response =
+        call_handler::<String, _, _>(create_transfer_handler_wrapper, transfer("n-2", now() - 60))
+            .await;
+    assert_eq!(response.status, StatusCode::OK);
+}
@@ -0,0 +1,103 @@
+// Integration test to verify `optimistic` patches the cache around mutations
+use serde::{Deserialize, Serialize};
+use yew_extra::cache::OptimisticUpdate;
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Todo {
+    pub title: String,
+    pub done: bool,
+}
+
+#[yewserverhook(path = "/api/todos", method = "GET")]
+pub async fn list_todos() -> Result<Vec<Todo>, String> {
+    Ok(Vec::new())
+}
+
+/// Shows the new todo in the list before the server confirms it
+pub fn add_to_list(title: &str, optimistic: &mut OptimisticUpdate) {
+    optimistic.update::<Vec<Todo>>("GET /api/todos", |todos| {
+        todos.push(Todo {
+            title: title.to_string(),
+            done: false,
+        })
+    });
+}
+
+#[yewserverhook(path = "/api/todos", method = "POST", optimistic = "add_to_list")]
+pub async fn add_todo(title: String) -> Result<Todo, String> {
+    Ok(Todo { title, done: false })
+}
+
+#[test]
+fn test_update_is_rolled_back_when_the_mutation_fails() {
+    let milk = Todo {
+        title: "Buy milk".to_string(),
+        done: true,
+    };
+    yew_extra::cache::insert("GET /api/todos", vec![milk.clone()]);
+
+    // The client function runs the update before sending the request
+    let mut optimistic = OptimisticUpdate::new();
+    add_to_list("Walk the dog", &mut optimistic);
+    let todos = yew_extra::cache::get::<Vec<Todo>>("GET /api/todos").unwrap();
+    assert_eq!(todos.value.len(), 2);
+    assert_eq!(todos.value[1].title, "Walk the dog");
+
+    // and settles it with the response
+    optimistic.settle(false);
+    let todos = yew_extra::cache::get::<Vec<Todo>>("GET /api/todos").unwrap();
+    assert_eq!(todos.value, vec![milk]);
+}
+
+#[derive(PartialEq, yew::Properties)]
+struct AddTodoProps {
+    title: String,
+}
+
+#[yew::function_component]
+fn AddTodo(props: &AddTodoProps) -> yew::Html {
+    let add = use_add_todo_mutation();
+    let onclick = {
+        let title = props.title.clone();
+        add.trigger.reform(move |_| AddTodoParams {
+            title: title.clone(),
+        })
+    };
+
+    yew::html! { <button {onclick}>{ format!("{:?}", add.data) }</button> }
+}
+
+#[test]
+fn test_rendering_leaves_the_cache_alone() {
+    let renderer = yew::LocalServerRenderer::<AddTodo>::with_props(AddTodoProps {
+        title: "Water the plants".to_string(),
+    })
+    .hydratable(false);
+    let html = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(renderer.render());
+
+    assert_eq!(html, "<button>None</button>");
+    let todos = yew_extra::cache::get::<Vec<Todo>>("GET /api/todos")
+        .map(|cached| cached.value)
+        .unwrap_or_default();
+    assert!(todos.iter().all(|todo| todo.title != "Water the plants"));
+}
@@ -0,0 +1,58 @@
+// Integration test to verify the `params` option renames the generated parameter struct
+use serde::{Deserialize, Serialize};
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+pub mod products {
+    pub use super::{ApiHook, DataState};
+    use yew_server_hook::yewserverhook;
+
+    #[yewserverhook(
+        path = "/api/products/item",
+        method = "GET",
+        params = "ProductItemParams"
+    )]
+    pub async fn get_item(id: u32) -> Result<String, String> {
+        Ok(format!("Product {}", id))
+    }
+}
+
+pub mod orders {
+    pub use super::{ApiHook, DataState};
+    use yew_server_hook::yewserverhook;
+
+    #[yewserverhook(path = "/api/orders/item", method = "POST", params = "OrderItemParams")]
+    pub async fn get_item(
+        id: u32,
+        #[validate(range(min = 1))] quantity: u32,
+    ) -> Result<String, String> {
+        Ok(format!("Order {} x{}", id, quantity))
+    }
+}
+
+// Both structs can be re-exported side by side
+pub use orders::OrderItemParams;
+pub use products::ProductItemParams;
+
+#[test]
+fn test_renamed_params_structs() {
+    let product = ProductItemParams { id: 1 };
+    let order = OrderItemParams { id: 2, quantity: 0 };
+
+    assert_eq!(product.id, 1);
+    assert!(order.validate().is_err());
+}
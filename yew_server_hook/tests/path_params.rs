@@ -0,0 +1,59 @@
+// Integration test to verify parameters named in the path are read from the URL instead of
+// the query string or the JSON body
+use axum::body::to_bytes;
+use axum::http::StatusCode;
+use tower::ServiceExt;
+use yew_extra::test::{test_router, TestRequest};
+use yew_server_hook_fixture::path_params::{ListPostsParams, RenameUserParams};
+
+async fn send(request: TestRequest) -> (StatusCode, String) {
+    let response = test_router().oneshot(request.into_request()).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_the_server_reads_the_path() {
+    assert_eq!(
+        send(TestRequest::get("/api/users/7")).await,
+        (StatusCode::OK, r#""user 7""#.to_string())
+    );
+    assert_eq!(
+        send(TestRequest::get("/api/users/ada")).await.0,
+        StatusCode::BAD_REQUEST
+    );
+}
+
+#[tokio::test]
+async fn test_other_parameters_stay_in_the_query() {
+    let request = TestRequest::get("/api/users/7/posts").query(&ListPostsParams { page: 2 });
+    assert_eq!(
+        send(request).await,
+        (StatusCode::OK, r#"["post of 7 on page 2"]"#.to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_colon_paths_are_mounted() {
+    // The body only carries the other parameters
+    let request = TestRequest::put("/api/users/7").json(&RenameUserParams {
+        name: "Ada".to_string(),
+    });
+    assert_eq!(
+        send(request).await,
+        (StatusCode::OK, r#""7 is now Ada""#.to_string())
+    );
+}
+
+#[test]
+fn test_path_parameter_names() {
+    assert_eq!(
+        yew_extra::path_params::parameter_names("/api/{org}/files/{*path}"),
+        ["org", "path"]
+    );
+    assert_eq!(
+        yew_extra::path_params::parameter_names("/api/:org/files/*path"),
+        ["org", "path"]
+    );
+}
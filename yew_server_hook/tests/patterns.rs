@@ -0,0 +1,105 @@
+// Integration test to verify parameters declared with patterns and attributes
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserFilter {
+    pub q: String,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page(pub u32);
+
+#[yewserverhook(path = "/api/users/search", method = "POST")]
+pub async fn search_users(
+    UserFilter { q, limit }: UserFilter,
+    Page(page): Page,
+    #[allow(unused)] tenant: String,
+    mut offset: u32,
+) -> Result<Vec<String>, String> {
+    offset += page * limit as u32;
+    Ok(vec![format!("{} from {}", q, offset)])
+}
+
+#[test]
+fn test_patterns_become_named_fields() {
+    // Patterns are named after the type they destructure
+    let params = SearchUsersParams {
+        user_filter: UserFilter {
+            q: "ann".to_string(),
+            limit: 10,
+        },
+        page: Page(2),
+        tenant: "acme".to_string(),
+        offset: 5,
+    };
+
+    let json = serde_json::to_value(&params).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "user_filter": { "q": "ann", "limit": 10 },
+            "page": 2,
+            "tenant": "acme",
+            "offset": 5,
+        })
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn test_handler_rebinds_patterns() {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    let handler = search_users_handler(axum::Json(SearchUsersParams {
+        user_filter: UserFilter {
+            q: "ann".to_string(),
+            limit: 10,
+        },
+        page: Page(2),
+        tenant: "acme".to_string(),
+        offset: 5,
+    }));
+
+    let mut handler = pin!(handler);
+    let Poll::Ready(Ok(axum::Json(users))) = handler
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    else {
+        panic!("handler did not complete successfully");
+    };
+    assert_eq!(users, vec!["ann from 25".to_string()]);
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn test_client_takes_named_params() {
+    drop(search_users(
+        UserFilter {
+            q: "ann".to_string(),
+            limit: 10,
+        },
+        Page(2),
+        "acme".to_string(),
+        5,
+    ));
+}
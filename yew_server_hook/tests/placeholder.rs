@@ -0,0 +1,91 @@
+// Integration test to verify hooks start with the data of their `placeholder_data` function
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Product {
+    pub id: u32,
+    pub name: String,
+}
+
+/// Shows the product from the cached list while its details load
+pub fn product_from_list(id: &u32) -> Option<Product> {
+    yew_extra::cache::get::<Vec<Product>>("GET /api/products")
+        .and_then(|cached| cached.value.into_iter().find(|product| product.id == *id))
+}
+
+#[yewserverhook(
+    path = "/api/product",
+    method = "GET",
+    placeholder_data = "product_from_list"
+)]
+pub async fn get_product(id: u32) -> Result<Product, String> {
+    Ok(Product {
+        id,
+        name: format!("Product {}", id),
+    })
+}
+
+#[derive(PartialEq, yew::Properties)]
+struct ProductNameProps {
+    id: u32,
+}
+
+#[yew::function_component]
+fn ProductName(props: &ProductNameProps) -> yew::Html {
+    let product = use_get_product(props.id);
+    let name = match &product.state {
+        DataState::Data(product) => product.name.clone(),
+        DataState::Loading => "loading".to_string(),
+        _ => "other".to_string(),
+    };
+
+    yew::html! { <p>{ format!("{} {}", name, product.is_loading) }</p> }
+}
+
+// Renders a component the way the server does, without running effects
+fn render<C: yew::BaseComponent>(props: C::Properties) -> String {
+    let renderer = yew::LocalServerRenderer::<C>::with_props(props).hydratable(false);
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(renderer.render())
+}
+
+#[test]
+fn test_placeholder_seeds_the_hook_state() {
+    yew_extra::cache::insert(
+        "GET /api/products",
+        vec![Product {
+            id: 7,
+            name: "Lamp".to_string(),
+        }],
+    );
+
+    // The first render shows the placeholder while the product loads
+    assert_eq!(
+        render::<ProductName>(ProductNameProps { id: 7 }),
+        "<p>Lamp true</p>"
+    );
+    // Without one, the hook starts out loading
+    assert_eq!(
+        render::<ProductName>(ProductNameProps { id: 8 }),
+        "<p>loading false</p>"
+    );
+}
@@ -0,0 +1,63 @@
+// Integration test to verify `poll_interval` hooks refetch on an interval
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/status", method = "GET", poll_interval = 5000)]
+pub async fn get_status() -> Result<String, String> {
+    Ok("running".to_string())
+}
+
+#[yewserverhook(path = "/api/jobs", method = "GET", poll_interval = "2m")]
+pub async fn list_jobs(owner: String) -> Result<Vec<String>, String> {
+    Ok(vec![owner])
+}
+
+#[yewserverhook(path = "/api/report", method = "GET")]
+pub async fn get_report() -> Result<String, String> {
+    Ok("report".to_string())
+}
+
+#[yew::function_component]
+fn Dashboard() -> yew::Html {
+    let status = use_get_status();
+    let jobs = use_list_jobs("ada".to_string());
+    let report = use_get_report();
+    let loading = [
+        matches!(status.state, DataState::Loading),
+        matches!(jobs.state, DataState::Loading),
+        matches!(report.state, DataState::Loading),
+    ];
+
+    yew::html! { <p>{ format!("{:?}", loading) }</p> }
+}
+
+// Renders a component the way the server does, without running effects
+fn render<C: yew::BaseComponent>(props: C::Properties) -> String {
+    let renderer = yew::LocalServerRenderer::<C>::with_props(props).hydratable(false);
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(renderer.render())
+}
+
+#[test]
+fn test_polling_hooks_render_on_the_server() {
+    // The server renders one pass without starting the timers, like any other hook
+    assert_eq!(render::<Dashboard>(()), "<p>[true, true, true]</p>");
+}
@@ -0,0 +1,44 @@
+// Integration test to verify GET parameters go through yew_extra::query, so `Vec`
+// parameters are sent as repeated keys
+use yew_extra::test::{call_handler, TestRequest};
+use yew_server_hook_fixture::query_params::{search_handler_wrapper, SearchParams};
+
+#[test]
+fn test_params_round_trip_with_repeated_keys() {
+    let params = SearchParams {
+        q: "boots".to_string(),
+        tag: vec!["sale".to_string(), "red".to_string()],
+        page: None,
+    };
+    let query = yew_extra::query::to_query_string(&params).unwrap();
+    assert_eq!(query, "q=boots&tag=sale&tag=red");
+
+    let parsed: SearchParams = yew_extra::query::from_query_str(&query).unwrap();
+    assert_eq!(parsed.tag, ["sale", "red"]);
+
+    let parsed: SearchParams = yew_extra::query::from_query_str("q=boots&page=2").unwrap();
+    assert!(parsed.tag.is_empty());
+    assert_eq!(parsed.page, Some(2));
+}
+
+#[tokio::test]
+async fn test_wrapper_gets_every_tag_in_order() {
+    let response = call_handler::<String, _, _>(
+        search_handler_wrapper,
+        TestRequest::get("/api/search?tag=b&q=x&tag=a"),
+    )
+    .await;
+    assert_eq!(response.data.unwrap(), "x [b,a] None");
+
+    let params = SearchParams {
+        q: "boots".to_string(),
+        tag: vec!["sale".to_string()],
+        page: Some(2),
+    };
+    let response = call_handler::<String, _, _>(
+        search_handler_wrapper,
+        TestRequest::get("/api/search").query(&params),
+    )
+    .await;
+    assert_eq!(response.data.unwrap(), "boots [sale] Some(2)");
+}
@@ -0,0 +1,41 @@
+// Integration test to verify `raw_body` keeps the request body for `extract_body()`
+use axum::http::StatusCode;
+use yew_extra::test::{call_handler, TestRequest};
+use yew_server_hook_fixture::raw_body::{
+    add_note_handler_wrapper, payment_webhook_handler_wrapper,
+};
+
+#[tokio::test]
+async fn test_wrapper_keeps_the_body() {
+    let payload = br#"{"id":"evt_1","type":"charge.succeeded"}"#;
+    let response = call_handler::<usize, _, _>(
+        payment_webhook_handler_wrapper,
+        TestRequest::post("/api/webhooks/payments")
+            .header("content-type", "application/json")
+            .body(payload.to_vec()),
+    )
+    .await;
+    assert_eq!(response.data.unwrap(), payload.len());
+}
+
+#[tokio::test]
+async fn test_body_limit() {
+    let response = call_handler::<usize, _, _>(
+        payment_webhook_handler_wrapper,
+        TestRequest::post("/api/webhooks/payments").body(vec![b'x'; 64 * 1024 + 1]),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_params_are_parsed_from_the_kept_body() {
+    let response = call_handler::<String, _, _>(
+        add_note_handler_wrapper,
+        TestRequest::post("/api/raw-notes")
+            .header("content-type", "application/json")
+            .body(br#"{"title":"Groceries","pinned":true}"#.to_vec()),
+    )
+    .await;
+    assert_eq!(response.data.unwrap(), "Groceries (2 fields)");
+}
@@ -0,0 +1,38 @@
+// Integration test to verify a `RequestContext` parameter is filled in by the server handler
+use axum::body::to_bytes;
+use yew_extra::test::{call_handler, TestRequest};
+use yew_server_hook_fixture::request_context::{greet_handler_wrapper, GreetParams};
+
+#[tokio::test]
+async fn test_wrapper_passes_the_request() {
+    // The client only sends the other parameters
+    let request = TestRequest::post("/api/greeting")
+        .header("host", "example.com")
+        .json(&GreetParams {
+            name: "Ada".to_string(),
+        });
+
+    let response = call_handler::<String, _, _>(greet_handler_wrapper, request).await;
+    assert_eq!(response.data.unwrap(), "POST Ada from example.com");
+}
+
+#[tokio::test]
+async fn test_wrapper_runs_in_a_request_scope() {
+    // Called directly, outside the scope `call_handler` adds, the parts `extract()` reads
+    // still stay with the task handling each request
+    let greet = |host: &str| {
+        let request = TestRequest::post("/api/greeting")
+            .header("host", host)
+            .json(&GreetParams {
+                name: "Ada".to_string(),
+            });
+        async move {
+            let response = greet_handler_wrapper(request.into_request()).await;
+            to_bytes(response.into_body(), usize::MAX).await.unwrap()
+        }
+    };
+
+    let (first, second) = tokio::join!(greet("one.example.com"), greet("two.example.com"));
+    assert_eq!(first, r#""POST Ada from one.example.com""#);
+    assert_eq!(second, r#""POST Ada from two.example.com""#);
+}
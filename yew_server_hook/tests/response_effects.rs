@@ -0,0 +1,70 @@
+// Integration test to verify the generated handlers send the flash cookie, start the work
+// queued with `after_response()` and report mutations to the audit sink
+use axum::http::header::SET_COOKIE;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use yew_extra::audit::{set_audit_sink, AuditRecord};
+use yew_extra::test::{call_handler, TestRequest, TestResponse};
+use yew_server_hook_fixture::profile::{
+    get_profile_handler_wrapper, save_profile_handler_wrapper, PROFILE_INDEXED,
+};
+
+async fn save_profile() -> TestResponse<String> {
+    call_handler(
+        save_profile_handler_wrapper,
+        TestRequest::post("/api/profile").json(&serde_json::json!({ "name": "ada" })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_flash_cookie_goes_out_with_the_response() {
+    let response = save_profile().await;
+    assert_eq!(response.data.unwrap(), "ada");
+
+    let cookie = response.headers[SET_COOKIE].to_str().unwrap();
+    assert!(
+        cookie.starts_with("yew_flash=info%3AProfile%20saved;"),
+        "{}",
+        cookie
+    );
+}
+
+#[tokio::test]
+async fn test_queued_work_runs_after_the_response() {
+    save_profile().await;
+
+    // The work is spawned once the body has been read
+    for _ in 0..100 {
+        if PROFILE_INDEXED.load(Ordering::SeqCst) {
+            return;
+        }
+        tokio::task::yield_now().await;
+    }
+    panic!("the queued work didn't run");
+}
+
+#[tokio::test]
+async fn test_only_mutations_are_audited() {
+    let records = Arc::new(Mutex::new(Vec::<AuditRecord>::new()));
+    set_audit_sink({
+        let records = records.clone();
+        move |record| {
+            records.lock().unwrap().push(record);
+            async {}
+        }
+    });
+
+    call_handler::<String, _, _>(
+        get_profile_handler_wrapper,
+        TestRequest::get("/api/profile"),
+    )
+    .await;
+    save_profile().await;
+
+    let records = records.lock().unwrap();
+    assert!(records.iter().all(|record| record.method == "POST"));
+    assert!(records
+        .iter()
+        .any(|record| record.path == "/api/profile" && record.succeeded()));
+}
@@ -0,0 +1,32 @@
+// Integration test to verify retried mutations only run once on the server
+use axum::http::StatusCode;
+use std::sync::atomic::Ordering;
+use yew_extra::background_sync::IDEMPOTENCY_HEADER;
+use yew_extra::test::{call_handler, TestRequest};
+use yew_server_hook_fixture::retry::{
+    place_order_handler_wrapper, PlaceOrderParams, ORDERS_PLACED,
+};
+
+fn order(key: &str) -> TestRequest {
+    TestRequest::post("/api/checkout")
+        .header(IDEMPOTENCY_HEADER, key)
+        .json(&PlaceOrderParams {
+            sku: "lamp".to_string(),
+            quantity: 2,
+        })
+}
+
+#[tokio::test]
+async fn test_retries_with_the_same_key_get_the_first_response() {
+    let first = call_handler::<String, _, _>(place_order_handler_wrapper, order("k-1")).await;
+    let placed = ORDERS_PLACED.load(Ordering::SeqCst);
+    let retried = call_handler::<String, _, _>(place_order_handler_wrapper, order("k-1")).await;
+
+    assert_eq!(first.status, StatusCode::OK);
+    assert_eq!(retried.data.unwrap(), first.data.unwrap());
+    assert_eq!(ORDERS_PLACED.load(Ordering::SeqCst), placed);
+
+    // Another key places another order
+    let other = call_handler::<String, _, _>(place_order_handler_wrapper, order("k-2")).await;
+    assert!(other.data.unwrap().ends_with(": 2 x lamp"));
+}
@@ -0,0 +1,69 @@
+// Integration test to verify the metadata attached to registered routes
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use tower::ServiceExt;
+use yew_extra::route_registry::{find_route, matched_route, Auth, RouteMetadata};
+use yew_extra::test::test_router;
+
+// Links the fixture's endpoints into the registry
+extern crate yew_server_hook_fixture;
+
+#[test]
+fn test_metadata_is_attached_to_the_route() {
+    let route = find_route(&Method::POST, "/api/invoices").unwrap();
+    assert_eq!(route.metadata.auth, Some(Auth::Required));
+    assert_eq!(route.metadata.tags, ["billing", "audit"]);
+    assert_eq!(route.metadata.owner, Some("payments"));
+}
+
+#[test]
+fn test_a_single_tag() {
+    let route = find_route(&Method::GET, "/api/status").unwrap();
+    assert_eq!(route.metadata.tags, ["public"]);
+    assert_eq!(route.metadata.auth, None);
+}
+
+#[test]
+fn test_routes_without_metadata_keep_the_default() {
+    let route = find_route(&Method::GET, "/api/plain").unwrap();
+    assert_eq!(route.metadata, RouteMetadata::new());
+}
+
+// Rejects anonymous requests to the routes requiring sign-in
+async fn require_auth(request: Request<Body>, next: Next) -> Response {
+    let required =
+        matched_route(&request).is_some_and(|route| route.metadata.auth == Some(Auth::Required));
+    if required && !request.headers().contains_key("authorization") {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    next.run(request).await
+}
+
+#[tokio::test]
+async fn test_middleware_reads_the_metadata() {
+    let app = test_router().route_layer(middleware::from_fn(require_auth));
+    let send = |request: Request<Body>| app.clone().oneshot(request);
+
+    let invoice = || Request::post("/api/invoices").header("content-type", "application/json");
+    let response = send(invoice().body(Body::from(r#"{"amount":5}"#)).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = send(
+        invoice()
+            .header("authorization", "Bearer token")
+            .body(Body::from(r#"{"amount":5}"#))
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send(Request::get("/api/status").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,90 @@
+// Integration test to verify debug-mode response schema checks
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use yew_extra::schema::validate_json;
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum Status {
+    Draft,
+    Published,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Author {
+    pub name: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Article {
+    pub id: u32,
+    pub title: String,
+    pub status: Status,
+    pub authors: Vec<Author>,
+}
+
+#[yewserverhook(path = "/api/articles", method = "GET", schema_check = true)]
+pub async fn list_articles(status: Status) -> Result<Vec<Article>, String> {
+    let articles: Vec<Article> = Vec::new();
+    Ok(articles
+        .into_iter()
+        .filter(|article| article.status == status)
+        .collect())
+}
+
+fn article(json_authors: &str) -> String {
+    format!(
+        r#"{{"id": 7, "title": "Hello", "status": "Published", "authors": {}}}"#,
+        json_authors
+    )
+}
+
+#[test]
+fn test_matching_response_passes() {
+    let json = format!("[{}]", article(r#"[{"name": "Ann", "email": null}]"#));
+    assert!(validate_json::<Vec<Article>>(&json).is_ok());
+}
+
+#[test]
+fn test_mismatch_reports_path_and_types() {
+    let json = format!("[{}]", article(r#"[{"name": "Ann"}, {"name": 42}]"#));
+    assert_eq!(
+        validate_json::<Vec<Article>>(&json).unwrap_err(),
+        "Response doesn't match the expected type at $[0].authors[1].name: expected string, found number 42"
+    );
+
+    let json = r#"{"id": 7, "title": "Hello", "authors": []}"#;
+    assert_eq!(
+        validate_json::<Article>(json).unwrap_err(),
+        "Response doesn't match the expected type at $: missing field `status`"
+    );
+
+    let json = r#"{"id": 7, "title": "Hello", "status": "Archived", "authors": []}"#;
+    assert_eq!(
+        validate_json::<Article>(json).unwrap_err(),
+        "Response doesn't match the expected type at $.status: expected one of \"Draft\", \"Published\", found \"Archived\""
+    );
+
+    let json = r#"{"id": -1, "title": "Hello", "status": "Draft", "authors": []}"#;
+    assert_eq!(
+        validate_json::<Article>(json).unwrap_err(),
+        "Response doesn't match the expected type at $.id: -1 is less than 0"
+    );
+}
@@ -0,0 +1,59 @@
+// Integration test to verify `shared_data` hooks keep their data in an `Rc`
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
+use yew::functional::Hook;
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Row {
+    pub id: u32,
+    pub label: String,
+}
+
+#[yewserverhook(path = "/api/rows", method = "GET", shared_data = true)]
+pub async fn get_rows(limit: u32) -> Result<Vec<Row>, String> {
+    Ok((0..limit)
+        .map(|id| Row {
+            id,
+            label: format!("Row {}", id),
+        })
+        .collect())
+}
+
+#[yewserverhook(path = "/api/rows/first", method = "GET")]
+pub async fn get_first_row() -> Result<Row, String> {
+    Ok(Row {
+        id: 0,
+        label: "Row 0".to_string(),
+    })
+}
+
+fn hook_output<T, H: Hook<Output = ApiHook<T>>>(_hook: H) {}
+
+#[test]
+fn test_shared_data_hook_returns_rc() {
+    hook_output::<Rc<Vec<Row>>, _>(use_get_rows(10));
+    hook_output::<Rc<Vec<Row>>, _>(use_get_rows_with_options(
+        10,
+        yew_extra::RequestOptions::default(),
+    ));
+
+    // Without the option the data is returned as is
+    hook_output::<Row, _>(use_get_first_row());
+}
@@ -0,0 +1,67 @@
+// Integration test to verify endpoints declared with `state` take the app state as
+// `State<T>` and read it with `state()`
+use axum::body::to_bytes;
+use axum::http::StatusCode;
+use tower::ServiceExt;
+use yew_extra::test::{test_router, test_router_with_state, TestRequest};
+use yew_server_hook_fixture::state::{get_greeting, AppState, GreetParams};
+
+fn app_state() -> AppState {
+    AppState {
+        greeting: "Hello".to_string(),
+    }
+}
+
+async fn send(app: axum::Router, request: TestRequest) -> (StatusCode, String) {
+    let response = app.oneshot(request.into_request()).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_body_reads_the_state() {
+    let app = test_router_with_state(app_state());
+    assert_eq!(
+        send(app.clone(), TestRequest::get("/api/greeting")).await,
+        (StatusCode::OK, r#""Hello""#.to_string())
+    );
+
+    let request = TestRequest::post("/api/greet").json(&GreetParams {
+        name: "Ada".to_string(),
+    });
+    assert_eq!(
+        send(app, request).await,
+        (StatusCode::OK, r#""Hello, Ada""#.to_string())
+    );
+
+    // The function itself takes the state first on the server
+    assert_eq!(get_greeting(app_state()).await.unwrap(), "Hello");
+}
+
+#[tokio::test]
+async fn test_plain_router_asks_for_the_state() {
+    assert_eq!(
+        send(test_router(), TestRequest::get("/api/greeting")).await,
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "GET /api/greeting needs the app state, build the router with build_router_with_state()"
+                .to_string()
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_state_of_another_type_is_rejected() {
+    let (status, body) = send(
+        test_router_with_state(42u32),
+        TestRequest::get("/api/greeting"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(
+        body.contains("needs an app state of type AppState"),
+        "{}",
+        body
+    );
+}
@@ -0,0 +1,56 @@
+// Integration test to verify `impl Stream` returns are streamed as NDJSON
+use axum::body::to_bytes;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use tower::ServiceExt;
+use yew_extra::ndjson::LineDecoder;
+use yew_extra::test::{test_router, TestRequest};
+use yew_server_hook_fixture::stream::{JobLogsParams, LogLine, ReplayJobsParams};
+
+async fn send(request: TestRequest) -> (StatusCode, String, Vec<u8>) {
+    let response = test_router().oneshot(request.into_request()).await.unwrap();
+    let status = response.status();
+    let content_type = response.headers()[CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_type, body.to_vec())
+}
+
+#[tokio::test]
+async fn test_items_are_sent_as_ndjson() {
+    let request = TestRequest::get("/api/jobs/7/logs").query(&JobLogsParams {
+        level: "info".to_string(),
+    });
+    let (status, content_type, body) = send(request).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, yew_extra::ndjson::CONTENT_TYPE);
+    let mut decoder = LineDecoder::new();
+    let mut lines: Vec<LogLine> = decoder.push(&body).unwrap();
+    lines.extend(decoder.finish::<LogLine>().unwrap());
+    assert_eq!(
+        lines,
+        [
+            LogLine {
+                job: 7,
+                text: "INFO line 1".to_string(),
+            },
+            LogLine {
+                job: 7,
+                text: "INFO line 2".to_string(),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_mutations_stream_too() {
+    let request =
+        TestRequest::post("/api/jobs/replay").json(&ReplayJobsParams { jobs: vec![3, 1] });
+    let (_, content_type, body) = send(request).await;
+
+    assert_eq!(content_type, yew_extra::ndjson::CONTENT_TYPE);
+    assert_eq!(body, b"3\n1\n");
+}
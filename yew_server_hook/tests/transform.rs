@@ -0,0 +1,58 @@
+// Integration test to verify endpoints declared with `transform` pass their bodies through
+// the transform on the server
+use yew_extra::test::{call_handler, TestRequest};
+use yew_extra::transform::PayloadTransform;
+use yew_server_hook_fixture::transform::{
+    get_notes_handler_wrapper, save_note_handler_wrapper, save_plain_note_handler_wrapper, Reverse,
+    SaveNoteParams,
+};
+
+fn reversed(body: &str) -> String {
+    body.chars().rev().collect()
+}
+
+#[tokio::test]
+async fn test_wrapper_decodes_requests_and_encodes_responses() {
+    let body = serde_json::to_vec(&SaveNoteParams {
+        text: "hello".to_string(),
+    })
+    .unwrap();
+    let request = TestRequest::post("/api/secret-notes")
+        .header("content-type", "application/json")
+        .body(Reverse::encode_request(body).unwrap());
+
+    let response = call_handler::<String, _, _>(save_note_handler_wrapper, request).await;
+    assert_eq!(response.body, reversed(r#""HELLO""#));
+}
+
+#[tokio::test]
+async fn test_reads_only_encode_responses() {
+    let response = call_handler::<Vec<String>, _, _>(
+        get_notes_handler_wrapper,
+        TestRequest::get("/api/secret-notes?limit=3"),
+    )
+    .await;
+    assert_eq!(response.body, reversed(r#"["3"]"#));
+}
+
+#[tokio::test]
+async fn test_endpoints_without_a_transform_send_json() {
+    let response = call_handler::<String, _, _>(
+        save_plain_note_handler_wrapper,
+        TestRequest::post("/api/secret-notes/plain").json(&SaveNoteParams {
+            text: "hello".to_string(),
+        }),
+    )
+    .await;
+    assert_eq!(response.data.unwrap(), "hello");
+}
+
+#[test]
+fn test_transform_round_trips_the_json() {
+    let sent = Reverse::encode_request(br#""hello""#.to_vec()).unwrap();
+    assert_eq!(sent, br#""olleh""#);
+
+    let received = Reverse::encode_response(sent).unwrap();
+    let text = yew_extra::transform::response_text::<Reverse>(received).unwrap();
+    assert_eq!(serde_json::from_str::<String>(&text).unwrap(), "olleh");
+}
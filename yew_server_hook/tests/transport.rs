@@ -0,0 +1,23 @@
+// Integration test to verify requests sent over Tauri IPC, the desktop side of the
+// `transport` feature, reach the generated handlers
+use yew_extra::route_registry::routes;
+use yew_extra::transport::{dispatch, IpcRequest};
+use yew_server_hook_fixture::transport::AddNoteParams;
+
+#[tokio::test]
+async fn test_ipc_requests_reach_the_endpoint() {
+    let body = serde_json::to_string(&AddNoteParams {
+        text: "buy milk".to_string(),
+    })
+    .unwrap();
+    let request = IpcRequest {
+        method: "POST".to_string(),
+        uri: "/api/notes".to_string(),
+        headers: vec![("content-type".to_string(), "application/json".to_string())],
+        body: Some(body),
+    };
+
+    let response = dispatch(routes(), request).await;
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, "\"BUY MILK\"");
+}
@@ -0,0 +1,54 @@
+// Integration test to verify `typescript` endpoints are listed in the TypeScript client
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, yew_extra::schema::JsonSchema)]
+#[schemars(crate = "yew_extra::schema::schemars")]
+pub struct Todo {
+    pub id: u32,
+    pub title: String,
+    pub done: bool,
+}
+
+#[yewserverhook(path = "/api/todos", method = "GET", typescript = true)]
+pub async fn list_todos(done: Option<bool>) -> Result<Vec<Todo>, String> {
+    Ok(vec![Todo {
+        id: 1,
+        title: "Write the SDK".to_string(),
+        done: done.unwrap_or(false),
+    }])
+}
+
+#[yewserverhook(path = "/api/todos/count", method = "GET", typescript = true)]
+pub async fn count_todos() -> Result<u32, String> {
+    Ok(1)
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn test_client_has_the_endpoints() {
+    let client = yew_extra::typescript::generate_client();
+
+    assert!(client.contains("export type Todo = { done: boolean; id: number; title: string };"));
+    assert!(client.contains("export type ListTodosParams = { done?: boolean | null };"));
+    assert!(client.contains(
+        "export function listTodos(params: ListTodosParams, init?: RequestInit): Promise<Todo[]> {"
+    ));
+    assert!(client.contains("export function countTodos(init?: RequestInit): Promise<number> {"));
+}
@@ -0,0 +1,29 @@
+#![deny(deprecated)]
+
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/legacy", method = "GET")]
+#[deprecated(note = "use get_current instead")]
+pub async fn get_legacy() -> Result<String, String> {
+    Ok("legacy".to_string())
+}
+
+fn main() {
+    drop(get_legacy());
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/users", method = "POST")]
+pub async fn create_user(#[validate(length(message = "too short"))] name: String) -> Result<String, String> {
+    Ok(name)
+}
+
+#[yewserverhook(path = "/api/orders", method = "POST")]
+pub async fn create_order(#[validate(range(message = "out of range"))] quantity: u32) -> Result<u32, String> {
+    Ok(quantity)
+}
+
+#[yewserverhook(path = "/api/contacts", method = "POST")]
+pub async fn create_contact(#[validate(regex = "[a-z+")] email: String) -> Result<String, String> {
+    Ok(email)
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+#![deny(deprecated)]
+
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/todos", method = "POST")]
+pub async fn add_todo(title: String) -> Result<String, String> {
+    Ok(title)
+}
+
+// Rendering a component shouldn't send a mutation, only `trigger` should
+#[yew::function_component]
+fn AddTodo() -> yew::Html {
+    let added = use_add_todo("Buy milk".to_string());
+    let _ = added;
+    yew::html! {}
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+#[yewserverhook(path = "/api/tags", method = "GET")]
+pub async fn list_tags() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+#[yewserverhook(path = "/api/tags", method = "POST")]
+pub async fn add_tag(tag: String) -> Result<String, String> {
+    Ok(tag)
+}
+
+// Only reads are cached, so only they can be prefetched or read as a resource
+fn warm_up() {
+    let _ = prefetch_add_tag("rust".to_string());
+    let _ = resource_add_tag("rust".to_string());
+}
+
+// and only mutations are sent on demand
+#[yew::function_component]
+fn Tags() -> yew::Html {
+    let tags = use_list_tags_mutation();
+    let _ = tags;
+    yew::html! {}
+}
+
+fn main() {}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[derive(Clone)]
+pub struct AppState;
+
+pub struct Reverse;
+
+#[yewserverhook(path = "/api/notes", method = "GET", graphql = true, transform = "Reverse")]
+pub async fn list_notes() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+#[yewserverhook(path = "/api/tags", method = "GET", graphql = true, state = AppState)]
+pub async fn list_tags() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+#[yewserverhook(path = "/api/users/{id}", method = "GET", typescript = true)]
+pub async fn get_user(id: u32) -> Result<u32, String> {
+    Ok(id)
+}
+
+#[yewserverhook(path = "/api/logs", method = "GET", transform = "Reverse", stream_json = true)]
+pub async fn get_logs() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+#[yewserverhook(path = "/files", method = "GET", base = "https://files.example.com", sw_cache = "cache-first")]
+pub async fn list_files() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+#[yewserverhook(path = "/api/orders/export", method = "GET", coalesce = true, stream_json = true)]
+pub async fn export_orders() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/teams", form = true)]
+pub async fn create_team(name: String, members: Vec<String>) -> Result<String, String> {
+    Ok(format!("{} ({})", name, members.len()))
+}
+
+#[yewserverhook(path = "/api/points", form = true)]
+pub async fn create_point(position: (i32, i32)) -> Result<i32, String> {
+    Ok(position.0 + position.1)
+}
+
+#[yewserverhook(path = "/api/flags", form = true)]
+pub async fn create_flag(name: String, enabled: Option<bool>) -> Result<String, String> {
+    Ok(format!("{} {:?}", name, enabled))
+}
+
+#[yewserverhook(path = "/api/tags", form = true)]
+pub async fn create_tag(labels: &[String]) -> Result<usize, String> {
+    Ok(labels.len())
+}
+
+fn main() {}
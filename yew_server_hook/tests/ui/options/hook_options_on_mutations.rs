@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+fn todo_from_list(_title: &String) -> Option<String> {
+    None
+}
+
+fn title_key(title: &String) -> String {
+    title.clone()
+}
+
+#[yewserverhook(path = "/api/todos", method = "POST", placeholder_data = "todo_from_list")]
+pub async fn add_todo(title: String) -> Result<String, String> {
+    Ok(title)
+}
+
+#[yewserverhook(path = "/api/todos", method = "DELETE", key = "title_key")]
+pub async fn remove_todo(title: String) -> Result<String, String> {
+    Ok(title)
+}
+
+fn main() {}
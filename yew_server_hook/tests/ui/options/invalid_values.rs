@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/status", method = "GET", poll_interval = 0)]
+pub async fn get_status() -> Result<String, String> {
+    Ok("running".to_string())
+}
+
+#[yewserverhook(path = "/api/report", method = "GET", mock_latency = 200)]
+pub async fn get_report() -> Result<String, String> {
+    Ok("report".to_string())
+}
+
+#[yewserverhook(path = "/files", method = "GET", base = "files.example.com")]
+pub async fn list_files() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+#[yewserverhook(path = "/api/plot", method = "GET", cache_for = 60)]
+pub async fn plot() -> Result<String, String> {
+    Ok(String::new())
+}
+
+fn main() {}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+fn add_to_list(_title: &String, _optimistic: &mut yew_extra::cache::OptimisticUpdate) {}
+
+#[yewserverhook(path = "/api/todos", method = "GET", form = true)]
+pub async fn list_todos(done: bool) -> Result<Vec<String>, String> {
+    Ok(vec![done.to_string()])
+}
+
+#[yewserverhook(path = "/api/todo", method = "GET", nonce = true)]
+pub async fn get_todo(id: u32) -> Result<u32, String> {
+    Ok(id)
+}
+
+#[yewserverhook(path = "/api/todos/count", method = "GET", idempotent = true)]
+pub async fn count_todos() -> Result<u32, String> {
+    Ok(0)
+}
+
+#[yewserverhook(path = "/api/todos/search", method = "GET", optimistic = "add_to_list")]
+pub async fn search_todos(title: String) -> Result<Vec<String>, String> {
+    Ok(vec![title])
+}
+
+#[yewserverhook(path = "/api/todos/done", method = "GET", background_sync = true)]
+pub async fn done_todos() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+fn main() {}
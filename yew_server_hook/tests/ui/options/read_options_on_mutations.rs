@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/todos", method = "POST", sw_cache = "cache-first")]
+pub async fn add_todo(title: String) -> Result<String, String> {
+    Ok(title)
+}
+
+#[yewserverhook(path = "/api/todos", method = "PUT", poll_interval = 5000)]
+pub async fn rename_todo(title: String) -> Result<String, String> {
+    Ok(title)
+}
+
+#[yewserverhook(path = "/api/todos", method = "DELETE", coalesce = true)]
+pub async fn remove_todo(title: String) -> Result<String, String> {
+    Ok(title)
+}
+
+fn main() {}
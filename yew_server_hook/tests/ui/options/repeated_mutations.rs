@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/transfers", method = "POST", nonce = true, retry = 3, idempotent = true)]
+pub async fn create_transfer(amount: u64) -> Result<u64, String> {
+    Ok(amount)
+}
+
+#[yewserverhook(path = "/api/payments", method = "POST", nonce = true, background_sync = true)]
+pub async fn create_payment(amount: u64) -> Result<u64, String> {
+    Ok(amount)
+}
+
+#[yewserverhook(path = "/api/refunds", method = "POST", retry = 3)]
+pub async fn create_refund(amount: u64) -> Result<u64, String> {
+    Ok(amount)
+}
+
+fn main() {}
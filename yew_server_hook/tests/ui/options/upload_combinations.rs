@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/documents", upload = true, raw_body = true)]
+pub async fn upload_document(file: yew_extra::UploadedFile) -> Result<u64, String> {
+    Ok(file.size())
+}
+
+#[yewserverhook(path = "/api/avatars", upload = true, nonce = true)]
+pub async fn upload_avatar(file: yew_extra::UploadedFile) -> Result<u64, String> {
+    Ok(file.size())
+}
+
+#[yewserverhook(path = "/api/videos", upload = true, retry = 3, idempotent = true)]
+pub async fn upload_video(file: yew_extra::UploadedFile) -> Result<u64, String> {
+    Ok(file.size())
+}
+
+#[yewserverhook(path = "/api/images", upload = true, content_type = "application/vnd.images+json")]
+pub async fn upload_image(file: yew_extra::UploadedFile) -> Result<u64, String> {
+    Ok(file.size())
+}
+
+#[yewserverhook(path = "/api/archives", upload = true, credentials = "include")]
+pub async fn upload_archive(file: yew_extra::UploadedFile) -> Result<u64, String> {
+    Ok(file.size())
+}
+
+#[yewserverhook(path = "/api/exports", method = "GET", upload = true)]
+pub async fn upload_export(file: yew_extra::UploadedFile) -> Result<u64, String> {
+    Ok(file.size())
+}
+
+#[yewserverhook(path = "/api/attachments", upload = true)]
+pub async fn upload_attachment(file: yew_extra::UploadedFile, upload_id: String) -> Result<u64, String> {
+    Ok(file.size() + upload_id.len() as u64)
+}
+
+fn main() {}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/sync")]
+pub fn not_async(id: u32) -> Result<u32, String> {
+    Ok(id)
+}
+
+#[yewserverhook(path = "/api/label")]
+pub async fn impl_trait(label: impl ToString) -> Result<String, String> {
+    Ok(label.to_string())
+}
+
+#[yewserverhook(path = "/api/plain")]
+pub async fn plain_return(id: u32) -> u32 {
+    id
+}
+
+#[yewserverhook(path = "/api/nothing")]
+pub async fn no_return(id: u32) {
+    let _ = id;
+}
+
+#[yewserverhook(path = "/api/first")]
+pub async fn first<T: Clone>(items: Vec<T>) -> Result<T, String> {
+    items.first().cloned().ok_or_else(|| "empty".to_string())
+}
+
+#[yewserverhook(path = "/api/count", method = "GET", stream_json = true)]
+pub async fn streamed_count() -> Result<u32, String> {
+    Ok(0)
+}
+
+#[yewserverhook(path = "/api/labels", method = "GET")]
+pub async fn iterated_labels() -> Result<impl Iterator<Item = String>, String> {
+    Ok(std::iter::empty())
+}
+
+#[yewserverhook(path = "/api/users/{id}", method = "GET")]
+pub async fn unnamed_path_param(user_id: u32) -> Result<u32, String> {
+    Ok(user_id)
+}
+
+#[yew_server_hook::yewserverws(path = "/ws/echo")]
+pub async fn untyped_echo(messages: Vec<String>) -> Result<Vec<String>, String> {
+    Ok(messages)
+}
+
+fn main() {}
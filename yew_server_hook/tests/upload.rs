@@ -0,0 +1,44 @@
+// Integration test to verify upload endpoints expand correctly
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StoredFile {
+    pub name: String,
+    pub size: u64,
+}
+
+// The file arrives through the chunked upload protocol, the other parameters with the completion request
+#[yewserverhook(path = "/api/documents", upload = true)]
+pub async fn upload_document(
+    file: yew_extra::UploadedFile,
+    #[validate(length(min = 1))] folder: String,
+) -> Result<StoredFile, String> {
+    let _ = folder;
+    Ok(StoredFile {
+        name: file.file_name().to_string(),
+        size: file.size(),
+    })
+}
+
+// Upload endpoints don't need any other parameters
+#[yewserverhook(path = "/api/avatar", upload = true)]
+pub async fn upload_avatar(avatar: yew_extra::UploadedFile) -> Result<u64, String> {
+    Ok(avatar.size())
+}
+
+#[test]
+fn test_completion_params_carry_upload_id() {
+    let params = UploadDocumentParams {
+        upload_id: "abc".to_string(),
+        folder: String::new(),
+    };
+
+    assert!(params.validate().is_err());
+
+    let json = serde_json::to_value(&UploadAvatarParams {
+        upload_id: "abc".to_string(),
+    })
+    .unwrap();
+    assert_eq!(json["upload_id"], "abc");
+}
@@ -0,0 +1,104 @@
+// Integration test to verify validation rules expand and run correctly
+use serde::{Deserialize, Serialize};
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/signup")]
+pub async fn signup(
+    #[validate(length(min = 3, max = 20), regex = "^[a-z0-9_]+$")] username: String,
+    #[validate(range(min = 13, max = 130))] age: u32,
+    #[validate(length(max = 5, message = "too many tags"))] tags: Vec<String>,
+    #[validate(length(min = 1))] nickname: Option<String>,
+) -> Result<String, String> {
+    Ok(format!("{} {} {:?} {:?}", username, age, tags, nickname))
+}
+
+// Validation also applies to query parameters
+#[yewserverhook(path = "/api/lookup", method = "GET")]
+pub async fn lookup(
+    #[validate(regex(pattern = "^\\d{4}$", message = "must be a 4 digit code"))] code: String,
+) -> Result<String, String> {
+    Ok(code)
+}
+
+fn valid_signup() -> SignupParams {
+    SignupParams {
+        username: "alice_01".to_string(),
+        age: 30,
+        tags: vec!["rust".to_string()],
+        nickname: None,
+    }
+}
+
+#[test]
+fn test_valid_params_pass() {
+    assert!(valid_signup().validate().is_ok());
+    assert!(LookupParams {
+        code: "1234".to_string()
+    }
+    .validate()
+    .is_ok());
+}
+
+#[test]
+fn test_invalid_params_report_each_field() {
+    let params = SignupParams {
+        username: "Al".to_string(),
+        age: 5,
+        tags: vec!["x".to_string(); 6],
+        nickname: Some(String::new()),
+    };
+
+    let errors = params.validate().unwrap_err();
+
+    assert_eq!(
+        errors.fields["username"],
+        vec![
+            "length must be between 3 and 20".to_string(),
+            "does not match the required pattern".to_string(),
+        ]
+    );
+    assert_eq!(
+        errors.fields["age"],
+        vec!["must be between 13 and 130".to_string()]
+    );
+    assert_eq!(errors.fields["tags"], vec!["too many tags".to_string()]);
+    assert_eq!(
+        errors.fields["nickname"],
+        vec!["length must be at least 1".to_string()]
+    );
+}
+
+#[test]
+fn test_errors_match_response_shape() {
+    let errors = LookupParams {
+        code: "12a".to_string(),
+    }
+    .validate()
+    .unwrap_err();
+
+    let body = serde_json::to_value(&errors).unwrap();
+    assert_eq!(
+        body["error"],
+        "Validation failed: code: must be a 4 digit code"
+    );
+    assert_eq!(body["fields"]["code"][0], "must be a 4 digit code");
+
+    // The client surfaces the same message it would read from a 422 response
+    assert_eq!(errors.to_string(), body["error"]);
+}
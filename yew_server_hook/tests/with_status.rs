@@ -0,0 +1,40 @@
+// Integration test to verify `WithStatus<T>` and `(StatusCode, T)` returns send their status
+// with `T` as the body
+use axum::http::StatusCode;
+use yew_extra::test::{call_handler, TestRequest};
+use yew_extra::WithStatus;
+use yew_server_hook_fixture::with_status::{
+    create_order_handler_wrapper, start_export_handler_wrapper, CreateOrderParams, Order,
+    StartExportParams,
+};
+
+#[test]
+fn test_status_values() {
+    assert_eq!(WithStatus::accepted(()).status, 202);
+    assert_eq!(WithStatus::partial_content(()).status, 206);
+    assert_eq!(
+        WithStatus::from((StatusCode::CREATED, 1)),
+        WithStatus::created(1)
+    );
+}
+
+#[tokio::test]
+async fn test_responses_carry_the_status() {
+    let response = call_handler::<Order, _, _>(
+        create_order_handler_wrapper,
+        TestRequest::post("/api/orders").json(&CreateOrderParams { id: 7 }),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::CREATED);
+    assert_eq!(response.data.unwrap(), Order { id: 7 });
+
+    let response = call_handler::<String, _, _>(
+        start_export_handler_wrapper,
+        TestRequest::post("/api/exports").json(&StartExportParams {
+            name: "daily".to_string(),
+        }),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::ACCEPTED);
+    assert_eq!(response.data.unwrap(), "daily");
+}
@@ -0,0 +1,52 @@
+// Integration test to verify the `workers` feature generates handlers without global state
+// Run with `cargo test --features ssr,workers,yew_server_hook_fixture/workers --test workers`
+#![cfg(all(feature = "ssr", feature = "workers"))]
+
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use tower::ServiceExt;
+use yew_extra::RequestContext;
+use yew_server_hook::yewserverhook;
+
+// Required types for the macro
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataState<G> {
+    Loading,
+    Error(String),
+    Data(G),
+    Empty,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHook<G> {
+    pub state: DataState<G>,
+    pub is_loading: bool,
+    pub is_updating: bool,
+}
+
+#[yewserverhook(path = "/api/visits", method = "POST")]
+pub async fn count_visit(cx: RequestContext, page: String) -> Result<String, String> {
+    // Nothing stores the request for `extract()`, the context is the only way to it
+    let stored = yew_extra::extract::<Method>().await.is_ok();
+    Ok(format!("{} {} {}", cx.parts().method, page, stored))
+}
+
+#[tokio::test]
+async fn test_handlers_take_the_request_through_the_context() {
+    // Edge builds mount the handlers themselves
+    let app = Router::new().route("/api/visits", post(count_visit_handler));
+    let request = Request::post("/api/visits")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"page":"/pricing"}"#))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], br#""POST /pricing false""#);
+}
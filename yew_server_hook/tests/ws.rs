@@ -0,0 +1,69 @@
+// Integration test to verify `#[yewserverws]` registers a WebSocket route
+use axum::http::StatusCode;
+use futures_util::{SinkExt, StreamExt};
+use tokio_tungstenite::tungstenite::Message;
+use tower::ServiceExt;
+use yew_extra::test::{test_router, TestRequest};
+use yew_extra::ws::Inbound;
+use yew_server_hook_fixture::ws::{chat, ChatIn, ChatOut};
+
+#[tokio::test]
+async fn test_route_upgrades_get_requests() {
+    // Without the upgrade headers the request is turned down
+    let response = test_router()
+        .oneshot(TestRequest::get("/ws/chat").into_request())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = test_router()
+        .oneshot(TestRequest::post("/ws/chat").into_request())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[tokio::test]
+async fn test_messages_are_answered_over_the_socket() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, test_router()).await.unwrap() });
+
+    let url = format!("ws://{}/ws/chat", address);
+    let (mut socket, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+    socket
+        .send(Message::text(r#"{"text":"hello"}"#))
+        .await
+        .unwrap();
+
+    let Message::Text(text) = socket.next().await.unwrap().unwrap() else {
+        panic!("expected a text message");
+    };
+    assert_eq!(
+        serde_json::from_str::<ChatOut>(&text).unwrap(),
+        ChatOut {
+            from: "ada".to_string(),
+            text: "hello".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_function_answers_the_inbound_messages() {
+    let (inbound, sender) = Inbound::channel();
+    sender
+        .send(ChatIn {
+            text: "hello".to_string(),
+        })
+        .unwrap();
+    drop(sender);
+
+    let outbound: Vec<ChatOut> = chat(inbound).await.unwrap().collect().await;
+    assert_eq!(
+        outbound,
+        vec![ChatOut {
+            from: "ada".to_string(),
+            text: "hello".to_string(),
+        }]
+    );
+}